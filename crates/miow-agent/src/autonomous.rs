@@ -1,5 +1,5 @@
 use anyhow::{Context, Result, anyhow};
-use miow_llm::LLMProvider;
+use miow_llm::{parse_llm_json, LLMProvider};
 use miow_graph::KnowledgeGraph;
 use miow_vector::VectorStore;
 use std::sync::Arc;
@@ -188,9 +188,7 @@ OR
         );
 
         let response = self.llm.generate(&prompt).await?;
-        let clean = self.clean_json(&response.content);
-        
-        serde_json::from_str(&clean).context("Failed to parse agent decision")
+        parse_llm_json(&response.content).context("Failed to parse agent decision")
     }
 
     fn format_gathered_info(&self, info: &[VerifiedInfo]) -> String {
@@ -202,15 +200,6 @@ OR
             .collect::<Vec<_>>()
             .join("\n")
     }
-
-    fn clean_json(&self, input: &str) -> String {
-        input.trim()
-            .trim_start_matches("```json")
-            .trim_start_matches("```")
-            .trim_end_matches("```")
-            .trim()
-            .to_string()
-    }
 }
 
 /// Tool to search the knowledge graph and vector store
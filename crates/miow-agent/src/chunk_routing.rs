@@ -0,0 +1,139 @@
+//! Which context bucket a worker's chunks land in. Routes are keyed by worker and chunk kind,
+//! so the same kind can mean different things for different workers (an `auth_scanner`
+//! "middleware" chunk is a helper, a custom validation worker's "validator" is a schema).
+//! Chunks without a route fall back to guessing the bucket from their kind.
+
+use miow_llm::{ContextItem, GatheredContext};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Route kind matching every chunk of a worker that has no more specific route
+pub const ANY_KIND: &str = "*";
+
+/// A `GatheredContext` category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextBucket {
+    Components,
+    Helpers,
+    Types,
+    DesignTokens,
+    Constants,
+    Schemas,
+    SimilarImplementations,
+}
+
+impl ContextBucket {
+    /// The bucket a chunk's `kind` suggests on its own
+    pub fn for_kind(kind: &str) -> Self {
+        if kind.contains("component") || kind.contains("function") {
+            ContextBucket::Components
+        } else if kind.contains("type") || kind.contains("interface") {
+            ContextBucket::Types
+        } else if kind.contains("schema") || kind.contains("model") {
+            ContextBucket::Schemas
+        } else {
+            ContextBucket::Helpers
+        }
+    }
+
+    pub fn items<'a>(&self, context: &'a mut GatheredContext) -> &'a mut Vec<ContextItem> {
+        match self {
+            ContextBucket::Components => &mut context.components,
+            ContextBucket::Helpers => &mut context.helpers,
+            ContextBucket::Types => &mut context.types,
+            ContextBucket::DesignTokens => &mut context.design_tokens,
+            ContextBucket::Constants => &mut context.constants,
+            ContextBucket::Schemas => &mut context.schemas,
+            ContextBucket::SimilarImplementations => &mut context.similar_implementations,
+        }
+    }
+}
+
+/// Explicit `(worker_id, chunk kind)` → bucket routes for worker output
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkRouting {
+    routes: HashMap<(String, String), ContextBucket>,
+}
+
+impl Default for ChunkRouting {
+    /// Routes for built-in workers whose chunk kinds don't name their bucket
+    fn default() -> Self {
+        Self::empty()
+            .with_route("auth_scanner", "middleware", ContextBucket::Helpers)
+            .with_route("auth_scanner", "guard", ContextBucket::Helpers)
+            .with_route("auth_scanner", "session", ContextBucket::Types)
+            .with_route("api_scanner", "route", ContextBucket::Helpers)
+            .with_route("data_scanner", "migration", ContextBucket::Schemas)
+            .with_route("data_scanner", "table", ContextBucket::Schemas)
+            .with_route("config_scanner", "config", ContextBucket::Constants)
+            .with_route("config_scanner", "env", ContextBucket::Constants)
+    }
+}
+
+impl ChunkRouting {
+    /// No routes: every chunk goes where its kind suggests
+    pub fn empty() -> Self {
+        Self { routes: HashMap::new() }
+    }
+
+    /// Send `worker_id`'s chunks of `kind` (matched case-insensitively, or `ANY_KIND`) to `bucket`
+    pub fn with_route(mut self, worker_id: impl Into<String>, kind: &str, bucket: ContextBucket) -> Self {
+        self.routes.insert((worker_id.into(), kind.to_lowercase()), bucket);
+        self
+    }
+
+    /// Where a chunk of `kind` from `worker_id` belongs: its exact route, else the worker's
+    /// `ANY_KIND` route, else the bucket the kind suggests
+    pub fn bucket(&self, worker_id: &str, kind: &str) -> ContextBucket {
+        let kind = kind.to_lowercase();
+        self.routes
+            .get(&(worker_id.to_string(), kind.clone()))
+            .or_else(|| self.routes.get(&(worker_id.to_string(), ANY_KIND.to_string())))
+            .copied()
+            .unwrap_or_else(|| ContextBucket::for_kind(&kind))
+    }
+
+    /// The list in `context` a chunk of `kind` from `worker_id` is added to
+    pub fn items<'a>(&self, context: &'a mut GatheredContext, worker_id: &str, kind: &str) -> &'a mut Vec<ContextItem> {
+        self.bucket(worker_id, kind).items(context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str, kind: &str) -> ContextItem {
+        ContextItem {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            content: String::new(),
+            file_path: "src/validation/user.ts".to_string(),
+            relevance_score: 0.9,
+            props: vec![],
+            references: vec![],
+            tags: vec![],
+        }
+    }
+
+    #[test]
+    fn test_custom_worker_output_routes_into_schemas() {
+        let routing = ChunkRouting::default().with_route("zod_scanner", "validator", ContextBucket::Schemas);
+        let mut context = GatheredContext::default();
+        routing
+            .items(&mut context, "zod_scanner", "Validator")
+            .push(item("userSchema", "Validator"));
+        assert_eq!(context.schemas.len(), 1);
+        assert!(context.helpers.is_empty());
+
+        // Other workers' "validator" chunks keep the kind-based bucket
+        assert_eq!(routing.bucket("frontend_scanner", "validator"), ContextBucket::Helpers);
+        assert_eq!(routing.bucket("zod_scanner", "interface"), ContextBucket::Types);
+
+        let catch_all = routing.with_route("zod_scanner", ANY_KIND, ContextBucket::Schemas);
+        assert_eq!(catch_all.bucket("zod_scanner", "interface"), ContextBucket::Schemas);
+        assert_eq!(catch_all.bucket("auth_scanner", "middleware"), ContextBucket::Helpers);
+        assert_eq!(catch_all.bucket("config_scanner", "env"), ContextBucket::Constants);
+    }
+}
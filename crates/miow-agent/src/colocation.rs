@@ -0,0 +1,128 @@
+//! Co-location expansion: pulls a selected component's sibling style, test and story files
+//! (`Button.module.css`, `Button.test.tsx`, `Button.stories.tsx`) into the context.
+
+use miow_llm::{ContextItem, GatheredContext};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// File name infixes of co-located files, with the context kind they are reported as
+const CO_LOCATION_PATTERNS: [(&str, &str); 6] = [
+    (".module.css", "co-located style"),
+    (".module.scss", "co-located style"),
+    (".test.", "co-located test"),
+    (".spec.", "co-located test"),
+    (".stories.", "co-located story"),
+    (".styles.", "co-located style"),
+];
+
+/// Co-located files larger than this are skipped rather than blowing the budget
+pub const DEFAULT_MAX_CO_LOCATED_BYTES: usize = 32 * 1024;
+
+/// Finds co-located files on disk next to the files of selected symbols.
+#[derive(Debug, Clone)]
+pub struct CoLocationExpander {
+    root: PathBuf,
+    max_file_bytes: usize,
+}
+
+impl CoLocationExpander {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            max_file_bytes: DEFAULT_MAX_CO_LOCATED_BYTES,
+        }
+    }
+
+    pub fn with_max_file_bytes(mut self, max_file_bytes: usize) -> Self {
+        self.max_file_bytes = max_file_bytes;
+        self
+    }
+
+    /// Siblings of `file_path` (relative to the root) sharing its base name and matching a
+    /// co-location pattern, as sorted `(relative path, kind)` pairs.
+    pub fn siblings(&self, file_path: &str) -> Vec<(String, &'static str)> {
+        let path = Path::new(file_path);
+        let (Some(dir), Some(file_name)) = (path.parent(), path.file_name()) else {
+            return Vec::new();
+        };
+        let file_name = file_name.to_string_lossy();
+        let base = file_name.split('.').next().unwrap_or_default();
+        let Ok(entries) = fs::read_dir(self.root.join(dir)) else {
+            return Vec::new();
+        };
+
+        let mut siblings: Vec<(String, &'static str)> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name == file_name || name.split('.').next() != Some(base) {
+                    return None;
+                }
+                let (_, kind) = CO_LOCATION_PATTERNS
+                    .iter()
+                    .find(|(pattern, _)| name.contains(pattern))?;
+                let relative = dir.join(&name).to_string_lossy().replace('\\', "/");
+                Some((relative, *kind))
+            })
+            .collect();
+        siblings.sort();
+        siblings
+    }
+
+    /// Add the co-located files of every selected component and helper to
+    /// `gathered.similar_implementations`. Returns how many files were added.
+    pub fn expand(&self, gathered: &mut GatheredContext) -> usize {
+        let mut seen: HashSet<String> = gathered
+            .components
+            .iter()
+            .chain(&gathered.helpers)
+            .chain(&gathered.similar_implementations)
+            .map(|item| item.file_path.clone())
+            .collect();
+        let selected: Vec<String> = gathered
+            .components
+            .iter()
+            .chain(&gathered.helpers)
+            .map(|item| item.file_path.clone())
+            .collect();
+
+        let mut added = 0;
+        for file_path in selected {
+            for (sibling, kind) in self.siblings(&file_path) {
+                if !seen.insert(sibling.clone()) {
+                    continue;
+                }
+                let content = match fs::read_to_string(self.root.join(&sibling)) {
+                    Ok(content) if content.len() <= self.max_file_bytes => content,
+                    Ok(content) => {
+                        warn!("Skipping co-located {} ({} bytes over limit)", sibling, content.len());
+                        continue;
+                    }
+                    Err(e) => {
+                        warn!("Could not read co-located {}: {}", sibling, e);
+                        continue;
+                    }
+                };
+                gathered.similar_implementations.push(ContextItem {
+                    name: Path::new(&sibling)
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    kind: kind.to_string(),
+                    content,
+                    file_path: sibling,
+                    relevance_score: 0.0,
+                    props: vec![],
+                    references: vec![],
+                    tags: vec![],
+                });
+                added += 1;
+            }
+        }
+        added
+    }
+}
+
@@ -1,32 +1,177 @@
 use anyhow::{Context, Result};
-use miow_llm::{ContextItem, GatheredContext, LLMProvider, Message, Role};
-use serde::Deserialize;
+use miow_graph::{DocumentFrequencies, ImportContext};
+use miow_llm::{parse_llm_json, ContextItem, GatheredContext, GenerationOptions, LLMProvider, Message, Role};
+use miow_prompt::{CharRatioEstimator, ContextConfig, DroppedItem, RerankWeights, TokenEstimator};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 
+const AUDITED_CATEGORIES: [&str; 4] = ["components", "helpers", "types", "schemas"];
+
+/// Per-category item counts before and after an audit pass.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditReport {
+    pub before_counts: HashMap<String, usize>,
+    pub after_counts: HashMap<String, usize>,
+    pub removed_per_category: HashMap<String, usize>,
+}
+
+impl AuditReport {
+    fn snapshot(gathered: &GatheredContext) -> HashMap<String, usize> {
+        AUDITED_CATEGORIES
+            .iter()
+            .map(|category| (category.to_string(), category_items(gathered, category).len()))
+            .collect()
+    }
+
+    fn from_counts(before: HashMap<String, usize>, after: HashMap<String, usize>) -> Self {
+        let removed_per_category = before
+            .iter()
+            .map(|(category, count)| {
+                let kept = after.get(category).copied().unwrap_or(0);
+                (category.clone(), count.saturating_sub(kept))
+            })
+            .collect();
+
+        Self {
+            before_counts: before,
+            after_counts: after,
+            removed_per_category,
+        }
+    }
+
+    /// Number of items kept in `category` after the audit.
+    pub fn kept(&self, category: &str) -> usize {
+        self.after_counts.get(category).copied().unwrap_or(0)
+    }
+
+    /// Number of items in `category` before the audit.
+    pub fn total(&self, category: &str) -> usize {
+        self.before_counts.get(category).copied().unwrap_or(0)
+    }
+
+    /// Total number of items removed across all categories.
+    pub fn total_removed(&self) -> usize {
+        self.removed_per_category.values().sum()
+    }
+
+    /// Human readable summary, e.g. "auditor kept 5/18 components, 8/8 helpers, ...".
+    pub fn summary(&self) -> String {
+        let parts: Vec<String> = AUDITED_CATEGORIES
+            .iter()
+            .map(|category| format!("{}/{} {}", self.kept(category), self.total(category), category))
+            .collect();
+        format!("auditor kept {}", parts.join(", "))
+    }
+}
+
+fn category_items<'a>(gathered: &'a GatheredContext, category: &str) -> &'a Vec<ContextItem> {
+    match category {
+        "components" => &gathered.components,
+        "helpers" => &gathered.helpers,
+        "types" => &gathered.types,
+        _ => &gathered.schemas,
+    }
+}
+
+/// Items of the audited categories in `before` that an audit left out of `after`, for a
+/// `ContextReport`
+pub fn audit_drops(before: &GatheredContext, after: &GatheredContext) -> Vec<DroppedItem> {
+    let estimator = CharRatioEstimator::default();
+    let mut dropped = Vec::new();
+    for category in AUDITED_CATEGORIES {
+        let kept = category_items(after, category);
+        for item in category_items(before, category) {
+            if kept.iter().any(|k| k.name == item.name && k.file_path == item.file_path) {
+                continue;
+            }
+            dropped.push(DroppedItem {
+                section: category.to_string(),
+                name: item.name.clone(),
+                file_path: Some(item.file_path.clone()),
+                tokens: estimator.estimate(&format!("{}{}", item.name, item.content)),
+            });
+        }
+    }
+    dropped
+}
+
+/// How much of each item's content the LLM auditor sees. A category may get longer previews
+/// (a dense type needs more than a helper to be judged), and previews shrink when a category
+/// holds so many items that `chars_budget` would be exceeded, down to `min_chars`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviewLengths {
+    pub default_chars: usize,
+    pub per_category: HashMap<String, usize>,
+    /// Preview characters shared by all items of one category
+    pub chars_budget: usize,
+    pub min_chars: usize,
+}
+
+impl Default for PreviewLengths {
+    fn default() -> Self {
+        Self {
+            default_chars: 320,
+            per_category: HashMap::from([("types".to_string(), 480)]),
+            chars_budget: 320 * 16,
+            min_chars: 80,
+        }
+    }
+}
+
+impl PreviewLengths {
+    pub fn with_category(mut self, category: &str, chars: usize) -> Self {
+        self.per_category.insert(category.to_string(), chars);
+        self
+    }
+
+    /// Preview length for each of `item_count` items of `category`
+    pub fn for_category(&self, category: &str, item_count: usize) -> usize {
+        let configured = self.per_category.get(category).copied().unwrap_or(self.default_chars);
+        let share = self.chars_budget / item_count.max(1);
+        configured.min(share.max(self.min_chars))
+    }
+}
+
 /// Simple LLM-backed context auditor that selects only the most essential items.
 pub struct GeminiContextAuditor {
     llm: Arc<dyn LLMProvider>,
+    audit_threshold: usize,
+    preview_lengths: PreviewLengths,
 }
 
 impl GeminiContextAuditor {
     pub fn new(llm: Arc<dyn LLMProvider>) -> Self {
-        Self { llm }
+        Self {
+            llm,
+            audit_threshold: DEFAULT_KEEP_PER_CATEGORY,
+            preview_lengths: PreviewLengths::default(),
+        }
+    }
+
+    /// Only ask the LLM to prune categories holding more than `threshold` items
+    pub fn with_audit_threshold(mut self, threshold: usize) -> Self {
+        self.audit_threshold = threshold;
+        self
+    }
+
+    pub fn with_preview_lengths(mut self, preview_lengths: PreviewLengths) -> Self {
+        self.preview_lengths = preview_lengths;
+        self
     }
 
     /// Audit and prune a gathered context in-place. Never fails hard – on error it leaves context unchanged.
+    /// Returns a report of how many items each category held before and after pruning.
     pub async fn audit(
         &self,
         user_prompt: &str,
         gathered: &mut GatheredContext,
-    ) -> Result<()> {
+    ) -> Result<AuditReport> {
+        let before = AuditReport::snapshot(gathered);
+
         // Only bother the LLM if we have more than a handful of items.
-        if gathered.components.len()
-            + gathered.helpers.len()
-            + gathered.types.len()
-            + gathered.schemas.len()
-            <= 12
-        {
-            return Ok(());
+        if before.values().sum::<usize>() <= 12 {
+            return Ok(AuditReport::from_counts(before.clone(), before));
         }
 
         self.audit_category("components", user_prompt, &mut gathered.components)
@@ -42,7 +187,8 @@ impl GeminiContextAuditor {
             .await
             .ok();
 
-        Ok(())
+        let after = AuditReport::snapshot(gathered);
+        Ok(AuditReport::from_counts(before, after))
     }
 
     async fn audit_category(
@@ -51,11 +197,12 @@ impl GeminiContextAuditor {
         user_prompt: &str,
         items: &mut Vec<ContextItem>,
     ) -> Result<()> {
-        if items.len() <= 8 {
+        if items.len() <= self.audit_threshold {
             return Ok(());
         }
 
         // Build a lightweight summary of each item to keep tokens manageable.
+        let preview_chars = self.preview_lengths.for_category(category, items.len());
         let summaries: Vec<ItemSummary> = items
             .iter()
             .enumerate()
@@ -64,7 +211,7 @@ impl GeminiContextAuditor {
                 name: item.name.clone(),
                 kind: item.kind.clone(),
                 file_path: item.file_path.clone(),
-                preview: truncate_preview(&item.content, 320),
+                preview: truncate_preview(&item.content, preview_chars),
             })
             .collect();
 
@@ -100,20 +247,15 @@ You MUST respond with JSON only, matching:
 
         let response = self
             .llm
-            .generate_with_context(messages)
+            .generate_with_options(
+                messages,
+                GenerationOptions::deterministic().with_json_output(Some(audit_decision_schema())),
+            )
             .await
             .context("Context auditor LLM call failed")?;
 
-        let raw = response.content.trim();
-        let clean = raw
-            .trim_start_matches("```json")
-            .trim_start_matches("```")
-            .trim_end_matches("```")
-            .trim();
-
-        let parsed: AuditDecision = serde_json::from_str(clean)
-            .or_else(|_| serde_json::from_str::<AuditDecision>(raw))
-            .context("Failed to parse context auditor JSON")?;
+        let parsed: AuditDecision =
+            parse_llm_json(&response.content).context("Failed to parse context auditor JSON")?;
 
         if parsed.keep_indices.is_empty() {
             return Ok(()); // Don't change anything on empty decision.
@@ -133,12 +275,142 @@ You MUST respond with JSON only, matching:
     }
 }
 
+/// Default audit threshold: larger categories are cut down to this many items, chosen by
+/// the LLM auditor or by the pattern auditor's scores.
+const DEFAULT_KEEP_PER_CATEGORY: usize = 8;
+
+/// Context auditor that needs no LLM: scores items by keyword matches and keeps the top N
+/// of each category.
+#[derive(Debug, Clone)]
+pub struct PatternContextAuditor {
+    keep_per_category: usize,
+    weights: RerankWeights,
+    import_context: Option<ImportContext>,
+    document_frequencies: Option<DocumentFrequencies>,
+    symbol_priorities: HashMap<(String, String), f32>,
+}
+
+impl Default for PatternContextAuditor {
+    fn default() -> Self {
+        Self::new(DEFAULT_KEEP_PER_CATEGORY)
+    }
+}
+
+impl PatternContextAuditor {
+    pub fn new(keep_per_category: usize) -> Self {
+        Self {
+            keep_per_category,
+            weights: RerankWeights::default(),
+            import_context: None,
+            document_frequencies: None,
+            symbol_priorities: HashMap::new(),
+        }
+    }
+
+    /// Keep `audit_threshold` items per category, scored with the config's rerank weights
+    pub fn from_config(config: &ContextConfig) -> Self {
+        Self::new(config.audit_threshold).with_rerank_weights(config.rerank)
+    }
+
+    /// Weigh keyword matches in names, paths and bodies with `weights`
+    pub fn with_rerank_weights(mut self, weights: RerankWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Boost items imported by, or co-located with, the file the user is working in
+    pub fn with_import_context(mut self, import_context: ImportContext) -> Self {
+        self.import_context = Some(import_context);
+        self
+    }
+
+    /// Decay the keyword score of symbols that appear in many files, so a ubiquitous `Button`
+    /// doesn't crowd out the specific symbol a query is after
+    pub fn with_document_frequencies(mut self, document_frequencies: DocumentFrequencies) -> Self {
+        self.document_frequencies = Some(document_frequencies);
+        self
+    }
+
+    /// Scale scores by the base priority stamped on each symbol at index time, keyed by
+    /// `(name, file_path)`, so core source outranks identical code in scripts or examples
+    pub fn with_symbol_priorities(mut self, symbol_priorities: HashMap<(String, String), f32>) -> Self {
+        self.symbol_priorities = symbol_priorities;
+        self
+    }
+
+    /// Rank every audited category by score and keep the best `keep_per_category` items.
+    pub fn audit(&self, user_prompt: &str, gathered: &mut GatheredContext) -> AuditReport {
+        let before = AuditReport::snapshot(gathered);
+        let keywords = crate::router::HeuristicRouterAgent::keywords(user_prompt);
+
+        for items in [
+            &mut gathered.components,
+            &mut gathered.helpers,
+            &mut gathered.types,
+            &mut gathered.schemas,
+        ] {
+            for item in items.iter_mut() {
+                item.relevance_score = item.relevance_score.max(self.weighted_score(item, &keywords));
+                if let Some(document_frequencies) = &self.document_frequencies {
+                    item.relevance_score *= document_frequencies.weight(&item.name);
+                }
+                if let Some(priority) = self
+                    .symbol_priorities
+                    .get(&(item.name.clone(), item.file_path.clone()))
+                {
+                    item.relevance_score *= priority;
+                }
+                if let Some(import_context) = &self.import_context {
+                    item.relevance_score += import_context.boost(&item.name, &item.file_path);
+                }
+            }
+            items.sort_by(|a, b| {
+                b.relevance_score
+                    .partial_cmp(&a.relevance_score)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| a.file_path.cmp(&b.file_path))
+                    .then_with(|| a.name.cmp(&b.name))
+            });
+            items.truncate(self.keep_per_category);
+        }
+
+        let after = AuditReport::snapshot(gathered);
+        AuditReport::from_counts(before, after)
+    }
+
+    /// Keyword score with the default weights: name matches weigh most, then file path, then
+    /// content.
+    pub fn score(item: &ContextItem, keywords: &[String]) -> f32 {
+        Self::default().weighted_score(item, keywords)
+    }
+
+    fn weighted_score(&self, item: &ContextItem, keywords: &[String]) -> f32 {
+        self.weights.keyword_score(
+            &item.name.to_lowercase(),
+            &item.file_path.to_lowercase(),
+            &item.content.to_lowercase(),
+            keywords,
+        )
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct AuditDecision {
     #[serde(default)]
     keep_indices: Vec<usize>,
 }
 
+/// Response schema of `AuditDecision`, for providers with structured JSON output
+fn audit_decision_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "OBJECT",
+        "properties": {
+            "keep_indices": { "type": "ARRAY", "items": { "type": "INTEGER" } }
+        },
+        "required": ["keep_indices"]
+    })
+}
+
 #[derive(Debug, serde::Serialize)]
 struct ItemSummary {
     index: usize,
@@ -156,4 +428,329 @@ fn truncate_preview(content: &str, max_chars: usize) -> String {
     s
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miow_llm::LLMResponse;
+
+    struct KeepFirstThree;
 
+    #[async_trait::async_trait]
+    impl LLMProvider for KeepFirstThree {
+        async fn generate(&self, _prompt: &str) -> Result<LLMResponse> {
+            Ok(LLMResponse {
+                content: "```json\n{ \"keep_indices\": [0, 1, 2] }\n```".to_string(),
+                finish_reason: Some("stop".to_string()),
+                usage: None,
+            })
+        }
+
+        async fn generate_with_context(&self, _messages: Vec<Message>) -> Result<LLMResponse> {
+            self.generate("").await
+        }
+
+        async fn stream_generate(
+            &self,
+            _prompt: &str,
+        ) -> Result<Box<dyn futures::Stream<Item = Result<String>> + Unpin>> {
+            Ok(Box::new(futures::stream::empty()))
+        }
+
+        async fn generate_multi_step(&self, _steps: Vec<String>, _context: &str) -> Result<LLMResponse> {
+            self.generate("").await
+        }
+
+        async fn generate_with_framework(&self, _prompt: &str, _framework: &str, _lang: &str) -> Result<LLMResponse> {
+            self.generate("").await
+        }
+    }
+
+    fn items(prefix: &str, count: usize) -> Vec<ContextItem> {
+        (0..count)
+            .map(|i| ContextItem {
+                name: format!("{}{}", prefix, i),
+                kind: "function".to_string(),
+                content: format!("fn {}{}() {{}}", prefix, i),
+                file_path: format!("src/{}.rs", prefix),
+                relevance_score: 0.5,
+                props: vec![],
+                references: vec![],
+                tags: vec![],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_preview_length_follows_config_and_item_count() {
+        let lengths = PreviewLengths::default().with_category("helpers", 40);
+        assert_eq!(lengths.for_category("helpers", 3), 40);
+        assert_eq!(lengths.for_category("components", 3), 320);
+        assert_eq!(lengths.for_category("types", 3), 480);
+        // 40 components share the budget, but never drop below the minimum
+        assert_eq!(lengths.for_category("components", 40), 128);
+        assert_eq!(lengths.for_category("components", 400), 80);
+
+        let content = "é".repeat(60);
+        let preview = truncate_preview(&content, lengths.for_category("helpers", 3));
+        assert_eq!(preview, format!("{}…", "é".repeat(40)));
+        assert_eq!(truncate_preview("short", 40), "short");
+    }
+
+    #[tokio::test]
+    async fn test_audit_report_reflects_reduction() {
+        let mut gathered = GatheredContext {
+            components: items("component", 18),
+            helpers: items("helper", 4),
+            types: vec![],
+            design_tokens: vec![],
+            constants: vec![],
+            schemas: vec![],
+            similar_implementations: vec![],
+        };
+
+        let auditor = GeminiContextAuditor::new(Arc::new(KeepFirstThree));
+        let report = auditor.audit("add a login page", &mut gathered).await.unwrap();
+
+        assert_eq!(gathered.components.len(), 3);
+        assert_eq!(report.total("components"), 18);
+        assert_eq!(report.kept("components"), 3);
+        assert_eq!(report.removed_per_category["components"], 15);
+        // Categories at or below the per-category threshold are left alone.
+        assert_eq!(report.kept("helpers"), 4);
+        assert_eq!(report.removed_per_category["helpers"], 0);
+        assert_eq!(report.total_removed(), 15);
+        assert!(report.summary().contains("3/18 components"));
+    }
+
+    #[test]
+    fn test_pattern_auditor_keeps_top_scoring_items() {
+        let mut components = items("component", 5);
+        components[3].name = "LoginForm".to_string();
+        let mut gathered = GatheredContext {
+            components,
+            helpers: items("helper", 2),
+            types: vec![],
+            design_tokens: vec![],
+            constants: vec![],
+            schemas: vec![],
+            similar_implementations: vec![],
+        };
+
+        let report = PatternContextAuditor::new(2).audit("add a login page", &mut gathered);
+
+        assert_eq!(gathered.components.len(), 2);
+        assert_eq!(gathered.components[0].name, "LoginForm");
+        assert_eq!(report.kept("components"), 2);
+        assert_eq!(report.removed_per_category["components"], 3);
+        assert_eq!(report.kept("helpers"), 2);
+    }
+
+    #[test]
+    fn test_pattern_auditor_honors_context_config() {
+        let gathered = || {
+            let mut components = items("component", 5);
+            components[1].name = "LoginForm".to_string();
+            components[3].file_path = "src/login/panel.rs".to_string();
+            GatheredContext {
+                components,
+                helpers: vec![],
+                types: vec![],
+                design_tokens: vec![],
+                constants: vec![],
+                schemas: vec![],
+                similar_implementations: vec![],
+            }
+        };
+
+        let mut defaults = gathered();
+        PatternContextAuditor::default().audit("add a login page", &mut defaults);
+        assert_eq!(defaults.components.len(), 5);
+        assert_eq!(defaults.components[0].name, "LoginForm");
+
+        // Path matches now outweigh name matches, and only one item survives
+        let config = ContextConfig {
+            audit_threshold: 1,
+            rerank: RerankWeights {
+                path_match: 5.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut tuned = gathered();
+        let report = PatternContextAuditor::from_config(&config).audit("add a login page", &mut tuned);
+        assert_eq!(report.kept("components"), 1);
+        assert_eq!(tuned.components[0].file_path, "src/login/panel.rs");
+    }
+
+    #[test]
+    fn test_imported_symbol_outranks_namesake() {
+        let cart = |file_path: &str| ContextItem {
+            name: "Cart".to_string(),
+            kind: "Component".to_string(),
+            content: "export function Cart() {}".to_string(),
+            file_path: file_path.to_string(),
+            relevance_score: 1.0,
+            props: vec![],
+            references: vec![],
+            tags: vec![],
+        };
+        let mut gathered = GatheredContext {
+            components: vec![cart("src/legacy/Cart.tsx"), cart("src/cart/Cart.tsx")],
+            ..Default::default()
+        };
+        let import_context = ImportContext::new(
+            "src/checkout/Checkout.tsx",
+            &[miow_graph::ImportData {
+                source: "../cart/Cart".to_string(),
+                names: vec!["Cart".to_string()],
+                start_line: 1,
+                end_line: 1,
+            }],
+        );
+
+        PatternContextAuditor::default()
+            .with_import_context(import_context)
+            .audit("modify the checkout flow", &mut gathered);
+
+        assert_eq!(gathered.components[0].file_path, "src/cart/Cart.tsx");
+    }
+
+    #[test]
+    fn test_rare_symbol_outranks_ubiquitous_button() {
+        let component = |name: &str, file_path: &str, content: &str| ContextItem {
+            name: name.to_string(),
+            kind: "Component".to_string(),
+            content: content.to_string(),
+            file_path: file_path.to_string(),
+            relevance_score: 1.0,
+            props: vec![],
+            references: vec![],
+            tags: vec![],
+        };
+        let gathered = GatheredContext {
+            components: vec![
+                component("Button", "src/components/Button.tsx", "export function Button() { return <button />; }"),
+                component("CheckoutSummary", "src/orders/CheckoutSummary.tsx", "export function CheckoutSummary() {}"),
+            ],
+            ..Default::default()
+        };
+
+        // Every page renders a Button; CheckoutSummary lives in one file
+        let graph = miow_graph::KnowledgeGraph::in_memory().unwrap();
+        for page in 0..20 {
+            let parsed = miow_graph::ParsedFileData {
+                symbols: vec![miow_graph::SymbolData {
+                    name: format!("Page{}", page),
+                    kind: "Component".to_string(),
+                    start_line: 1,
+                    end_line: 1,
+                    start_byte: 0,
+                    end_byte: 10,
+                    content: "<Button />".to_string(),
+                    metadata: "{}".to_string(),
+                    style_tags: None,
+                    children: vec![],
+                    references: vec!["Button".to_string()],
+                    qualified_name: None,
+                }],
+                imports: vec![],
+                design_tokens: vec![],
+                type_definitions: vec![],
+                constants: vec![],
+                schemas: vec![],
+                language: "tsx".to_string(),
+                exports: vec![],
+                tests: vec![],
+            };
+            graph.insert_file(&format!("src/pages/Page{}.tsx", page), &parsed).unwrap();
+        }
+        graph.compute_document_frequencies().unwrap();
+        let frequencies = graph.document_frequencies().unwrap();
+        assert_eq!(frequencies.file_count("Button"), 20);
+
+        let prompt = "add a button to the checkout";
+        let mut undecayed = gathered.clone();
+        PatternContextAuditor::default().audit(prompt, &mut undecayed);
+        assert_eq!(undecayed.components[0].name, "Button");
+
+        let mut decayed = gathered;
+        PatternContextAuditor::default()
+            .with_document_frequencies(frequencies)
+            .audit(prompt, &mut decayed);
+        assert_eq!(decayed.components[0].name, "CheckoutSummary");
+    }
+
+    #[test]
+    fn test_src_symbol_outranks_identical_example() {
+        let priorities = miow_core::PathPriorities::new(&miow_core::IndexConfig::default().path_priorities).unwrap();
+        let content = "export function OrderTable() { return <table />; }";
+        let files = ["examples/OrderTable.tsx", "src/components/OrderTable.tsx"];
+
+        let graph = miow_graph::KnowledgeGraph::in_memory().unwrap();
+        for file_path in files {
+            let metadata = serde_json::json!({ "priority": priorities.symbol_priority(file_path, true) });
+            let parsed = miow_graph::ParsedFileData {
+                symbols: vec![miow_graph::SymbolData {
+                    name: "OrderTable".to_string(),
+                    kind: "Component".to_string(),
+                    start_line: 1,
+                    end_line: 1,
+                    start_byte: 0,
+                    end_byte: content.len(),
+                    content: content.to_string(),
+                    metadata: metadata.to_string(),
+                    style_tags: None,
+                    children: vec![],
+                    references: vec![],
+                    qualified_name: None,
+                }],
+                imports: vec![],
+                design_tokens: vec![],
+                type_definitions: vec![],
+                constants: vec![],
+                schemas: vec![],
+                language: "tsx".to_string(),
+                exports: vec![],
+                tests: vec![],
+            };
+            graph.insert_file(file_path, &parsed).unwrap();
+        }
+        let symbol_priorities: HashMap<(String, String), f32> = graph
+            .search_symbols("OrderTable")
+            .unwrap()
+            .into_iter()
+            .filter_map(|s| Some(((s.name.clone(), s.file_path.clone()), s.priority()?)))
+            .collect();
+        assert_eq!(symbol_priorities.len(), 2);
+
+        let gathered = GatheredContext {
+            components: files
+                .iter()
+                .map(|file_path| ContextItem {
+                    name: "OrderTable".to_string(),
+                    kind: "Component".to_string(),
+                    content: content.to_string(),
+                    file_path: file_path.to_string(),
+                    relevance_score: 1.0,
+                    props: vec![],
+                    references: vec![],
+                    tags: vec![],
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        // Identical scores fall back to path order, which puts examples/ first
+        let prompt = "reuse the order table";
+        let mut unprioritized = gathered.clone();
+        PatternContextAuditor::default().audit(prompt, &mut unprioritized);
+        assert_eq!(unprioritized.components[0].file_path, "examples/OrderTable.tsx");
+
+        let mut prioritized = gathered;
+        PatternContextAuditor::default()
+            .with_symbol_priorities(symbol_priorities)
+            .audit(prompt, &mut prioritized);
+        assert_eq!(prioritized.components[0].file_path, "src/components/OrderTable.tsx");
+        assert!(prioritized.components[0].relevance_score > prioritized.components[1].relevance_score);
+    }
+}
@@ -21,6 +21,7 @@ pub struct ExecutionPlan {
     pub goal: String,
     pub steps: Vec<PlanStep>,
     pub estimated_duration: u64, // seconds
+    #[serde(default)]
     pub created_at: u64,
 }
 
@@ -6,12 +6,26 @@ pub mod tools;
 pub mod prompt_registry;
 pub mod enhanced_planner;
 pub mod self_monitor;
+pub mod no_llm;
+pub mod colocation;
+pub mod summarizer;
+pub mod streaming;
+pub mod refinement;
+pub mod chunk_routing;
 
 pub use autonomous::AutonomousAgent;
-pub use router::{GeminiRouterAgent, RouterAgent, SearchPlan, SearchQuery, WorkerPlan};
-pub use workers::{WorkerAgent, GeminiWorkerAgent, WorkerResult};
-pub use context_auditor::GeminiContextAuditor;
+pub use router::{GeminiRouterAgent, HeuristicRouterAgent, RouterAgent, SearchPlan, SearchQuery, WorkerPlan};
+pub use workers::{
+    chunks_token_cost, fit_worker_results_to_budget, WorkerAgent, GeminiWorkerAgent, WorkerOutputMode, WorkerResult,
+};
+pub use context_auditor::{audit_drops, AuditReport, GeminiContextAuditor, PatternContextAuditor, PreviewLengths};
+pub use no_llm::{NoLlmOutput, NoLlmPipeline};
+pub use colocation::CoLocationExpander;
+pub use streaming::{ContextUpdate, StreamingAssembler};
+pub use chunk_routing::{ChunkRouting, ContextBucket};
+pub use refinement::{refine_context, ContextFeedback};
+pub use summarizer::{SymbolSummarizer, DEFAULT_SUMMARIZE_THRESHOLD_LINES, SUMMARIZED_TAG};
 pub use tools::{Tool, ToolRegistry, ViewFileTool, ListDirTool, RunCommandTool, WriteFileTool};
-pub use prompt_registry::{PromptRegistry, SpecializedPrompt, PromptCategory, Priority};
+pub use prompt_registry::{CustomPromptConfig, PromptRegistry, SpecializedPrompt, PromptCategory, Priority};
 pub use enhanced_planner::{EnhancedPlanner, ExecutionPlan, PlanStep};
 pub use self_monitor::{SelfMonitor, HealthMetrics, HealthIssue};
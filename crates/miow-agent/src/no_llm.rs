@@ -0,0 +1,437 @@
+//! LLM-free context gathering.
+//!
+//! Wires the [`HeuristicRouterAgent`], keyword/graph search over the knowledge graph and the
+//! [`PatternContextAuditor`] into a single pipeline that produces a [`ContextData`] without any
+//! network calls. Useful for CI smoke checks and for sanity-checking indexing offline.
+
+use crate::colocation::CoLocationExpander;
+use crate::context_auditor::{audit_drops, AuditReport, PatternContextAuditor};
+use crate::router::{HeuristicRouterAgent, RouterAgent, SearchPlan};
+use anyhow::Result;
+use miow_core::{ContextIgnore, ProjectSignature};
+use miow_graph::{KnowledgeGraph, SymbolSearchResult};
+use miow_llm::{ContextItem, GatheredContext};
+use miow_prompt::{ConstantInfo, ContextConfig, ContextData, ContextReport, ContextStage, DesignTokenInfo, SchemaInfo, SymbolInfo, TypeInfo};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tracing::info;
+
+/// Symbol kinds that are members of another symbol and not useful on their own.
+const MEMBER_KINDS: [&str; 3] = ["Field", "Property", "EnumMember"];
+
+/// Default cap on design tokens and constants, which the auditor does not prune.
+const MAX_AUX_ITEMS: usize = 10;
+
+/// Result of a no-LLM pipeline run.
+#[derive(Debug, Clone)]
+pub struct NoLlmOutput {
+    pub plan: SearchPlan,
+    pub audit: AuditReport,
+    pub context: ContextData,
+    /// Items the audit dropped, with the tokens that saved
+    pub report: ContextReport,
+    /// Nothing scored above the relevance floor, so `context` was left empty; the caller
+    /// should ask for clarification rather than proceed
+    pub insufficient_context: bool,
+}
+
+/// Context-gathering pipeline that never calls an LLM.
+pub struct NoLlmPipeline {
+    graph: Arc<KnowledgeGraph>,
+    router: HeuristicRouterAgent,
+    auditor: PatternContextAuditor,
+    context_ignore: ContextIgnore,
+    current_file: Option<String>,
+    include_deprecated: bool,
+    co_location: Option<CoLocationExpander>,
+    max_aux_items: usize,
+    neighborhood_depth: usize,
+    relevance_floor: Option<f32>,
+}
+
+impl NoLlmPipeline {
+    pub fn new(graph: Arc<KnowledgeGraph>) -> Self {
+        Self {
+            graph,
+            router: HeuristicRouterAgent::new(),
+            auditor: PatternContextAuditor::default(),
+            context_ignore: ContextIgnore::empty(),
+            current_file: None,
+            include_deprecated: false,
+            co_location: None,
+            max_aux_items: MAX_AUX_ITEMS,
+            neighborhood_depth: 1,
+            relevance_floor: None,
+        }
+    }
+
+    pub fn with_auditor(mut self, auditor: PatternContextAuditor) -> Self {
+        self.auditor = auditor;
+        self
+    }
+
+    /// Audit with the config's threshold and rerank weights, cap design tokens and
+    /// constants at its per-category limit, expand hits to its neighborhood depth and apply
+    /// its relevance floor
+    pub fn with_context_config(mut self, config: &ContextConfig) -> Self {
+        self.auditor = PatternContextAuditor::from_config(config);
+        self.max_aux_items = config.max_items_per_category;
+        self.neighborhood_depth = config.neighborhood_depth;
+        self.relevance_floor = config.relevance_floor;
+        self
+    }
+
+    /// Return no context when the best reranked item scores below `floor`
+    pub fn with_relevance_floor(mut self, floor: Option<f32>) -> Self {
+        self.relevance_floor = floor;
+        self
+    }
+
+    /// Follow reference edges this many hops from each hit (1 by default, 0 disables)
+    pub fn with_neighborhood_depth(mut self, depth: usize) -> Self {
+        self.neighborhood_depth = depth;
+        self
+    }
+
+    /// Exclude `.miowignore`d files and symbols from the gathered context
+    pub fn with_context_ignore(mut self, context_ignore: ContextIgnore) -> Self {
+        self.context_ignore = context_ignore;
+        self
+    }
+
+    /// Scope router queries to these feature/domain directories when the prompt names one
+    pub fn with_feature_modules(mut self, feature_modules: Vec<String>) -> Self {
+        self.router = self.router.with_feature_modules(feature_modules);
+        self
+    }
+
+    /// Keep symbols tagged deprecated, which are excluded by default so dead APIs
+    /// aren't suggested for reuse
+    pub fn with_include_deprecated(mut self, include_deprecated: bool) -> Self {
+        self.include_deprecated = include_deprecated;
+        self
+    }
+
+    /// Also pull in selected symbols' co-located style, test and story files from disk.
+    /// Off by default since those files cost context budget.
+    pub fn with_co_location(mut self, expander: CoLocationExpander) -> Self {
+        self.co_location = Some(expander);
+        self
+    }
+
+    /// Rank symbols imported by, or next to, this file (relative to the project root) higher
+    pub fn with_current_file(mut self, file_path: impl Into<String>) -> Self {
+        self.current_file = Some(file_path.into());
+        self
+    }
+
+    /// Plan, search, audit and convert the result into prompt context.
+    pub async fn run(&self, user_prompt: &str, signature: &ProjectSignature) -> Result<NoLlmOutput> {
+        let mut plan = self.router.plan(user_prompt, signature).await?;
+        plan.dedup_queries();
+        let queries = plan.all_query_strings();
+        info!("no-llm router planned {} queries ({})", queries.len(), plan.global_intent);
+
+        let mut gathered = GatheredContext::default();
+        let mut lines: HashMap<(String, String), (i64, i64)> = HashMap::new();
+        let mut seen: HashSet<(String, String)> = HashSet::new();
+        let mut priorities: HashMap<(String, String), f32> = HashMap::new();
+
+        // Keyword search over symbol names, then the hits' neighborhoods along the reference
+        // graph, scored lower the further they are from a hit.
+        let mut matched = Vec::new();
+        for query in &queries {
+            matched.extend(self.graph.search_symbols(query)?);
+        }
+        if !self.include_deprecated {
+            matched.retain(|s| !s.is_deprecated());
+        }
+        let mut neighbours = Vec::new();
+        for symbol in &matched {
+            for neighbor in self.graph.expand_neighborhood(symbol, self.neighborhood_depth)? {
+                neighbours.push((neighbor.symbol, 0.5_f32.powi(neighbor.depth as i32)));
+            }
+        }
+        for (symbol, base_score) in matched.into_iter().map(|s| (s, 1.0)).chain(neighbours) {
+            if MEMBER_KINDS.contains(&symbol.kind.as_str())
+                || (!self.include_deprecated && symbol.is_deprecated())
+                || !seen.insert((symbol.name.clone(), symbol.file_path.clone()))
+            {
+                continue;
+            }
+            lines.insert(
+                (symbol.name.clone(), symbol.file_path.clone()),
+                (symbol.start_line, symbol.end_line),
+            );
+            if let Some(priority) = symbol.priority() {
+                priorities.insert((symbol.name.clone(), symbol.file_path.clone()), priority);
+            }
+            let bucket = match symbol.kind.as_str() {
+                "Component" => &mut gathered.components,
+                "Interface" | "Enum" | "Struct" | "TypeAlias" => &mut gathered.types,
+                _ => &mut gathered.helpers,
+            };
+            bucket.push(symbol_item(symbol, base_score));
+        }
+
+        for query in &queries {
+            for ty in self.graph.find_merged_type_definitions(query)? {
+                if seen.insert((ty.name.clone(), ty.file_path.clone())) {
+                    gathered.types.push(ContextItem {
+                        name: ty.name,
+                        kind: ty.kind,
+                        content: ty.definition,
+                        file_path: ty.file_path,
+                        relevance_score: 1.0,
+                        props: vec![],
+                        references: vec![],
+                        tags: vec![],
+                    });
+                }
+            }
+            for schema in self.graph.find_schemas(query)? {
+                if seen.insert((schema.name.clone(), schema.file_path.clone())) {
+                    gathered.schemas.push(ContextItem {
+                        name: schema.name,
+                        kind: schema.schema_type,
+                        content: schema.definition,
+                        file_path: schema.file_path,
+                        relevance_score: 1.0,
+                        props: vec![],
+                        references: vec![],
+                        tags: vec![],
+                    });
+                }
+            }
+            for constant in self.graph.find_constants(query)? {
+                if seen.insert((constant.name.clone(), constant.file_path.clone())) {
+                    gathered.constants.push(ContextItem {
+                        name: constant.name,
+                        kind: constant.category,
+                        content: constant.value,
+                        file_path: constant.file_path,
+                        relevance_score: 1.0,
+                        props: vec![],
+                        references: vec![],
+                        tags: vec![],
+                    });
+                }
+            }
+            for token in self.graph.find_design_tokens(query)? {
+                if seen.insert((token.name.clone(), token.file_path.clone())) {
+                    gathered.design_tokens.push(ContextItem {
+                        name: token.name,
+                        kind: token.token_type,
+                        content: token.value,
+                        file_path: token.file_path,
+                        relevance_score: 1.0,
+                        props: vec![],
+                        references: vec![],
+                        tags: vec![],
+                    });
+                }
+            }
+        }
+
+        let ignored = self.context_ignore.filter_gathered(&mut gathered);
+        if ignored > 0 {
+            info!("no-llm dropped {} items excluded by .miowignore", ignored);
+        }
+
+        let mut auditor = self.auditor.clone().with_symbol_priorities(priorities);
+        let document_frequencies = self.graph.document_frequencies()?;
+        if !document_frequencies.is_empty() {
+            auditor = auditor.with_document_frequencies(document_frequencies);
+        }
+        if let Some(current_file) = &self.current_file {
+            auditor = auditor.with_import_context(self.graph.import_context(current_file)?);
+        }
+        let before_audit = gathered.clone();
+        let audit = auditor.audit(user_prompt, &mut gathered);
+        info!("no-llm {}", audit.summary());
+        let mut report = ContextReport::default();
+        report.record(ContextStage::Audit, audit_drops(&before_audit, &gathered));
+
+        if let Some(floor) = self.relevance_floor {
+            let best = [&gathered.components, &gathered.helpers, &gathered.types, &gathered.schemas]
+                .into_iter()
+                .flatten()
+                .map(|item| item.relevance_score)
+                .fold(0.0_f32, f32::max);
+            if best < floor {
+                info!("no-llm best score {:.2} is below the relevance floor {:.2}", best, floor);
+                return Ok(NoLlmOutput {
+                    plan,
+                    audit,
+                    context: ContextData::default(),
+                    report,
+                    insufficient_context: true,
+                });
+            }
+        }
+
+        if let Some(expander) = &self.co_location {
+            let added = expander.expand(&mut gathered);
+            gathered
+                .similar_implementations
+                .retain(|item| !self.context_ignore.is_file_ignored(&item.file_path));
+            info!("no-llm added {} co-located files", added);
+        }
+
+        let context = to_context_data(gathered, &lines, self.max_aux_items);
+        Ok(NoLlmOutput {
+            plan,
+            audit,
+            context,
+            report,
+            insufficient_context: false,
+        })
+    }
+}
+
+fn symbol_item(symbol: SymbolSearchResult, relevance_score: f32) -> ContextItem {
+    ContextItem {
+        name: symbol.name,
+        kind: symbol.kind,
+        content: symbol.content,
+        file_path: symbol.file_path,
+        relevance_score,
+        props: vec![],
+        references: vec![],
+        tags: vec![],
+    }
+}
+
+fn to_context_data(
+    gathered: GatheredContext,
+    lines: &HashMap<(String, String), (i64, i64)>,
+    max_aux_items: usize,
+) -> ContextData {
+    let symbol_info = |item: ContextItem| {
+        let (start_line, end_line) = lines
+            .get(&(item.name.clone(), item.file_path.clone()))
+            .copied()
+            .unwrap_or((0, 0));
+        SymbolInfo {
+            name: item.name,
+            kind: item.kind,
+            content: item.content,
+            file_path: item.file_path,
+            start_line,
+            end_line,
+            props: item.props,
+            references: item.references,
+            language: String::new(),
+        }
+    };
+
+    ContextData {
+        relevant_symbols: gathered
+            .components
+            .into_iter()
+            .chain(gathered.helpers)
+            .map(symbol_info)
+            .collect(),
+        similar_symbols: gathered.similar_implementations.into_iter().map(symbol_info).collect(),
+        design_tokens: gathered
+            .design_tokens
+            .into_iter()
+            .take(max_aux_items)
+            .map(|t| DesignTokenInfo {
+                name: t.name,
+                value: t.content,
+                token_type: t.kind,
+            })
+            .collect(),
+        common_imports: Vec::new(),
+        types: gathered
+            .types
+            .into_iter()
+            .map(|t| TypeInfo {
+                name: t.name,
+                kind: t.kind,
+                definition: t.content,
+            })
+            .collect(),
+        constants: gathered
+            .constants
+            .into_iter()
+            .take(max_aux_items)
+            .map(|c| ConstantInfo {
+                name: c.name,
+                value: c.content,
+                category: c.kind,
+            })
+            .collect(),
+        schemas: gathered
+            .schemas
+            .into_iter()
+            .map(|s| SchemaInfo {
+                name: s.name,
+                schema_type: s.kind,
+                definition: s.content,
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miow_graph::{ParsedFileData, SymbolData};
+
+    fn fixture_graph() -> Arc<KnowledgeGraph> {
+        let graph = KnowledgeGraph::in_memory().unwrap();
+        for (file_path, name, content) in [
+            ("src/auth/LoginForm.tsx", "LoginForm", "export function LoginForm() { return <form />; }"),
+            ("src/app/layout.tsx", "AppLayout", "export function AppLayout({ children }) { return <main>{children}</main>; }"),
+        ] {
+            let parsed = ParsedFileData {
+                symbols: vec![SymbolData {
+                    name: name.to_string(),
+                    kind: "Component".to_string(),
+                    start_line: 1,
+                    end_line: 1,
+                    start_byte: 0,
+                    end_byte: content.len(),
+                    content: content.to_string(),
+                    metadata: "{}".to_string(),
+                    style_tags: None,
+                    children: vec![],
+                    references: vec![],
+                    qualified_name: None,
+                }],
+                imports: vec![],
+                design_tokens: vec![],
+                type_definitions: vec![],
+                constants: vec![],
+                schemas: vec![],
+                language: "tsx".to_string(),
+                exports: vec![],
+                tests: vec![],
+            };
+            graph.insert_file(file_path, &parsed).unwrap();
+        }
+        Arc::new(graph)
+    }
+
+    #[tokio::test]
+    async fn test_off_topic_prompt_reports_insufficient_context() {
+        let signature = ProjectSignature::default();
+        let prompt = "design a marketing page for the bakery";
+
+        // Without a floor the `page` → `layout` expansion pads the context with AppLayout
+        let unfloored = NoLlmPipeline::new(fixture_graph()).run(prompt, &signature).await.unwrap();
+        assert!(!unfloored.insufficient_context);
+        assert_eq!(unfloored.context.relevant_symbols[0].name, "AppLayout");
+
+        let pipeline = NoLlmPipeline::new(fixture_graph()).with_relevance_floor(Some(3.0));
+        let off_topic = pipeline.run(prompt, &signature).await.unwrap();
+        assert!(off_topic.insufficient_context);
+        assert!(off_topic.context.relevant_symbols.is_empty());
+
+        let on_topic = pipeline.run("add a remember-me checkbox to the login form", &signature).await.unwrap();
+        assert!(!on_topic.insufficient_context);
+        assert_eq!(on_topic.context.relevant_symbols[0].name, "LoginForm");
+    }
+}
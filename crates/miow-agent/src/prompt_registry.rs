@@ -1,5 +1,23 @@
+use anyhow::Context;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use tracing::warn;
+
+/// Placeholders workers know how to substitute into a prompt template
+pub const KNOWN_PLACEHOLDERS: [&str; 9] = [
+    "user_prompt",
+    "project_info",
+    "project_stack",
+    "file_path",
+    "error_message",
+    "file_list",
+    "package_managers",
+    "config_files",
+    "environment_vars",
+];
 
 /// A specialized prompt with its key, description, and template
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,6 +31,56 @@ pub struct SpecializedPrompt {
     pub provides_context: Vec<String>, // Context keys this prompt provides (e.g., "framework", "language")
 }
 
+impl SpecializedPrompt {
+    /// `{name}` placeholders in the template, in order of first appearance
+    pub fn placeholders(&self) -> Vec<String> {
+        let mut found: Vec<String> = Vec::new();
+        let mut rest = self.template.as_str();
+        while let Some(open) = rest.find('{') {
+            rest = &rest[open + 1..];
+            let Some(close) = rest.find('}') else { break };
+            let name = &rest[..close];
+            if is_placeholder_name(name) && !found.iter().any(|f| f == name) {
+                found.push(name.to_string());
+            }
+        }
+        found
+    }
+
+    /// Placeholders that no worker substitutes and would leak into the LLM input
+    pub fn unknown_placeholders(&self) -> Vec<String> {
+        self.placeholders()
+            .into_iter()
+            .filter(|p| !KNOWN_PLACEHOLDERS.contains(&p.as_str()))
+            .collect()
+    }
+
+    /// Substitute `values` into the template. Placeholders without a value are replaced
+    /// with an empty string (with a warning for unknown ones) rather than sent verbatim.
+    pub fn render(&self, values: &[(&str, &str)]) -> String {
+        let mut rendered = self.template.clone();
+        for name in self.placeholders() {
+            let value = match values.iter().find(|(key, _)| *key == name) {
+                Some((_, value)) => *value,
+                None => {
+                    if !KNOWN_PLACEHOLDERS.contains(&name.as_str()) {
+                        warn!("Prompt '{}' uses unknown placeholder {{{}}}; leaving it empty", self.key, name);
+                    }
+                    ""
+                }
+            };
+            rendered = rendered.replace(&format!("{{{}}}", name), value);
+        }
+        rendered
+    }
+}
+
+/// Identifier-like text between braces; JSON examples such as `{ "a": 1 }` don't qualify
+fn is_placeholder_name(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum PromptCategory {
     StackDetection,
@@ -35,6 +103,150 @@ pub enum Priority {
     Low,
 }
 
+/// Lowercase with `_`, `-` and spaces removed, so "Error Analysis", "error-analysis",
+/// "error_analysis" and "ErrorAnalysis" compare equal
+fn normalize_enum_name(s: &str) -> String {
+    s.chars()
+        .filter(|c| !matches!(c, '_' | '-' | ' '))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+impl PromptCategory {
+    pub const ALL: [PromptCategory; 10] = [
+        PromptCategory::StackDetection,
+        PromptCategory::TaskClassification,
+        PromptCategory::Frontend,
+        PromptCategory::Backend,
+        PromptCategory::Data,
+        PromptCategory::Security,
+        PromptCategory::Testing,
+        PromptCategory::Infrastructure,
+        PromptCategory::ErrorAnalysis,
+        PromptCategory::Documentation,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PromptCategory::StackDetection => "stack_detection",
+            PromptCategory::TaskClassification => "task_classification",
+            PromptCategory::Frontend => "frontend",
+            PromptCategory::Backend => "backend",
+            PromptCategory::Data => "data",
+            PromptCategory::Security => "security",
+            PromptCategory::Testing => "testing",
+            PromptCategory::Infrastructure => "infrastructure",
+            PromptCategory::ErrorAnalysis => "error_analysis",
+            PromptCategory::Documentation => "documentation",
+        }
+    }
+}
+
+impl fmt::Display for PromptCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PromptCategory {
+    type Err = anyhow::Error;
+
+    /// Case-insensitive; accepts snake_case, kebab-case, spaced or CamelCase names
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let name = normalize_enum_name(s);
+        Self::ALL
+            .into_iter()
+            .find(|category| normalize_enum_name(category.as_str()) == name)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown prompt category '{}' (expected one of: {})",
+                    s,
+                    Self::ALL.map(|c| c.as_str()).join(", ")
+                )
+            })
+    }
+}
+
+impl Priority {
+    pub const ALL: [Priority; 4] = [Priority::Critical, Priority::High, Priority::Medium, Priority::Low];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Priority::Critical => "critical",
+            Priority::High => "high",
+            Priority::Medium => "medium",
+            Priority::Low => "low",
+        }
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Priority {
+    type Err = anyhow::Error;
+
+    /// Case-insensitive, e.g. "high", "High" or "HIGH"
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let name = normalize_enum_name(s);
+        Self::ALL
+            .into_iter()
+            .find(|priority| priority.as_str() == name)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown prompt priority '{}' (expected one of: {})",
+                    s,
+                    Self::ALL.map(|p| p.as_str()).join(", ")
+                )
+            })
+    }
+}
+
+/// A user-defined prompt as written in a custom prompts file, with human-friendly
+/// `category`/`priority` strings such as `"frontend"` and `"high"`
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomPromptConfig {
+    pub key: String,
+    pub description: String,
+    pub template: String,
+    pub category: String,
+    #[serde(default = "default_custom_priority")]
+    pub priority: String,
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    #[serde(default)]
+    pub provides_context: Vec<String>,
+}
+
+fn default_custom_priority() -> String {
+    Priority::Medium.to_string()
+}
+
+impl TryFrom<CustomPromptConfig> for SpecializedPrompt {
+    type Error = anyhow::Error;
+
+    fn try_from(config: CustomPromptConfig) -> anyhow::Result<Self> {
+        Ok(SpecializedPrompt {
+            category: config
+                .category
+                .parse()
+                .with_context(|| format!("Invalid category for prompt '{}'", config.key))?,
+            priority: config
+                .priority
+                .parse()
+                .with_context(|| format!("Invalid priority for prompt '{}'", config.key))?,
+            key: config.key,
+            description: config.description,
+            template: config.template,
+            dependencies: config.dependencies,
+            provides_context: config.provides_context,
+        })
+    }
+}
+
 /// Registry of all specialized prompts for autonomous orchestration
 pub struct PromptRegistry {
     prompts: HashMap<String, SpecializedPrompt>,
@@ -249,6 +461,7 @@ Return JSON with analysis and relevant code locations."#.to_string(),
 
 Task: {user_prompt}
 Project: {project_info}
+Expected environment variables (from .env.example): {environment_vars}
 
 Search for:
 - Environment variable usage
@@ -379,6 +592,38 @@ Return JSON with refactoring suggestions and code examples."#.to_string(),
         }
     }
 
+    /// Add or replace a prompt, rejecting templates with placeholders no worker substitutes
+    pub fn register(&mut self, prompt: SpecializedPrompt) -> anyhow::Result<()> {
+        let unknown = prompt.unknown_placeholders();
+        if !unknown.is_empty() {
+            anyhow::bail!(
+                "Prompt '{}' uses unknown placeholders: {} (known: {})",
+                prompt.key,
+                unknown.iter().map(|p| format!("{{{}}}", p)).collect::<Vec<_>>().join(", "),
+                KNOWN_PLACEHOLDERS.join(", ")
+            );
+        }
+        self.prompts.insert(prompt.key.clone(), prompt);
+        Ok(())
+    }
+
+    /// Register every prompt in a JSON file holding an array of [`CustomPromptConfig`]s.
+    /// Fails on the first invalid prompt; returns how many were registered.
+    pub fn load_custom_prompts(&mut self, path: &Path) -> anyhow::Result<usize> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read custom prompts from {}", path.display()))?;
+        let configs: Vec<CustomPromptConfig> = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse custom prompts in {}", path.display()))?;
+
+        let count = configs.len();
+        for config in configs {
+            let prompt = SpecializedPrompt::try_from(config)?;
+            tracing::info!("Registering custom prompt '{}' ({}, {} priority)", prompt.key, prompt.category, prompt.priority);
+            self.register(prompt)?;
+        }
+        Ok(count)
+    }
+
     pub fn get_prompt(&self, key: &str) -> Option<&SpecializedPrompt> {
         self.prompts.get(key)
     }
@@ -439,3 +684,82 @@ impl Default for PromptRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt(template: &str) -> SpecializedPrompt {
+        SpecializedPrompt {
+            key: "custom_scanner".to_string(),
+            description: "Custom scanner".to_string(),
+            template: template.to_string(),
+            category: PromptCategory::Frontend,
+            priority: Priority::Medium,
+            dependencies: vec![],
+            provides_context: vec![],
+        }
+    }
+
+    #[test]
+    fn test_builtin_prompts_use_known_placeholders() {
+        let registry = PromptRegistry::new();
+        for prompt in registry.get_all_prompts().values() {
+            assert!(prompt.unknown_placeholders().is_empty(), "{} leaks placeholders", prompt.key);
+        }
+    }
+
+    #[test]
+    fn test_register_flags_unknown_placeholder() {
+        let mut registry = PromptRegistry::new();
+        let custom = prompt("Task: {user_prompt}\nRoutes: {route_list}\nReturn JSON like {\"routes\": []}");
+
+        assert_eq!(custom.placeholders(), vec!["user_prompt", "route_list"]);
+        let err = registry.register(custom.clone()).unwrap_err();
+        assert!(err.to_string().contains("{route_list}"));
+        assert!(registry.get_prompt("custom_scanner").is_none());
+
+        let rendered = custom.render(&[("user_prompt", "add a page")]);
+        assert_eq!(rendered, "Task: add a page\nRoutes: \nReturn JSON like {\"routes\": []}");
+
+        registry.register(prompt("Task: {user_prompt}")).unwrap();
+        assert!(registry.get_prompt("custom_scanner").is_some());
+    }
+
+    #[test]
+    fn test_priority_and_category_round_trip_strings() {
+        let priority: Priority = "high".parse().unwrap();
+        assert_eq!(priority, Priority::High);
+        assert_eq!(priority.to_string(), "high");
+        assert_eq!("CRITICAL".parse::<Priority>().unwrap(), Priority::Critical);
+
+        assert_eq!("Error Analysis".parse::<PromptCategory>().unwrap(), PromptCategory::ErrorAnalysis);
+        assert_eq!("stack-detection".parse::<PromptCategory>().unwrap(), PromptCategory::StackDetection);
+        for category in PromptCategory::ALL {
+            assert_eq!(category.to_string().parse::<PromptCategory>().unwrap(), category);
+        }
+
+        let err = "urgent".parse::<Priority>().unwrap_err();
+        assert!(err.to_string().contains("Unknown prompt priority 'urgent'"));
+        assert!("frontendish".parse::<PromptCategory>().is_err());
+    }
+
+    #[test]
+    fn test_load_custom_prompts_parses_friendly_names() {
+        let path = std::env::temp_dir().join(format!("miow-custom-prompts-{}.json", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"[{ "key": "route_scanner", "description": "Find routes", "template": "Task: {user_prompt}", "category": "frontend", "priority": "high" }]"#,
+        )
+        .unwrap();
+
+        let mut registry = PromptRegistry::new();
+        let loaded = registry.load_custom_prompts(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.unwrap(), 1);
+        let prompt = registry.get_prompt("route_scanner").unwrap();
+        assert_eq!(prompt.category, PromptCategory::Frontend);
+        assert_eq!(prompt.priority, Priority::High);
+    }
+}
@@ -0,0 +1,177 @@
+//! Refining a gathered context from user feedback. After a round of context has been used,
+//! the user can say what was missing ("missed the auth middleware") and what was noise
+//! ("`LegacyButton` is irrelevant"); `refine_context` turns that into a follow-up
+//! `SearchPlan` of targeted queries and exclusions instead of gathering from scratch.
+
+use crate::router::{SearchPlan, SearchQuery};
+use miow_prompt::ContextData;
+use serde::{Deserialize, Serialize};
+
+/// Leading words of a feedback phrase that carry no search signal
+const FILLER_WORDS: [&str; 9] = ["missing", "missed", "the", "a", "an", "our", "my", "no", "any"];
+
+/// Words in a missed item hinting at the `SearchQuery::kind` to look for
+const KIND_HINTS: [(&str, &str); 10] = [
+    ("component", "component"),
+    ("page", "component"),
+    ("hook", "function"),
+    ("type", "type"),
+    ("interface", "type"),
+    ("schema", "schema"),
+    ("validation", "schema"),
+    ("middleware", "api"),
+    ("endpoint", "api"),
+    ("route", "api"),
+];
+
+/// What the user said about a previously gathered context
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContextFeedback {
+    /// Things the context should have included, in the user's words ("the auth middleware")
+    #[serde(default)]
+    pub missed: Vec<String>,
+    /// Symbol names, file paths or descriptions of included items that were not relevant
+    #[serde(default)]
+    pub irrelevant: Vec<String>,
+}
+
+/// A follow-up plan for `previous` given `feedback`: one query per missed item and an
+/// exclusion per irrelevant item. Irrelevant items are resolved against `previous`, so
+/// "button" excludes the `Button` symbol and `src/legacy/` excludes everything under it;
+/// items matching nothing are excluded as written.
+pub fn refine_context(previous: &ContextData, feedback: &ContextFeedback) -> SearchPlan {
+    let search_queries = feedback
+        .missed
+        .iter()
+        .map(|item| search_terms(item))
+        .filter(|query| !query.is_empty())
+        .map(|query| SearchQuery {
+            kind: kind_hint(&query).map(str::to_string),
+            query,
+            target_paths: Vec::new(),
+        })
+        .collect();
+
+    let mut exclude = Vec::new();
+    for item in &feedback.irrelevant {
+        let item = item.trim();
+        if item.is_empty() {
+            continue;
+        }
+        let matches = matching_items(previous, item);
+        if matches.is_empty() {
+            push_unique(&mut exclude, item.to_string());
+        }
+        for matched in matches {
+            push_unique(&mut exclude, matched);
+        }
+    }
+
+    let mut plan = SearchPlan {
+        global_intent: "refine_context".to_string(),
+        search_queries,
+        workers: Vec::new(),
+        execution_plan: Vec::new(),
+        exclude,
+    };
+    plan.dedup_queries();
+    plan
+}
+
+fn push_unique(exclude: &mut Vec<String>, item: String) {
+    if !exclude.contains(&item) {
+        exclude.push(item);
+    }
+}
+
+/// `item` without leading filler words and surrounding punctuation
+fn search_terms(item: &str) -> String {
+    let words: Vec<&str> = item
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| c == '`' || c == '"' || c == '\'' || c == ',' || c == '.'))
+        .skip_while(|word| FILLER_WORDS.contains(&word.to_lowercase().as_str()))
+        .filter(|word| !word.is_empty())
+        .collect();
+    words.join(" ")
+}
+
+fn kind_hint(query: &str) -> Option<&'static str> {
+    let query = query.to_lowercase();
+    KIND_HINTS
+        .iter()
+        .find(|(word, _)| query.split_whitespace().any(|w| w.starts_with(word)))
+        .map(|(_, kind)| *kind)
+}
+
+/// Names of symbols and paths of files in `previous` that `item` refers to: a case-insensitive
+/// symbol name, a file path, or a directory prefix ending in `/`
+fn matching_items(previous: &ContextData, item: &str) -> Vec<String> {
+    let terms = search_terms(item);
+    let mut matches = Vec::new();
+    for symbol in previous.relevant_symbols.iter().chain(&previous.similar_symbols) {
+        if symbol.name.eq_ignore_ascii_case(&terms) {
+            matches.push(symbol.name.clone());
+        } else if symbol.file_path == terms {
+            matches.push(symbol.file_path.clone());
+        } else if terms.ends_with('/') && symbol.file_path.starts_with(&terms) {
+            matches.push(terms.clone());
+        }
+    }
+    for type_info in &previous.types {
+        if type_info.name.eq_ignore_ascii_case(&terms) {
+            matches.push(type_info.name.clone());
+        }
+    }
+    for schema in &previous.schemas {
+        if schema.name.eq_ignore_ascii_case(&terms) {
+            matches.push(schema.name.clone());
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miow_prompt::SymbolInfo;
+
+    fn symbol(name: &str, file_path: &str) -> SymbolInfo {
+        SymbolInfo {
+            name: name.to_string(),
+            kind: "function".to_string(),
+            content: String::new(),
+            file_path: file_path.to_string(),
+            start_line: 1,
+            end_line: 1,
+            props: vec![],
+            references: vec![],
+            language: "typescript".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_missed_middleware_becomes_query_and_irrelevant_becomes_exclusion() {
+        let previous = ContextData {
+            relevant_symbols: vec![symbol("LoginForm", "src/auth/LoginForm.tsx"), symbol("LegacyButton", "src/legacy/Button.tsx")],
+            similar_symbols: vec![],
+            design_tokens: vec![],
+            common_imports: vec![],
+            types: vec![],
+            constants: vec![],
+            schemas: vec![],
+        };
+        let feedback = ContextFeedback {
+            missed: vec!["missing the auth middleware".to_string()],
+            irrelevant: vec!["legacybutton".to_string(), "src/legacy/".to_string(), "analytics".to_string()],
+        };
+
+        let plan = refine_context(&previous, &feedback);
+
+        assert_eq!(plan.all_query_strings(), vec!["auth middleware"]);
+        assert_eq!(plan.search_queries[0].kind.as_deref(), Some("api"));
+        assert_eq!(plan.exclude, vec!["LegacyButton", "src/legacy/", "analytics"]);
+        assert!(plan.excludes("LegacyButton", "src/legacy/Button.tsx"));
+        assert!(plan.excludes("OldInput", "src/legacy/Input.tsx"));
+        assert!(!plan.excludes("LoginForm", "src/auth/LoginForm.tsx"));
+    }
+}
@@ -2,10 +2,12 @@ use crate::PromptRegistry;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use miow_core::ProjectSignature;
-use miow_llm::{LLMProvider, Message, Role};
+use miow_llm::{parse_llm_json, parse_llm_json_lenient, with_few_shot, FewShotExample, GenerationOptions, LLMProvider, Message, Role};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use tracing::warn;
 
 /// A single semantic search query the router wants to execute.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,6 +49,10 @@ pub struct SearchPlan {
     /// Execution order for workers (considering dependencies)
     #[serde(default)]
     pub execution_plan: Vec<String>, // Worker IDs in execution order
+    /// Symbol names or file paths to leave out of the gathered context, e.g. items the user
+    /// marked irrelevant in a previous round (see `refinement::refine_context`)
+    #[serde(default)]
+    pub exclude: Vec<String>,
 }
 
 impl SearchPlan {
@@ -68,11 +74,117 @@ impl SearchPlan {
         out
     }
 
+    /// Whether `exclude` rules out a symbol named `name` in `file_path`: an exact name, an
+    /// exact path, or a path prefix ending in `/`
+    pub fn excludes(&self, name: &str, file_path: &str) -> bool {
+        self.exclude.iter().any(|excluded| {
+            excluded == name
+                || excluded == file_path
+                || (excluded.ends_with('/') && file_path.starts_with(excluded.as_str()))
+        })
+    }
+
     pub fn is_empty(&self) -> bool {
         self.global_intent.trim().is_empty()
             && self.search_queries.is_empty()
             && self.workers.is_empty()
     }
+
+    /// Remove duplicate queries across general and worker queries, keeping the first
+    /// occurrence. Queries are compared trimmed, lowercased and with collapsed whitespace;
+    /// the kept query inherits the most specific `kind` and the union of `target_paths`
+    /// of its duplicates. Returns the number of queries removed.
+    pub fn dedup_queries(&mut self) -> usize {
+        let mut merged: HashMap<String, SearchQuery> = HashMap::new();
+        for query in self
+            .search_queries
+            .iter()
+            .chain(self.workers.iter().flat_map(|w| w.queries.iter()))
+        {
+            let key = normalize_query(&query.query);
+            match merged.get_mut(&key) {
+                Some(existing) => existing.merge_hints(query),
+                None => {
+                    merged.insert(key, query.clone());
+                }
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let mut removed = 0;
+        let mut keep = |query: &mut SearchQuery| {
+            let key = normalize_query(&query.query);
+            if key.is_empty() || !seen.insert(key.clone()) {
+                removed += 1;
+                return false;
+            }
+            if let Some(hints) = merged.remove(&key) {
+                query.kind = hints.kind;
+                query.target_paths = hints.target_paths;
+            }
+            true
+        };
+
+        self.search_queries.retain_mut(&mut keep);
+        for worker in &mut self.workers {
+            worker.queries.retain_mut(&mut keep);
+        }
+        removed
+    }
+}
+
+impl SearchQuery {
+    /// Fold another query's hints into this one: a specific kind beats `any`/none,
+    /// and target paths are unioned in order.
+    fn merge_hints(&mut self, other: &SearchQuery) {
+        let is_generic = |kind: &Option<String>| kind.as_deref().is_none_or(|k| k.trim().is_empty() || k == "any");
+        if (is_generic(&self.kind) && !is_generic(&other.kind)) || self.kind.is_none() {
+            self.kind = other.kind.clone();
+        }
+        for path in &other.target_paths {
+            if !self.target_paths.contains(path) {
+                self.target_paths.push(path.clone());
+            }
+        }
+    }
+}
+
+/// Response schema of the router plan, for providers with structured JSON output
+fn search_plan_schema() -> serde_json::Value {
+    let query = json!({
+        "type": "OBJECT",
+        "properties": {
+            "query": { "type": "STRING" },
+            "kind": { "type": "STRING", "enum": ["component", "type", "schema", "api", "style", "helper", "any"] },
+            "target_paths": { "type": "ARRAY", "items": { "type": "STRING" } }
+        },
+        "required": ["query"]
+    });
+    json!({
+        "type": "OBJECT",
+        "properties": {
+            "global_intent": { "type": "STRING" },
+            "search_queries": { "type": "ARRAY", "items": query },
+            "workers": {
+                "type": "ARRAY",
+                "items": {
+                    "type": "OBJECT",
+                    "properties": {
+                        "worker_id": { "type": "STRING" },
+                        "description": { "type": "STRING" },
+                        "queries": { "type": "ARRAY", "items": query }
+                    },
+                    "required": ["worker_id", "description"]
+                }
+            }
+        },
+        "required": ["global_intent", "search_queries", "workers"]
+    })
+}
+
+/// Normalize a query for duplicate detection: trim, lowercase and collapse whitespace.
+fn normalize_query(query: &str) -> String {
+    query.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
 }
 
 /// Trait for router agents that take a task + project context and produce a search plan.
@@ -104,6 +216,14 @@ impl GeminiRouterAgent {
     }
 }
 
+/// One worked example shown to the router before the real task, so it sees the expected plan shape.
+fn router_exemplar() -> FewShotExample {
+    FewShotExample::new(
+        "User task:\nAdd a password reset form\n\nDetected project description:\nReact + TypeScript app using Zod\n\nRecommended workers based on task type: frontend_scanner, backend_scanner, data_scanner, api_scanner\n",
+        r#"{"global_intent":"create_password_reset_form","search_queries":[{"query":"LoginForm","kind":"component","target_paths":["src/components"]},{"query":"password","kind":"schema","target_paths":[]},{"query":"User","kind":"type","target_paths":[]},{"query":"resetPassword","kind":"api","target_paths":[]}],"workers":[{"worker_id":"frontend_scanner","description":"Find existing form components to reuse","queries":[{"query":"Form","kind":"component","target_paths":["src/components"]}]},{"worker_id":"data_scanner","description":"Find auth types and validation schemas","queries":[{"query":"auth","kind":"schema","target_paths":[]}]}]}"#,
+    )
+}
+
 #[async_trait]
 impl RouterAgent for GeminiRouterAgent {
     async fn plan(
@@ -160,33 +280,32 @@ Guidelines:
             user_prompt, project_description, recommended_workers.join(", ")
         );
 
-        let messages = vec![
-            Message {
-                role: Role::System,
-                content: system_prompt.to_string(),
-            },
-            Message {
-                role: Role::User,
-                content: user_message,
-            },
-        ];
+        let messages = with_few_shot(
+            vec![
+                Message {
+                    role: Role::System,
+                    content: system_prompt.to_string(),
+                },
+                Message {
+                    role: Role::User,
+                    content: user_message,
+                },
+            ],
+            &[router_exemplar()],
+        );
 
+        let options = GenerationOptions::deterministic().with_json_output(Some(search_plan_schema()));
         let response = self
             .llm
-            .generate_with_context(messages)
+            .generate_with_options(messages, options)
             .await
             .context("Router LLM call failed")?;
 
-        // Strip possible markdown fences.
-        let raw = response.content.trim();
-        let clean = raw
-            .trim_start_matches("```json")
-            .trim_start_matches("```")
-            .trim_end_matches("```")
-            .trim();
-
         let plan: Result<SearchPlan> =
-            serde_json::from_str(clean).context("Failed to parse router JSON plan");
+            parse_llm_json_lenient(&response.content).context("Failed to parse router JSON plan");
+        if let Err(e) = &plan {
+            warn!("{:#}; falling back to recommended workers", e);
+        }
 
         match plan {
             Ok(mut p) if !p.is_empty() => {
@@ -256,13 +375,13 @@ impl GeminiRouterAgent {
         let classifier = self.registry.get_prompt("task_classifier")
             .ok_or_else(|| anyhow::anyhow!("task_classifier prompt not found"))?;
 
-        let template = &classifier.template;
         let project_info = project_signature.to_description();
 
-        let full_prompt = template
-            .replace("{user_prompt}", user_prompt)
-            .replace("{project_info}", &project_info)
-            .replace("{project_stack}", &project_info);
+        let full_prompt = classifier.render(&[
+            ("user_prompt", user_prompt),
+            ("project_info", &project_info),
+            ("project_stack", &project_info),
+        ]);
 
         let messages = vec![
             Message {
@@ -275,17 +394,12 @@ impl GeminiRouterAgent {
             },
         ];
 
-        let response = self.llm.generate_with_context(messages).await?;
-
-        // Try to parse JSON response
-        let clean = response.content
-            .trim()
-            .trim_start_matches("```json")
-            .trim_start_matches("```")
-            .trim_end_matches("```")
-            .trim();
+        let response = self
+            .llm
+            .generate_with_options(messages, GenerationOptions::deterministic())
+            .await?;
 
-        if let Ok(json) = serde_json::from_str::<serde_json::Value>(clean) {
+        if let Ok(json) = parse_llm_json::<serde_json::Value>(&response.content) {
             let task_type = json.get("task_type")
                 .and_then(|v| v.as_str())
                 .unwrap_or("feature")
@@ -335,6 +449,7 @@ impl GeminiRouterAgent {
             }],
             workers,
             execution_plan: vec![],
+            exclude: vec![],
         })
     }
 }
@@ -346,3 +461,199 @@ struct TaskClassification {
 }
 
 
+
+/// Words that carry no search signal in a task description.
+const HEURISTIC_STOP_WORDS: [&str; 32] = [
+    "a", "an", "the", "and", "or", "for", "to", "of", "in", "on", "with", "that", "this", "it",
+    "is", "be", "my", "our", "new", "please", "can", "you", "we", "i", "create", "add", "build",
+    "make", "implement", "fix", "update", "write",
+];
+
+/// Related terms searched alongside common task keywords.
+const HEURISTIC_EXPANSIONS: [(&str, &[&str]); 8] = [
+    ("login", &["auth", "signin", "session"]),
+    ("signup", &["auth", "register"]),
+    ("auth", &["session", "user"]),
+    ("page", &["layout"]),
+    ("form", &["input", "button"]),
+    ("modal", &["dialog"]),
+    ("api", &["fetch", "client"]),
+    ("profile", &["user", "avatar"]),
+];
+
+/// Router that plans searches from the prompt's keywords alone, without calling an LLM.
+///
+/// Used by the no-LLM pipeline for offline development and CI smoke checks.
+#[derive(Debug, Clone, Default)]
+pub struct HeuristicRouterAgent {
+    /// Feature/domain directories (see `ProjectSignature::feature_modules`)
+    feature_modules: Vec<String>,
+}
+
+impl HeuristicRouterAgent {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scope queries to the feature directories the prompt's keywords name
+    pub fn with_feature_modules(mut self, feature_modules: Vec<String>) -> Self {
+        self.feature_modules = feature_modules;
+        self
+    }
+
+    /// Feature directories whose name matches one of `terms`, ignoring a plural `s`
+    fn matching_feature_modules(&self, terms: &[String]) -> Vec<String> {
+        self.feature_modules
+            .iter()
+            .filter(|module| {
+                let name = module.rsplit('/').next().unwrap_or(module).to_lowercase();
+                let singular = name.strip_suffix('s').unwrap_or(&name);
+                terms.iter().any(|term| *term == name || term == singular)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Lowercased, de-duplicated keywords of `user_prompt` in order of appearance.
+    pub fn keywords(user_prompt: &str) -> Vec<String> {
+        let mut keywords: Vec<String> = Vec::new();
+        for word in user_prompt
+            .split(|c: char| !c.is_alphanumeric())
+            .map(|w| w.to_lowercase())
+        {
+            if word.len() < 3 || HEURISTIC_STOP_WORDS.contains(&word.as_str()) {
+                continue;
+            }
+            if !keywords.contains(&word) {
+                keywords.push(word);
+            }
+        }
+        keywords
+    }
+
+    fn intent_label(user_prompt: &str, keywords: &[String]) -> String {
+        let action = user_prompt
+            .split_whitespace()
+            .map(|w| w.to_lowercase())
+            .find(|w| ["create", "add", "build", "fix", "update", "implement"].contains(&w.as_str()))
+            .unwrap_or_else(|| "explore".to_string());
+
+        let mut parts = vec![action];
+        parts.extend(keywords.iter().take(3).cloned());
+        parts.join("_")
+    }
+}
+
+#[async_trait]
+impl RouterAgent for HeuristicRouterAgent {
+    async fn plan(
+        &self,
+        user_prompt: &str,
+        _project_signature: &ProjectSignature,
+    ) -> Result<SearchPlan> {
+        let keywords = Self::keywords(user_prompt);
+
+        let mut queries: Vec<String> = keywords.clone();
+        for keyword in &keywords {
+            if let Some((_, related)) = HEURISTIC_EXPANSIONS.iter().find(|(k, _)| k == keyword) {
+                for term in related.iter() {
+                    if !queries.iter().any(|q| q == term) {
+                        queries.push(term.to_string());
+                    }
+                }
+            }
+        }
+
+        let target_paths = self.matching_feature_modules(&queries);
+
+        if queries.is_empty() && !user_prompt.trim().is_empty() {
+            queries.push(user_prompt.trim().to_string());
+        }
+
+        Ok(SearchPlan {
+            global_intent: Self::intent_label(user_prompt, &keywords),
+            search_queries: queries
+                .into_iter()
+                .map(|query| SearchQuery {
+                    query,
+                    kind: Some("any".to_string()),
+                    target_paths: target_paths.clone(),
+                })
+                .collect(),
+            workers: Vec::new(),
+            execution_plan: Vec::new(),
+            exclude: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(text: &str, kind: Option<&str>, target_paths: &[&str]) -> SearchQuery {
+        SearchQuery {
+            query: text.to_string(),
+            kind: kind.map(String::from),
+            target_paths: target_paths.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_dedup_queries_across_workers() {
+        let mut plan = SearchPlan {
+            global_intent: "create_login_page".to_string(),
+            search_queries: vec![query("login form", None, &[])],
+            workers: vec![
+                WorkerPlan {
+                    worker_id: "ui".to_string(),
+                    description: "UI components".to_string(),
+                    queries: vec![query("Button", Some("any"), &["src/ui"])],
+                },
+                WorkerPlan {
+                    worker_id: "design".to_string(),
+                    description: "Design system".to_string(),
+                    queries: vec![
+                        query("  button ", Some("component"), &["src/components"]),
+                        query("Login   Form", Some("component"), &[]),
+                    ],
+                },
+            ],
+            execution_plan: vec![],
+            exclude: vec![],
+        };
+
+        let removed = plan.dedup_queries();
+
+        assert_eq!(removed, 2);
+        assert_eq!(plan.all_query_strings(), vec!["login form", "Button"]);
+        let button = &plan.workers[0].queries[0];
+        assert_eq!(button.kind.as_deref(), Some("component"));
+        assert_eq!(button.target_paths, vec!["src/ui", "src/components"]);
+        assert_eq!(plan.search_queries[0].kind.as_deref(), Some("component"));
+        assert!(plan.workers[1].queries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_heuristic_plan_targets_matching_feature_module() {
+        let router = HeuristicRouterAgent::new().with_feature_modules(vec![
+            "src/features/auth".to_string(),
+            "src/features/billing".to_string(),
+        ]);
+        let signature = ProjectSignature::default();
+
+        let plan = router.plan("Add a login page", &signature).await.unwrap();
+        assert!(plan.search_queries.iter().all(|q| q.target_paths == vec!["src/features/auth"]));
+
+        let plan = router.plan("Show a profile card", &signature).await.unwrap();
+        assert!(plan.search_queries.iter().all(|q| q.target_paths.is_empty()));
+    }
+
+    #[test]
+    fn test_router_exemplar_is_a_valid_plan() {
+        let example = router_exemplar();
+        let plan: SearchPlan = serde_json::from_str(&example.assistant).unwrap();
+        assert!(!plan.is_empty());
+        assert_eq!(plan.workers.len(), 2);
+    }
+}
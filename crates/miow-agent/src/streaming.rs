@@ -0,0 +1,346 @@
+//! Incremental context assembly: workers run level by level (a worker starts once the workers
+//! it depends on have finished) and each one's contribution is emitted as soon as it completes,
+//! so a frontend can render results before the whole plan is done.
+
+use crate::chunk_routing::ChunkRouting;
+use crate::context_auditor::{AuditReport, PatternContextAuditor};
+use crate::{PromptRegistry, SearchPlan, SearchQuery, WorkerAgent, WorkerResult};
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream, FuturesUnordered, StreamExt};
+use miow_common::{CodeChunk, Result as MiowResult};
+use miow_core::ProjectSignature;
+use miow_llm::{ContextItem, GatheredContext};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use tracing::warn;
+
+/// One event of a streaming context run
+#[derive(Debug, Clone)]
+pub enum ContextUpdate {
+    /// A worker finished; `context` holds only the items no earlier worker contributed
+    Worker {
+        worker_id: String,
+        level: usize,
+        context: GatheredContext,
+    },
+    /// A worker failed; the run continues without its contribution
+    WorkerFailed { worker_id: String, error: String },
+    /// Every worker finished: the merged context after auditing. Always the last update.
+    Complete {
+        context: GatheredContext,
+        audit: AuditReport,
+    },
+}
+
+/// Runs a plan's workers and streams their deduplicated contributions as they complete.
+pub struct StreamingAssembler {
+    worker: Arc<dyn WorkerAgent>,
+    registry: Arc<PromptRegistry>,
+    auditor: PatternContextAuditor,
+    routing: ChunkRouting,
+}
+
+impl StreamingAssembler {
+    pub fn new(worker: Arc<dyn WorkerAgent>, registry: Arc<PromptRegistry>) -> Self {
+        Self {
+            worker,
+            registry,
+            auditor: PatternContextAuditor::default(),
+            routing: ChunkRouting::default(),
+        }
+    }
+
+    /// Auditor that prunes the merged context before the final `Complete` update
+    pub fn with_auditor(mut self, auditor: PatternContextAuditor) -> Self {
+        self.auditor = auditor;
+        self
+    }
+
+    /// Routes deciding which context category each worker's chunks are merged into
+    pub fn with_chunk_routing(mut self, routing: ChunkRouting) -> Self {
+        self.routing = routing;
+        self
+    }
+
+    /// Group the plan's workers into dependency levels: a worker's level is one more than the
+    /// highest level among its planned dependencies. Dependencies outside the plan are ignored.
+    pub fn dependency_levels(&self, plan: &SearchPlan) -> Vec<Vec<String>> {
+        let order: Vec<String> = if plan.execution_plan.is_empty() {
+            plan.workers.iter().map(|w| w.worker_id.clone()).collect()
+        } else {
+            plan.execution_plan.clone()
+        };
+        let planned: HashSet<&str> = order.iter().map(String::as_str).collect();
+
+        let mut levels_by_worker: HashMap<String, usize> = HashMap::new();
+        let mut levels: Vec<Vec<String>> = Vec::new();
+        let mut remaining: VecDeque<String> = order.iter().cloned().collect();
+        let mut stalled = 0;
+        while let Some(worker_id) = remaining.pop_front() {
+            let dependencies: Vec<&String> = self
+                .registry
+                .get_prompt(&worker_id)
+                .map(|p| p.dependencies.iter().filter(|d| planned.contains(d.as_str())).collect())
+                .unwrap_or_default();
+            let resolved: Option<Vec<usize>> = dependencies
+                .iter()
+                .map(|d| levels_by_worker.get(d.as_str()).copied())
+                .collect();
+            let level = match resolved {
+                Some(dep_levels) => dep_levels.iter().max().map_or(0, |l| l + 1),
+                // A dependency is planned later; retry after it, unless it's a cycle
+                None if stalled <= remaining.len() => {
+                    stalled += 1;
+                    remaining.push_back(worker_id);
+                    continue;
+                }
+                None => levels.len(),
+            };
+            stalled = 0;
+            if levels.len() <= level {
+                levels.resize(level + 1, Vec::new());
+            }
+            levels[level].push(worker_id.clone());
+            levels_by_worker.insert(worker_id, level);
+        }
+        levels
+    }
+
+    /// Run the plan, yielding a `Worker` update per finished worker and a final `Complete`.
+    pub fn stream(
+        &self,
+        plan: &SearchPlan,
+        user_prompt: &str,
+        signature: &ProjectSignature,
+    ) -> BoxStream<'static, ContextUpdate> {
+        let queries: HashMap<String, Vec<SearchQuery>> = plan
+            .workers
+            .iter()
+            .map(|w| (w.worker_id.clone(), w.queries.clone()))
+            .collect();
+        let state = StreamState {
+            worker: self.worker.clone(),
+            auditor: self.auditor.clone(),
+            routing: self.routing.clone(),
+            user_prompt: user_prompt.to_string(),
+            signature: signature.clone(),
+            queries,
+            levels: self.dependency_levels(plan).into_iter().enumerate().collect(),
+            in_flight: FuturesUnordered::new(),
+            merged: GatheredContext::default(),
+            seen: HashSet::new(),
+            finished: false,
+        };
+        stream::unfold(state, |mut state| async move {
+            let update = state.next_update().await?;
+            Some((update, state))
+        })
+        .boxed()
+    }
+}
+
+type WorkerOutcome = (String, usize, MiowResult<WorkerResult>);
+
+struct StreamState {
+    worker: Arc<dyn WorkerAgent>,
+    auditor: PatternContextAuditor,
+    routing: ChunkRouting,
+    user_prompt: String,
+    signature: ProjectSignature,
+    queries: HashMap<String, Vec<SearchQuery>>,
+    levels: VecDeque<(usize, Vec<String>)>,
+    in_flight: FuturesUnordered<BoxFuture<'static, WorkerOutcome>>,
+    merged: GatheredContext,
+    seen: HashSet<(String, String)>,
+    finished: bool,
+}
+
+impl StreamState {
+    async fn next_update(&mut self) -> Option<ContextUpdate> {
+        if self.finished {
+            return None;
+        }
+        while self.in_flight.is_empty() {
+            let Some((level, worker_ids)) = self.levels.pop_front() else {
+                self.finished = true;
+                let mut context = std::mem::take(&mut self.merged);
+                let audit = self.auditor.audit(&self.user_prompt, &mut context);
+                return Some(ContextUpdate::Complete { context, audit });
+            };
+            for worker_id in worker_ids {
+                self.in_flight.push(self.run_worker(worker_id, level));
+            }
+        }
+
+        let (worker_id, level, result) = self.in_flight.next().await?;
+        match result {
+            Ok(result) => {
+                let context = self.merge(&result);
+                Some(ContextUpdate::Worker {
+                    worker_id,
+                    level,
+                    context,
+                })
+            }
+            Err(e) => {
+                warn!("Worker {} failed: {}", worker_id, e);
+                Some(ContextUpdate::WorkerFailed {
+                    worker_id,
+                    error: e.to_string(),
+                })
+            }
+        }
+    }
+
+    fn run_worker(&self, worker_id: String, level: usize) -> BoxFuture<'static, WorkerOutcome> {
+        let worker = self.worker.clone();
+        let user_prompt = self.user_prompt.clone();
+        let signature = self.signature.clone();
+        let queries = self.queries.get(&worker_id).cloned().unwrap_or_default();
+        Box::pin(async move {
+            let result = worker.execute(&worker_id, &user_prompt, &signature, &queries).await;
+            (worker_id, level, result)
+        })
+    }
+
+    /// Add a worker's chunks to the merged context; returns just the new ones
+    fn merge(&mut self, result: &WorkerResult) -> GatheredContext {
+        let mut contribution = GatheredContext::default();
+        for chunk in &result.chunks {
+            if !self.seen.insert((chunk.id.clone(), chunk.file_path.clone())) {
+                continue;
+            }
+            let item = chunk_item(chunk, result.confidence);
+            let bucket = self.routing.bucket(&result.worker_id, &chunk.kind);
+            bucket.items(&mut self.merged).push(item.clone());
+            bucket.items(&mut contribution).push(item);
+        }
+        contribution
+    }
+}
+
+fn chunk_item(chunk: &CodeChunk, confidence: f32) -> ContextItem {
+    ContextItem {
+        name: chunk.id.clone(),
+        kind: chunk.kind.clone(),
+        content: chunk.content.clone(),
+        file_path: chunk.file_path.clone(),
+        relevance_score: confidence,
+        props: vec![],
+        references: vec![],
+        tags: vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WorkerPlan;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// Worker that sleeps for a per-worker delay and returns fixed chunks
+    struct StaggeredWorker {
+        delays_ms: HashMap<&'static str, u64>,
+        chunks: HashMap<&'static str, Vec<(&'static str, &'static str)>>,
+        completed: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl WorkerAgent for StaggeredWorker {
+        async fn execute(
+            &self,
+            prompt_key: &str,
+            _user_prompt: &str,
+            _project_signature: &ProjectSignature,
+            _search_queries: &[SearchQuery],
+        ) -> MiowResult<WorkerResult> {
+            tokio::time::sleep(Duration::from_millis(self.delays_ms[prompt_key])).await;
+            self.completed.fetch_add(1, Ordering::SeqCst);
+            let chunks = self.chunks[prompt_key]
+                .iter()
+                .map(|(id, kind)| CodeChunk {
+                    id: id.to_string(),
+                    content: format!("export function {}() {{}}", id),
+                    file_path: format!("src/{}.tsx", id),
+                    language: "tsx".to_string(),
+                    start_line: 1,
+                    end_line: 1,
+                    kind: kind.to_string(),
+                    metadata: serde_json::Value::Null,
+                })
+                .collect();
+            Ok(WorkerResult {
+                worker_id: prompt_key.to_string(),
+                chunks,
+                summary: String::new(),
+                confidence: 0.8,
+                token_cost: 0,
+            })
+        }
+    }
+
+    fn worker_plan(worker_id: &str) -> WorkerPlan {
+        WorkerPlan {
+            worker_id: worker_id.to_string(),
+            description: String::new(),
+            queries: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_updates_arrive_as_workers_complete() {
+        // frontend and backend both depend on stack_detector; backend finishes first
+        let worker = Arc::new(StaggeredWorker {
+            delays_ms: HashMap::from([("stack_detector", 30), ("backend_scanner", 5), ("frontend_scanner", 200)]),
+            chunks: HashMap::from([
+                ("stack_detector", vec![("NextConfig", "config")]),
+                ("backend_scanner", vec![("createOrder", "function"), ("Order", "interface")]),
+                ("frontend_scanner", vec![("OrderForm", "component"), ("Order", "interface")]),
+            ]),
+            completed: AtomicUsize::new(0),
+        });
+        let plan = SearchPlan {
+            global_intent: "add_order_form".to_string(),
+            search_queries: vec![],
+            workers: ["frontend_scanner", "backend_scanner", "stack_detector"].map(worker_plan).to_vec(),
+            execution_plan: vec![],
+            exclude: vec![],
+        };
+        let assembler = StreamingAssembler::new(worker.clone(), Arc::new(PromptRegistry::new()));
+        assert_eq!(
+            assembler.dependency_levels(&plan),
+            vec![vec!["stack_detector".to_string()], vec!["frontend_scanner".to_string(), "backend_scanner".to_string()]]
+        );
+
+        let mut updates = assembler.stream(&plan, "add an order form", &ProjectSignature::default());
+
+        let Some(ContextUpdate::Worker { worker_id, level, context }) = updates.next().await else {
+            panic!("expected the stack detector's update first");
+        };
+        assert_eq!((worker_id.as_str(), level), ("stack_detector", 0));
+        assert_eq!(context.helpers[0].name, "NextConfig");
+        assert_eq!(worker.completed.load(Ordering::SeqCst), 1);
+
+        let Some(ContextUpdate::Worker { worker_id, context, .. }) = updates.next().await else {
+            panic!("expected the backend scanner's update");
+        };
+        assert_eq!(worker_id, "backend_scanner");
+        assert_eq!(context.types[0].name, "Order");
+        // The slow frontend worker is still running when the backend's update arrives
+        assert_eq!(worker.completed.load(Ordering::SeqCst), 2);
+
+        let Some(ContextUpdate::Worker { worker_id, level, context }) = updates.next().await else {
+            panic!("expected the frontend scanner's update");
+        };
+        assert_eq!((worker_id.as_str(), level), ("frontend_scanner", 1));
+        assert_eq!(context.components[0].name, "OrderForm");
+        assert!(context.types.is_empty(), "Order was already contributed by the backend scanner");
+
+        let Some(ContextUpdate::Complete { context, .. }) = updates.next().await else {
+            panic!("expected the final update");
+        };
+        assert_eq!(context.components.len() + context.helpers.len() + context.types.len(), 4);
+        assert!(updates.next().await.is_none());
+    }
+}
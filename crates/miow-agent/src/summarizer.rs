@@ -0,0 +1,209 @@
+//! Summarization of oversized symbols: instead of truncating a 500-line component, its body is
+//! replaced by an LLM-written summary of props, key behavior and exports, keeping the signature.
+
+use anyhow::Result;
+use miow_llm::{ContextItem, GatheredContext, LLMProvider};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::warn;
+
+/// Tag added to context items whose body was replaced by a summary
+pub const SUMMARIZED_TAG: &str = "summarized";
+
+/// Symbols longer than this many lines are summarized by default
+pub const DEFAULT_SUMMARIZE_THRESHOLD_LINES: usize = 200;
+
+/// The signature is taken from at most this many leading lines
+const MAX_SIGNATURE_LINES: usize = 12;
+
+/// Replaces the bodies of oversized context items with LLM-generated summaries.
+pub struct SymbolSummarizer {
+    llm: Arc<dyn LLMProvider>,
+    threshold_lines: usize,
+}
+
+impl SymbolSummarizer {
+    pub fn new(llm: Arc<dyn LLMProvider>) -> Self {
+        Self {
+            llm,
+            threshold_lines: DEFAULT_SUMMARIZE_THRESHOLD_LINES,
+        }
+    }
+
+    pub fn with_threshold_lines(mut self, threshold_lines: usize) -> Self {
+        self.threshold_lines = threshold_lines;
+        self
+    }
+
+    /// Summarize every oversized item in place. Never fails hard – an item whose summary
+    /// cannot be generated keeps its original content. Returns how many items were summarized.
+    pub async fn summarize(&self, gathered: &mut GatheredContext) -> usize {
+        let mut summarized = 0;
+        for items in [
+            &mut gathered.components,
+            &mut gathered.helpers,
+            &mut gathered.types,
+            &mut gathered.constants,
+            &mut gathered.schemas,
+            &mut gathered.similar_implementations,
+        ] {
+            for item in items.iter_mut() {
+                match self.summarize_item(item).await {
+                    Ok(true) => summarized += 1,
+                    Ok(false) => {}
+                    Err(e) => warn!("Failed to summarize {} ({}): {}", item.name, item.file_path, e),
+                }
+            }
+        }
+        summarized
+    }
+
+    /// Summarize a single item if it exceeds the threshold; returns whether it was replaced.
+    pub async fn summarize_item(&self, item: &mut ContextItem) -> Result<bool> {
+        let line_count = item.content.lines().count();
+        if line_count <= self.threshold_lines || item.tags.iter().any(|t| t == SUMMARIZED_TAG) {
+            return Ok(false);
+        }
+
+        let prompt = format!(
+            r#"Summarize this {kind} `{name}` from {file} for a developer who cannot see its source.
+Cover, as short bullet points:
+- its props / parameters and their purpose
+- its key behavior (state, effects, data flow, side effects)
+- what the file exports alongside it
+Respond with the bullet points only.
+
+```
+{content}
+```"#,
+            kind = item.kind,
+            name = item.name,
+            file = item.file_path,
+            content = item.content,
+        );
+        let response = self.llm.generate(&prompt).await?;
+        let summary = response.content.trim();
+        if summary.is_empty() {
+            anyhow::bail!("empty summary");
+        }
+
+        let comment = comment_prefix(&item.file_path);
+        let mut content = signature(&item.content);
+        content.push('\n');
+        content.push_str(&format!(
+            "{} [summarized: {} lines omitted]\n",
+            comment, line_count
+        ));
+        for line in summary.lines() {
+            content.push_str(&format!("{} {}\n", comment, line.trim_end()));
+        }
+        item.content = content;
+        item.tags.push(SUMMARIZED_TAG.to_string());
+        Ok(true)
+    }
+}
+
+/// Leading lines of a symbol up to where its body opens, kept verbatim.
+fn signature(content: &str) -> String {
+    let mut lines = Vec::new();
+    for line in content.lines().take(MAX_SIGNATURE_LINES) {
+        lines.push(line);
+        let trimmed = line.trim_end();
+        if trimmed.ends_with('{') || trimmed.ends_with("=>") || trimmed.ends_with(':') {
+            break;
+        }
+    }
+    lines.join("\n")
+}
+
+fn comment_prefix(file_path: &str) -> &'static str {
+    match Path::new(file_path).extension().and_then(|e| e.to_str()) {
+        Some("py") | Some("rb") | Some("sh") => "#",
+        _ => "//",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miow_llm::{LLMResponse, Message};
+
+    struct FixedSummary;
+
+    #[async_trait::async_trait]
+    impl LLMProvider for FixedSummary {
+        async fn generate(&self, _prompt: &str) -> Result<LLMResponse> {
+            Ok(LLMResponse {
+                content: "- props: items, onSelect\n- renders a filterable list".to_string(),
+                finish_reason: Some("stop".to_string()),
+                usage: None,
+            })
+        }
+
+        async fn generate_with_context(&self, _messages: Vec<Message>) -> Result<LLMResponse> {
+            self.generate("").await
+        }
+
+        async fn stream_generate(
+            &self,
+            _prompt: &str,
+        ) -> Result<Box<dyn futures::Stream<Item = Result<String>> + Unpin>> {
+            Ok(Box::new(futures::stream::empty()))
+        }
+
+        async fn generate_multi_step(&self, _steps: Vec<String>, _context: &str) -> Result<LLMResponse> {
+            self.generate("").await
+        }
+
+        async fn generate_with_framework(&self, _prompt: &str, _framework: &str, _lang: &str) -> Result<LLMResponse> {
+            self.generate("").await
+        }
+    }
+
+    fn component(name: &str, body_lines: usize) -> ContextItem {
+        let mut content = format!("export function {}({{ items, onSelect }}: Props) {{\n", name);
+        for i in 0..body_lines {
+            content.push_str(&format!("  const step{} = items[{}];\n", i, i));
+        }
+        content.push_str("}\n");
+        ContextItem {
+            name: name.to_string(),
+            kind: "component".to_string(),
+            content,
+            file_path: format!("src/components/{}.tsx", name),
+            relevance_score: 0.9,
+            props: vec![],
+            references: vec![],
+            tags: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_oversized_symbol_replaced_by_summary() {
+        let small = component("Badge", 5);
+        let mut gathered = GatheredContext {
+            components: vec![component("ProductTable", 500), small.clone()],
+            helpers: vec![],
+            types: vec![],
+            design_tokens: vec![],
+            constants: vec![],
+            schemas: vec![],
+            similar_implementations: vec![],
+        };
+
+        let summarizer = SymbolSummarizer::new(Arc::new(FixedSummary)).with_threshold_lines(100);
+        assert_eq!(summarizer.summarize(&mut gathered).await, 1);
+
+        let big = &gathered.components[0];
+        assert_eq!(big.tags, vec![SUMMARIZED_TAG.to_string()]);
+        assert!(big
+            .content
+            .starts_with("export function ProductTable({ items, onSelect }: Props) {\n"));
+        assert!(big.content.contains("// - renders a filterable list"));
+        assert!(big.content.lines().count() < 10);
+
+        let unchanged = &gathered.components[1];
+        assert_eq!(unchanged.content, small.content);
+        assert!(unchanged.tags.is_empty());
+    }
+}
@@ -2,10 +2,13 @@ use crate::{SearchQuery, SpecializedPrompt, PromptRegistry};
 use async_trait::async_trait;
 use miow_common::{CodeChunk, Result as MiowResult};
 use miow_core::ProjectSignature;
-use miow_llm::{LLMProvider, Message, Role};
+use miow_llm::{parse_llm_json, parse_llm_json_lenient, LLMProvider, Message, Role};
+use miow_prompt::{CharRatioEstimator, TokenEstimator};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
+use std::collections::HashMap;
 use std::sync::Arc;
+use tracing::warn;
 
 /// Result from running a worker agent
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +17,35 @@ pub struct WorkerResult {
     pub chunks: Vec<CodeChunk>,
     pub summary: String,
     pub confidence: f32,
+    /// Estimated tokens of `chunks`, for deciding which workers to drop under a budget
+    pub token_cost: usize,
+}
+
+/// Estimated tokens of a worker's chunks
+pub fn chunks_token_cost(chunks: &[CodeChunk], estimator: &dyn TokenEstimator) -> usize {
+    chunks.iter().map(|chunk| estimator.estimate(&chunk.content)).sum()
+}
+
+/// Drop worker results until their combined `token_cost` fits in `budget`: the least
+/// confident go first and, among equally confident ones, the most expensive.
+pub fn fit_worker_results_to_budget(results: &mut Vec<WorkerResult>, budget: usize) {
+    let mut total: usize = results.iter().map(|r| r.token_cost).sum();
+    while total > budget {
+        let Some(drop) = results
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.confidence.total_cmp(&b.confidence).then(b.token_cost.cmp(&a.token_cost)))
+            .map(|(i, _)| i)
+        else {
+            break;
+        };
+        let dropped = results.remove(drop);
+        warn!(
+            "Dropping worker '{}' ({} tokens, confidence {:.2}) to fit the {} token budget",
+            dropped.worker_id, dropped.token_cost, dropped.confidence, budget
+        );
+        total -= dropped.token_cost;
+    }
 }
 
 /// Trait for worker agents that execute specialized prompts
@@ -28,10 +60,25 @@ pub trait WorkerAgent: Send + Sync {
     ) -> MiowResult<WorkerResult>;
 }
 
+/// How a worker is asked to format its answer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WorkerOutputMode {
+    /// Structured JSON validated against the worker's schema
+    #[default]
+    Json,
+    /// Free text with fenced code blocks, each preceded by its file path
+    Prose,
+}
+
+/// Appended to the prompt of workers running in `WorkerOutputMode::Prose`
+const PROSE_OUTPUT_INSTRUCTIONS: &str = "Respond in prose instead of JSON. Put every code excerpt in a fenced code block tagged with its language, with the file path on its own line directly above the fence, e.g.\nsrc/components/Button.tsx:\n```tsx\n...\n```";
+
 /// LLM-backed worker agent that can execute any specialized prompt
 pub struct GeminiWorkerAgent {
     llm: Arc<dyn LLMProvider>,
     registry: Arc<PromptRegistry>,
+    output_modes: HashMap<String, WorkerOutputMode>,
+    estimator: Arc<dyn TokenEstimator>,
 }
 
 impl GeminiWorkerAgent {
@@ -39,14 +86,30 @@ impl GeminiWorkerAgent {
         Self {
             llm,
             registry: registry.clone(),
+            output_modes: HashMap::new(),
+            estimator: Arc::new(CharRatioEstimator::default()),
         }
     }
 
     pub fn new_with_registry(llm: Arc<dyn LLMProvider>) -> Self {
-        Self {
-            llm,
-            registry: Arc::new(PromptRegistry::new()),
-        }
+        Self::new(llm, Arc::new(PromptRegistry::new()))
+    }
+
+    /// Estimate `WorkerResult::token_cost` with `estimator` instead of the default char ratio
+    pub fn with_estimator(mut self, estimator: Arc<dyn TokenEstimator>) -> Self {
+        self.estimator = estimator;
+        self
+    }
+
+    /// Ask the worker `prompt_key` for output in `mode` (JSON by default), for models that
+    /// follow prose instructions more reliably than JSON schemas
+    pub fn with_output_mode(mut self, prompt_key: impl Into<String>, mode: WorkerOutputMode) -> Self {
+        self.output_modes.insert(prompt_key.into(), mode);
+        self
+    }
+
+    pub fn output_mode(&self, prompt_key: &str) -> WorkerOutputMode {
+        self.output_modes.get(prompt_key).copied().unwrap_or_default()
     }
 }
 
@@ -65,22 +128,30 @@ impl WorkerAgent for GeminiWorkerAgent {
             ))?;
 
         // Build the full prompt by substituting variables
-        let template = &prompt.template;
         let project_info = project_signature.to_description();
         let query_list = search_queries.iter()
             .map(|q| format!("- {} ({})", q.query, q.kind.as_deref().unwrap_or("any")))
             .collect::<Vec<_>>()
             .join("\n");
 
-        let full_prompt = template
-            .replace("{user_prompt}", user_prompt)
-            .replace("{project_info}", &project_info)
-            .replace("{project_stack}", &project_info)
-            .replace("{file_path}", "") // Could be enhanced to pass specific files
-            .replace("{error_message}", "") // Could be enhanced for error analysis
-            .replace("{file_list}", "") // Could be enhanced with FileMap
-            .replace("{package_managers}", "") // Could be enhanced with package info
-            .replace("{config_files}", ""); // Could be enhanced with config detection
+        let environment_vars = match project_signature.environment_vars.as_slice() {
+            [] => "none declared".to_string(),
+            names => names.join(", "),
+        };
+
+        // file_path, error_message, file_list, package_managers and config_files are left
+        // empty for now; unknown placeholders are blanked with a warning.
+        let mut full_prompt = prompt.render(&[
+            ("user_prompt", user_prompt),
+            ("project_info", &project_info),
+            ("project_stack", &project_info),
+            ("environment_vars", &environment_vars),
+        ]);
+        let mode = self.output_mode(prompt_key);
+        if mode == WorkerOutputMode::Prose {
+            full_prompt.push_str("\n\n");
+            full_prompt.push_str(PROSE_OUTPUT_INSTRUCTIONS);
+        }
 
         // Create messages for LLM
         let messages = vec![
@@ -95,72 +166,86 @@ impl WorkerAgent for GeminiWorkerAgent {
         ];
 
         // Call LLM
-        let response = self.llm.generate_with_context(messages)
+        let response = self.llm.generate_with_context(messages.clone())
             .await
             .map_err(|e| miow_common::MiowError::Llm(e.to_string()))?;
+        let mut content = response.content;
 
-        // Parse response (this would be specific to each prompt type)
-        // For now, return a basic result - in practice, each worker would have custom parsing
-        let chunks = self.parse_llm_response(prompt_key, &response.content)?;
+        // Validate against the worker's output schema, giving the LLM one chance to fix its JSON.
+        if let Some(schema) = worker_output_schema(prompt_key).filter(|_| mode == WorkerOutputMode::Json) {
+            if let Err(errors) = validate_worker_output(&schema, &content) {
+                warn!("Worker '{}' returned invalid JSON ({}), asking it to fix the output", prompt_key, errors);
+                content = self.retry_with_schema(messages, content, &schema, &errors).await;
+            }
+        }
+
+        let chunks = parse_worker_response(prompt_key, &content, mode);
+        let token_cost = chunks_token_cost(&chunks, self.estimator.as_ref());
 
         Ok(WorkerResult {
             worker_id: prompt_key.to_string(),
             chunks,
             summary: format!("Executed {} worker", prompt_key),
             confidence: 0.8, // Could be calculated based on response quality
+            token_cost,
         })
     }
 }
 
 impl GeminiWorkerAgent {
-    /// Parse LLM response into CodeChunk objects (basic implementation)
-    fn parse_llm_response(&self, prompt_key: &str, response: &str) -> MiowResult<Vec<CodeChunk>> {
-        // This is a simplified parser - in practice, each worker type would have
-        // custom JSON schema parsing based on what it returns
-
-        // Try to parse as JSON first
-        if let Ok(json) = serde_json::from_str::<serde_json::Value>(response) {
-            if let Some(array) = json.as_array() {
-                let mut chunks = Vec::new();
-                for item in array {
-                    if let Some(obj) = item.as_object() {
-                        let chunk = CodeChunk {
-                            id: format!("{}-{}", prompt_key, chunks.len()),
-                            content: obj.get("content")
-                                .or_else(|| obj.get("definition"))
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("")
-                                .to_string(),
-                            file_path: obj.get("file_path")
-                                .or_else(|| obj.get("path"))
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("")
-                                .to_string(),
-                            language: obj.get("language")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("unknown")
-                                .to_string(),
-                            start_line: 0,
-                            end_line: 0,
-                            kind: obj.get("kind")
-                                .or_else(|| obj.get("type"))
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("unknown")
-                                .to_string(),
-                            metadata: json!({
-                                "worker": prompt_key,
-                                "description": obj.get("description").and_then(|v| v.as_str()).unwrap_or("")
-                            }),
-                        };
-                        chunks.push(chunk);
-                    }
+    /// Re-prompt the worker with the schema violations. Returns the corrected response if it
+    /// validates, otherwise the original one so parsing can fall back.
+    async fn retry_with_schema(
+        &self,
+        mut messages: Vec<Message>,
+        previous: String,
+        schema: &Value,
+        errors: &str,
+    ) -> String {
+        messages.push(Message {
+            role: Role::Assistant,
+            content: previous.clone(),
+        });
+        messages.push(Message {
+            role: Role::User,
+            content: format!(
+                "Your previous response did not match the required JSON schema:\n{}\n\nFix your JSON to match this schema and respond with JSON only:\n{}",
+                errors,
+                serde_json::to_string_pretty(schema).unwrap_or_default()
+            ),
+        });
+
+        match self.llm.generate_with_context(messages).await {
+            Ok(retry) => match validate_worker_output(schema, &retry.content) {
+                Ok(_) => retry.content,
+                Err(errors) => {
+                    warn!("Worker output still invalid after retry ({}), falling back", errors);
+                    previous
                 }
-                return Ok(chunks);
+            },
+            Err(e) => {
+                warn!("Worker schema retry failed: {}, falling back", e);
+                previous
             }
         }
+    }
+}
+
+/// Parse a worker response into chunks. JSON mode reads the worker's JSON array (or
+/// `{ analysis, locations }` object); prose mode extracts fenced code blocks. Either mode
+/// falls back to the other format, then to a single chunk holding the raw response.
+pub fn parse_worker_response(prompt_key: &str, response: &str, mode: WorkerOutputMode) -> Vec<CodeChunk> {
+    let chunks = match mode {
+        WorkerOutputMode::Json => {
+            parse_json_chunks(prompt_key, response).or_else(|| parse_prose_chunks(prompt_key, response))
+        }
+        WorkerOutputMode::Prose => {
+            parse_prose_chunks(prompt_key, response).or_else(|| parse_json_chunks(prompt_key, response))
+        }
+    };
 
-        // Fallback: create a single chunk with the raw response
-        Ok(vec![CodeChunk {
+    chunks.unwrap_or_else(|| {
+        vec![CodeChunk {
             id: format!("{}-fallback", prompt_key),
             content: response.to_string(),
             file_path: format!("{}_analysis.txt", prompt_key),
@@ -172,11 +257,314 @@ impl GeminiWorkerAgent {
                 "worker": prompt_key,
                 "fallback": true
             }),
-        }])
+        }]
+    })
+}
+
+fn parse_json_chunks(prompt_key: &str, response: &str) -> Option<Vec<CodeChunk>> {
+    let json = parse_llm_json_lenient::<Value>(response)
+        .map_err(|e| warn!("Worker {}: {:#}", prompt_key, e))
+        .ok()?;
+    // Analysis-style workers return { analysis, locations: [...] }
+    let array = json.as_array().or_else(|| json.get("locations").and_then(|l| l.as_array()))?;
+    let analysis = json.get("analysis").and_then(|v| v.as_str()).unwrap_or("");
+    let mut chunks = Vec::new();
+    for item in array {
+        if let Some(obj) = item.as_object() {
+            let chunk = CodeChunk {
+                id: format!("{}-{}", prompt_key, chunks.len()),
+                content: obj.get("content")
+                    .or_else(|| obj.get("definition"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string(),
+                file_path: obj.get("file_path")
+                    .or_else(|| obj.get("path"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string(),
+                language: obj.get("language")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown")
+                    .to_string(),
+                start_line: 0,
+                end_line: 0,
+                kind: obj.get("kind")
+                    .or_else(|| obj.get("type"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown")
+                    .to_string(),
+                metadata: json!({
+                    "worker": prompt_key,
+                    "description": obj.get("description").and_then(|v| v.as_str()).unwrap_or(analysis)
+                }),
+            };
+            chunks.push(chunk);
+        }
     }
+    Some(chunks)
+}
+
+/// Extract fenced code blocks, taking the file path from the line directly above each fence
+/// (`src/a.ts:`, `**src/a.ts**`, `` `src/a.ts` ``, `### File: src/a.ts`) and the prose before
+/// it as the description. `None` when the response has no fenced blocks.
+fn parse_prose_chunks(prompt_key: &str, response: &str) -> Option<Vec<CodeChunk>> {
+    let mut chunks = Vec::new();
+    let mut prose: Vec<&str> = Vec::new();
+    let mut lines = response.lines();
+
+    while let Some(line) = lines.next() {
+        let Some(info) = line.trim_start().strip_prefix("```") else {
+            if !line.trim().is_empty() {
+                prose.push(line.trim());
+            }
+            continue;
+        };
+
+        let body: Vec<&str> = lines.by_ref().take_while(|l| !l.trim_start().starts_with("```")).collect();
+        let file_path = prose.last().and_then(|header| header_path(header));
+        if file_path.is_some() {
+            prose.pop();
+        }
+        let language = info.split_whitespace().next().unwrap_or("");
+        if language == "json" && file_path.is_none() {
+            // A fenced JSON answer, not a code excerpt
+            return None;
+        }
+
+        chunks.push(CodeChunk {
+            id: format!("{}-{}", prompt_key, chunks.len()),
+            content: body.join("\n"),
+            file_path: file_path.unwrap_or_default(),
+            language: if language.is_empty() { "unknown" } else { language }.to_string(),
+            start_line: 0,
+            end_line: 0,
+            kind: "snippet".to_string(),
+            metadata: json!({
+                "worker": prompt_key,
+                "description": prose.join(" ")
+            }),
+        });
+        prose.clear();
+    }
+
+    (!chunks.is_empty()).then_some(chunks)
+}
+
+/// The file path named by a header line, if it names one
+fn header_path(header: &str) -> Option<String> {
+    let mut header = header.trim_start_matches('#').trim();
+    for prefix in ["File:", "file:", "Path:", "path:"] {
+        if let Some(rest) = header.strip_prefix(prefix) {
+            header = rest.trim();
+        }
+    }
+    let path = header
+        .trim_end_matches(':')
+        .trim_matches(|c| c == '*' || c == '`')
+        .trim_end_matches(':');
+
+    let is_path = !path.is_empty()
+        && !path.contains(char::is_whitespace)
+        && path.rsplit('/').next().is_some_and(|name| name.contains('.') && !name.ends_with('.'));
+    is_path.then(|| path.to_string())
+}
+
+/// JSON schema that a worker category's output must satisfy, if the category has one.
+pub fn worker_output_schema(prompt_key: &str) -> Option<Value> {
+    let location = json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "file_path": { "type": "string" },
+            "description": { "type": "string" },
+            "content": { "type": "string" },
+            "kind": { "type": "string" }
+        },
+        "anyOf": [
+            { "required": ["path"] },
+            { "required": ["file_path"] }
+        ]
+    });
+
+    match prompt_key {
+        "frontend_scanner" | "backend_scanner" | "data_scanner" | "auth_scanner"
+        | "api_scanner" | "test_scanner" | "config_scanner" => {
+            let mut snippet = location;
+            snippet["required"] = json!(["description"]);
+            Some(json!({
+                "type": "array",
+                "items": snippet
+            }))
+        }
+        "error_analyzer" => Some(json!({
+            "type": "object",
+            "required": ["analysis", "locations"],
+            "properties": {
+                "analysis": { "type": "string" },
+                "locations": { "type": "array", "items": location }
+            }
+        })),
+        _ => None,
+    }
+}
+
+/// Validate a raw worker response against `schema`, returning the parsed JSON or a
+/// summary of the violations.
+pub fn validate_worker_output(schema: &Value, response: &str) -> Result<Value, String> {
+    let instance: Value =
+        parse_llm_json(response).map_err(|e| format!("response is not valid JSON: {:#}", e))?;
+    let compiled = jsonschema::JSONSchema::compile(schema)
+        .map_err(|e| format!("invalid worker schema: {}", e))?;
+
+    if let Err(errors) = compiled.validate(&instance) {
+        let messages: Vec<String> = errors
+            .map(|e| format!("{} at '{}'", e, e.instance_path))
+            .collect();
+        return Err(messages.join("; "));
+    }
+
+    Ok(instance)
 }
 
 /// Factory function to create worker agents
 pub fn create_worker_agent(llm: Arc<dyn LLMProvider>) -> Box<dyn WorkerAgent> {
     Box::new(GeminiWorkerAgent::new_with_registry(llm))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_frontend_worker_response() {
+        let schema = worker_output_schema("frontend_scanner").unwrap();
+        let response = r#"```json
+[
+  { "path": "src/components/Button.tsx", "description": "Shared button", "kind": "component" },
+  { "file_path": "src/styles/theme.ts", "description": "Design tokens" }
+]
+```"#;
+
+        let parsed = validate_worker_output(&schema, response).unwrap();
+        assert_eq!(parsed.as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_frontend_worker_response() {
+        let schema = worker_output_schema("frontend_scanner").unwrap();
+
+        // Missing description and path
+        let err = validate_worker_output(&schema, r#"[{ "content": "export const x = 1" }]"#).unwrap_err();
+        assert!(err.contains("description"));
+
+        // Object instead of array
+        assert!(validate_worker_output(&schema, r#"{ "snippets": [] }"#).is_err());
+
+        // Prose
+        let err = validate_worker_output(&schema, "The Button component lives in src/").unwrap_err();
+        assert!(err.contains("not valid JSON"));
+    }
+
+    #[test]
+    fn test_parse_json_mode_response() {
+        let response = r#"```json
+[
+  { "path": "src/components/Button.tsx", "description": "Shared button", "kind": "component", "content": "export function Button() {}" },
+  { "file_path": "src/styles/theme.ts", "description": "Design tokens" }
+]
+```"#;
+
+        let chunks = parse_worker_response("frontend_scanner", response, WorkerOutputMode::Json);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].file_path, "src/components/Button.tsx");
+        assert_eq!(chunks[0].kind, "component");
+        assert_eq!(chunks[0].content, "export function Button() {}");
+        assert_eq!(chunks[1].metadata["description"], "Design tokens");
+    }
+
+    #[test]
+    fn test_parse_prose_mode_response() {
+        let response = "The login flow reuses the shared button.\n\nsrc/components/Button.tsx:\n```tsx\nexport function Button() {\n  return <button />;\n}\n```\n\nSessions are created here:\n**src/lib/auth.ts**\n```ts\nexport async function signIn() {}\n```\n\nA loose example:\n```\nsignIn();\n```\n";
+
+        let chunks = parse_worker_response("frontend_scanner", response, WorkerOutputMode::Prose);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].file_path, "src/components/Button.tsx");
+        assert_eq!(chunks[0].language, "tsx");
+        assert_eq!(chunks[0].content, "export function Button() {\n  return <button />;\n}");
+        assert_eq!(chunks[0].metadata["description"], "The login flow reuses the shared button.");
+        assert_eq!(chunks[1].file_path, "src/lib/auth.ts");
+        assert_eq!(chunks[1].metadata["description"], "Sessions are created here:");
+        assert_eq!(chunks[2].file_path, "");
+        assert_eq!(chunks[2].language, "unknown");
+
+        // Prose without code falls back to a single raw chunk; a JSON answer still parses
+        let fallback = parse_worker_response("frontend_scanner", "Nothing relevant found.", WorkerOutputMode::Prose);
+        assert_eq!(fallback.len(), 1);
+        assert_eq!(fallback[0].metadata["fallback"], true);
+        let json = parse_worker_response("frontend_scanner", r#"[{ "path": "src/a.ts", "description": "A" }]"#, WorkerOutputMode::Prose);
+        assert_eq!(json[0].file_path, "src/a.ts");
+    }
+
+    struct FixedResponse(String);
+
+    #[async_trait]
+    impl LLMProvider for FixedResponse {
+        async fn generate(&self, _prompt: &str) -> anyhow::Result<miow_llm::LLMResponse> {
+            Ok(miow_llm::LLMResponse {
+                content: self.0.clone(),
+                finish_reason: Some("stop".to_string()),
+                usage: None,
+            })
+        }
+
+        async fn generate_with_context(&self, _messages: Vec<Message>) -> anyhow::Result<miow_llm::LLMResponse> {
+            self.generate("").await
+        }
+
+        async fn stream_generate(
+            &self,
+            _prompt: &str,
+        ) -> anyhow::Result<Box<dyn futures::Stream<Item = anyhow::Result<String>> + Unpin>> {
+            Ok(Box::new(futures::stream::empty()))
+        }
+
+        async fn generate_multi_step(&self, _steps: Vec<String>, _context: &str) -> anyhow::Result<miow_llm::LLMResponse> {
+            self.generate("").await
+        }
+
+        async fn generate_with_framework(&self, _prompt: &str, _framework: &str, _lang: &str) -> anyhow::Result<miow_llm::LLMResponse> {
+            self.generate("").await
+        }
+    }
+
+    async fn run_worker(content: &str) -> WorkerResult {
+        let response = json!([{ "path": "src/components/Button.tsx", "description": "Shared button", "content": content }]);
+        let worker = GeminiWorkerAgent::new_with_registry(Arc::new(FixedResponse(response.to_string())));
+        worker
+            .execute("frontend_scanner", "add a button", &ProjectSignature::default(), &[])
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_larger_chunks_report_higher_token_cost() {
+        let small = run_worker("export function Button() {}").await;
+        let large = run_worker(&"export function Button() { return <button className=\"btn\" />; }\n".repeat(20)).await;
+        assert!(small.token_cost > 0);
+        assert!(large.token_cost > small.token_cost);
+
+        // Over budget, the expensive result goes first when confidence ties
+        let (small_cost, large_cost) = (small.token_cost, large.token_cost);
+        let mut results = vec![large, small];
+        fit_worker_results_to_budget(&mut results, small_cost + large_cost - 1);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].token_cost, small_cost);
+    }
+
+    #[test]
+    fn test_workers_without_schema() {
+        assert!(worker_output_schema("documentation_scanner").is_none());
+        assert!(worker_output_schema("error_analyzer").is_some());
+    }
+}
@@ -1,6 +1,22 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use miow_llm::{GenerationOptions, LLMProvider, Message, Role};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use tracing::warn;
+
+const INTENT_SYSTEM_PROMPT: &str = r#"You classify a developer's request to a coding assistant.
+Pick the single best intent:
+- Create: add something new that isn't a component, function or page
+- CreateComponent, CreateFunction, CreatePage: add a new UI component / function or helper / page or screen
+- Modify: change existing behavior on purpose
+- Fix: something is broken or behaves wrongly (bugs, errors, "doesn't work")
+- Refactor: restructure or optimize without changing behavior
+- Unknown: none of the above
+Also list the code entities (components, functions, types, files) the request names.
+
+You MUST respond with JSON only, matching:
+{ "intent": "Fix", "entities": ["Button"] }
+"#;
 
 /// Context analyzer - analyzes user prompts and finds relevant context
 pub struct ContextAnalyzer;
@@ -24,6 +40,56 @@ impl ContextAnalyzer {
         }
     }
 
+    /// Analyze each task of a multi-task input (a backlog) in order. Pair with
+    /// `SharedSearchTerms::union` to gather context for all of them in one search pass.
+    pub fn analyze_prompts(&self, prompts: &[String]) -> Vec<AnalyzedPrompt> {
+        prompts.iter().map(|prompt| self.analyze_prompt(prompt)).collect()
+    }
+
+    /// Like `analyze_prompt`, but asks the LLM for the intent and entities, which handles
+    /// prompts keyword matching misreads ("the button doesn't respond when clicked" is a fix).
+    /// Falls back to the heuristic analysis without an LLM or when the call or parsing fails.
+    pub async fn analyze_prompt_llm(&self, prompt: &str, llm: Option<&dyn LLMProvider>) -> AnalyzedPrompt {
+        let heuristic = self.analyze_prompt(prompt);
+        let Some(llm) = llm else {
+            return heuristic;
+        };
+        match Self::classify_with_llm(prompt, llm).await {
+            Ok(classification) => AnalyzedPrompt {
+                intent: classification.intent,
+                entities: if classification.entities.is_empty() {
+                    heuristic.entities
+                } else {
+                    classification.entities
+                },
+                ..heuristic
+            },
+            Err(e) => {
+                warn!("LLM intent classification failed, using keyword heuristics: {:#}", e);
+                heuristic
+            }
+        }
+    }
+
+    async fn classify_with_llm(prompt: &str, llm: &dyn LLMProvider) -> Result<IntentClassification> {
+        let messages = vec![
+            Message {
+                role: Role::System,
+                content: INTENT_SYSTEM_PROMPT.to_string(),
+            },
+            Message {
+                role: Role::User,
+                content: prompt.to_string(),
+            },
+        ];
+        let response = llm
+            .generate_with_options(messages, GenerationOptions::deterministic())
+            .await
+            .context("intent classification LLM call failed")?;
+
+        miow_llm::parse_llm_json(&response.content).context("failed to parse intent classification JSON")
+    }
+
     /// Extract keywords from the prompt
     fn extract_keywords(&self, prompt: &str) -> Vec<String> {
         let stop_words: HashSet<&str> = [
@@ -187,6 +253,54 @@ pub struct AnalyzedPrompt {
     pub entities: Vec<String>,
 }
 
+/// Keywords and entities of several analyzed tasks, for one context search shared by all of
+/// them instead of a search per task. Overlapping tasks contribute each term once.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SharedSearchTerms {
+    /// Keywords in order of first appearance across the tasks, without duplicates
+    pub keywords: Vec<String>,
+    /// Entities in order of first appearance across the tasks, without duplicates
+    pub entities: Vec<String>,
+}
+
+impl SharedSearchTerms {
+    /// Union of the keywords and entities of `analyzed`
+    pub fn union(analyzed: &[AnalyzedPrompt]) -> Self {
+        let mut terms = Self::default();
+        for prompt in analyzed {
+            for keyword in &prompt.keywords {
+                if !terms.keywords.contains(keyword) {
+                    terms.keywords.push(keyword.clone());
+                }
+            }
+            for entity in &prompt.entities {
+                if !terms.entities.contains(entity) {
+                    terms.entities.push(entity.clone());
+                }
+            }
+        }
+        terms
+    }
+
+    /// Search query covering every term: entities first, then keywords they don't already name
+    pub fn query(&self) -> String {
+        let mut terms: Vec<&str> = self.entities.iter().map(String::as_str).collect();
+        for keyword in &self.keywords {
+            if !self.entities.iter().any(|entity| entity.eq_ignore_ascii_case(keyword)) {
+                terms.push(keyword);
+            }
+        }
+        terms.join(" ")
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct IntentClassification {
+    intent: PromptIntent,
+    #[serde(default)]
+    entities: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum PromptIntent {
     Create,
@@ -228,4 +342,76 @@ mod tests {
         let prompt2 = "Fix the authentication bug";
         assert_eq!(analyzer.analyze_prompt(prompt2).intent, PromptIntent::Fix);
     }
+
+    #[test]
+    fn test_related_prompts_share_one_keyword_set() {
+        let analyzer = ContextAnalyzer::new();
+        let prompts = vec![
+            "Add a password reset form to the LoginPage".to_string(),
+            "Fix password validation on the LoginPage".to_string(),
+        ];
+        let analyzed = analyzer.analyze_prompts(&prompts);
+        assert_eq!(analyzed.len(), 2);
+        assert_eq!(analyzed[1].intent, PromptIntent::Fix);
+
+        let shared = SharedSearchTerms::union(&analyzed);
+        assert_eq!(shared.keywords, ["password", "reset", "form", "loginpage", "fix", "validation"]);
+        assert_eq!(shared.entities, ["Add", "LoginPage", "Fix"]);
+        assert_eq!(shared.query(), "Add LoginPage Fix password reset form validation");
+    }
+
+    /// Replies with a fixed response, or errors when there is none
+    struct ScriptedLLM(Option<&'static str>);
+
+    #[async_trait::async_trait]
+    impl LLMProvider for ScriptedLLM {
+        async fn generate(&self, _prompt: &str) -> Result<miow_llm::LLMResponse> {
+            match self.0 {
+                Some(content) => Ok(miow_llm::LLMResponse {
+                    content: content.to_string(),
+                    finish_reason: Some("stop".to_string()),
+                    usage: None,
+                }),
+                None => anyhow::bail!("provider unavailable"),
+            }
+        }
+
+        async fn generate_with_context(&self, _messages: Vec<Message>) -> Result<miow_llm::LLMResponse> {
+            self.generate("").await
+        }
+
+        async fn stream_generate(
+            &self,
+            _prompt: &str,
+        ) -> Result<Box<dyn futures::Stream<Item = Result<String>> + Unpin>> {
+            Ok(Box::new(futures::stream::empty()))
+        }
+
+        async fn generate_multi_step(&self, _steps: Vec<String>, _context: &str) -> Result<miow_llm::LLMResponse> {
+            self.generate("").await
+        }
+
+        async fn generate_with_framework(&self, _prompt: &str, _framework: &str, _lang: &str) -> Result<miow_llm::LLMResponse> {
+            self.generate("").await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_llm_intent_with_heuristic_fallback() {
+        let analyzer = ContextAnalyzer::new();
+        // "new" misleads the keyword heuristic into Create
+        let prompt = "the new Button doesn't respond when clicked";
+        assert_eq!(analyzer.analyze_prompt(prompt).intent, PromptIntent::Create);
+
+        let llm = ScriptedLLM(Some("```json\n{ \"intent\": \"Fix\", \"entities\": [\"Button\"] }\n```"));
+        let analyzed = analyzer.analyze_prompt_llm(prompt, Some(&llm)).await;
+        assert_eq!(analyzed.intent, PromptIntent::Fix);
+        assert_eq!(analyzed.entities, vec!["Button".to_string()]);
+        assert!(analyzed.keywords.contains(&"button".to_string()));
+
+        let failing = ScriptedLLM(None);
+        let fallback = analyzer.analyze_prompt_llm(prompt, Some(&failing)).await;
+        assert_eq!(fallback.intent, PromptIntent::Create);
+        assert_eq!(analyzer.analyze_prompt_llm(prompt, None).await.intent, PromptIntent::Create);
+    }
 }
@@ -0,0 +1,158 @@
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use miow_llm::{ContextItem, GatheredContext};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File name of the context-inclusion ignore file at the project root
+pub const CONTEXT_IGNORE_FILE: &str = ".miowignore";
+
+/// Prefix marking a `.miowignore` line as a symbol-name glob instead of a path pattern
+const SYMBOL_PREFIX: &str = "symbol:";
+
+/// Exclusions applied to search and context results before they are injected into a prompt.
+///
+/// Unlike the indexer's walk ignores, files matched here are still indexed into the graph;
+/// they are only kept out of the final context. Lines use gitignore syntax, except lines
+/// starting with `symbol:` which hold a glob matched against symbol names:
+///
+/// ```text
+/// src/components/icons.tsx
+/// legacy/**
+/// symbol:Deprecated*
+/// ```
+#[derive(Debug, Clone)]
+pub struct ContextIgnore {
+    root: PathBuf,
+    files: Gitignore,
+    symbols: GlobSet,
+}
+
+impl ContextIgnore {
+    /// Ignore nothing
+    pub fn empty() -> Self {
+        Self {
+            root: PathBuf::new(),
+            files: Gitignore::empty(),
+            symbols: GlobSet::empty(),
+        }
+    }
+
+    /// Load `<root>/.miowignore`; a missing file ignores nothing
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(CONTEXT_IGNORE_FILE);
+        if !path.exists() {
+            return Ok(Self::empty());
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::from_patterns(root, &content)
+    }
+
+    /// Build from `.miowignore` content, with path patterns relative to `root`
+    pub fn from_patterns(root: &Path, content: &str) -> Result<Self> {
+        let mut files = GitignoreBuilder::new(root);
+        let mut symbols = GlobSetBuilder::new();
+
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if let Some(pattern) = trimmed.strip_prefix(SYMBOL_PREFIX) {
+                symbols.add(Glob::new(pattern.trim()).with_context(|| format!("Invalid symbol pattern: {}", trimmed))?);
+            } else {
+                files
+                    .add_line(None, line)
+                    .with_context(|| format!("Invalid ignore pattern: {}", trimmed))?;
+            }
+        }
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            files: files.build()?,
+            symbols: symbols.build()?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.symbols.is_empty()
+    }
+
+    /// Whether `file_path` (relative to the root, or absolute under it) is excluded
+    pub fn is_file_ignored(&self, file_path: &str) -> bool {
+        if self.files.is_empty() || file_path.is_empty() {
+            return false;
+        }
+        let path = Path::new(file_path);
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        if relative.is_absolute() {
+            return false;
+        }
+        self.files.matched_path_or_any_parents(relative, false).is_ignore()
+    }
+
+    pub fn is_symbol_ignored(&self, symbol_name: &str) -> bool {
+        self.symbols.is_match(symbol_name)
+    }
+
+    /// Whether a symbol named `symbol_name` defined in `file_path` is excluded
+    pub fn is_ignored(&self, file_path: &str, symbol_name: &str) -> bool {
+        self.is_symbol_ignored(symbol_name) || self.is_file_ignored(file_path)
+    }
+
+    /// Drop excluded items from every category; returns how many were removed
+    pub fn filter_gathered(&self, gathered: &mut GatheredContext) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        let mut removed = 0;
+        let mut keep = |item: &ContextItem| {
+            let ignored = self.is_ignored(&item.file_path, &item.name);
+            removed += ignored as usize;
+            !ignored
+        };
+
+        gathered.components.retain(&mut keep);
+        gathered.helpers.retain(&mut keep);
+        gathered.types.retain(&mut keep);
+        gathered.design_tokens.retain(&mut keep);
+        gathered.constants.retain(&mut keep);
+        gathered.schemas.retain(&mut keep);
+        gathered.similar_implementations.retain(&mut keep);
+        removed
+    }
+}
+
+impl Default for ContextIgnore {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_and_symbol_patterns() {
+        let ignore = ContextIgnore::from_patterns(
+            Path::new("/repo"),
+            "# noisy files\nsrc/icons.tsx\nlegacy/\nsymbol:Deprecated*\n",
+        )
+        .unwrap();
+
+        assert!(ignore.is_file_ignored("src/icons.tsx"));
+        assert!(ignore.is_file_ignored("/repo/src/icons.tsx"));
+        assert!(ignore.is_file_ignored("legacy/old/Button.tsx"));
+        assert!(!ignore.is_file_ignored("src/Button.tsx"));
+        assert!(ignore.is_ignored("src/Button.tsx", "DeprecatedButton"));
+        assert!(!ignore.is_ignored("src/Button.tsx", "Button"));
+    }
+
+    #[test]
+    fn test_missing_file_ignores_nothing() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let ignore = ContextIgnore::load(temp_dir.path()).unwrap();
+        assert!(ignore.is_empty());
+        assert!(!ignore.is_ignored("src/icons.tsx", "Icon"));
+    }
+}
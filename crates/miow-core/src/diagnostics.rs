@@ -0,0 +1,106 @@
+//! Index health reporting.
+//!
+//! A completed indexing run writes a small JSON manifest (when, how many files, how many
+//! failed to parse, the detected signature). `CodebaseIndexer::diagnostics` combines it with
+//! live counts from the knowledge graph and vector store for a `miow status`-style overview.
+
+use crate::project_signature::ProjectSignature;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Default manifest file name, stored next to the graph database in `.miow/`
+pub const INDEX_MANIFEST_FILE: &str = "index_manifest.json";
+
+/// Summary of the last completed indexing run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexManifest {
+    pub indexed_at_secs: u64,
+    pub total_files: usize,
+    pub parse_errors: usize,
+    pub duration_ms: u128,
+    pub signature: ProjectSignature,
+}
+
+impl IndexManifest {
+    pub fn new(total_files: usize, parse_errors: usize, duration_ms: u128, signature: ProjectSignature) -> Self {
+        let indexed_at_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        Self {
+            indexed_at_secs,
+            total_files,
+            parse_errors,
+            duration_ms,
+            signature,
+        }
+    }
+
+    /// The manifest at `path`, or `None` if no run has completed yet
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read index manifest {:?}", path))?;
+        let manifest = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid index manifest {:?}", path))?;
+        Ok(Some(manifest))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write index manifest {:?}", path))
+    }
+}
+
+/// Health of an index, as reported by `CodebaseIndexer::diagnostics`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexDiagnostics {
+    /// Files in the knowledge graph, or in the last run's manifest without a graph
+    pub files_indexed: usize,
+    /// Symbols in the knowledge graph (0 without a graph)
+    pub symbols_indexed: usize,
+    /// Points in the vector store; `None` when no store is attached or it is unreachable
+    pub vector_count: Option<u64>,
+    pub signature: Option<ProjectSignature>,
+    /// Unix time the last run completed, from the manifest
+    pub last_indexed_secs: Option<u64>,
+    /// Files with a parser that failed to parse in the last run
+    pub parse_errors: usize,
+    /// Groups of files importing each other in a cycle
+    pub import_cycles: Vec<Vec<String>>,
+}
+
+impl IndexDiagnostics {
+    /// Human-readable lines for a status command
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("Files indexed: {}", self.files_indexed),
+            format!("Symbols indexed: {}", self.symbols_indexed),
+            match self.vector_count {
+                Some(count) => format!("Vector store points: {}", count),
+                None => "Vector store: unavailable".to_string(),
+            },
+            match self.last_indexed_secs {
+                Some(secs) => format!("Last indexed: {} (unix time)", secs),
+                None => "Last indexed: never".to_string(),
+            },
+            format!("Parse errors: {}", self.parse_errors),
+        ];
+        if let Some(signature) = &self.signature {
+            lines.push(format!("Signature: {}", signature.to_description()));
+        }
+        lines.push(format!("Import cycles: {}", self.import_cycles.len()));
+        for cycle in &self.import_cycles {
+            lines.push(format!("  {}", cycle.join(" <-> ")));
+        }
+        lines
+    }
+}
@@ -0,0 +1,108 @@
+//! Conversion from parser output to the knowledge graph's insert types
+
+use miow_graph::{
+    ConstantData, DesignTokenData, ExportData, ImportData, ParsedFileData, SchemaData, SymbolData, TestCaseData,
+    TypeDefinitionData,
+};
+use miow_parsers::{ParsedFile, Symbol};
+
+/// Convert parser output into the rows `KnowledgeGraph::insert_file` stores
+pub fn to_graph_data(parsed: ParsedFile) -> ParsedFileData {
+    ParsedFileData {
+        symbols: parsed.symbols.into_iter().map(convert_symbol).collect(),
+        imports: parsed
+            .imports
+            .into_iter()
+            .map(|imp| ImportData {
+                source: imp.source,
+                names: imp.names.into_iter().map(|n| n.name).collect(),
+                start_line: imp.range.start_line,
+                end_line: imp.range.end_line,
+            })
+            .collect(),
+        design_tokens: parsed
+            .design_tokens
+            .into_iter()
+            .map(|token| DesignTokenData {
+                token_type: format!("{:?}", token.token_type),
+                name: token.name,
+                value: token.value,
+                context: token.context,
+                start_line: token.range.start_line,
+                end_line: token.range.end_line,
+            })
+            .collect(),
+        type_definitions: parsed
+            .type_definitions
+            .into_iter()
+            .map(|td| TypeDefinitionData {
+                name: td.name,
+                kind: format!("{:?}", td.kind),
+                definition: td.definition,
+                start_line: td.range.start_line,
+                end_line: td.range.end_line,
+            })
+            .collect(),
+        constants: parsed
+            .constants
+            .into_iter()
+            .map(|c| ConstantData {
+                name: c.name,
+                value: c.value,
+                category: format!("{:?}", c.category),
+                start_line: c.range.start_line,
+                end_line: c.range.end_line,
+            })
+            .collect(),
+        schemas: parsed
+            .schemas
+            .into_iter()
+            .map(|s| SchemaData {
+                name: s.name,
+                schema_type: format!("{:?}", s.schema_type),
+                definition: s.definition,
+                start_line: s.range.start_line,
+                end_line: s.range.end_line,
+            })
+            .collect(),
+        language: parsed.language,
+        exports: parsed
+            .exports
+            .into_iter()
+            .map(|e| ExportData {
+                name: e.name,
+                alias: e.alias,
+                source: e.source,
+                start_line: e.range.start_line,
+                end_line: e.range.end_line,
+            })
+            .collect(),
+        tests: parsed
+            .tests
+            .into_iter()
+            .map(|t| TestCaseData {
+                name: t.name,
+                references: t.references,
+                start_line: t.range.start_line,
+                end_line: t.range.end_line,
+            })
+            .collect(),
+    }
+}
+
+fn convert_symbol(symbol: Symbol) -> SymbolData {
+    SymbolData {
+        qualified_name: symbol.metadata.qualified_name.clone(),
+        name: symbol.name,
+        kind: format!("{:?}", symbol.kind),
+        start_line: symbol.range.start_line,
+        end_line: symbol.range.end_line,
+        start_byte: symbol.range.start_byte,
+        end_byte: symbol.range.end_byte,
+        content: symbol.content,
+        metadata: serde_json::to_string(&symbol.metadata).unwrap_or_default(),
+        style_tags: None, // Will be populated during style analysis
+        children: symbol.children.into_iter().map(convert_symbol).collect(),
+        references: symbol.references,
+    }
+}
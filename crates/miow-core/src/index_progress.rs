@@ -0,0 +1,111 @@
+//! Crash recovery for a single indexing run.
+//!
+//! Each processed file is appended to a JSON-lines progress file as soon as it has been
+//! parsed and inserted, so an interrupted run can be restarted without redoing that work.
+//! The file is removed once the run completes; it is not an incremental-reindex cache.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tracing::warn;
+
+/// Default progress file name, stored next to the graph database in `.miow/`
+pub const INDEX_PROGRESS_FILE: &str = "index_progress.jsonl";
+
+/// A file that finished indexing, with the size and mtime it had at the time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgressEntry {
+    pub relative_path: String,
+    pub size: u64,
+    pub modified_secs: u64,
+}
+
+impl ProgressEntry {
+    pub fn for_file(relative_path: &str, metadata: &fs::Metadata) -> Self {
+        let modified_secs = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        Self {
+            relative_path: relative_path.to_string(),
+            size: metadata.len(),
+            modified_secs,
+        }
+    }
+}
+
+/// Files completed so far by an interrupted (or the current) indexing run
+pub struct IndexProgress {
+    path: PathBuf,
+    completed: HashMap<String, ProgressEntry>,
+    writer: File,
+}
+
+impl IndexProgress {
+    /// Load progress left by an interrupted run, if any, and append to it from now on.
+    /// Malformed lines (e.g. one cut short by a crash) are ignored.
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut completed = HashMap::new();
+        if let Ok(content) = fs::read_to_string(path) {
+            for line in content.lines().filter(|l| !l.trim().is_empty()) {
+                match serde_json::from_str::<ProgressEntry>(line) {
+                    Ok(entry) => {
+                        completed.insert(entry.relative_path.clone(), entry);
+                    }
+                    Err(e) => warn!("Ignoring malformed index progress line in {:?}: {}", path, e),
+                }
+            }
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let writer = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open index progress file {:?}", path))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            completed,
+            writer,
+        })
+    }
+
+    /// Files recorded as completed
+    pub fn len(&self) -> usize {
+        self.completed.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.completed.is_empty()
+    }
+
+    /// Whether the file was completed and hasn't changed since
+    pub fn is_completed(&self, entry: &ProgressEntry) -> bool {
+        self.completed.get(&entry.relative_path) == Some(entry)
+    }
+
+    /// Durably record a completed file
+    pub fn record(&mut self, entry: ProgressEntry) -> Result<()> {
+        let line = serde_json::to_string(&entry)?;
+        writeln!(self.writer, "{}", line)?;
+        self.writer.flush()?;
+        self.completed.insert(entry.relative_path.clone(), entry);
+        Ok(())
+    }
+
+    /// The run completed: discard the progress so the next run starts fresh
+    pub fn finish(self) -> Result<()> {
+        drop(self.writer);
+        fs::remove_file(&self.path)
+            .with_context(|| format!("Failed to remove index progress file {:?}", self.path))
+    }
+}
@@ -1,7 +1,17 @@
+use crate::diagnostics::{IndexDiagnostics, IndexManifest};
+use crate::index_progress::{IndexProgress, ProgressEntry};
+use crate::insert_pipeline::{InsertPipeline, SymbolSink};
+use crate::path_priority::{is_ui_component, PathPriorities};
+use crate::source_provider::{FsSourceProvider, SourceProvider};
 use crate::types::*;
 use anyhow::Result;
 use ignore::WalkBuilder;
-use miow_parsers::{parse_python, parse_rust, parse_typescript, ParsedFile};
+use miow_parsers::{
+    classify_json, is_css_module, is_tailwind_config, parse_embedded, parse_go_with_mode, parse_json_config, parse_python_with_mode,
+    parse_css_module, parse_rust_file_with_mode, parse_tailwind_config, parse_typescript_file_with_mode, signature_embed_text, tag_platform, JsonRole,
+    Constant, ConstantCategory, ParseMode, ParsedFile, Symbol, SymbolType, TypeDefinition, USAGE_EXAMPLE_TAG,
+};
+use miow_graph::KnowledgeGraph;
 use miow_vector::{SymbolVector, VectorStore};
 use std::collections::HashMap;
 use std::fs;
@@ -11,16 +21,28 @@ use std::time::Instant;
 use tracing::{debug, info, warn};
 
 // Add project signature import
-use crate::project_signature::ProjectSignature;
+use crate::project_signature::{ProjectSignature, SignatureDiff};
 
 /// Indexes a codebase by traversing files and extracting metadata
 pub struct CodebaseIndexer {
     root_path: PathBuf,
     config: IndexConfig,
     vector_store: Option<Arc<VectorStore>>,
-    project_signature: Option<ProjectSignature>,
+    /// Where symbols are embedded; the vector store unless replaced with `with_symbol_sink`
+    sink: Option<Arc<dyn SymbolSink>>,
+    progress_events: Option<tokio::sync::mpsc::Sender<IndexProgressEvent>>,
+    project_signature: Option<Arc<ProjectSignature>>,
+    /// How the signature changed since the run recorded in the manifest
+    signature_diff: Option<SignatureDiff>,
+    progress_path: Option<PathBuf>,
+    manifest_path: Option<PathBuf>,
+    graph: Option<Arc<KnowledgeGraph>>,
+    source: Arc<dyn SourceProvider>,
 }
 
+/// Extensions `parse_file_enhanced` has a parser for; failures on others aren't errors
+const PARSED_EXTENSIONS: [&str; 8] = ["ts", "tsx", "rs", "py", "go", "json", "vue", "astro"];
+
 impl CodebaseIndexer {
     pub fn new(root_path: PathBuf) -> Result<Self> {
         if !root_path.exists() {
@@ -35,27 +57,139 @@ impl CodebaseIndexer {
             root_path,
             config: IndexConfig::default(),
             vector_store: None,
+            sink: None,
+            progress_events: None,
             project_signature: None,
+            signature_diff: None,
+            progress_path: None,
+            manifest_path: None,
+            graph: None,
+            source: Arc::new(FsSourceProvider),
         })
     }
 
+    /// Read file contents through `source` (e.g. an overlay of unsaved editor buffers)
+    /// instead of straight from disk
+    pub fn with_source_provider(mut self, source: Arc<dyn SourceProvider>) -> Self {
+        self.source = source;
+        self
+    }
+
     pub fn with_config(mut self, config: IndexConfig) -> Self {
         self.config = config;
         self
     }
 
     pub fn with_vector_store(mut self, store: Arc<VectorStore>) -> Self {
+        self.sink = Some(store.clone());
         self.vector_store = Some(store);
         self
     }
 
-    // New method to detect and set project signature
-    pub fn detect_project_signature(&mut self) -> Result<&ProjectSignature> {
-        if self.project_signature.is_none() {
-            self.project_signature = Some(ProjectSignature::detect(&self.root_path)?);
-            info!("Detected project signature: {:?}", self.project_signature.as_ref().unwrap());
+    /// Send parsed symbols to `sink` instead of the vector store
+    pub fn with_symbol_sink(mut self, sink: Arc<dyn SymbolSink>) -> Self {
+        self.sink = Some(sink);
+        self
+    }
+
+    /// Report each processed file on `events`. Events are dropped rather than slowing
+    /// indexing down when the receiver falls behind.
+    pub fn with_progress_events(mut self, events: tokio::sync::mpsc::Sender<IndexProgressEvent>) -> Self {
+        self.progress_events = Some(events);
+        self
+    }
+
+    /// Use an already-detected signature instead of detecting it from `root_path`
+    pub fn with_signature(mut self, signature: Arc<ProjectSignature>) -> Self {
+        self.project_signature = Some(signature);
+        self
+    }
+
+    /// Record each completed file in `path` so an interrupted run can be resumed: the next
+    /// run skips parsing and vector insertion for files recorded there (unless they changed)
+    pub fn with_progress_file(mut self, path: PathBuf) -> Self {
+        self.progress_path = Some(path);
+        self
+    }
+
+    /// Write a summary of each completed run to `path`, read back by `diagnostics`
+    pub fn with_manifest_file(mut self, path: PathBuf) -> Self {
+        self.manifest_path = Some(path);
+        self
+    }
+
+    /// Knowledge graph built from this index, queried by `diagnostics`
+    pub fn with_graph(mut self, graph: Arc<KnowledgeGraph>) -> Self {
+        self.graph = Some(graph);
+        self
+    }
+
+    /// Report index health: file and symbol counts, vector store size, signature, last run
+    /// time, parse errors and import cycles, from the manifest, graph and vector store.
+    pub async fn diagnostics(&mut self) -> Result<IndexDiagnostics> {
+        let manifest = match &self.manifest_path {
+            Some(path) => IndexManifest::load(path)?,
+            None => None,
+        };
+
+        let (files_indexed, symbols_indexed, import_cycles) = match &self.graph {
+            Some(graph) => (graph.count_files()?, graph.count_symbols()?, graph.import_cycles()?),
+            None => (manifest.as_ref().map_or(0, |m| m.total_files), 0, Vec::new()),
+        };
+
+        let vector_count = match &self.vector_store {
+            Some(store) => match store.count().await {
+                Ok(count) => Some(count),
+                Err(e) => {
+                    warn!("Could not count vector store points: {}", e);
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let signature = match &manifest {
+            Some(manifest) => Some(manifest.signature.clone()),
+            None => self.detect_project_signature().ok().map(|s| (*s).clone()),
+        };
+
+        Ok(IndexDiagnostics {
+            files_indexed,
+            symbols_indexed,
+            vector_count,
+            signature,
+            last_indexed_secs: manifest.as_ref().map(|m| m.indexed_at_secs),
+            parse_errors: manifest.as_ref().map_or(0, |m| m.parse_errors),
+            import_cycles,
+        })
+    }
+
+    /// Detect the project signature once and share it for the indexer's lifetime
+    pub fn detect_project_signature(&mut self) -> Result<Arc<ProjectSignature>> {
+        if let Some(signature) = &self.project_signature {
+            return Ok(signature.clone());
         }
-        Ok(self.project_signature.as_ref().unwrap())
+        let signature = match &self.manifest_path {
+            Some(manifest_path) => {
+                let (signature, diff) = ProjectSignature::detect_cached(&self.root_path, manifest_path)?;
+                if let Some(diff) = diff.as_ref().filter(|d| !d.is_empty()) {
+                    info!("Project stack changed since the last run: {:?}", diff);
+                }
+                self.signature_diff = diff;
+                signature
+            }
+            None => ProjectSignature::detect(&self.root_path)?,
+        };
+        let signature = Arc::new(signature);
+        info!("Detected project signature: {:?}", signature);
+        self.project_signature = Some(signature.clone());
+        Ok(signature)
+    }
+
+    /// How the detected signature differs from the last completed run's, once
+    /// `detect_project_signature` has run with a manifest file
+    pub fn signature_diff(&self) -> Option<&SignatureDiff> {
+        self.signature_diff.as_ref()
     }
 
     pub async fn index(&mut self) -> Result<IndexReport> {
@@ -63,22 +197,68 @@ impl CodebaseIndexer {
         info!("Starting codebase indexing at {:?}", self.root_path);
 
         // Detect project signature first for smarter parsing
-        let signature = self.detect_project_signature()?.clone();
+        let signature = self.detect_project_signature()?;
 
-        self.do_index_with_signature(signature, start).await
+        self.do_index_with_signature(signature, None, start).await
     }
 
-    async fn do_index_with_signature(&mut self, signature: ProjectSignature, start: Instant) -> Result<IndexReport> {
+    /// Index only the files under `dir` (relative to the root), e.g. one workspace package
+    /// that `IndexConfig::lazy_packages` left out of the full walk. Paths in the report stay
+    /// relative to the root; the progress and manifest files are left untouched.
+    pub async fn index_dir(&mut self, dir: &str) -> Result<IndexReport> {
+        let start = Instant::now();
+        info!("Indexing {} under {:?}", dir, self.root_path);
+        let signature = self.detect_project_signature()?;
+        self.do_index_with_signature(signature, Some(dir), start).await
+    }
+
+    async fn do_index_with_signature(&mut self, signature: Arc<ProjectSignature>, subtree: Option<&str>, start: Instant) -> Result<IndexReport> {
         let config = &self.config;
+        let path_priorities = PathPriorities::new(&config.path_priorities)?;
         let root_path = &self.root_path;
-        let vector_store = &self.vector_store;
+        let inserts = self
+            .sink
+            .clone()
+            .map(|sink| InsertPipeline::spawn(sink, config.insert_queue_capacity));
+        let source = self.source.as_ref();
+        let mut ignore_patterns = config.ignore_patterns.clone();
+        for pattern in signature.ignore_patterns() {
+            if !ignore_patterns.iter().any(|p| p == pattern) {
+                ignore_patterns.push(pattern.to_string());
+            }
+        }
+        // Lazy mode: packages are indexed one at a time by `index_dir` when first needed
+        if subtree.is_none() && config.lazy_packages.is_some() {
+            for package in ProjectSignature::detect_workspaces(root_path) {
+                ignore_patterns.push(format!("{}/", package.dir));
+            }
+        }
 
         let mut files = Vec::new();
         let mut files_by_language: HashMap<String, usize> = HashMap::new();
         let mut total_size = 0u64;
 
+        // Files an interrupted run completed were tagged for the old stack; parse them again
+        let retag = self.signature_diff.as_ref().is_some_and(|d| d.requires_retagging());
+        let mut progress = match self.progress_path.as_ref().filter(|_| subtree.is_none()) {
+            Some(path) => {
+                if retag && path.exists() {
+                    info!("Project stack changed; re-tagging every file instead of resuming");
+                    fs::remove_file(path)?;
+                }
+                Some(IndexProgress::open(path)?)
+            }
+            None => None,
+        };
+        if let Some(progress) = progress.as_ref().filter(|p| !p.is_empty()) {
+            info!("Resuming interrupted indexing run ({} files already done)", progress.len());
+        }
+        let mut resumed_files = 0;
+        let mut parse_errors = 0;
+
         // Build walker with gitignore support
-        let mut builder = WalkBuilder::new(&self.root_path);
+        let walk_root = subtree.map_or_else(|| self.root_path.clone(), |dir| self.root_path.join(dir));
+        let mut builder = WalkBuilder::new(&walk_root);
         builder.git_ignore(true)
             .git_global(true)
             .git_exclude(true)
@@ -115,17 +295,20 @@ impl CodebaseIndexer {
             }
 
             // Check if file should be ignored
-            if Self::should_ignore_static(path, &config.ignore_patterns) {
+            if Self::should_ignore_static(path.strip_prefix(root_path).unwrap_or(path), &ignore_patterns) {
                 continue;
             }
 
             // Get file extension
             let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
 
-            // Check if extension is in include list
-            if !config
-                .include_extensions
-                .contains(&extension.to_string())
+            // Check if extension is in include list; files with a missing or ambiguous
+            // extension are kept for content sniffing below.
+            let needs_sniffing = Language::is_ambiguous_extension(extension);
+            if !needs_sniffing
+                && !config
+                    .include_extensions
+                    .contains(&extension.to_string())
             {
                 continue;
             }
@@ -148,26 +331,78 @@ impl CodebaseIndexer {
             }
 
             // Read file content
-            let content = match fs::read_to_string(path) {
-                Ok(c) => c,
+            let (content, lossy) = match Self::read_source_file(source, path, extension) {
+                Ok(Some(read)) => read,
+                Ok(None) => {
+                    debug!("Skipping binary or non-UTF-8 file: {:?}", path);
+                    continue;
+                }
                 Err(err) => {
                     warn!("Error reading file {:?}: {}", path, err);
                     continue;
                 }
             };
+            if lossy {
+                warn!("{:?} is not valid UTF-8; indexing a lossy decode", path);
+            }
 
-            let language = Language::from_extension(extension);
+            let mut language = Language::from_extension(extension);
+            let mut parse_extension = extension;
+            if language == Language::Unknown && needs_sniffing {
+                language = Language::from_content(&content);
+                if !language.is_parseable() {
+                    continue;
+                }
+                debug!("Detected {:?} from content for {:?}", language, path);
+                parse_extension = language.parser_extension().unwrap_or(extension);
+            }
             let relative_path = path
                 .strip_prefix(&root_path)
                 .unwrap_or(path)
                 .to_string_lossy()
                 .to_string();
 
+            if language == Language::JSON && classify_json(&relative_path, &content) == JsonRole::Data {
+                debug!("Skipping data-only JSON: {:?} ({} bytes)", path, size);
+                continue;
+            }
+
+            let progress_entry = ProgressEntry::for_file(&relative_path, &metadata);
+            // An unsaved buffer isn't described by the on-disk metadata the progress file records
+            let resumed = !source.is_overlaid(path)
+                && progress.as_ref().is_some_and(|p| p.is_completed(&progress_entry));
+            if resumed {
+                resumed_files += 1;
+            }
+
             // Enhanced parsing with project signature context
-            if let Ok(parsed) = self.parse_file_enhanced(&content, extension, &signature, &config) {
+            let parsed = if resumed {
+                None
+            } else {
+                match self.parse_file_enhanced(&content, parse_extension, &relative_path, self.config.parse_mode, &signature, &path_priorities) {
+                    Ok(parsed) => Some(parsed),
+                    Err(e) => {
+                        if PARSED_EXTENSIONS.contains(&parse_extension) {
+                            parse_errors += 1;
+                            warn!("Failed to parse {}: {}", relative_path, e);
+                        }
+                        None
+                    }
+                }
+            };
+            if let Some(mut parsed) = parsed {
+                if lossy {
+                    for symbol in &mut parsed.symbols {
+                        symbol.metadata.tags.push(LOSSY_DECODE_TAG.to_string());
+                    }
+                }
+
                 // Index symbols with enhanced metadata
-                if let Some(store) = &vector_store {
+                if let Some(inserts) = &inserts {
                     for symbol in parsed.symbols {
+                        if !config.indexes_kind(&symbol.kind) {
+                            continue;
+                        }
                         let mut enhanced_metadata = symbol.metadata.clone();
                         
                         // Tag with UI library if applicable
@@ -180,10 +415,9 @@ impl CodebaseIndexer {
                             enhanced_metadata.tags.push(format!("validation:{}", val_lib.to_lowercase()));
                         }
 
-                        // Prioritize common UI components
-                        if Self::is_common_ui_component(&symbol.name) {
+                        // UI components were already boosted by `path_priorities.stamp`
+                        if is_ui_component(&symbol) {
                             enhanced_metadata.tags.push("common-ui".to_string());
-                            enhanced_metadata.priority = Some(1.0); // High priority
                         }
 
                         // Tag Zod schemas and form-related symbols
@@ -195,6 +429,7 @@ impl CodebaseIndexer {
                             enhanced_metadata.tags.push("form-validation".to_string());
                         }
 
+                        let embed_text = Self::embed_text(config, &symbol, &content);
                         let symbol_vector = SymbolVector {
                             id: format!("{}:{}", relative_path, symbol.name),
                             name: symbol.name,
@@ -202,14 +437,10 @@ impl CodebaseIndexer {
                             content: symbol.content,
                             file_path: relative_path.clone(),
                             metadata: serde_json::to_string(&enhanced_metadata).unwrap_or_default(),
+                            embed_text,
                         };
 
-                        if let Err(e) = store.insert_symbol(&symbol_vector).await {
-                            warn!(
-                                "Failed to insert symbol {} into vector store: {}",
-                                symbol_vector.name, e
-                            );
-                        }
+                        inserts.push(symbol_vector).await?;
                     }
 
                     // Index validation schemas separately for better search
@@ -221,20 +452,62 @@ impl CodebaseIndexer {
                             content: schema.definition.clone(),
                             file_path: relative_path.clone(),
                             metadata: serde_json::to_string(schema).unwrap_or_default(),
+                            embed_text: None,
                         };
-                        if let Err(e) = store.insert_symbol(&schema_vector).await {
-                            warn!("Failed to insert schema {}: {}", schema.name, e);
+                        inserts.push(schema_vector).await?;
+                    }
+
+                    // `@example` code is indexed on its own, linked to the symbol it documents
+                    for (i, example) in parsed.examples.iter().enumerate() {
+                        let metadata = serde_json::json!({
+                            "symbol": example.symbol,
+                            "language": example.language,
+                            "tags": [USAGE_EXAMPLE_TAG],
+                        });
+                        let example_vector = SymbolVector {
+                            id: format!("{}:{}:example:{}", relative_path, example.symbol, i),
+                            name: example.symbol.clone(),
+                            kind: USAGE_EXAMPLE_TAG.to_string(),
+                            content: example.code.clone(),
+                            file_path: relative_path.clone(),
+                            metadata: metadata.to_string(),
+                            embed_text: None,
+                        };
+                        inserts.push(example_vector).await?;
+                    }
+
+                    // Constants ("the API base URL") and types are searched for by name too
+                    if config.indexes_kind(&SymbolType::Constant) {
+                        for constant in &parsed.constants {
+                            inserts.push(Self::constant_vector(constant, &relative_path)).await?;
                         }
                     }
+                    for type_def in &parsed.type_definitions {
+                        inserts.push(Self::type_definition_vector(type_def, &relative_path)).await?;
+                    }
+                }
+            }
+
+            if let Some(progress) = progress.as_mut().filter(|_| !resumed) {
+                if let Err(e) = progress.record(progress_entry) {
+                    warn!("Failed to record indexing progress for {}: {}", relative_path, e);
                 }
             }
 
+            let mut tags = Vec::new();
+            if lossy {
+                tags.push(LOSSY_DECODE_TAG.to_string());
+            }
+            if resumed {
+                tags.push(RESUMED_TAG.to_string());
+            }
             files.push(CodeFile {
                 path: path.to_path_buf(),
                 relative_path,
                 language,
                 size,
                 content,
+                tags,
             });
 
             total_size += size;
@@ -242,8 +515,31 @@ impl CodebaseIndexer {
             // Update language counts
             let lang_name = format!("{:?}", language);
             *files_by_language.entry(lang_name).or_insert(0) += 1;
+
+            if let (Some(events), Some(file)) = (&self.progress_events, files.last()) {
+                let _ = events.try_send(IndexProgressEvent {
+                    relative_path: file.relative_path.clone(),
+                    files_indexed: files.len(),
+                    insert_queue_depth: inserts.as_ref().map_or(0, InsertPipeline::queue_depth),
+                });
+            }
+        }
+
+        if let Some(inserts) = inserts {
+            let inserted = inserts.finish().await?;
+            debug!("Inserted {} symbols into the vector store", inserted);
         }
 
+        if let Some(progress) = progress {
+            progress.finish()?;
+        }
+        if resumed_files > 0 {
+            info!("Skipped {} files completed by the interrupted run", resumed_files);
+        }
+
+        // The walk order is filesystem-dependent; sort so reports are reproducible
+        files.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+
         let duration = start.elapsed();
         info!(
             "Indexed {} files in {:.2}s",
@@ -251,23 +547,67 @@ impl CodebaseIndexer {
             duration.as_secs_f64()
         );
 
+        if let Some(path) = self.manifest_path.as_ref().filter(|_| subtree.is_none()) {
+            let manifest = IndexManifest::new(files.len(), parse_errors, duration.as_millis(), (*signature).clone());
+            if let Err(e) = manifest.save(path) {
+                warn!("Failed to write index manifest: {}", e);
+            }
+        }
+
         Ok(IndexReport {
             total_files: files.len(),
             files_by_language,
             total_size,
             duration_ms: duration.as_millis(),
             files,
+            parse_errors,
         })
     }
 
-    fn parse_file_enhanced(&self, content: &str, extension: &str, signature: &ProjectSignature, _config: &IndexConfig) -> Result<ParsedFile> {
+    /// Parse a single file again, reading it through the source provider so an unsaved editor
+    /// buffer is parsed rather than the stale file on disk. `path` is relative to the indexer
+    /// root or already joined with it. Always a full parse, so this also fills in files that a
+    /// `ParseMode::SignaturesOnly` index only skimmed.
+    pub fn reparse_file(&self, path: &std::path::Path) -> Result<ParsedFile> {
+        let path = if path.starts_with(&self.root_path) {
+            path.to_path_buf()
+        } else {
+            self.root_path.join(path)
+        };
+        let content = self.source.read(&path)?;
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let mut parse_extension = extension;
+        if Language::from_extension(extension) == Language::Unknown && Language::is_ambiguous_extension(extension) {
+            parse_extension = Language::from_content(&content).parser_extension().unwrap_or(extension);
+        }
+        let relative_path = path
+            .strip_prefix(&self.root_path)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .to_string();
+        let signature = self.project_signature.as_deref().cloned().unwrap_or_default();
+        let path_priorities = PathPriorities::new(&self.config.path_priorities)?;
+        self.parse_file_enhanced(&content, parse_extension, &relative_path, ParseMode::Full, &signature, &path_priorities)
+    }
+
+    fn parse_file_enhanced(&self, content: &str, extension: &str, relative_path: &str, mode: ParseMode, signature: &ProjectSignature, path_priorities: &PathPriorities) -> Result<ParsedFile> {
         let mut parsed = match extension {
-            "ts" => parse_typescript(content, false),
-            "tsx" => parse_typescript(content, true),
-            "rs" => parse_rust(content),
-            "py" => parse_python(content),
+            // Theme tokens with their real values, whether the config is JS or TS
+            _ if is_tailwind_config(relative_path) => parse_tailwind_config(content),
+            // Class rules that `styles.foo` in components resolves to
+            _ if is_css_module(relative_path) => parse_css_module(content),
+            "ts" => parse_typescript_file_with_mode(content, false, relative_path, mode),
+            "tsx" => parse_typescript_file_with_mode(content, true, relative_path, mode),
+            "rs" => parse_rust_file_with_mode(content, relative_path, mode),
+            "py" => parse_python_with_mode(content, mode),
+            "go" => parse_go_with_mode(content, mode),
+            "json" => parse_json_config(content, relative_path),
+            // Script and style blocks keep their own language on each symbol and token
+            "vue" | "astro" => parse_embedded(content, extension, mode),
             _ => anyhow::bail!("Unsupported extension: {}", extension),
         }?;
+        // `Button.ios.tsx` and `Button.android.tsx` are variants of one `Button` module
+        tag_platform(&mut parsed, relative_path);
 
         // Enhance parsed data with signature context
         // For example, tag symbols based on detected libraries
@@ -279,8 +619,8 @@ impl CodebaseIndexer {
                 }
             }
 
-            // Tag common UI components regardless of library
-            if Self::is_common_ui_component(&symbol.name) {
+            // Tag UI components regardless of library
+            if is_ui_component(symbol) {
                 symbol.metadata.tags.push("common-ui-component".to_string());
             }
 
@@ -295,23 +635,98 @@ impl CodebaseIndexer {
             }
         }
 
+        // Base priority from the file's path, combined with the common-UI boost
+        path_priorities.stamp(relative_path, &mut parsed);
+
         Ok(parsed)
     }
 
-    fn is_common_ui_component(name: &str) -> bool {
-        let common_ui = vec!["InputBox", "Button", "Form", "Modal", "Dialog", "Input", "Select", "Checkbox", "Textarea", "Label"];
-        common_ui.iter().any(|c| name.contains(c))
+    /// Vector-store entry for a constant, tagged `constant` plus its category (`api-endpoint`,
+    /// `config`, ...). The embedded text spells the category out so "API base URL" finds it.
+    fn constant_vector(constant: &Constant, relative_path: &str) -> SymbolVector {
+        let (category_tag, category_label) = match constant.category {
+            ConstantCategory::APIEndpoint => (Some("api-endpoint"), "API endpoint URL"),
+            ConstantCategory::Config => (Some("config"), "configuration"),
+            ConstantCategory::ErrorMessage => (Some("error-message"), "error message"),
+            ConstantCategory::DefaultValue => (Some("default-value"), "default value"),
+            ConstantCategory::Other => (None, "value"),
+        };
+        let mut tags = vec!["constant".to_string()];
+        tags.extend(category_tag.map(str::to_string));
+        let value: String = constant.value.chars().take(200).collect();
+        SymbolVector {
+            id: format!("constant:{}:{}", relative_path, constant.name),
+            name: constant.name.clone(),
+            kind: "constant".to_string(),
+            content: format!("{} = {}", constant.name, constant.value),
+            file_path: relative_path.to_string(),
+            metadata: serde_json::json!({ "tags": tags, "category": constant.category }).to_string(),
+            embed_text: Some(format!("{} constant ({}) = {}", constant.name, category_label, value)),
+        }
+    }
+
+    /// Vector-store entry for an interface, type alias, struct, enum or model type
+    fn type_definition_vector(type_def: &TypeDefinition, relative_path: &str) -> SymbolVector {
+        SymbolVector {
+            id: format!("type:{}:{}", relative_path, type_def.name),
+            name: type_def.name.clone(),
+            kind: "type".to_string(),
+            content: type_def.definition.clone(),
+            file_path: relative_path.to_string(),
+            metadata: serde_json::json!({
+                "tags": ["type-definition"],
+                "type_kind": type_def.kind,
+                "generic_params": type_def.generic_params,
+            })
+            .to_string(),
+            embed_text: None,
+        }
+    }
+
+    /// What to embed for `symbol` instead of its body, if `IndexConfig::embed_signatures` is on
+    fn embed_text(config: &IndexConfig, symbol: &Symbol, source: &str) -> Option<String> {
+        config
+            .embed_signatures
+            .then(|| signature_embed_text(symbol, source))
+    }
+
+    /// Read a source file as text. Returns `None` for binaries (NUL bytes) and for invalid
+    /// UTF-8 in files without a parseable extension; invalid UTF-8 in parseable source is
+    /// decoded lossily and flagged with `true`.
+    fn read_source_file(source: &dyn SourceProvider, path: &std::path::Path, extension: &str) -> Result<Option<(String, bool)>> {
+        let bytes = source.read_bytes(path)?;
+        if Self::looks_binary(&bytes) {
+            return Ok(None);
+        }
+        match String::from_utf8(bytes) {
+            Ok(content) => Ok(Some((content, false))),
+            Err(err) if Language::from_extension(extension).is_parseable() => {
+                Ok(Some((String::from_utf8_lossy(err.as_bytes()).into_owned(), true)))
+            }
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Text files never contain NUL bytes; check the first 8 KiB like git does
+    fn looks_binary(bytes: &[u8]) -> bool {
+        bytes.iter().take(8 * 1024).any(|&b| b == 0)
     }
 
     fn should_ignore(&self, path: &std::path::Path) -> bool {
-        Self::should_ignore_static(path, &self.config.ignore_patterns)
+        Self::should_ignore_static(path.strip_prefix(&self.root_path).unwrap_or(path), &self.config.ignore_patterns)
     }
 
-    fn should_ignore_static(path: &std::path::Path, ignore_patterns: &[String]) -> bool {
-        let path_str = path.to_string_lossy();
+    /// Whether `relative_path` falls under an ignore pattern (see `IndexConfig::ignore_patterns`)
+    fn should_ignore_static(relative_path: &std::path::Path, ignore_patterns: &[String]) -> bool {
+        let path_str = relative_path.to_string_lossy();
 
         for pattern in ignore_patterns {
-            if path_str.contains(pattern) {
+            let matches = if pattern.contains('/') {
+                path_str.contains(pattern.as_str())
+            } else {
+                relative_path.components().any(|c| c.as_os_str() == pattern.as_str())
+            };
+            if matches {
                 return true;
             }
         }
@@ -323,6 +738,7 @@ impl CodebaseIndexer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use miow_parsers::parse_typescript;
 
     #[tokio::test]
     async fn test_indexer_creation() {
@@ -330,6 +746,187 @@ mod tests {
         assert!(indexer.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_python_walk_skips_virtualenvs_and_caches() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("requirements.txt"), "fastapi\n").unwrap();
+        for (path, content) in [
+            ("app.py", "def main():\n    pass\n"),
+            // Only whole path components match, so this isn't caught by `build`
+            ("build_utils.py", "def render():\n    pass\n"),
+            (".venv/lib/site.py", "def site():\n    pass\n"),
+            ("venv/lib/site.py", "def site():\n    pass\n"),
+            ("__pycache__/app.py", "def cached():\n    pass\n"),
+            (".pytest_cache/conftest.py", "def cached():\n    pass\n"),
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        let mut indexer = CodebaseIndexer::new(root.to_path_buf()).unwrap();
+        let report = indexer.index().await.unwrap();
+        let paths: Vec<&str> = report.files.iter().map(|f| f.relative_path.as_str()).collect();
+        assert_eq!(paths, vec!["app.py", "build_utils.py"]);
+    }
+
+    #[tokio::test]
+    async fn test_overlay_buffer_is_indexed_instead_of_disk() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file = temp_dir.path().join("cart.ts");
+        fs::write(&file, "export function addItem() {}\n").unwrap();
+
+        let overlay = Arc::new(crate::OverlaySourceProvider::default());
+        overlay.set_buffer(&file, "export function addItem() {}\nexport function removeItem() {}\n");
+        let mut indexer = CodebaseIndexer::new(temp_dir.path().to_path_buf())
+            .unwrap()
+            .with_source_provider(overlay.clone());
+
+        let parsed = indexer.reparse_file(std::path::Path::new("cart.ts")).unwrap();
+        let names: Vec<&str> = parsed.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["addItem", "removeItem"]);
+
+        let report = indexer.index().await.unwrap();
+        assert!(report.files[0].content.contains("removeItem"));
+
+        // Once the buffer is saved and cleared, disk is authoritative again
+        overlay.clear_buffer(&file);
+        let parsed = indexer.reparse_file(&file).unwrap();
+        assert_eq!(parsed.symbols.len(), 1);
+    }
+
+    #[test]
+    fn test_quick_scan_config_still_reparses_fully() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("cart.ts"), "export function total(items: number[]) {\n    return items.reduce((a, b) => a + b, 0);\n}\n").unwrap();
+
+        let config = IndexConfig {
+            parse_mode: ParseMode::SignaturesOnly,
+            ..Default::default()
+        };
+        let indexer = CodebaseIndexer::new(temp_dir.path().to_path_buf())
+            .unwrap()
+            .with_config(config);
+        let signature = ProjectSignature::default();
+        let priorities = PathPriorities::new(&indexer.config.path_priorities).unwrap();
+
+        let content = fs::read_to_string(temp_dir.path().join("cart.ts")).unwrap();
+        let quick = indexer
+            .parse_file_enhanced(&content, "ts", "cart.ts", indexer.config.parse_mode, &signature, &priorities)
+            .unwrap();
+        assert_eq!(quick.symbols[0].content, "function total(items: number[])");
+
+        let full = indexer.reparse_file(std::path::Path::new("cart.ts")).unwrap();
+        assert!(full.symbols[0].content.contains("items.reduce"));
+    }
+
+    /// Embeds one symbol at a time, slowly, recording how many it has seen
+    struct SlowEmbedder {
+        inserted: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl SymbolSink for SlowEmbedder {
+        async fn insert_symbol(&self, symbol: &SymbolVector) -> Result<()> {
+            tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+            self.inserted.lock().unwrap().push(symbol.id.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slow_embedder_keeps_insert_queue_bounded() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for i in 0..12 {
+            let source: String = (0..4).map(|j| format!("export function f{}_{}() {{}}\n", i, j)).collect();
+            fs::write(temp_dir.path().join(format!("m{}.ts", i)), source).unwrap();
+        }
+
+        let sink = Arc::new(SlowEmbedder {
+            inserted: std::sync::Mutex::new(Vec::new()),
+        });
+        let (events_tx, mut events_rx) = tokio::sync::mpsc::channel(64);
+        let config = IndexConfig {
+            insert_queue_capacity: 3,
+            ..Default::default()
+        };
+        let mut indexer = CodebaseIndexer::new(temp_dir.path().to_path_buf())
+            .unwrap()
+            .with_config(config)
+            .with_symbol_sink(sink.clone())
+            .with_progress_events(events_tx);
+
+        indexer.index().await.unwrap();
+        drop(indexer);
+
+        let mut depths = Vec::new();
+        while let Some(event) = events_rx.recv().await {
+            depths.push(event.insert_queue_depth);
+        }
+        assert_eq!(depths.len(), 12);
+        assert!(depths.iter().all(|&depth| depth <= 3), "{:?}", depths);
+        // The embedder was the bottleneck, so the queue actually filled up
+        assert!(depths.contains(&3), "{:?}", depths);
+        assert_eq!(sink.inserted.lock().unwrap().len(), 48);
+    }
+
+    #[tokio::test]
+    async fn test_api_endpoint_constant_is_inserted_for_search() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("config.ts"),
+            "export const API_BASE_URL = \"https://api.example.com/v1\";\n\nexport enum Region { Eu = \"eu\", Us = \"us\" }\n",
+        )
+        .unwrap();
+
+        let sink = Arc::new(SlowEmbedder {
+            inserted: std::sync::Mutex::new(Vec::new()),
+        });
+        let mut indexer = CodebaseIndexer::new(temp_dir.path().to_path_buf())
+            .unwrap()
+            .with_symbol_sink(sink.clone());
+        indexer.index().await.unwrap();
+
+        let inserted = sink.inserted.lock().unwrap();
+        assert!(inserted.contains(&"constant:config.ts:API_BASE_URL".to_string()), "{:?}", inserted);
+        assert!(inserted.contains(&"type:config.ts:Region".to_string()), "{:?}", inserted);
+
+        let parsed = parse_typescript("export const API_BASE_URL = \"https://api.example.com/v1\";", false).unwrap();
+        let vector = CodebaseIndexer::constant_vector(&parsed.constants[0], "config.ts");
+        assert_eq!(vector.kind, "constant");
+        assert!(vector.metadata.contains("api-endpoint"));
+        assert!(vector.embed_text.unwrap().contains("API_BASE_URL constant (API endpoint URL) = \"https://api.example.com/v1\""));
+    }
+
+    #[test]
+    fn test_embed_text_is_signature_and_doc_when_enabled() {
+        let source = r#"
+/**
+ * Formats a price in cents as a localized currency string.
+ */
+export function formatPrice(cents: number, currency: string): string {
+    const amount = cents / 100;
+    return new Intl.NumberFormat("en-US", { style: "currency", currency }).format(amount);
+}
+"#;
+        let parsed = parse_typescript(source, false).unwrap();
+        let symbol = parsed.symbols.iter().find(|s| s.name == "formatPrice").unwrap();
+
+        let mut config = IndexConfig::default();
+        assert_eq!(CodebaseIndexer::embed_text(&config, symbol, source), None);
+
+        config.embed_signatures = true;
+        let text = CodebaseIndexer::embed_text(&config, symbol, source).unwrap();
+        assert_eq!(
+            text,
+            "formatPrice\nfunction formatPrice(cents: number, currency: string): string\n\
+             Formats a price in cents as a localized currency string."
+        );
+        assert!(!text.contains("Intl.NumberFormat"));
+        assert!(symbol.content.contains("Intl.NumberFormat"));
+    }
+
     #[tokio::test]
     async fn test_indexer_invalid_path() {
         let indexer = CodebaseIndexer::new(PathBuf::from("/nonexistent/path"));
@@ -342,4 +939,324 @@ mod tests {
         let signature = indexer.detect_project_signature().unwrap();
         assert!(!signature.language.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_injected_signature_is_not_redetected() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("main.py"), "def main():\n    pass\n").unwrap();
+
+        let injected = Arc::new(ProjectSignature {
+            language: "Rust".to_string(),
+            ..Default::default()
+        });
+        let mut indexer = CodebaseIndexer::new(temp_dir.path().to_path_buf())
+            .unwrap()
+            .with_signature(injected.clone());
+
+        let signature = indexer.detect_project_signature().unwrap();
+        assert!(Arc::ptr_eq(&signature, &injected));
+        assert_eq!(signature.language, "Rust");
+
+        indexer.index().await.unwrap();
+        assert!(Arc::ptr_eq(&indexer.detect_project_signature().unwrap(), &injected));
+    }
+
+    #[tokio::test]
+    async fn test_latin1_source_indexed_via_lossy_decode() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        // "é" encoded as latin-1 (0xE9) is invalid UTF-8
+        fs::write(
+            temp_dir.path().join("legacy.py"),
+            b"# Auteur: Ren\xe9\ndef greet():\n    return 'caf\xe9'\n",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("blob.py"), b"\x00\x01\xff\xfe").unwrap();
+
+        let mut indexer = CodebaseIndexer::new(temp_dir.path().to_path_buf()).unwrap();
+        let report = indexer.index().await.unwrap();
+
+        let legacy = report
+            .files
+            .iter()
+            .find(|f| f.relative_path == "legacy.py")
+            .expect("latin-1 file should be indexed");
+        assert!(legacy.content.contains("def greet()"));
+        assert!(legacy.content.contains('\u{FFFD}'));
+        assert_eq!(legacy.tags, vec![LOSSY_DECODE_TAG.to_string()]);
+        assert!(!report.files.iter().any(|f| f.relative_path == "blob.py"));
+    }
+
+    #[tokio::test]
+    async fn test_report_files_ordered_deterministically() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for path in ["src/z.ts", "lib/b.py", "src/a/nested.ts", "main.rs", "src/A.tsx"] {
+            let path = temp_dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "export const x = 1;\n").unwrap();
+        }
+
+        let mut first = CodebaseIndexer::new(temp_dir.path().to_path_buf()).unwrap();
+        let first = first.index().await.unwrap();
+        let mut second = CodebaseIndexer::new(temp_dir.path().to_path_buf()).unwrap();
+        let second = second.index().await.unwrap();
+
+        let paths: Vec<&str> = first.files.iter().map(|f| f.relative_path.as_str()).collect();
+        assert_eq!(paths, vec!["lib/b.py", "main.rs", "src/A.tsx", "src/a/nested.ts", "src/z.ts"]);
+        assert_eq!(
+            serde_json::to_vec(&first.files).unwrap(),
+            serde_json::to_vec(&second.files).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_diagnostics_report_indexed_symbols_and_import_cycles() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let sources = [
+            ("src/cart.ts", "import { price } from './pricing';\nexport function cart() { return price(); }\nexport const CART_LIMIT = 10;\n"),
+            ("src/pricing.ts", "import { CART_LIMIT } from './cart';\nexport function price() { return CART_LIMIT; }\n"),
+            ("src/util.ts", "export function noop() {}\n"),
+        ];
+        fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        for (path, content) in sources {
+            fs::write(temp_dir.path().join(path), content).unwrap();
+        }
+        let manifest_path = temp_dir.path().join(".miow").join(crate::INDEX_MANIFEST_FILE);
+
+        let mut indexer = CodebaseIndexer::new(temp_dir.path().to_path_buf())
+            .unwrap()
+            .with_manifest_file(manifest_path.clone());
+        let report = indexer.index().await.unwrap();
+        assert_eq!(report.parse_errors, 0);
+
+        // Build the graph from the same parse results the `index` command stores
+        let graph = KnowledgeGraph::in_memory().unwrap();
+        let mut expected_symbols = 0;
+        for file in &report.files {
+            let parsed = parse_typescript(&file.content, false).unwrap();
+            expected_symbols += parsed.symbols.len();
+            let data = miow_graph::ParsedFileData {
+                symbols: parsed
+                    .symbols
+                    .iter()
+                    .map(|s| miow_graph::SymbolData {
+                        name: s.name.clone(),
+                        kind: format!("{:?}", s.kind),
+                        start_line: s.range.start_line,
+                        end_line: s.range.end_line,
+                        start_byte: s.range.start_byte,
+                        end_byte: s.range.end_byte,
+                        content: s.content.clone(),
+                        metadata: "{}".to_string(),
+                        style_tags: None,
+                        children: vec![],
+                        references: vec![],
+                        qualified_name: None,
+                    })
+                    .collect(),
+                imports: parsed
+                    .imports
+                    .iter()
+                    .map(|i| miow_graph::ImportData {
+                        source: i.source.clone(),
+                        names: i.names.iter().map(|n| n.name.clone()).collect(),
+                        start_line: i.range.start_line,
+                        end_line: i.range.end_line,
+                    })
+                    .collect(),
+                design_tokens: vec![],
+                type_definitions: vec![],
+                constants: vec![],
+                schemas: vec![],
+                language: "typescript".to_string(),
+                exports: vec![],
+                tests: vec![],
+            };
+            graph.insert_file(&file.relative_path, &data).unwrap();
+        }
+        assert!(expected_symbols >= 4);
+
+        let diagnostics = indexer.with_graph(Arc::new(graph)).diagnostics().await.unwrap();
+        assert_eq!(diagnostics.files_indexed, 3);
+        assert_eq!(diagnostics.symbols_indexed, expected_symbols);
+        assert_eq!(diagnostics.parse_errors, 0);
+        assert_eq!(diagnostics.vector_count, None);
+        assert!(diagnostics.last_indexed_secs.is_some());
+        assert!(diagnostics.signature.is_some());
+        assert_eq!(
+            diagnostics.import_cycles,
+            vec![vec!["src/cart.ts".to_string(), "src/pricing.ts".to_string()]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resumed_run_only_processes_missing_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for name in ["a.ts", "b.ts", "c.ts"] {
+            fs::write(temp_dir.path().join(name), "export const x = 1;\n").unwrap();
+        }
+        let progress_path = temp_dir.path().join(".miow").join(crate::INDEX_PROGRESS_FILE);
+
+        // An interrupted run finished a.ts and b.ts, but b.ts changed afterwards
+        let mut partial = IndexProgress::open(&progress_path).unwrap();
+        let a = fs::metadata(temp_dir.path().join("a.ts")).unwrap();
+        partial.record(ProgressEntry::for_file("a.ts", &a)).unwrap();
+        let b = fs::metadata(temp_dir.path().join("b.ts")).unwrap();
+        let mut stale = ProgressEntry::for_file("b.ts", &b);
+        stale.size += 1;
+        partial.record(stale).unwrap();
+        drop(partial);
+
+        let mut indexer = CodebaseIndexer::new(temp_dir.path().to_path_buf())
+            .unwrap()
+            .with_progress_file(progress_path.clone());
+        let report = indexer.index().await.unwrap();
+
+        let resumed: Vec<&str> = report
+            .files
+            .iter()
+            .filter(|f| f.tags.iter().any(|t| t == RESUMED_TAG))
+            .map(|f| f.relative_path.as_str())
+            .collect();
+        assert_eq!(report.total_files, 3);
+        assert_eq!(resumed, vec!["a.ts"]);
+        assert!(!progress_path.exists(), "a completed run discards its progress");
+    }
+
+    /// Keeps every symbol it is sent
+    struct RecordingSink {
+        symbols: std::sync::Mutex<Vec<SymbolVector>>,
+    }
+
+    #[async_trait::async_trait]
+    impl SymbolSink for RecordingSink {
+        async fn insert_symbol(&self, symbol: &SymbolVector) -> Result<()> {
+            self.symbols.lock().unwrap().push(symbol.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_adding_zod_is_diffed_and_retags_schemas() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+        fs::write(&package_json, r#"{"dependencies":{"react":"18.2.0"}}"#).unwrap();
+        fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        fs::write(
+            temp_dir.path().join("src/user.ts"),
+            "export function userSchema() { return z.object({ name: z.string() }); }\n",
+        )
+        .unwrap();
+        let miow_dir = temp_dir.path().join(".miow");
+        let manifest_path = miow_dir.join(crate::INDEX_MANIFEST_FILE);
+        let progress_path = miow_dir.join(crate::INDEX_PROGRESS_FILE);
+
+        let mut first = CodebaseIndexer::new(temp_dir.path().to_path_buf())
+            .unwrap()
+            .with_manifest_file(manifest_path.clone());
+        first.index().await.unwrap();
+        assert!(first.signature_diff().is_none(), "nothing to compare against on the first run");
+
+        // An interrupted run completed the schema file before zod was added
+        let mut partial = IndexProgress::open(&progress_path).unwrap();
+        let user = fs::metadata(temp_dir.path().join("src/user.ts")).unwrap();
+        partial.record(ProgressEntry::for_file("src/user.ts", &user)).unwrap();
+        drop(partial);
+        fs::write(&package_json, r#"{"dependencies":{"react":"18.3.1","zod":"^3.22.0"}}"#).unwrap();
+
+        let sink = Arc::new(RecordingSink {
+            symbols: std::sync::Mutex::new(Vec::new()),
+        });
+        let mut second = CodebaseIndexer::new(temp_dir.path().to_path_buf())
+            .unwrap()
+            .with_manifest_file(manifest_path)
+            .with_progress_file(progress_path)
+            .with_symbol_sink(sink.clone());
+        let report = second.index().await.unwrap();
+
+        let diff = second.signature_diff().unwrap();
+        assert_eq!(diff.added_dependencies, vec!["zod".to_string()]);
+        assert!(diff.removed_dependencies.is_empty());
+        assert_eq!(diff.changed_dependencies["react"].after, "18.3.1");
+        let validation = diff.validation_library.as_ref().unwrap();
+        assert_eq!(validation.before, None);
+        assert_eq!(validation.after.as_deref(), Some("Zod"));
+        assert!(diff.requires_retagging());
+
+        // The schema file was parsed again instead of resumed, and tagged for zod
+        assert!(report.files.iter().all(|f| !f.tags.iter().any(|t| t == RESUMED_TAG)));
+        let symbols = sink.symbols.lock().unwrap();
+        let schema = symbols.iter().find(|s| s.name == "userSchema").unwrap();
+        assert!(schema.metadata.contains("zod-schema"), "{}", schema.metadata);
+        assert!(schema.metadata.contains("validation:zod"), "{}", schema.metadata);
+    }
+
+    #[tokio::test]
+    async fn test_excluded_kinds_are_not_inserted() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("api.ts"),
+            "export const API_BASE_URL = 'https://api.example.com';\nexport function fetchUser(id: string) { return fetch(API_BASE_URL + id); }\n",
+        )
+        .unwrap();
+
+        async fn inserted_kinds(root: &std::path::Path, config: IndexConfig) -> Vec<String> {
+            let sink = Arc::new(RecordingSink {
+                symbols: std::sync::Mutex::new(Vec::new()),
+            });
+            let mut indexer = CodebaseIndexer::new(root.to_path_buf())
+                .unwrap()
+                .with_config(config)
+                .with_symbol_sink(sink.clone());
+            indexer.index().await.unwrap();
+            let symbols = sink.symbols.lock().unwrap();
+            symbols.iter().map(|s| s.kind.clone()).collect()
+        }
+
+        let all = inserted_kinds(temp_dir.path(), IndexConfig::default()).await;
+        assert!(all.iter().any(|k| k.eq_ignore_ascii_case("constant")), "{:?}", all);
+
+        let config = IndexConfig {
+            indexed_kinds: Some([SymbolType::Function, SymbolType::Class, SymbolType::Interface].into_iter().collect()),
+            ..Default::default()
+        };
+        let lean = inserted_kinds(temp_dir.path(), config).await;
+        assert!(lean.contains(&"Function".to_string()), "{:?}", lean);
+        assert!(!lean.iter().any(|k| k.eq_ignore_ascii_case("constant")), "{:?}", lean);
+    }
+
+    #[tokio::test]
+    async fn test_extensionless_shebang_file_detected_as_python() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("manage"),
+            "#!/usr/bin/env python3\nimport sys\n\ndef main():\n    print(sys.argv)\n",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("LICENSE"), "MIT License\n").unwrap();
+
+        let mut indexer = CodebaseIndexer::new(temp_dir.path().to_path_buf()).unwrap();
+        let report = indexer.index().await.unwrap();
+
+        assert_eq!(report.total_files, 1);
+        assert_eq!(report.files[0].relative_path, "manage");
+        assert_eq!(report.files[0].language, Language::Python);
+    }
+
+    #[tokio::test]
+    async fn test_node_shebang_script_parsed_as_javascript() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("deploy"),
+            "#!/usr/bin/env node\nconst { execSync } = require('child_process');\n\nfunction deploy(target) {\n  execSync(`git push ${target}`);\n}\n\ndeploy(process.argv[2]);\n",
+        )
+        .unwrap();
+
+        let mut indexer = CodebaseIndexer::new(temp_dir.path().to_path_buf()).unwrap();
+        let report = indexer.index().await.unwrap();
+        assert_eq!(report.files[0].language, Language::JavaScript);
+        assert_eq!(report.parse_errors, 0);
+
+        let parsed = indexer.reparse_file(std::path::Path::new("deploy")).unwrap();
+        assert!(parsed.symbols.iter().any(|s| s.name == "deploy"), "{:?}", parsed.symbols);
+    }
 }
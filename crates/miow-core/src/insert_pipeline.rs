@@ -0,0 +1,72 @@
+//! Vector inserts decoupled from parsing. Parsed symbols go through a bounded channel to a
+//! single task that embeds and stores them, so parsing runs ahead of a slow embedder only
+//! until the queue fills, then waits for it: memory stays bounded however large the repo.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use miow_vector::{SymbolVector, VectorStore};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+/// Default `IndexConfig::insert_queue_capacity`
+pub const DEFAULT_INSERT_QUEUE_CAPACITY: usize = 64;
+
+/// Where indexed symbols are embedded and stored
+#[async_trait]
+pub trait SymbolSink: Send + Sync {
+    async fn insert_symbol(&self, symbol: &SymbolVector) -> Result<()>;
+}
+
+#[async_trait]
+impl SymbolSink for VectorStore {
+    async fn insert_symbol(&self, symbol: &SymbolVector) -> Result<()> {
+        VectorStore::insert_symbol(self, symbol).await
+    }
+}
+
+/// A bounded queue of pending inserts drained by a background task
+pub struct InsertPipeline {
+    sender: mpsc::Sender<SymbolVector>,
+    worker: JoinHandle<usize>,
+}
+
+impl InsertPipeline {
+    /// Start draining into `sink`, holding at most `capacity` (at least 1) pending symbols
+    pub fn spawn(sink: Arc<dyn SymbolSink>, capacity: usize) -> Self {
+        let (sender, mut receiver) = mpsc::channel::<SymbolVector>(capacity.max(1));
+        let worker = tokio::spawn(async move {
+            let mut inserted = 0;
+            while let Some(symbol) = receiver.recv().await {
+                match sink.insert_symbol(&symbol).await {
+                    Ok(()) => inserted += 1,
+                    Err(e) => warn!("Failed to insert {} into vector store: {}", symbol.name, e),
+                }
+            }
+            inserted
+        });
+        Self { sender, worker }
+    }
+
+    /// Queue `symbol`, waiting while the queue is full
+    pub async fn push(&self, symbol: SymbolVector) -> Result<()> {
+        self.sender
+            .send(symbol)
+            .await
+            .map_err(|_| anyhow!("Vector insert task stopped"))
+    }
+
+    /// Symbols queued but not yet picked up for embedding
+    pub fn queue_depth(&self) -> usize {
+        self.sender.max_capacity() - self.sender.capacity()
+    }
+
+    /// Wait for every queued symbol to be inserted; returns how many inserts succeeded
+    pub async fn finish(self) -> Result<usize> {
+        drop(self.sender);
+        self.worker
+            .await
+            .map_err(|e| anyhow!("Vector insert task failed: {}", e))
+    }
+}
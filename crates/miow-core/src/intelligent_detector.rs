@@ -212,7 +212,7 @@ Respond ONLY with valid JSON."#,
             &response.content
         };
         
-        serde_json::from_str(json_str)
+        miow_llm::parse_llm_json(json_str)
             .context("Failed to parse LLM project analysis")
     }
     
@@ -223,8 +223,9 @@ Respond ONLY with valid JSON."#,
     ) -> Result<ProjectSignature> {
         // Parse dependencies from actual files for accuracy
         let dependencies = self.parse_dependencies(project_root, &analysis.package_manager)?;
+        let state_management = ProjectSignature::detect_state_management(&dependencies.0);
         
-        Ok(ProjectSignature {
+        let mut signature = ProjectSignature {
             language: analysis.language,
             framework: analysis.framework,
             package_manager: analysis.package_manager,
@@ -234,8 +235,16 @@ Respond ONLY with valid JSON."#,
             styling: analysis.styling,
             dependencies: dependencies.0,
             dev_dependencies: dependencies.1,
+            internal_dependencies: HashMap::new(),
+            workspace_packages: HashMap::new(),
+            state_management,
             features: analysis.features,
-        })
+            target_dependencies: HashMap::new(),
+            environment_vars: ProjectSignature::detect_environment_vars(project_root),
+            declared_ranges: HashMap::new(),
+        };
+        ProjectSignature::apply_locked_versions(project_root, &mut signature);
+        Ok(signature)
     }
     
     fn parse_dependencies(
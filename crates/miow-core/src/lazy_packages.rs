@@ -0,0 +1,199 @@
+//! On-demand indexing for monorepos too large to index upfront. With
+//! `IndexConfig::lazy_packages` set, the full walk skips every workspace package; a package is
+//! indexed into the graph the first time a search targets it (or when asked explicitly), and
+//! the least recently used package is dropped from the graph once too many are loaded.
+
+use crate::graph_data::to_graph_data;
+use crate::indexer::CodebaseIndexer;
+use crate::project_signature::{ProjectSignature, WorkspacePackage};
+use crate::types::IndexConfig;
+use anyhow::{anyhow, Result};
+use miow_graph::KnowledgeGraph;
+use miow_vector::VectorStore;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{debug, info};
+
+/// Workspace packages indexed into the graph on first use, with an LRU of loaded packages
+pub struct LazyPackageIndex {
+    packages: Vec<WorkspacePackage>,
+    graph: Arc<KnowledgeGraph>,
+    capacity: usize,
+    state: Mutex<LazyState>,
+}
+
+struct LazyState {
+    indexer: CodebaseIndexer,
+    /// Directories of indexed packages, least recently used first
+    indexed: VecDeque<String>,
+}
+
+impl LazyPackageIndex {
+    /// Enumerate the packages under `root_path` without indexing any of them; at most
+    /// `capacity` (at least 1) stay in `graph` at once
+    pub fn new(root_path: PathBuf, graph: Arc<KnowledgeGraph>, capacity: usize) -> Result<Self> {
+        let packages = ProjectSignature::detect_workspaces(&root_path);
+        let config = IndexConfig {
+            lazy_packages: Some(capacity),
+            ..Default::default()
+        };
+        let indexer = CodebaseIndexer::new(root_path)?.with_config(config);
+        Ok(Self {
+            packages,
+            graph,
+            capacity: capacity.max(1),
+            state: Mutex::new(LazyState {
+                indexer,
+                indexed: VecDeque::new(),
+            }),
+        })
+    }
+
+    /// Embed the symbols of packages as they are indexed. Evicting a package only removes it
+    /// from the graph; its vectors stay in the store.
+    pub fn with_vector_store(mut self, store: Arc<VectorStore>) -> Self {
+        let state = self.state.into_inner();
+        self.state = Mutex::new(LazyState {
+            indexer: state.indexer.with_vector_store(store),
+            indexed: state.indexed,
+        });
+        self
+    }
+
+    /// Every workspace package, indexed or not
+    pub fn packages(&self) -> &[WorkspacePackage] {
+        &self.packages
+    }
+
+    /// Directories of the packages currently in the graph, least recently used first
+    pub async fn indexed_packages(&self) -> Vec<String> {
+        self.state.lock().await.indexed.iter().cloned().collect()
+    }
+
+    /// Packages containing any of `target_paths` (a router hint such as `packages/ui/src`
+    /// or a package name). A path above the packages, like `packages`, targets none of them.
+    pub fn packages_for_paths(&self, target_paths: &[String]) -> Vec<&WorkspacePackage> {
+        self.packages
+            .iter()
+            .filter(|package| {
+                target_paths.iter().any(|path| {
+                    let path = path.trim_start_matches("./").trim_end_matches('/');
+                    path == package.name
+                        || path == package.dir
+                        || Path::new(path).starts_with(&package.dir)
+                })
+            })
+            .collect()
+    }
+
+    /// Index every package `target_paths` points into that isn't loaded yet; returns how many
+    /// were newly indexed
+    pub async fn ensure_indexed(&self, target_paths: &[String]) -> Result<usize> {
+        let mut newly_indexed = 0;
+        for package in self.packages_for_paths(target_paths) {
+            if self.load(package).await? {
+                newly_indexed += 1;
+            }
+        }
+        Ok(newly_indexed)
+    }
+
+    /// Index the package with this name or directory now; `false` if it was already loaded
+    pub async fn index_package(&self, name_or_dir: &str) -> Result<bool> {
+        let package = self
+            .packages
+            .iter()
+            .find(|p| p.name == name_or_dir || p.dir == name_or_dir.trim_end_matches('/'))
+            .ok_or_else(|| anyhow!("No workspace package named {}", name_or_dir))?;
+        self.load(package).await
+    }
+
+    async fn load(&self, package: &WorkspacePackage) -> Result<bool> {
+        let mut state = self.state.lock().await;
+        if let Some(position) = state.indexed.iter().position(|dir| *dir == package.dir) {
+            let dir = state.indexed.remove(position).unwrap_or_default();
+            state.indexed.push_back(dir);
+            return Ok(false);
+        }
+
+        let report = state.indexer.index_dir(&package.dir).await?;
+        for file in &report.files {
+            match state.indexer.reparse_file(Path::new(&file.relative_path)) {
+                Ok(parsed) => {
+                    self.graph.insert_file(&file.relative_path, &to_graph_data(parsed))?;
+                }
+                Err(e) => debug!("Not adding {} to the graph: {}", file.relative_path, e),
+            }
+        }
+        info!("Indexed workspace package {} ({} files)", package.name, report.total_files);
+
+        state.indexed.push_back(package.dir.clone());
+        while state.indexed.len() > self.capacity {
+            if let Some(evicted) = state.indexed.pop_front() {
+                let removed = self.graph.remove_files_under(&evicted)?;
+                debug!("Evicted workspace package {} ({} files)", evicted, removed);
+            }
+        }
+        self.graph.compute_document_frequencies()?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[tokio::test]
+    async fn test_package_indexed_only_after_search_scoped_to_it() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("package.json"), r#"{"workspaces": ["packages/*"]}"#).unwrap();
+        for (dir, name, source) in [
+            ("packages/ui", "@org/ui", "export function Button() {\n    return null;\n}\n"),
+            ("packages/charts", "@org/charts", "export function LineChart() {\n    return null;\n}\n"),
+        ] {
+            fs::create_dir_all(root.join(dir).join("src")).unwrap();
+            fs::write(root.join(dir).join("package.json"), format!(r#"{{"name":"{}"}}"#, name)).unwrap();
+            fs::write(root.join(dir).join("src/index.ts"), source).unwrap();
+        }
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/app.ts"), "export function main() {}\n").unwrap();
+
+        // The upfront walk leaves packages out
+        let config = IndexConfig {
+            lazy_packages: Some(1),
+            ..Default::default()
+        };
+        let report = CodebaseIndexer::new(root.to_path_buf())
+            .unwrap()
+            .with_config(config)
+            .index()
+            .await
+            .unwrap();
+        let paths: Vec<&str> = report.files.iter().map(|f| f.relative_path.as_str()).collect();
+        assert_eq!(paths, vec!["package.json", "src/app.ts"]);
+
+        let graph = Arc::new(KnowledgeGraph::in_memory().unwrap());
+        let lazy = LazyPackageIndex::new(root.to_path_buf(), graph.clone(), 1).unwrap();
+        assert_eq!(lazy.packages().len(), 2);
+        assert!(graph.search_symbols("Button").unwrap().is_empty());
+
+        // A search outside the packages indexes nothing
+        assert_eq!(lazy.ensure_indexed(&["packages".to_string()]).await.unwrap(), 0);
+        assert!(graph.search_symbols("Button").unwrap().is_empty());
+
+        assert_eq!(lazy.ensure_indexed(&["packages/ui/src".to_string()]).await.unwrap(), 1);
+        assert_eq!(graph.search_symbols("Button").unwrap()[0].file_path, "packages/ui/src/index.ts");
+        assert!(graph.search_symbols("LineChart").unwrap().is_empty());
+        assert_eq!(lazy.ensure_indexed(&["packages/ui".to_string()]).await.unwrap(), 0);
+
+        // Loading a second package past the capacity evicts the first
+        assert!(lazy.index_package("@org/charts").await.unwrap());
+        assert_eq!(lazy.indexed_packages().await, vec!["packages/charts"]);
+        assert!(!graph.search_symbols("LineChart").unwrap().is_empty());
+        assert!(graph.search_symbols("Button").unwrap().is_empty());
+    }
+}
@@ -7,12 +7,32 @@ pub mod types;
 pub mod project_signature;
 pub mod intelligent_detector;
 pub mod language_registry;
+pub mod context_ignore;
+pub mod index_progress;
+pub mod diagnostics;
+pub mod path_priority;
+pub mod source_provider;
+pub mod insert_pipeline;
+pub mod graph_data;
+pub mod lazy_packages;
+pub mod lockfile;
 
 pub use indexer::CodebaseIndexer;
 pub use types::*;
-pub use project_signature::ProjectSignature;
+pub use project_signature::{Change, ProjectSignature, SignatureDiff, WorkspacePackage, CARGO_FEATURE_PREFIX};
 pub use intelligent_detector::IntelligentSignatureDetector;
 pub use language_registry::{LanguageRegistry, LanguageConfig};
+pub use context_ignore::{ContextIgnore, CONTEXT_IGNORE_FILE};
+pub use index_progress::{IndexProgress, ProgressEntry, INDEX_PROGRESS_FILE};
+pub use diagnostics::{IndexDiagnostics, IndexManifest, INDEX_MANIFEST_FILE};
+pub use path_priority::{
+    is_ui_component, PathPriorities, COMMON_UI_PRIORITY_BOOST, DEFAULT_PATH_PRIORITY,
+    PERIPHERAL_PATH_PRIORITY,
+};
+pub use source_provider::{FsSourceProvider, OverlaySourceProvider, SourceProvider};
+pub use insert_pipeline::{InsertPipeline, SymbolSink, DEFAULT_INSERT_QUEUE_CAPACITY};
+pub use graph_data::to_graph_data;
+pub use lazy_packages::LazyPackageIndex;
 
 /// Main entry point for indexing a codebase
 pub async fn index_codebase(path: PathBuf) -> Result<IndexReport> {
@@ -0,0 +1,161 @@
+//! Exact installed versions from lockfiles. Manifests declare ranges (`^18.0.0`); the lockfile
+//! says which release is actually installed, which decides the APIs that exist.
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Package name → exact version locked by `package-lock.json`, `pnpm-lock.yaml` or
+/// `Cargo.lock` under `root_path` (the first of these that exists and parses wins for a name)
+pub fn locked_versions(root_path: &Path) -> HashMap<String, String> {
+    let mut versions = HashMap::new();
+    let read = |file: &str| fs::read_to_string(root_path.join(file)).ok();
+
+    if let Some(content) = read("package-lock.json") {
+        for (name, version) in parse_package_lock(&content) {
+            versions.entry(name).or_insert(version);
+        }
+    }
+    if let Some(content) = read("pnpm-lock.yaml") {
+        for (name, version) in parse_pnpm_lock(&content) {
+            versions.entry(name).or_insert(version);
+        }
+    }
+    if let Some(content) = read("Cargo.lock") {
+        for (name, version) in parse_cargo_lock(&content) {
+            versions.entry(name).or_insert(version);
+        }
+    }
+    versions
+}
+
+/// Top-level installs from `packages["node_modules/<name>"]` (lockfile v2/v3), falling back to
+/// the v1 `dependencies` map
+fn parse_package_lock(content: &str) -> HashMap<String, String> {
+    let Ok(lock) = serde_json::from_str::<Value>(content) else {
+        return HashMap::new();
+    };
+    let mut versions = HashMap::new();
+    if let Some(packages) = lock["packages"].as_object() {
+        for (path, package) in packages {
+            // Nested `node_modules/a/node_modules/b` copies aren't what the app imports
+            let Some(name) = path.strip_prefix("node_modules/").filter(|n| !n.contains("/node_modules/")) else {
+                continue;
+            };
+            if let Some(version) = package["version"].as_str() {
+                versions.insert(name.to_string(), version.to_string());
+            }
+        }
+    }
+    if let Some(dependencies) = lock["dependencies"].as_object() {
+        for (name, dependency) in dependencies {
+            if let Some(version) = dependency["version"].as_str() {
+                versions.entry(name.clone()).or_insert_with(|| version.to_string());
+            }
+        }
+    }
+    versions
+}
+
+/// Minimal reader for the root project's dependencies in `pnpm-lock.yaml`: the v6+ layout
+/// (`importers: '.': dependencies: react: version: 18.2.0`) and the older top-level
+/// `dependencies: react: 18.2.0`. Peer suffixes like `18.2.0(react@18.2.0)` are dropped.
+fn parse_pnpm_lock(content: &str) -> HashMap<String, String> {
+    let mut versions = HashMap::new();
+    let unquote = |s: &str| s.trim().trim_matches(|c| c == '\'' || c == '"').to_string();
+    let exact = |s: &str| unquote(s.split('(').next().unwrap_or(s));
+    // Indent of the dependency names in the current dependency section, if in one
+    let mut names_indent = None;
+    let mut in_root_importer = false;
+    let mut current = String::new();
+
+    for line in content.lines() {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        let trimmed = line.trim();
+        let (key, value) = trimmed.split_once(':').map_or((trimmed, ""), |(k, v)| (k, v.trim()));
+        let key = unquote(key);
+
+        if names_indent.is_some_and(|names| indent < names) {
+            names_indent = None;
+        }
+        match indent {
+            0 => in_root_importer = false,
+            2 => in_root_importer = key == ".",
+            _ => {}
+        }
+
+        if let Some(names) = names_indent {
+            if indent == names {
+                current = key.clone();
+                if !value.is_empty() {
+                    versions.insert(current.clone(), exact(value));
+                }
+            } else if key == "version" && !current.is_empty() {
+                versions.insert(current.clone(), exact(value));
+            }
+            continue;
+        }
+
+        let is_section = matches!(key.as_str(), "dependencies" | "devDependencies" | "optionalDependencies");
+        if is_section && value.is_empty() && (indent == 0 || (indent == 4 && in_root_importer)) {
+            names_indent = Some(indent + 2);
+            current.clear();
+        }
+    }
+    versions
+}
+
+/// `[[package]]` entries of `Cargo.lock`; when several versions of a crate are locked the
+/// highest one is kept
+fn parse_cargo_lock(content: &str) -> HashMap<String, String> {
+    let Ok(lock) = content.parse::<toml::Table>() else {
+        return HashMap::new();
+    };
+    let mut versions: HashMap<String, String> = HashMap::new();
+    for package in lock.get("package").and_then(|p| p.as_array()).into_iter().flatten() {
+        let (Some(name), Some(version)) = (
+            package.get("name").and_then(|v| v.as_str()),
+            package.get("version").and_then(|v| v.as_str()),
+        ) else {
+            continue;
+        };
+        let newer = versions
+            .get(name)
+            .is_none_or(|existing| version_key(version) > version_key(existing));
+        if newer {
+            versions.insert(name.to_string(), version.to_string());
+        }
+    }
+    versions
+}
+
+fn version_key(version: &str) -> Vec<u64> {
+    version
+        .split(['.', '-', '+'])
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lockfile_formats() {
+        let pnpm = "lockfileVersion: '9.0'\n\nimporters:\n\n  .:\n    dependencies:\n      react:\n        specifier: ^18.0.0\n        version: 18.2.0\n      '@tanstack/react-query':\n        specifier: ^5.0.0\n        version: 5.17.9(react@18.2.0)\n\n  apps/web:\n    dependencies:\n      next:\n        specifier: ^14.0.0\n        version: 14.1.0\n\npackages:\n\n  react@18.2.0:\n    resolution: {integrity: sha512-abc}\n";
+        let versions = parse_pnpm_lock(pnpm);
+        assert_eq!(versions["react"], "18.2.0");
+        assert_eq!(versions["@tanstack/react-query"], "5.17.9");
+        assert!(!versions.contains_key("next"));
+
+        let pnpm_v5 = "lockfileVersion: 5.4\n\nspecifiers:\n  zod: ^3.0.0\n\ndependencies:\n  zod: 3.22.4\n";
+        assert_eq!(parse_pnpm_lock(pnpm_v5)["zod"], "3.22.4");
+
+        let cargo = "version = 3\n\n[[package]]\nname = \"syn\"\nversion = \"1.0.109\"\n\n[[package]]\nname = \"syn\"\nversion = \"2.0.48\"\n";
+        assert_eq!(parse_cargo_lock(cargo)["syn"], "2.0.48");
+    }
+}
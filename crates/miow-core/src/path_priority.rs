@@ -0,0 +1,95 @@
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use miow_parsers::{ParsedFile, Symbol, SymbolType};
+use std::collections::HashMap;
+
+/// Priority of files no path glob matches
+pub const DEFAULT_PATH_PRIORITY: f32 = 1.0;
+
+/// Priority of peripheral directories (scripts, examples, docs, benchmarks) by default
+pub const PERIPHERAL_PATH_PRIORITY: f32 = 0.5;
+
+/// Multiplier applied on top of the path priority for UI components
+pub const COMMON_UI_PRIORITY_BOOST: f32 = 1.2;
+
+/// Default `IndexConfig::path_priorities`: core source at full weight, peripheral code halved
+pub fn default_path_priorities() -> HashMap<String, f32> {
+    let mut priorities = HashMap::new();
+    for core in ["src/**", "lib/**", "app/**"] {
+        priorities.insert(core.to_string(), DEFAULT_PATH_PRIORITY);
+    }
+    for peripheral in ["scripts/**", "examples/**", "docs/**", "benches/**"] {
+        priorities.insert(peripheral.to_string(), PERIPHERAL_PATH_PRIORITY);
+    }
+    priorities
+}
+
+/// Whether the parser classified `symbol` as a UI component (PascalCase and rendering JSX, or
+/// typed/declared as a component), rather than guessing from its name
+pub fn is_ui_component(symbol: &Symbol) -> bool {
+    matches!(symbol.kind, SymbolType::Component)
+}
+
+/// Compiled `IndexConfig::path_priorities`, stamping a base priority onto each symbol's
+/// metadata so rerankers prefer core source over peripheral scripts and examples.
+#[derive(Debug, Clone)]
+pub struct PathPriorities {
+    globs: GlobSet,
+    /// Pattern length (as a specificity measure) and weight, indexed like `globs`
+    weights: Vec<(usize, f32)>,
+}
+
+impl PathPriorities {
+    pub fn new(path_priorities: &HashMap<String, f32>) -> Result<Self> {
+        let mut builder = GlobSetBuilder::new();
+        let mut weights = Vec::new();
+        for (pattern, weight) in path_priorities {
+            builder.add(Glob::new(pattern).with_context(|| format!("Invalid path priority glob: {}", pattern))?);
+            weights.push((pattern.len(), *weight));
+        }
+        Ok(Self {
+            globs: builder.build()?,
+            weights,
+        })
+    }
+
+    /// Weight of the most specific glob matching `relative_path`, or `DEFAULT_PATH_PRIORITY`
+    pub fn weight(&self, relative_path: &str) -> f32 {
+        self.globs
+            .matches(relative_path)
+            .into_iter()
+            .map(|i| self.weights[i])
+            .max_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)))
+            .map_or(DEFAULT_PATH_PRIORITY, |(_, weight)| weight)
+    }
+
+    /// Priority of a symbol defined in `relative_path`: the path weight, boosted for UI
+    /// components
+    pub fn symbol_priority(&self, relative_path: &str, is_component: bool) -> f32 {
+        let weight = self.weight(relative_path);
+        if is_component {
+            weight * COMMON_UI_PRIORITY_BOOST
+        } else {
+            weight
+        }
+    }
+
+    /// Stamp `metadata.priority` onto every symbol (and nested symbol) of a parsed file
+    pub fn stamp(&self, relative_path: &str, parsed: &mut ParsedFile) {
+        fn stamp_symbol(priorities: &PathPriorities, relative_path: &str, symbol: &mut Symbol) {
+            symbol.metadata.priority = Some(priorities.symbol_priority(relative_path, is_ui_component(symbol)));
+            for child in &mut symbol.children {
+                stamp_symbol(priorities, relative_path, child);
+            }
+        }
+        for symbol in &mut parsed.symbols {
+            stamp_symbol(self, relative_path, symbol);
+        }
+    }
+}
+
+impl Default for PathPriorities {
+    fn default() -> Self {
+        Self::new(&default_path_priorities()).expect("default path priority globs are valid")
+    }
+}
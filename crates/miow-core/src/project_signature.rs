@@ -1,6 +1,8 @@
+use crate::lockfile::locked_versions;
 use anyhow::{Context, Result};
+use miow_common::FileMap;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -13,12 +15,162 @@ pub struct ProjectSignature {
     pub validation_library: Option<String>,
     pub auth_library: Option<String>,
     pub styling: Vec<String>,
+    /// Dependency versions: exact when a lockfile pins them, else the manifest's range
     pub dependencies: HashMap<String, String>,
     pub dev_dependencies: HashMap<String, String>,
+    /// Manifest ranges of dependencies whose version above came from the lockfile,
+    /// e.g. `react` → `^18.0.0` while `dependencies["react"]` is `18.2.0`
+    #[serde(default)]
+    pub declared_ranges: HashMap<String, String>,
+    /// Dependencies declared with a local protocol (`workspace:`, `link:`, `file:`),
+    /// mapped to their original specifier.
+    #[serde(default)]
+    pub internal_dependencies: HashMap<String, String>,
+    /// Monorepo workspace packages mapped to their entry point relative to the root,
+    /// e.g. `@org/ui` → `packages/ui/src/index.ts`
+    #[serde(default)]
+    pub workspace_packages: HashMap<String, String>,
+    /// Global state libraries in use (e.g. "Zustand", "Redux Toolkit")
+    #[serde(default)]
+    pub state_management: Vec<String>,
     pub features: Vec<String>,
+    /// `[target.'cfg(...)'.dependencies]` from Cargo.toml, keyed by target expression
+    #[serde(default)]
+    pub target_dependencies: HashMap<String, HashMap<String, String>>,
+    /// Environment variable names the app expects, from `.env.example` or a similar
+    /// template; real `.env` files are never read
+    #[serde(default)]
+    pub environment_vars: Vec<String>,
 }
 
+/// A monorepo workspace package found by `ProjectSignature::detect_workspaces`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WorkspacePackage {
+    /// `name` from the package's `package.json`
+    pub name: String,
+    /// Package directory relative to the root, e.g. `packages/ui`
+    pub dir: String,
+}
+
+/// A field that differs between two signatures
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Change<T> {
+    pub before: T,
+    pub after: T,
+}
+
+/// How a project's stack changed between two indexing runs
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SignatureDiff {
+    /// Runtime or dev dependencies that are new, sorted by name
+    pub added_dependencies: Vec<String>,
+    pub removed_dependencies: Vec<String>,
+    /// Dependencies whose version specifier changed, keyed by name
+    pub changed_dependencies: BTreeMap<String, Change<String>>,
+    pub framework: Option<Change<String>>,
+    pub ui_library: Option<Change<Option<String>>>,
+    pub validation_library: Option<Change<Option<String>>>,
+}
+
+impl SignatureDiff {
+    pub fn is_empty(&self) -> bool {
+        *self == SignatureDiff::default()
+    }
+
+    /// Whether tags derived from the signature (`ui:*`, `validation:*`, `zod-schema`,
+    /// `nextjs-*`) are stale, so every file has to be parsed and tagged again
+    pub fn requires_retagging(&self) -> bool {
+        self.framework.is_some() || self.ui_library.is_some() || self.validation_library.is_some()
+    }
+}
+
+/// Names in `dependencies` that `other` doesn't have, sorted
+fn missing_from(dependencies: &BTreeMap<&str, &str>, other: &BTreeMap<&str, &str>) -> Vec<String> {
+    dependencies
+        .keys()
+        .filter(|name| !other.contains_key(*name))
+        .map(|name| name.to_string())
+        .collect()
+}
+
+fn change<T: PartialEq + Clone>(before: &T, after: &T) -> Option<Change<T>> {
+    (before != after).then(|| Change {
+        before: before.clone(),
+        after: after.clone(),
+    })
+}
+
+/// Entry points tried, in order, when a workspace package declares no `source` field.
+/// Source files come first so imports resolve to code rather than build output.
+const PACKAGE_ENTRY_CANDIDATES: [&str; 8] = [
+    "src/index.ts",
+    "src/index.tsx",
+    "src/index.js",
+    "src/index.jsx",
+    "index.ts",
+    "index.tsx",
+    "index.js",
+    "index.jsx",
+];
+
+/// Prefix for Cargo `[features]` entries in `ProjectSignature.features`
+pub const CARGO_FEATURE_PREFIX: &str = "cargo-feature:";
+
+/// Directories whose children are feature/domain modules
+const FEATURE_ROOT_DIRS: [&str; 3] = ["features", "modules", "domains"];
+
+/// Files a top-level directory needs before it counts as a feature module
+const MIN_FEATURE_DIR_FILES: usize = 5;
+
+/// Technical layers that organize code by kind rather than by feature
+const GENERIC_LAYER_DIRS: [&str; 16] = [
+    "components", "utils", "lib", "hooks", "types", "styles", "assets", "common", "shared",
+    "config", "test", "tests", "__tests__", "public", "node_modules", "target",
+];
+
 impl ProjectSignature {
+    /// Detect the signature and compare it with the one recorded by the last completed run
+    /// in the index manifest at `manifest_path`. The diff is `None` when no run has completed.
+    pub fn detect_cached(root_path: &Path, manifest_path: &Path) -> Result<(Self, Option<SignatureDiff>)> {
+        let signature = Self::detect(root_path)?;
+        let diff = crate::diagnostics::IndexManifest::load(manifest_path)?
+            .map(|manifest| signature.diff(&manifest.signature));
+        Ok((signature, diff))
+    }
+
+    /// What changed going from `previous` to this signature
+    pub fn diff(&self, previous: &ProjectSignature) -> SignatureDiff {
+        let before = previous.all_dependencies();
+        let after = self.all_dependencies();
+        SignatureDiff {
+            added_dependencies: missing_from(&after, &before),
+            removed_dependencies: missing_from(&before, &after),
+            changed_dependencies: after
+                .iter()
+                .filter_map(|(name, version)| {
+                    let previous_version = before.get(name).filter(|v| *v != version)?;
+                    let change = Change {
+                        before: previous_version.to_string(),
+                        after: version.to_string(),
+                    };
+                    Some((name.to_string(), change))
+                })
+                .collect(),
+            framework: change(&previous.framework, &self.framework),
+            ui_library: change(&previous.ui_library, &self.ui_library),
+            validation_library: change(&previous.validation_library, &self.validation_library),
+        }
+    }
+
+    /// Runtime and dev dependencies by name; a runtime declaration wins over a dev one
+    fn all_dependencies(&self) -> BTreeMap<&str, &str> {
+        self.dev_dependencies
+            .iter()
+            .chain(&self.dependencies)
+            .map(|(name, version)| (name.as_str(), version.as_str()))
+            .collect()
+    }
+
     pub fn detect(root_path: &Path) -> Result<Self> {
         let mut signature = ProjectSignature::default();
 
@@ -29,6 +181,7 @@ impl ProjectSignature {
                 "npm" | "yarn" | "pnpm" => {
                     if let Ok(package_json) = Self::parse_package_json(root_path) {
                         signature = Self::analyze_npm_package(&package_json, signature);
+                        Self::resolve_protocol_versions(root_path, &package_json, &mut signature);
                     }
                 }
                 "cargo" => {
@@ -43,9 +196,13 @@ impl ProjectSignature {
                 _ => {}
             }
         }
+        Self::apply_locked_versions(root_path, &mut signature);
 
-        // Detect language from file extensions
-        signature.language = Self::detect_language_from_files(root_path)?;
+        // Detect language from file extensions, keeping what the manifest implied if that fails
+        let detected_language = Self::detect_language_from_files(root_path)?;
+        if detected_language != "unknown" || signature.language.is_empty() {
+            signature.language = detected_language;
+        }
 
         // Detect framework from files/config
         signature.framework = Self::detect_framework(root_path, &signature.language)?;
@@ -57,8 +214,20 @@ impl ProjectSignature {
         // Styling detection
         signature.styling = Self::detect_styling(root_path, &signature.dependencies)?;
 
-        // Features detection
+        // Libraries the indexer tags symbols with, compared between runs by `diff`
+        signature.ui_library = Self::detect_ui_library(root_path, &signature.dependencies);
+        signature.validation_library = Self::detect_validation_library(&signature.dependencies);
+        signature.auth_library = Self::detect_auth_library(&signature.dependencies);
+
+        // State management detection
+        signature.state_management = Self::detect_state_management(&signature.dependencies);
+
+        signature.environment_vars = Self::detect_environment_vars(root_path);
+
+        // Features detection (keeping features declared in the manifest)
+        let manifest_features = std::mem::take(&mut signature.features);
         signature.features = Self::detect_features(root_path, &signature);
+        signature.features.extend(manifest_features);
 
         Ok(signature)
     }
@@ -96,12 +265,14 @@ impl ProjectSignature {
     fn analyze_npm_package(package_json: &Value, mut signature: ProjectSignature) -> ProjectSignature {
         if let Some(deps) = package_json["dependencies"].as_object() {
             for (name, version) in deps {
-                signature.dependencies.insert(name.clone(), version.as_str().unwrap_or("").to_string());
+                let version = Self::normalize_npm_version(name, version.as_str().unwrap_or(""), &mut signature);
+                signature.dependencies.insert(name.clone(), version);
             }
         }
         if let Some(dev_deps) = package_json["devDependencies"].as_object() {
             for (name, version) in dev_deps {
-                signature.dev_dependencies.insert(name.clone(), version.as_str().unwrap_or("").to_string());
+                let version = Self::normalize_npm_version(name, version.as_str().unwrap_or(""), &mut signature);
+                signature.dev_dependencies.insert(name.clone(), version);
             }
         }
 
@@ -114,25 +285,298 @@ impl ProjectSignature {
         signature
     }
 
-    fn analyze_rust_package(cargo_toml: &str, mut signature: ProjectSignature) -> ProjectSignature {
-        // Parse Cargo.toml for Rust crates
-        // Basic parsing - could use toml crate for better parsing
-        for line in cargo_toml.lines() {
-            if line.trim().starts_with('[') {
+    /// Strip package-manager protocols from a version specifier. Local protocols are
+    /// recorded in `internal_dependencies`; versions that can only be resolved against
+    /// the workspace (`workspace:*`, `catalog:`) become `*` until resolved.
+    fn normalize_npm_version(name: &str, version: &str, signature: &mut ProjectSignature) -> String {
+        if let Some(range) = version.strip_prefix("workspace:") {
+            signature.internal_dependencies.insert(name.to_string(), version.to_string());
+            match range {
+                "*" | "^" | "~" | "" => "*".to_string(),
+                range => range.to_string(),
+            }
+        } else if version.starts_with("link:") || version.starts_with("file:") {
+            signature.internal_dependencies.insert(name.to_string(), version.to_string());
+            "*".to_string()
+        } else if version.starts_with("catalog:") {
+            "*".to_string()
+        } else {
+            version.to_string()
+        }
+    }
+
+    /// Resolve `workspace:` versions to sibling package versions and `catalog:` versions
+    /// to the ranges declared in `pnpm-workspace.yaml`.
+    fn resolve_protocol_versions(root_path: &Path, package_json: &Value, signature: &mut ProjectSignature) {
+        let (package_globs, catalogs) = Self::workspace_globs(root_path, package_json);
+        let sibling_versions = Self::workspace_package_versions(root_path, &package_globs);
+        signature.workspace_packages = Self::workspace_package_entries(root_path, &package_globs);
+
+        for (section, deps) in [
+            ("dependencies", &mut signature.dependencies),
+            ("devDependencies", &mut signature.dev_dependencies),
+        ] {
+            let Some(declared) = package_json[section].as_object() else { continue };
+            for (name, spec) in declared {
+                let spec = spec.as_str().unwrap_or("");
+                // A plain range on a workspace member's name still means the local package
+                if signature.workspace_packages.contains_key(name) {
+                    signature
+                        .internal_dependencies
+                        .entry(name.clone())
+                        .or_insert_with(|| spec.to_string());
+                }
+                let resolved = if let Some(range) = spec.strip_prefix("workspace:") {
+                    sibling_versions.get(name).map(|version| match range {
+                        "^" | "~" => format!("{}{}", range, version),
+                        _ => version.clone(),
+                    })
+                } else if let Some(catalog) = spec.strip_prefix("catalog:") {
+                    let catalog = if catalog == "default" { "" } else { catalog };
+                    catalogs.get(catalog).and_then(|entries| entries.get(name)).cloned()
+                } else {
+                    None
+                };
+
+                // Only `*` placeholders need resolving; explicit ranges are kept.
+                if let Some(version) = resolved {
+                    if deps.get(name).is_some_and(|v| v == "*") {
+                        deps.insert(name.clone(), version);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Replace declared ranges with the exact versions the lockfile installed, keeping each
+    /// range in `declared_ranges`. Local packages (`workspace:`, `path:`) are left alone.
+    pub(crate) fn apply_locked_versions(root_path: &Path, signature: &mut ProjectSignature) {
+        let locked = locked_versions(root_path);
+        if locked.is_empty() {
+            return;
+        }
+        for deps in [&mut signature.dependencies, &mut signature.dev_dependencies] {
+            for (name, range) in deps.iter_mut() {
+                if signature.internal_dependencies.contains_key(name) || range.starts_with("path:") {
+                    continue;
+                }
+                let Some(version) = locked.get(name).filter(|v| *v != range) else { continue };
+                signature.declared_ranges.insert(name.clone(), std::mem::replace(range, version.clone()));
+            }
+        }
+    }
+
+    /// Workspace package globs from `pnpm-workspace.yaml` and `package.json`, with the pnpm
+    /// catalogs keyed by name
+    fn workspace_globs(root_path: &Path, package_json: &Value) -> (Vec<String>, HashMap<String, HashMap<String, String>>) {
+        let workspace_yaml = fs::read_to_string(root_path.join("pnpm-workspace.yaml")).unwrap_or_default();
+        let (mut package_globs, catalogs) = Self::parse_pnpm_workspace(&workspace_yaml);
+
+        // npm/yarn workspaces: either an array or { packages: [...] }
+        let workspaces = package_json["workspaces"]
+            .as_array()
+            .or_else(|| package_json["workspaces"]["packages"].as_array());
+        if let Some(globs) = workspaces {
+            package_globs.extend(globs.iter().filter_map(|g| g.as_str().map(String::from)));
+        }
+        (package_globs, catalogs)
+    }
+
+    /// Enumerate the monorepo's workspace packages without reading their sources, sorted by
+    /// directory. Empty for a single-package repo.
+    pub fn detect_workspaces(root_path: &Path) -> Vec<WorkspacePackage> {
+        let package_json = Self::parse_package_json(root_path).unwrap_or(Value::Null);
+        let (package_globs, _) = Self::workspace_globs(root_path, &package_json);
+        let mut packages: Vec<WorkspacePackage> = Self::workspace_package_manifests(root_path, &package_globs)
+            .into_iter()
+            .filter_map(|(dir, package)| {
+                let relative = dir.strip_prefix(root_path).unwrap_or(&dir);
+                Some(WorkspacePackage {
+                    name: package["name"].as_str()?.to_string(),
+                    dir: relative.to_string_lossy().replace('\\', "/"),
+                })
+            })
+            .collect();
+        packages.sort_by(|a, b| a.dir.cmp(&b.dir));
+        packages.dedup_by(|a, b| a.dir == b.dir);
+        packages
+    }
+
+    /// Minimal reader for `pnpm-workspace.yaml`: returns the `packages` globs and the
+    /// catalogs keyed by name (the default `catalog:` is keyed by "").
+    fn parse_pnpm_workspace(content: &str) -> (Vec<String>, HashMap<String, HashMap<String, String>>) {
+        let mut packages = Vec::new();
+        let mut catalogs: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let mut section = "";
+        let mut named_catalog = String::new();
+
+        for line in content.lines() {
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+            let indent = line.len() - line.trim_start().len();
+            let trimmed = line.trim();
+            let unquote = |s: &str| s.trim().trim_matches(|c| c == '\'' || c == '"').to_string();
+
+            if indent == 0 {
+                section = match trimmed.trim_end_matches(':') {
+                    "packages" => "packages",
+                    "catalog" => "catalog",
+                    "catalogs" => "catalogs",
+                    _ => "",
+                };
                 continue;
             }
-            if line.contains(" = ") {
-                let parts: Vec<&str> = line.split(" = ").collect();
-                if parts.len() == 2 {
-                    let name = parts[0].trim().trim_end_matches('"').trim_start_matches('"');
-                    let version = parts[1].trim().trim_matches('"');
-                    signature.dependencies.insert(name.to_string(), version.to_string());
+
+            match section {
+                "packages" => {
+                    if let Some(glob) = trimmed.strip_prefix("- ") {
+                        packages.push(unquote(glob));
+                    }
                 }
+                "catalog" => {
+                    if let Some((name, version)) = trimmed.split_once(':') {
+                        catalogs.entry(String::new()).or_default().insert(unquote(name), unquote(version));
+                    }
+                }
+                "catalogs" => {
+                    if let Some((name, version)) = trimmed.split_once(':') {
+                        if version.trim().is_empty() {
+                            named_catalog = unquote(name);
+                        } else {
+                            catalogs
+                                .entry(named_catalog.clone())
+                                .or_default()
+                                .insert(unquote(name), unquote(version));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        (packages, catalogs)
+    }
+
+    /// Map package name → version for every workspace package matched by `globs`
+    fn workspace_package_versions(root_path: &Path, globs: &[String]) -> HashMap<String, String> {
+        let mut versions = HashMap::new();
+        for (_, package) in Self::workspace_package_manifests(root_path, globs) {
+            if let (Some(name), Some(version)) = (package["name"].as_str(), package["version"].as_str()) {
+                versions.insert(name.to_string(), version.to_string());
+            }
+        }
+        versions
+    }
+
+    /// Map package name → entry point (relative to `root_path`) for every workspace package
+    /// matched by `globs`: its `source` field, else the first existing conventional index
+    /// file, else `module` / `main`.
+    fn workspace_package_entries(root_path: &Path, globs: &[String]) -> HashMap<String, String> {
+        let mut entries = HashMap::new();
+        for (dir, package) in Self::workspace_package_manifests(root_path, globs) {
+            let Some(name) = package["name"].as_str() else { continue };
+            let declared = |field: &str| package[field].as_str().map(|p| p.trim_start_matches("./").to_string());
+            let entry = declared("source")
+                .filter(|p| dir.join(p).is_file())
+                .or_else(|| {
+                    PACKAGE_ENTRY_CANDIDATES
+                        .iter()
+                        .find(|candidate| dir.join(candidate).is_file())
+                        .map(|candidate| candidate.to_string())
+                })
+                .or_else(|| declared("module"))
+                .or_else(|| declared("main"));
+            let Some(entry) = entry else { continue };
+            let relative = dir.join(entry);
+            let relative = relative.strip_prefix(root_path).unwrap_or(&relative);
+            entries.insert(name.to_string(), relative.to_string_lossy().replace('\\', "/"));
+        }
+        entries
+    }
+
+    /// Directory and parsed `package.json` of every workspace package matched by `globs`
+    /// (supports literal directories and a trailing `/*` or `/**`).
+    fn workspace_package_manifests(root_path: &Path, globs: &[String]) -> Vec<(PathBuf, Value)> {
+        let mut manifests = Vec::new();
+
+        for glob in globs {
+            let base = glob.trim_end_matches("/**").trim_end_matches("/*");
+            let dirs: Vec<PathBuf> = if base.len() != glob.len() {
+                fs::read_dir(root_path.join(base))
+                    .map(|entries| entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()).collect())
+                    .unwrap_or_default()
+            } else {
+                vec![root_path.join(base)]
+            };
+
+            for dir in dirs {
+                let Ok(content) = fs::read_to_string(dir.join("package.json")) else { continue };
+                let Ok(package) = serde_json::from_str::<Value>(&content) else { continue };
+                manifests.push((dir, package));
+            }
+        }
+
+        manifests
+    }
+
+    fn analyze_rust_package(cargo_toml: &str, mut signature: ProjectSignature) -> ProjectSignature {
+        let Ok(manifest) = cargo_toml.parse::<toml::Table>() else {
+            return signature;
+        };
+        let table = |value: Option<&toml::Value>| value.and_then(|v| v.as_table()).cloned().unwrap_or_default();
+
+        let workspace = table(manifest.get("workspace"));
+        for deps in [table(manifest.get("dependencies")), table(workspace.get("dependencies"))] {
+            for (name, spec) in &deps {
+                signature.dependencies.insert(name.clone(), Self::cargo_dependency_spec(spec));
+            }
+        }
+        for (name, spec) in &table(manifest.get("dev-dependencies")) {
+            signature.dev_dependencies.insert(name.clone(), Self::cargo_dependency_spec(spec));
+        }
+
+        // `default` only lists other features, so it isn't one itself
+        for feature in table(manifest.get("features")).keys() {
+            if feature != "default" {
+                signature.features.push(format!("{}{}", CARGO_FEATURE_PREFIX, feature));
+            }
+        }
+
+        for (target, config) in &table(manifest.get("target")) {
+            let deps = table(config.get("dependencies"));
+            if deps.is_empty() {
+                continue;
+            }
+            let target_deps = signature.target_dependencies.entry(target.clone()).or_default();
+            for (name, spec) in &deps {
+                target_deps.insert(name.clone(), Self::cargo_dependency_spec(spec));
             }
         }
         signature
     }
 
+    /// Version requirement of a Cargo dependency, or where it comes from when unversioned
+    fn cargo_dependency_spec(spec: &toml::Value) -> String {
+        match spec {
+            toml::Value::String(version) => version.clone(),
+            toml::Value::Table(table) => {
+                if let Some(version) = table.get("version").and_then(|v| v.as_str()) {
+                    version.to_string()
+                } else if table.get("workspace").and_then(|v| v.as_bool()) == Some(true) {
+                    "workspace".to_string()
+                } else if let Some(path) = table.get("path").and_then(|v| v.as_str()) {
+                    format!("path:{}", path)
+                } else if let Some(git) = table.get("git").and_then(|v| v.as_str()) {
+                    format!("git:{}", git)
+                } else {
+                    "*".to_string()
+                }
+            }
+            other => other.to_string(),
+        }
+    }
+
     fn detect_language_from_files(root_path: &Path) -> Result<String> {
         let mut counts = HashMap::new();
         let extensions = vec![".ts", ".tsx", ".js", ".jsx", ".rs", ".py", ".go", ".java"];
@@ -254,6 +698,39 @@ impl ProjectSignature {
         None
     }
 
+    /// Variable names from the first env template at the root, in `ENV_EXAMPLE_FILES` order
+    pub(crate) fn detect_environment_vars(root_path: &Path) -> Vec<String> {
+        miow_parsers::ENV_EXAMPLE_FILES
+            .iter()
+            .find_map(|name| fs::read_to_string(root_path.join(name)).ok())
+            .map(|content| miow_parsers::env_var_names(&content))
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn detect_state_management(dependencies: &HashMap<String, String>) -> Vec<String> {
+        let state_indicators = vec![
+            ("@reduxjs/toolkit", "Redux Toolkit"),
+            ("redux", "Redux"),
+            ("zustand", "Zustand"),
+            ("jotai", "Jotai"),
+            ("recoil", "Recoil"),
+            ("mobx", "MobX"),
+            ("valtio", "Valtio"),
+        ];
+
+        let mut libraries: Vec<String> = state_indicators
+            .into_iter()
+            .filter(|(dep, _)| dependencies.contains_key(*dep))
+            .map(|(_, name)| name.to_string())
+            .collect();
+
+        // Redux Toolkit already implies Redux
+        if libraries.iter().any(|l| l == "Redux Toolkit") {
+            libraries.retain(|l| l != "Redux");
+        }
+        libraries
+    }
+
     fn detect_styling(root_path: &Path, dependencies: &HashMap<String, String>) -> Result<Vec<String>> {
         let mut styling = Vec::new();
 
@@ -320,15 +797,102 @@ impl ProjectSignature {
         if let Some(auth) = &signature.auth_library {
             features.push(format!("Auth: {}", auth));
         }
+        for state in &signature.state_management {
+            features.push(format!("State: {}", state));
+        }
 
         // Styling features
         for style in &signature.styling {
             features.push(format!("Styling: {}", style));
         }
 
+        features.extend(Self::detect_build_features(root_path, signature));
         features
     }
 
+    /// Bundler, monorepo tooling and PWA/service-worker features, from dependencies and the
+    /// config files each tool reads
+    fn detect_build_features(root_path: &Path, signature: &ProjectSignature) -> Vec<String> {
+        let has_dependency =
+            |name: &str| signature.dependencies.contains_key(name) || signature.dev_dependencies.contains_key(name);
+        let has_config = |stem: &str| {
+            ["js", "mjs", "cjs", "ts", "mts"]
+                .iter()
+                .any(|ext| root_path.join(format!("{}.{}", stem, ext)).exists())
+        };
+        let mut features = Vec::new();
+
+        // Bundlers
+        if has_dependency("vite") || has_config("vite.config") {
+            features.push("Bundler: Vite".to_string());
+        }
+        if has_dependency("webpack") || has_config("webpack.config") {
+            features.push("Bundler: Webpack".to_string());
+        }
+        let package_json = fs::read_to_string(root_path.join("package.json")).unwrap_or_default();
+        if package_json.contains("--turbo") {
+            features.push("Bundler: Turbopack".to_string());
+        }
+
+        // Monorepo tooling
+        if root_path.join("turbo.json").exists() || has_dependency("turbo") {
+            features.push("Monorepo: Turborepo".to_string());
+        }
+        if root_path.join("nx.json").exists() || has_dependency("nx") {
+            features.push("Monorepo: Nx".to_string());
+        }
+
+        // PWA: a web app manifest, or a plugin that generates one
+        let pwa_plugin = ["vite-plugin-pwa", "next-pwa", "@ducanh2912/next-pwa"].iter().any(|p| has_dependency(p));
+        if pwa_plugin || Self::has_web_app_manifest(root_path) {
+            features.push("PWA".to_string());
+        }
+        let workbox = signature
+            .dependencies
+            .keys()
+            .chain(signature.dev_dependencies.keys())
+            .any(|name| name.starts_with("workbox-"));
+        if pwa_plugin || workbox || Self::has_service_worker(root_path) {
+            features.push("Service Worker".to_string());
+        }
+
+        features
+    }
+
+    /// A `.webmanifest`, or a `manifest.json` with web app fields (browser extensions also
+    /// ship a `manifest.json`, without `start_url`/`display`)
+    fn has_web_app_manifest(root_path: &Path) -> bool {
+        ["", "public", "static", "src", "app"].iter().any(|dir| {
+            let dir = root_path.join(dir);
+            dir.join("manifest.webmanifest").exists()
+                || dir.join("site.webmanifest").exists()
+                || fs::read_to_string(dir.join("manifest.json"))
+                    .is_ok_and(|manifest| manifest.contains("\"start_url\"") || manifest.contains("\"display\""))
+        })
+    }
+
+    /// A service worker script, or a `serviceWorker.register` call in an entry file
+    fn has_service_worker(root_path: &Path) -> bool {
+        const WORKER_FILES: [&str; 4] = ["sw.js", "service-worker.js", "sw.ts", "service-worker.ts"];
+        const ENTRY_FILES: [&str; 8] = [
+            "src/main.ts",
+            "src/main.tsx",
+            "src/main.js",
+            "src/index.ts",
+            "src/index.tsx",
+            "src/index.js",
+            "src/serviceWorkerRegistration.ts",
+            "src/serviceWorkerRegistration.js",
+        ];
+        let worker_file = ["", "public", "src", "static"]
+            .iter()
+            .any(|dir| WORKER_FILES.iter().any(|file| root_path.join(dir).join(file).exists()));
+        worker_file
+            || ENTRY_FILES.iter().any(|entry| {
+                fs::read_to_string(root_path.join(entry)).is_ok_and(|source| source.contains("serviceWorker.register"))
+            })
+    }
+
     fn scan_for_component_usage(root_path: &Path, component_name: &str) -> Option<PathBuf> {
         // Simple scan - could be enhanced with git grep or tree-sitter
         let pattern = format!("{}(", component_name); // Usage like InputBox(props)
@@ -370,10 +934,108 @@ impl ProjectSignature {
         if let Some(ref val) = self.validation_library {
             parts.push(format!("Validation: {}", val));
         }
+        if !self.state_management.is_empty() {
+            parts.push(format!("State Management: {}", self.state_management.join(" + ")));
+        }
+        let mut cargo_features: Vec<&str> = self
+            .features
+            .iter()
+            .filter_map(|f| f.strip_prefix(CARGO_FEATURE_PREFIX))
+            .collect();
+        if !cargo_features.is_empty() {
+            cargo_features.sort();
+            parts.push(format!("Cargo Features: {}", cargo_features.join(" + ")));
+        }
         parts.join(", ")
     }
 
+    /// Feature/domain folders such as `src/features/auth`: every child of a `features/`,
+    /// `modules/` or `domains/` directory. Without such roots, falls back to directories
+    /// directly under the source root (`src/` when most files live there) that hold at
+    /// least `MIN_FEATURE_DIR_FILES` files and aren't generic layers like `components/`.
+    pub fn feature_modules(file_map: &FileMap) -> Vec<String> {
+        let paths: Vec<Vec<&str>> = file_map
+            .files
+            .iter()
+            .filter(|f| !f.is_binary)
+            .map(|f| f.path.split(['/', '\\']).filter(|c| !c.is_empty() && *c != ".").collect())
+            .collect();
+
+        let mut modules: Vec<String> = Vec::new();
+        for components in &paths {
+            // The last component is the file name, so a module needs a component after it
+            let dirs = &components[..components.len().saturating_sub(1)];
+            if let Some(root) = dirs.iter().position(|c| FEATURE_ROOT_DIRS.contains(c)) {
+                if let Some(module) = dirs.get(root + 1) {
+                    let path = [&dirs[..=root], &[*module]].concat().join("/");
+                    if !modules.contains(&path) {
+                        modules.push(path);
+                    }
+                }
+            }
+        }
+
+        if modules.is_empty() {
+            let in_src = paths.iter().filter(|c| c.first() == Some(&"src")).count();
+            let prefix: &[&str] = if in_src * 2 > paths.len() { &["src"] } else { &[] };
+
+            let mut counts: HashMap<&str, usize> = HashMap::new();
+            for components in &paths {
+                if components.len() > prefix.len() + 1 && components.starts_with(prefix) {
+                    *counts.entry(components[prefix.len()]).or_insert(0) += 1;
+                }
+            }
+            modules = counts
+                .into_iter()
+                .filter(|(dir, count)| {
+                    *count >= MIN_FEATURE_DIR_FILES && !GENERIC_LAYER_DIRS.contains(dir) && !dir.starts_with('.')
+                })
+                .map(|(dir, _)| [prefix, &[dir]].concat().join("/"))
+                .collect();
+        }
+
+        modules.sort();
+        modules
+    }
+
     /// Get the dominant language (alias for language field)
+    /// Build output and tool caches of this project's framework and language, added to
+    /// `IndexConfig::ignore_patterns` before the walk
+    pub fn ignore_patterns(&self) -> Vec<&'static str> {
+        let has_dependency = |name: &str| self.dependencies.contains_key(name) || self.dev_dependencies.contains_key(name);
+        let mut patterns = Vec::new();
+
+        if self.language == "python" || self.package_manager == "pip" {
+            patterns.extend(["__pycache__", ".venv", "venv", ".mypy_cache", ".pytest_cache", ".ruff_cache", ".tox"]);
+        }
+        if self.language == "rust" || self.package_manager == "cargo" {
+            patterns.push("target");
+        }
+        if matches!(self.package_manager.as_str(), "npm" | "yarn" | "pnpm") {
+            patterns.extend([".turbo", ".vercel", ".parcel-cache"]);
+        }
+        if self.framework.contains("Next.js") || has_dependency("next") {
+            // `next export` writes the static site to `out/`
+            patterns.extend([".next", "out"]);
+        }
+        if has_dependency("@sveltejs/kit") {
+            patterns.push(".svelte-kit");
+        }
+        if has_dependency("nuxt") {
+            patterns.extend([".nuxt", ".output"]);
+        }
+        if has_dependency("astro") {
+            patterns.push(".astro");
+        }
+        if has_dependency("gatsby") {
+            patterns.push(".cache");
+        }
+        if has_dependency("expo") {
+            patterns.push(".expo");
+        }
+        patterns
+    }
+
     pub fn dominant_language(&self) -> &str {
         &self.language
     }
@@ -423,6 +1085,11 @@ impl ProjectSignature {
             }
         }
 
+        // State management questions
+        if !self.state_management.is_empty() {
+            questions.push("What global state stores exist?".to_string());
+        }
+
         questions
     }
 }
@@ -447,4 +1114,291 @@ mod tests {
         let signature = ProjectSignature::analyze_npm_package(&package_json, ProjectSignature::default());
         assert_eq!(signature.framework, "Next.js".to_string());
     }
+
+    #[test]
+    fn test_vite_bundler_detected_from_dependency_and_config() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"dependencies":{"react":"18.2.0"},"devDependencies":{"vite":"^5.0.0"}}"#,
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("vite.config.ts"), "export default {}").unwrap();
+
+        let signature = ProjectSignature::detect(temp_dir.path()).unwrap();
+        assert!(signature.features.contains(&"Bundler: Vite".to_string()));
+        assert!(!signature.features.contains(&"Bundler: Webpack".to_string()));
+        assert!(!signature.features.contains(&"PWA".to_string()));
+    }
+
+    #[test]
+    fn test_pwa_manifest_and_service_worker_detected() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("package.json"), r#"{"dependencies":{"react":"18.2.0"}}"#).unwrap();
+        fs::create_dir_all(temp_dir.path().join("public")).unwrap();
+        fs::write(
+            temp_dir.path().join("public/manifest.json"),
+            r#"{"name":"App","start_url":"/","display":"standalone"}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        fs::write(
+            temp_dir.path().join("src/main.tsx"),
+            "navigator.serviceWorker.register('/sw.js');",
+        )
+        .unwrap();
+
+        let signature = ProjectSignature::detect(temp_dir.path()).unwrap();
+        assert!(signature.features.contains(&"PWA".to_string()));
+        assert!(signature.features.contains(&"Service Worker".to_string()));
+    }
+
+    #[test]
+    fn test_detect_zustand_state_management() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"dependencies":{"react":"18.2.0","zustand":"^4.5.0"}}"#,
+        )
+        .unwrap();
+
+        let signature = ProjectSignature::detect(temp_dir.path()).unwrap();
+        assert_eq!(signature.state_management, vec!["Zustand".to_string()]);
+        assert!(signature.to_description().contains("State Management: Zustand"));
+        assert!(signature
+            .get_question_templates()
+            .contains(&"What global state stores exist?".to_string()));
+    }
+
+    #[test]
+    fn test_cargo_features_and_target_dependencies() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            r#"
+[package]
+name = "geo"
+version = "0.3.0"
+
+[dependencies]
+serde = { version = "1.0", optional = true }
+rayon = "1.8"
+
+[features]
+default = ["parallel"]
+parallel = ["dep:rayon"]
+serde = ["dep:serde"]
+
+[target.'cfg(windows)'.dependencies]
+winapi = { version = "0.3", features = ["fileapi"] }
+"#,
+        )
+        .unwrap();
+
+        let signature = ProjectSignature::detect(temp_dir.path()).unwrap();
+        let mut cargo_features: Vec<&str> = signature
+            .features
+            .iter()
+            .filter_map(|f| f.strip_prefix(CARGO_FEATURE_PREFIX))
+            .collect();
+        cargo_features.sort();
+        assert_eq!(cargo_features, vec!["parallel", "serde"]);
+        assert!(signature.to_description().contains("Cargo Features: parallel + serde"));
+
+        assert_eq!(signature.dependencies.get("serde"), Some(&"1.0".to_string()));
+        assert!(!signature.dependencies.contains_key("name"), "package fields are not dependencies");
+        assert!(!signature.dependencies.contains_key("winapi"));
+        assert_eq!(
+            signature.target_dependencies["cfg(windows)"].get("winapi"),
+            Some(&"0.3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_env_example_lists_expected_vars_without_reading_env() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("package.json"), r#"{"name":"shop"}"#).unwrap();
+        fs::write(
+            root.join(".env.example"),
+            "# Copy to .env\nDATABASE_URL=postgres://localhost/shop\nNEXTAUTH_SECRET=changeme\nSTRIPE_KEY=\n",
+        )
+        .unwrap();
+        fs::write(root.join(".env"), "DATABASE_URL=postgres://prod\nLEAKED_TOKEN=hunter2\n").unwrap();
+
+        let signature = ProjectSignature::detect(root).unwrap();
+        assert_eq!(signature.environment_vars, vec!["DATABASE_URL", "NEXTAUTH_SECRET", "STRIPE_KEY"]);
+        let serialized = serde_json::to_string(&signature).unwrap();
+        assert!(!serialized.contains("hunter2") && !serialized.contains("changeme"));
+    }
+
+    #[test]
+    fn test_feature_modules_under_features_root() {
+        let mut file_map = FileMap::new();
+        for path in [
+            "src/features/auth/LoginForm.tsx",
+            "src/features/auth/hooks/useSession.ts",
+            "src/features/billing/InvoiceList.tsx",
+            "src/components/Button.tsx",
+            "src/features/README.md",
+        ] {
+            file_map.add_file(PathBuf::from(path), 100, "TypeScript".to_string(), false);
+        }
+
+        assert_eq!(
+            ProjectSignature::feature_modules(&file_map),
+            vec!["src/features/auth".to_string(), "src/features/billing".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_workspace_protocol_resolves_to_sibling_version() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{
+                "workspaces": ["packages/*"],
+                "dependencies": {
+                    "@acme/ui": "workspace:*",
+                    "@acme/utils": "workspace:^",
+                    "@acme/local": "file:../local",
+                    "next": "14.0.0"
+                }
+            }"#,
+        )
+        .unwrap();
+        for (dir, name, version) in [("ui", "@acme/ui", "1.4.0"), ("utils", "@acme/utils", "0.2.1")] {
+            let package_dir = temp_dir.path().join("packages").join(dir);
+            fs::create_dir_all(&package_dir).unwrap();
+            fs::write(
+                package_dir.join("package.json"),
+                format!(r#"{{"name":"{}","version":"{}"}}"#, name, version),
+            )
+            .unwrap();
+        }
+
+        let signature = ProjectSignature::detect(temp_dir.path()).unwrap();
+
+        assert_eq!(signature.dependencies["@acme/ui"], "1.4.0");
+        assert_eq!(signature.dependencies["@acme/utils"], "^0.2.1");
+        assert_eq!(signature.dependencies["@acme/local"], "*");
+        assert_eq!(signature.dependencies["next"], "14.0.0");
+        assert_eq!(signature.internal_dependencies["@acme/ui"], "workspace:*");
+        assert_eq!(signature.internal_dependencies["@acme/local"], "file:../local");
+        assert!(!signature.internal_dependencies.contains_key("next"));
+    }
+
+    #[test]
+    fn test_lockfile_pins_react_to_installed_version() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"dependencies": {"react": "^18.0.0", "zod": "3.22.4"}, "devDependencies": {"typescript": "~5.3.0"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("package-lock.json"),
+            r#"{
+                "lockfileVersion": 3,
+                "packages": {
+                    "": {"dependencies": {"react": "^18.0.0"}},
+                    "node_modules/react": {"version": "18.2.0"},
+                    "node_modules/zod": {"version": "3.22.4"},
+                    "node_modules/typescript": {"version": "5.3.3"},
+                    "node_modules/legacy/node_modules/react": {"version": "16.14.0"}
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let signature = ProjectSignature::detect(temp_dir.path()).unwrap();
+
+        assert_eq!(signature.dependencies["react"], "18.2.0");
+        assert_eq!(signature.declared_ranges["react"], "^18.0.0");
+        assert_eq!(signature.dev_dependencies["typescript"], "5.3.3");
+        assert_eq!(signature.declared_ranges["typescript"], "~5.3.0");
+        // Already exact: nothing to remember
+        assert_eq!(signature.dependencies["zod"], "3.22.4");
+        assert!(!signature.declared_ranges.contains_key("zod"));
+    }
+
+    #[test]
+    fn test_catalog_protocol_resolves_from_pnpm_workspace() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{
+                "dependencies": { "react": "catalog:react", "zod": "catalog:" },
+                "devDependencies": { "typescript": "catalog:missing" }
+            }"#,
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("pnpm-workspace.yaml"),
+            "packages:\n  - 'packages/*'\ncatalog:\n  zod: ^3.22.0\ncatalogs:\n  react:\n    react: ^18.2.0\n    react-dom: ^18.2.0\n",
+        )
+        .unwrap();
+
+        let signature = ProjectSignature::detect(temp_dir.path()).unwrap();
+
+        assert_eq!(signature.dependencies["react"], "^18.2.0");
+        assert_eq!(signature.dependencies["zod"], "^3.22.0");
+        // Unknown catalogs stay unresolved instead of keeping the protocol string
+        assert_eq!(signature.dev_dependencies["typescript"], "*");
+        assert!(signature.internal_dependencies.is_empty());
+    }
+
+    #[test]
+    fn test_scoped_workspace_import_resolves_to_local_package() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("package.json"), r#"{"workspaces": ["apps/*", "packages/*"]}"#).unwrap();
+        for (dir, manifest) in [
+            ("apps/web", r#"{"name":"web","dependencies":{"@org/ui":"^1.0.0","react":"18.2.0"}}"#),
+            ("packages/ui", r#"{"name":"@org/ui","version":"1.0.0","main":"dist/index.js"}"#),
+        ] {
+            fs::create_dir_all(root.join(dir).join("src")).unwrap();
+            fs::write(root.join(dir).join("package.json"), manifest).unwrap();
+        }
+        fs::write(root.join("packages/ui/src/index.ts"), "export { Button } from './Button';\n").unwrap();
+
+        let signature = ProjectSignature::detect(root).unwrap();
+        assert_eq!(signature.workspace_packages["@org/ui"], "packages/ui/src/index.ts");
+        assert!(!signature.workspace_packages.contains_key("react"));
+
+        let graph = miow_graph::KnowledgeGraph::in_memory().unwrap();
+        let file = |imports: Vec<miow_graph::ImportData>| miow_graph::ParsedFileData {
+            symbols: vec![],
+            imports,
+            design_tokens: vec![],
+            type_definitions: vec![],
+            constants: vec![],
+            schemas: vec![],
+            language: "tsx".to_string(),
+            exports: vec![],
+            tests: vec![],
+        };
+        let import = |source: &str, names: &[&str]| miow_graph::ImportData {
+            source: source.to_string(),
+            names: names.iter().map(|n| n.to_string()).collect(),
+            start_line: 1,
+            end_line: 1,
+        };
+        graph
+            .insert_file("apps/web/src/page.tsx", &file(vec![import("@org/ui", &["Button"]), import("react", &["useState"])]))
+            .unwrap();
+        graph.insert_file("packages/ui/src/index.ts", &file(vec![import("./Button", &["Button"])])).unwrap();
+        graph.insert_file("packages/ui/src/Button.tsx", &file(vec![])).unwrap();
+        graph.set_workspace_packages(&signature.workspace_packages).unwrap();
+
+        let files: Vec<String> = ["apps/web/src/page.tsx", "packages/ui/src/Button.tsx", "packages/ui/src/index.ts"]
+            .map(String::from)
+            .to_vec();
+        let context = graph.import_context("apps/web/src/page.tsx").unwrap();
+        assert_eq!(context.imported_files(&files), vec!["packages/ui/src/index.ts"]);
+        assert_eq!(
+            context.boost("Button", "packages/ui/src/index.ts"),
+            miow_graph::import_context::IMPORTED_SYMBOL_BOOST
+        );
+    }
 }
@@ -0,0 +1,91 @@
+//! Where the indexer reads source text from. Editors and language servers hold unsaved buffers
+//! that are newer than the files on disk; an `OverlaySourceProvider` serves those buffers and
+//! falls through to the filesystem for everything else.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// Source of file contents for indexing and re-parsing
+pub trait SourceProvider: Send + Sync {
+    fn read(&self, path: &Path) -> Result<String>;
+
+    /// Raw contents, so callers can detect binary files and invalid UTF-8
+    fn read_bytes(&self, path: &Path) -> Result<Vec<u8>> {
+        self.read(path).map(String::into_bytes)
+    }
+
+    /// Whether `path` is served from memory rather than disk, in which case on-disk metadata
+    /// (size, mtime) doesn't describe what `read` returns
+    fn is_overlaid(&self, _path: &Path) -> bool {
+        false
+    }
+}
+
+/// Reads files from disk
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FsSourceProvider;
+
+impl SourceProvider for FsSourceProvider {
+    fn read(&self, path: &Path) -> Result<String> {
+        fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))
+    }
+
+    fn read_bytes(&self, path: &Path) -> Result<Vec<u8>> {
+        fs::read(path).with_context(|| format!("Failed to read {:?}", path))
+    }
+}
+
+/// Unsaved editor buffers layered over another provider. Buffers are keyed by the same path
+/// the indexer walks (the indexer root joined with the relative path).
+pub struct OverlaySourceProvider {
+    buffers: RwLock<HashMap<PathBuf, String>>,
+    fallback: Arc<dyn SourceProvider>,
+}
+
+impl OverlaySourceProvider {
+    pub fn new(fallback: Arc<dyn SourceProvider>) -> Self {
+        Self {
+            buffers: RwLock::new(HashMap::new()),
+            fallback,
+        }
+    }
+
+    /// Serve `content` for `path` until the buffer is cleared
+    pub fn set_buffer(&self, path: impl Into<PathBuf>, content: impl Into<String>) {
+        self.buffers.write().unwrap().insert(path.into(), content.into());
+    }
+
+    /// Drop the buffer for `path` (e.g. once it's saved), reading it from the fallback again
+    pub fn clear_buffer(&self, path: &Path) {
+        self.buffers.write().unwrap().remove(path);
+    }
+}
+
+impl Default for OverlaySourceProvider {
+    fn default() -> Self {
+        Self::new(Arc::new(FsSourceProvider))
+    }
+}
+
+impl SourceProvider for OverlaySourceProvider {
+    fn read(&self, path: &Path) -> Result<String> {
+        match self.buffers.read().unwrap().get(path) {
+            Some(buffer) => Ok(buffer.clone()),
+            None => self.fallback.read(path),
+        }
+    }
+
+    fn read_bytes(&self, path: &Path) -> Result<Vec<u8>> {
+        match self.buffers.read().unwrap().get(path) {
+            Some(buffer) => Ok(buffer.clone().into_bytes()),
+            None => self.fallback.read_bytes(path),
+        }
+    }
+
+    fn is_overlaid(&self, path: &Path) -> bool {
+        self.buffers.read().unwrap().contains_key(path) || self.fallback.is_overlaid(path)
+    }
+}
@@ -1,4 +1,6 @@
+use miow_parsers::{ParseMode, SymbolType};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
 /// Represents a file in the codebase
@@ -9,8 +11,17 @@ pub struct CodeFile {
     pub language: Language,
     pub size: u64,
     pub content: String,
+    /// Indexing notes such as [`LOSSY_DECODE_TAG`]
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
+/// Tag for files (and their symbols) that were not valid UTF-8 and were decoded lossily
+pub const LOSSY_DECODE_TAG: &str = "lossy_decode";
+
+/// Tag for files a resumed run skipped because an interrupted run already indexed them
+pub const RESUMED_TAG: &str = "resumed";
+
 /// Supported programming languages
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Language {
@@ -20,6 +31,7 @@ pub enum Language {
     JSX,
     Python,
     Rust,
+    Go,
     CSS,
     JSON,
     Unknown,
@@ -34,12 +46,69 @@ impl Language {
             "jsx" => Language::JSX,
             "py" => Language::Python,
             "rs" => Language::Rust,
+            "go" => Language::Go,
             "css" => Language::CSS,
             "json" => Language::JSON,
             _ => Language::Unknown,
         }
     }
 
+    /// Whether an extension says too little to pick a language, so the content should be sniffed.
+    pub fn is_ambiguous_extension(ext: &str) -> bool {
+        matches!(ext, "" | "txt" | "script")
+    }
+
+    /// Detect a language from file content: a shebang line first, then simple
+    /// declaration heuristics. Returns `Unknown` when nothing matches.
+    pub fn from_content(content: &str) -> Self {
+        let first_line = content.lines().next().unwrap_or("").trim();
+        if let Some(interpreter) = first_line.strip_prefix("#!") {
+            if interpreter.contains("python") {
+                return Language::Python;
+            }
+            if interpreter.contains("ts-node") || interpreter.contains("deno") || interpreter.contains("tsx") {
+                return Language::TypeScript;
+            }
+            if interpreter.contains("node") || interpreter.contains("bun") {
+                return Language::JavaScript;
+            }
+            if interpreter.contains("rust-script") {
+                return Language::Rust;
+            }
+            return Language::Unknown;
+        }
+
+        let lines: Vec<&str> = content.lines().map(str::trim_start).take(200).collect();
+        let any_line = |pred: &dyn Fn(&str) -> bool| lines.iter().any(|l| pred(l));
+
+        if any_line(&|l| l.starts_with("fn ") || l.starts_with("pub fn ") || l.starts_with("impl ") || (l.starts_with("use ") && l.contains("::"))) {
+            Language::Rust
+        } else if any_line(&|l| (l.starts_with("def ") || l.starts_with("class ")) && l.trim_end().ends_with(':'))
+            || any_line(&|l| l.starts_with("from ") && l.contains(" import "))
+        {
+            Language::Python
+        } else if any_line(&|l| l.starts_with("interface ") || l.starts_with("export interface ") || (l.starts_with("type ") && l.contains(" = "))) {
+            Language::TypeScript
+        } else if any_line(&|l| (l.starts_with("import ") && l.contains(" from ")) || l.starts_with("export ") || l.contains("require(")) {
+            Language::JavaScript
+        } else {
+            Language::Unknown
+        }
+    }
+
+    /// Extension understood by the parsers for this language, if it has a parser.
+    pub fn parser_extension(&self) -> Option<&'static str> {
+        match self {
+            // The TypeScript grammar parses plain JavaScript too
+            Language::TypeScript | Language::JavaScript => Some("ts"),
+            Language::TSX | Language::JSX => Some("tsx"),
+            Language::Python => Some("py"),
+            Language::Rust => Some("rs"),
+            Language::Go => Some("go"),
+            _ => None,
+        }
+    }
+
     pub fn is_parseable(&self) -> bool {
         matches!(
             self,
@@ -49,10 +118,22 @@ impl Language {
                 | Language::JSX
                 | Language::Python
                 | Language::Rust
+                | Language::Go
         )
     }
 }
 
+/// Emitted after each file an indexing run processes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexProgressEvent {
+    pub relative_path: String,
+    /// Files processed so far in this run, including this one
+    pub files_indexed: usize,
+    /// Symbols waiting for the embedder; stays at `IndexConfig::insert_queue_capacity` while
+    /// embedding is the bottleneck
+    pub insert_queue_depth: usize,
+}
+
 /// Report generated after indexing
 #[derive(Debug, Serialize, Deserialize)]
 pub struct IndexReport {
@@ -61,14 +142,93 @@ pub struct IndexReport {
     pub total_size: u64,
     pub duration_ms: u128,
     pub files: Vec<CodeFile>,
+    /// Files with a parser that failed to parse
+    #[serde(default)]
+    pub parse_errors: usize,
+}
+
+impl IndexReport {
+    /// Lightweight structure of the indexed files, with paths relative to the root
+    pub fn file_map(&self) -> miow_common::FileMap {
+        let mut file_map = miow_common::FileMap::new();
+        for file in &self.files {
+            file_map.add_file(
+                PathBuf::from(&file.relative_path),
+                file.size,
+                format!("{:?}", file.language),
+                false,
+            );
+        }
+        file_map
+    }
+
+    /// Combine two reports (e.g. from several roots or an incremental pass). Files are
+    /// deduplicated by path, with `other`'s copy winning; counts and sizes are recomputed
+    /// to match, and the longer duration is kept.
+    pub fn merge(mut self, other: IndexReport) -> IndexReport {
+        for (language, count) in other.files_by_language {
+            *self.files_by_language.entry(language).or_insert(0) += count;
+        }
+        self.total_files += other.total_files;
+        self.total_size += other.total_size;
+        self.duration_ms = self.duration_ms.max(other.duration_ms);
+        self.parse_errors += other.parse_errors;
+
+        for file in other.files {
+            if let Some(existing) = self.files.iter_mut().find(|f| f.path == file.path) {
+                let language = format!("{:?}", existing.language);
+                if let Some(count) = self.files_by_language.get_mut(&language) {
+                    *count = count.saturating_sub(1);
+                    if *count == 0 {
+                        self.files_by_language.remove(&language);
+                    }
+                }
+                self.total_files = self.total_files.saturating_sub(1);
+                self.total_size = self.total_size.saturating_sub(existing.size);
+                *existing = file;
+            } else {
+                self.files.push(file);
+            }
+        }
+        self.files.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+        self
+    }
 }
 
 /// Configuration for indexing
 #[derive(Debug, Clone)]
 pub struct IndexConfig {
     pub max_file_size: u64,
+    /// Path components to skip (`build` skips `build/` but not `buildUtils.ts`); patterns
+    /// containing `/` match anywhere in the relative path. Extended per project with
+    /// `ProjectSignature::ignore_patterns` before the walk.
     pub ignore_patterns: Vec<String>,
     pub include_extensions: Vec<String>,
+    /// Path glob → base priority stamped onto each symbol's metadata (most specific glob wins;
+    /// unmatched files get `DEFAULT_PATH_PRIORITY`)
+    pub path_priorities: HashMap<String, f32>,
+    /// Embed each symbol's name, signature and doc comment instead of its body; the full
+    /// content is still stored for display
+    pub embed_signatures: bool,
+    /// `SignaturesOnly` for a quick first scan of a large repo: declarations and imports without
+    /// bodies, references or design tokens. `Indexer::reparse_file` always parses fully.
+    pub parse_mode: ParseMode,
+    /// Symbols parsed but not yet embedded that may be buffered; parsing waits when the queue
+    /// is full. A file is recorded as done in the progress file once its symbols are queued.
+    pub insert_queue_capacity: usize,
+    /// Symbol kinds that get embedded; `None` indexes every kind. Excluding `Constant` also
+    /// skips the extracted constants (API URLs, config values) for a lean index.
+    pub indexed_kinds: Option<HashSet<SymbolType>>,
+    /// Skip monorepo workspace packages during the walk so `LazyPackageIndex` can index each
+    /// one on first use, keeping at most this many packages in the graph at once
+    pub lazy_packages: Option<usize>,
+}
+
+impl IndexConfig {
+    /// Whether symbols of `kind` are embedded
+    pub fn indexes_kind(&self, kind: &SymbolType) -> bool {
+        self.indexed_kinds.as_ref().is_none_or(|kinds| kinds.contains(kind))
+    }
 }
 
 impl Default for IndexConfig {
@@ -83,6 +243,12 @@ impl Default for IndexConfig {
                 ".git".to_string(),
                 ".next".to_string(),
                 "coverage".to_string(),
+                ".svelte-kit".to_string(),
+                ".turbo".to_string(),
+                ".vercel".to_string(),
+                ".parcel-cache".to_string(),
+                "__pycache__".to_string(),
+                ".venv".to_string(),
             ],
             include_extensions: vec![
                 "ts".to_string(),
@@ -91,9 +257,72 @@ impl Default for IndexConfig {
                 "jsx".to_string(),
                 "py".to_string(),
                 "rs".to_string(),
+                "go".to_string(),
                 "css".to_string(),
                 "json".to_string(),
             ],
+            path_priorities: crate::path_priority::default_path_priorities(),
+            embed_signatures: false,
+            parse_mode: ParseMode::Full,
+            insert_queue_capacity: crate::insert_pipeline::DEFAULT_INSERT_QUEUE_CAPACITY,
+            indexed_kinds: None,
+            lazy_packages: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn file(path: &str, language: Language, size: u64) -> CodeFile {
+        CodeFile {
+            path: PathBuf::from(path),
+            relative_path: path.to_string(),
+            language,
+            size,
+            content: String::new(),
+            tags: vec![],
+        }
+    }
+
+    fn report(files: Vec<CodeFile>, duration_ms: u128) -> IndexReport {
+        let mut files_by_language = HashMap::new();
+        for f in &files {
+            *files_by_language.entry(format!("{:?}", f.language)).or_insert(0) += 1;
+        }
+        IndexReport {
+            total_files: files.len(),
+            files_by_language,
+            total_size: files.iter().map(|f| f.size).sum(),
+            duration_ms,
+            files,
+            parse_errors: 0,
+        }
+    }
+
+    #[test]
+    fn test_merge_combines_counts_and_dedups_files() {
+        let first = report(
+            vec![file("a/main.rs", Language::Rust, 100), file("a/app.ts", Language::TypeScript, 50)],
+            120,
+        );
+        let second = report(
+            vec![file("b/lib.py", Language::Python, 30), file("a/app.ts", Language::TypeScript, 70)],
+            80,
+        );
+
+        let merged = first.merge(second);
+
+        assert_eq!(merged.total_files, 3);
+        assert_eq!(merged.files.len(), 3);
+        assert_eq!(merged.total_size, 200);
+        assert_eq!(merged.duration_ms, 120);
+        assert_eq!(merged.files_by_language.get("TypeScript"), Some(&1));
+        assert_eq!(merged.files_by_language.get("Rust"), Some(&1));
+        assert_eq!(merged.files_by_language.get("Python"), Some(&1));
+        let app = merged.files.iter().find(|f| f.relative_path == "a/app.ts").unwrap();
+        assert_eq!(app.size, 70);
+    }
+}
@@ -0,0 +1,127 @@
+//! `styles.button` in a component resolved to the `.button` rules of the CSS module it
+//! imports, so context for a component carries the styles it actually applies.
+
+use crate::reexports::module_candidates;
+use crate::KnowledgeGraph;
+use anyhow::Result;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+
+/// A CSS module class a symbol uses, with the rules that define it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CssModuleClass {
+    /// Symbol reading the class
+    pub symbol: String,
+    pub class_name: String,
+    /// Indexed path of the CSS module
+    pub module_path: String,
+    /// Rules styling the class
+    pub rules: String,
+}
+
+/// The `css_module_classes` entry stored in symbol metadata by the TypeScript parser
+#[derive(Deserialize)]
+struct ClassRef {
+    module: String,
+    class_name: String,
+}
+
+#[derive(Deserialize, Default)]
+struct UsedClasses {
+    #[serde(default)]
+    css_module_classes: Vec<ClassRef>,
+}
+
+impl KnowledgeGraph {
+    /// CSS module classes used by the symbols of `file_path`, resolved to their indexed rules.
+    /// Classes whose module isn't indexed or doesn't define them are skipped.
+    pub fn css_module_classes(&self, file_path: &str) -> Result<Vec<CssModuleClass>> {
+        let mut classes = Vec::new();
+        for symbol in self.get_file_symbols(file_path)? {
+            let used: UsedClasses = symbol
+                .metadata_value()
+                .and_then(|metadata| serde_json::from_value(metadata).ok())
+                .unwrap_or_default();
+            for class_ref in used.css_module_classes {
+                let candidates = module_candidates(file_path, &class_ref.module);
+                let Some(module_path) = self.existing_files(&candidates)?.into_iter().next() else {
+                    continue;
+                };
+                if let Some(rules) = self.css_module_rules(&module_path, &class_ref.class_name)? {
+                    classes.push(CssModuleClass {
+                        symbol: symbol.name.clone(),
+                        class_name: class_ref.class_name,
+                        module_path,
+                        rules,
+                    });
+                }
+            }
+        }
+        Ok(classes)
+    }
+
+    /// Rules of class `class_name` in the CSS module indexed at `module_path`
+    fn css_module_rules(&self, module_path: &str, class_name: &str) -> Result<Option<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT dt.value
+            FROM design_tokens dt
+            JOIN files f ON dt.file_id = f.id
+            WHERE f.path = ?1 AND dt.name = ?2 AND dt.token_type = 'CSSModuleClass'
+            "#,
+        )?;
+        let mut rows = stmt.query_map(params![module_path, class_name], |row| row.get::<_, String>(0))?;
+        Ok(rows.next().transpose()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn file(language: &str, symbols: Vec<SymbolData>, design_tokens: Vec<DesignTokenData>) -> ParsedFileData {
+        ParsedFileData {
+            symbols,
+            design_tokens,
+            language: language.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_styles_button_resolves_to_button_rule() {
+        let graph = KnowledgeGraph::in_memory().unwrap();
+        let button_class = DesignTokenData {
+            token_type: "CSSModuleClass".to_string(),
+            name: "button".to_string(),
+            value: ".button {padding: 8px 16px;}".to_string(),
+            context: ".button".to_string(),
+            start_line: 1,
+            end_line: 1,
+        };
+        graph
+            .insert_file("src/components/Button.module.css", &file("css", vec![], vec![button_class]))
+            .unwrap();
+
+        let component = SymbolData {
+            name: "Button".to_string(),
+            kind: "Component".to_string(),
+            start_line: 3,
+            end_line: 5,
+            content: "function Button() { return <button className={styles.button} />; }".to_string(),
+            metadata: r#"{"css_module_classes":[{"module":"./Button.module.css","class_name":"button"},{"module":"./Button.module.css","class_name":"missing"}]}"#.to_string(),
+            ..Default::default()
+        };
+        graph
+            .insert_file("src/components/Button.tsx", &file("tsx", vec![component], vec![]))
+            .unwrap();
+
+        let classes = graph.css_module_classes("src/components/Button.tsx").unwrap();
+        assert_eq!(classes.len(), 1);
+        assert_eq!(classes[0].symbol, "Button");
+        assert_eq!(classes[0].class_name, "button");
+        assert_eq!(classes[0].module_path, "src/components/Button.module.css");
+        assert_eq!(classes[0].rules, ".button {padding: 8px 16px;}");
+    }
+}
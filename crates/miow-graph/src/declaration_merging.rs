@@ -0,0 +1,232 @@
+//! TypeScript declaration merging: every `interface Window` (or `namespace Api`) with the same
+//! name contributes members to one type, whether repeated in a file, spread over files, or
+//! added by `declare global` / `declare module` augmentations. Lookups show the merged shape
+//! instead of whichever declaration happened to match.
+
+use crate::{KnowledgeGraph, TypeDefinitionResult};
+use anyhow::Result;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+
+/// Stored kinds whose same-named declarations merge
+const MERGEABLE_KINDS: [&str; 2] = ["Interface", "Namespace"];
+
+/// All declarations of one interface or namespace, and the members they add up to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergedTypeDefinition {
+    pub name: String,
+    /// Kind of the first declaration
+    pub kind: String,
+    /// Member declarations (`apiUrl: string`), in declaration order, without duplicates
+    pub members: Vec<String>,
+    /// The declarations merged, ordered by file and line
+    pub declarations: Vec<TypeDefinitionResult>,
+}
+
+impl MergedTypeDefinition {
+    /// One declaration with every member, headed by where the members come from
+    pub fn definition(&self) -> String {
+        let sources: Vec<String> = self
+            .declarations
+            .iter()
+            .map(|d| format!("{}:{}", d.file_path, d.start_line))
+            .collect();
+        let keyword = if self.kind == "Namespace" { "namespace" } else { "interface" };
+        let mut definition = format!("// merged from {}\n{} {} {{\n", sources.join(", "), keyword, self.name);
+        for member in &self.members {
+            definition.push_str(&format!("  {};\n", member));
+        }
+        definition.push('}');
+        definition
+    }
+
+    /// The merged view as a single lookup result, located at the first declaration
+    fn into_result(self) -> TypeDefinitionResult {
+        let definition = self.definition();
+        let first = &self.declarations[0];
+        TypeDefinitionResult {
+            name: self.name,
+            kind: self.kind,
+            definition,
+            file_path: first.file_path.clone(),
+            start_line: first.start_line,
+            end_line: first.end_line,
+        }
+    }
+}
+
+/// Top-level members of a declaration's `{ ... }` body, split on `;`, `,` and line breaks
+/// outside nested brackets. Comments are dropped.
+fn body_members(definition: &str) -> Vec<String> {
+    let Some(open) = definition.find('{') else {
+        return vec![];
+    };
+    let Some(close) = definition.rfind('}').filter(|close| *close > open) else {
+        return vec![];
+    };
+
+    let mut members = Vec::new();
+    let mut depth = 0usize;
+    let mut current = String::new();
+    let mut previous = '\0';
+    for c in definition[open + 1..close].chars() {
+        match c {
+            '{' | '[' | '(' | '<' => depth += 1,
+            // `=>` in a function type isn't a closing bracket
+            '>' if previous == '=' => {}
+            '}' | ']' | ')' | '>' => depth = depth.saturating_sub(1),
+            ';' | ',' | '\n' if depth == 0 => {
+                members.push(std::mem::take(&mut current));
+                previous = c;
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+        previous = c;
+    }
+    members.push(current);
+
+    members
+        .into_iter()
+        .map(|member| member.trim().to_string())
+        .filter(|member| !member.is_empty() && !member.starts_with("//") && !member.starts_with("/*") && !member.starts_with('*'))
+        .collect()
+}
+
+impl KnowledgeGraph {
+    /// The merged shape of the interface or namespace `name`, from every declaration of it in
+    /// the project. `None` when nothing mergeable has that name.
+    pub fn merged_type_definition(&self, name: &str) -> Result<Option<MergedTypeDefinition>> {
+        let declarations = self.mergeable_declarations(name)?;
+        let Some(first) = declarations.first() else {
+            return Ok(None);
+        };
+
+        let mut members: Vec<String> = Vec::new();
+        for declaration in &declarations {
+            for member in body_members(&declaration.definition) {
+                if !members.contains(&member) {
+                    members.push(member);
+                }
+            }
+        }
+        Ok(Some(MergedTypeDefinition {
+            name: first.name.clone(),
+            kind: first.kind.clone(),
+            members,
+            declarations,
+        }))
+    }
+
+    /// `find_type_definitions`, with the declarations of an interface or namespace declared
+    /// more than once collapsed into their merged view
+    pub fn find_merged_type_definitions(&self, query: &str) -> Result<Vec<TypeDefinitionResult>> {
+        let mut results: Vec<TypeDefinitionResult> = Vec::new();
+        for type_def in self.find_type_definitions(query)? {
+            if !MERGEABLE_KINDS.contains(&type_def.kind.as_str()) {
+                results.push(type_def);
+                continue;
+            }
+            if results.iter().any(|r| r.name == type_def.name && MERGEABLE_KINDS.contains(&r.kind.as_str())) {
+                continue;
+            }
+            match self.merged_type_definition(&type_def.name)? {
+                Some(merged) if merged.declarations.len() > 1 => results.push(merged.into_result()),
+                _ => results.push(type_def),
+            }
+        }
+        Ok(results)
+    }
+
+    fn mergeable_declarations(&self, name: &str) -> Result<Vec<TypeDefinitionResult>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT td.name, td.kind, td.definition, f.path, td.start_line, td.end_line
+            FROM type_definitions td
+            JOIN files f ON td.file_id = f.id
+            WHERE td.name = ?1 AND td.kind IN (?2, ?3)
+            ORDER BY f.path, td.start_line
+            "#,
+        )?;
+        let rows = stmt.query_map(params![name, MERGEABLE_KINDS[0], MERGEABLE_KINDS[1]], |row| {
+            Ok(TypeDefinitionResult {
+                name: row.get(0)?,
+                kind: row.get(1)?,
+                definition: row.get(2)?,
+                file_path: row.get(3)?,
+                start_line: row.get(4)?,
+                end_line: row.get(5)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParsedFileData, TypeDefinitionData};
+
+    fn file(type_definitions: Vec<TypeDefinitionData>) -> ParsedFileData {
+        ParsedFileData {
+            type_definitions,
+            language: "typescript".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn interface(definition: &str, start_line: usize) -> TypeDefinitionData {
+        TypeDefinitionData {
+            name: "Config".to_string(),
+            kind: "Interface".to_string(),
+            definition: definition.to_string(),
+            start_line,
+            end_line: start_line + definition.lines().count() - 1,
+        }
+    }
+
+    #[test]
+    fn test_two_config_interfaces_merge_their_properties() {
+        let graph = KnowledgeGraph::in_memory().unwrap();
+        graph
+            .insert_file(
+                "src/config.ts",
+                &file(vec![
+                    interface("interface Config {\n  apiUrl: string;\n  retries?: number;\n}", 1),
+                    interface("interface Config {\n  onError: (e: Error) => void, headers: Record<string, string>\n}", 6),
+                ]),
+            )
+            .unwrap();
+        // A module augmentation elsewhere adds to the same type
+        graph
+            .insert_file(
+                "src/plugins/auth.ts",
+                &file(vec![interface("interface Config {\n  // added by the auth plugin\n  token: string;\n  apiUrl: string;\n}", 3)]),
+            )
+            .unwrap();
+
+        let merged = graph.merged_type_definition("Config").unwrap().unwrap();
+        assert_eq!(
+            merged.members,
+            vec![
+                "apiUrl: string",
+                "retries?: number",
+                "onError: (e: Error) => void",
+                "headers: Record<string, string>",
+                "token: string",
+            ]
+        );
+        assert_eq!(merged.declarations.len(), 3);
+        assert!(merged.definition().starts_with("// merged from src/config.ts:1, src/config.ts:6, src/plugins/auth.ts:3\n"));
+
+        // Lookups return the one merged shape instead of three partial ones
+        let found = graph.find_merged_type_definitions("Config").unwrap();
+        assert_eq!(found.len(), 1);
+        assert!(found[0].definition.contains("retries?: number;") && found[0].definition.contains("token: string;"));
+        assert_eq!(graph.find_type_definitions("Config").unwrap().len(), 3);
+
+        assert!(graph.merged_type_definition("Missing").unwrap().is_none());
+    }
+}
@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+
+/// Floor on the decay so a ubiquitous symbol can still win on a strong keyword match
+pub const MIN_FREQUENCY_WEIGHT: f32 = 0.1;
+
+/// How many files define, reference or import each symbol name, computed at index time.
+/// Names that appear everywhere (`Button`, `useState`) match almost every query but are rarely
+/// the specific thing needed, so their relevance is decayed IDF-style.
+#[derive(Debug, Clone, Default)]
+pub struct DocumentFrequencies {
+    file_counts: HashMap<String, usize>,
+    total_files: usize,
+}
+
+impl DocumentFrequencies {
+    pub fn new(file_counts: HashMap<String, usize>, total_files: usize) -> Self {
+        Self {
+            file_counts,
+            total_files,
+        }
+    }
+
+    /// Number of files mentioning `name` (0 if unknown)
+    pub fn file_count(&self, name: &str) -> usize {
+        self.file_counts.get(name).copied().unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.file_counts.is_empty()
+    }
+
+    /// Relevance multiplier in `[MIN_FREQUENCY_WEIGHT, 1.0]`: the IDF of `name` normalized by
+    /// the IDF of a name that appears in a single file. Unknown names are not decayed.
+    pub fn weight(&self, name: &str) -> f32 {
+        let count = self.file_count(name);
+        if count <= 1 || self.total_files <= 1 {
+            return 1.0;
+        }
+        let total = self.total_files.max(count) as f32;
+        let idf = (1.0 + total / count as f32).ln();
+        let max_idf = (1.0 + total).ln();
+        (idf / max_idf).clamp(MIN_FREQUENCY_WEIGHT, 1.0)
+    }
+}
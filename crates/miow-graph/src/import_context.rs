@@ -0,0 +1,227 @@
+use crate::ImportData;
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+/// Boost for a symbol the current file imports by name from the symbol's own file
+pub const IMPORTED_SYMBOL_BOOST: f32 = 5.0;
+
+/// Boost for any other symbol from a file the current file imports
+pub const IMPORTED_FILE_BOOST: f32 = 2.5;
+
+/// Boost for a symbol in the same directory as the current file
+pub const CO_LOCATED_BOOST: f32 = 1.0;
+
+/// Where an import source points: an exact path stem for relative imports, otherwise a
+/// path suffix (aliases like `@/cart/Cart`, packages, Python dotted modules)
+#[derive(Debug, Clone)]
+enum ImportTarget {
+    Exact(String),
+    Suffix(String),
+}
+
+#[derive(Debug, Clone)]
+struct ResolvedImport {
+    source: String,
+    target: ImportTarget,
+    names: HashSet<String>,
+}
+
+/// The import edges of the file a user is working in, used to boost search results that
+/// file actually depends on over identically-named symbols elsewhere.
+#[derive(Debug, Clone)]
+pub struct ImportContext {
+    current_dir: PathBuf,
+    imports: Vec<ResolvedImport>,
+}
+
+impl ImportContext {
+    pub fn new(current_file: &str, imports: &[ImportData]) -> Self {
+        let current_dir = Path::new(current_file)
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+
+        let imports = imports
+            .iter()
+            .map(|import| ResolvedImport {
+                source: import.source.clone(),
+                target: resolve_source(&current_dir, &import.source),
+                names: import.names.iter().cloned().collect(),
+            })
+            .collect();
+
+        Self { current_dir, imports }
+    }
+
+    /// Resolve imports of monorepo workspace packages (package name → entry point path, e.g.
+    /// `@org/ui` → `packages/ui/src/index.ts`) into the sibling package instead of treating
+    /// them as external. `@org/ui/button` resolves next to the entry point.
+    pub fn with_workspace_packages(mut self, packages: &HashMap<String, String>) -> Self {
+        for import in &mut self.imports {
+            if let Some(target) = resolve_workspace_package(&import.source, packages) {
+                import.target = target;
+            }
+        }
+        self
+    }
+
+    /// Relevance boost for a symbol named `name` defined in `file_path`
+    pub fn boost(&self, name: &str, file_path: &str) -> f32 {
+        let stems = file_stems(file_path);
+        let mut boost: f32 = 0.0;
+
+        for import in &self.imports {
+            let matches = stems.iter().any(|stem| match &import.target {
+                ImportTarget::Exact(target) => stem == target,
+                ImportTarget::Suffix(suffix) => stem == suffix || stem.ends_with(&format!("/{}", suffix)),
+            });
+            if matches {
+                let by_name = import.names.contains(name) || import.names.contains("*");
+                boost = boost.max(if by_name { IMPORTED_SYMBOL_BOOST } else { IMPORTED_FILE_BOOST });
+            }
+        }
+
+        if Path::new(file_path).parent() == Some(self.current_dir.as_path()) {
+            boost = boost.max(CO_LOCATED_BOOST);
+        }
+        boost
+    }
+
+    /// Which of `files` the current file imports. Bare package imports (`react`) are not
+    /// matched against project files; aliased paths (`@/cart/Cart`) are, by suffix.
+    pub fn imported_files<'a>(&self, files: &'a [String]) -> Vec<&'a str> {
+        files
+            .iter()
+            .filter(|file| {
+                let stems = file_stems(file);
+                self.imports.iter().any(|import| {
+                    stems.iter().any(|stem| match &import.target {
+                        ImportTarget::Exact(target) => stem == target,
+                        ImportTarget::Suffix(suffix) => {
+                            suffix.contains('/') && (stem == suffix || stem.ends_with(&format!("/{}", suffix)))
+                        }
+                    })
+                })
+            })
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+fn resolve_workspace_package(source: &str, packages: &HashMap<String, String>) -> Option<ImportTarget> {
+    packages.iter().find_map(|(name, entry_path)| {
+        if source == name {
+            return Some(ImportTarget::Exact(normalize(Path::new(strip_extension(entry_path)))));
+        }
+        let subpath = source.strip_prefix(name.as_str())?.strip_prefix('/')?;
+        let entry_dir = Path::new(entry_path).parent().unwrap_or(Path::new(""));
+        Some(ImportTarget::Exact(normalize(&entry_dir.join(strip_extension(subpath)))))
+    })
+}
+
+fn resolve_source(current_dir: &Path, source: &str) -> ImportTarget {
+    if source.starts_with('.') && !source.starts_with("./") && !source.starts_with("../") {
+        // Python relative import: `.models`, `..cart.models` or a bare `.`
+        let depth = source.chars().take_while(|c| *c == '.').count();
+        let module = source[depth..].replace('.', "/");
+        let mut dir = current_dir.to_path_buf();
+        for _ in 1..depth {
+            dir.pop();
+        }
+        return ImportTarget::Exact(normalize(&dir.join(module)));
+    }
+    if source.starts_with("./") || source.starts_with("../") {
+        return ImportTarget::Exact(normalize(&current_dir.join(strip_extension(source))));
+    }
+
+    let path = source
+        .strip_prefix("@/")
+        .or_else(|| source.strip_prefix("~/"))
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            if source.contains('/') {
+                source.to_string()
+            } else {
+                source.replace('.', "/")
+            }
+        });
+    ImportTarget::Suffix(strip_extension(&path).to_string())
+}
+
+/// Path stems a file can be imported by: without extension, and without a trailing
+/// `index` / `__init__` module
+fn file_stems(file_path: &str) -> Vec<String> {
+    let stem = normalize(Path::new(strip_extension(file_path)));
+    let mut stems = vec![stem.clone()];
+    for module in ["/index", "/__init__", "/mod"] {
+        if let Some(dir) = stem.strip_suffix(module) {
+            stems.push(dir.to_string());
+        }
+    }
+    stems
+}
+
+fn strip_extension(path: &str) -> &str {
+    match path.rfind('.') {
+        Some(dot) if !path[dot..].contains('/') && dot > 0 && !path[..dot].ends_with('.') => &path[..dot],
+        _ => path,
+    }
+}
+
+/// Resolve `.` and `..` components and join with `/`
+fn normalize(path: &Path) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                parts.pop();
+            }
+            Component::Normal(part) => parts.push(part.to_string_lossy().to_string()),
+            _ => {}
+        }
+    }
+    parts.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import(source: &str, names: &[&str]) -> ImportData {
+        ImportData {
+            source: source.to_string(),
+            names: names.iter().map(|n| n.to_string()).collect(),
+            start_line: 1,
+            end_line: 1,
+        }
+    }
+
+    #[test]
+    fn test_boost_follows_import_edges() {
+        let context = ImportContext::new(
+            "src/checkout/Checkout.tsx",
+            &[
+                import("../cart/Cart", &["Cart"]),
+                import("@/lib/money", &["formatPrice"]),
+            ],
+        );
+
+        assert_eq!(context.boost("Cart", "src/cart/Cart.tsx"), IMPORTED_SYMBOL_BOOST);
+        assert_eq!(context.boost("CartItem", "src/cart/Cart.tsx"), IMPORTED_FILE_BOOST);
+        assert_eq!(context.boost("formatPrice", "src/lib/money.ts"), IMPORTED_SYMBOL_BOOST);
+        assert_eq!(context.boost("Summary", "src/checkout/Summary.tsx"), CO_LOCATED_BOOST);
+        assert_eq!(context.boost("Cart", "src/legacy/Cart.tsx"), 0.0);
+    }
+
+    #[test]
+    fn test_python_relative_and_dotted_imports() {
+        let context = ImportContext::new(
+            "shop/checkout/views.py",
+            &[import("..cart.models", &["Cart"]), import("shop.payments", &["charge"])],
+        );
+
+        assert_eq!(context.boost("Cart", "shop/cart/models.py"), IMPORTED_SYMBOL_BOOST);
+        assert_eq!(context.boost("charge", "shop/payments/__init__.py"), IMPORTED_SYMBOL_BOOST);
+        assert_eq!(context.boost("Cart", "legacy/cart/models.py"), 0.0);
+    }
+}
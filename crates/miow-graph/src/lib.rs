@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::Path;
 
 pub mod query;
@@ -8,12 +9,29 @@ pub mod schema;
 pub mod semantic_search;
 pub mod relationship_inference;
 pub mod query_expansion;
+pub mod import_context;
+pub mod document_frequency;
+pub mod platform_variants;
+pub mod reexports;
+pub mod public_api;
+pub mod neighborhood;
+pub mod declaration_merging;
+pub mod similar_shape;
+pub mod css_modules;
 
 pub use query::*;
 pub use schema::*;
 pub use semantic_search::{SemanticGraphSearch, SemanticSearchResult};
 pub use relationship_inference::{RelationshipInferencer, InferredRelationship, RelationshipType};
 pub use query_expansion::{QueryExpander, ExpandedQuery};
+pub use import_context::ImportContext;
+pub use document_frequency::{DocumentFrequencies, MIN_FREQUENCY_WEIGHT};
+pub use platform_variants::{group_platform_variants, PlatformVariants};
+pub use public_api::PublicExport;
+pub use neighborhood::Neighbor;
+pub use declaration_merging::MergedTypeDefinition;
+pub use css_modules::CssModuleClass;
+pub use similar_shape::{blend_embedding_similarity, SimilarShape, DEFAULT_EMBEDDING_WEIGHT};
 
 use std::sync::Mutex;
 
@@ -41,6 +59,7 @@ impl KnowledgeGraph {
 
     /// Initialize the database schema
     fn initialize_schema(&self) -> Result<()> {
+        self.migrate_symbols_table()?;
         self.conn.lock().unwrap().execute_batch(
             r#"
             CREATE TABLE IF NOT EXISTS files (
@@ -62,6 +81,7 @@ impl KnowledgeGraph {
                 content TEXT NOT NULL,
                 metadata TEXT,
                 parent_id INTEGER,
+                qualified_name TEXT,
                 FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE,
                 FOREIGN KEY (parent_id) REFERENCES symbols(id) ON DELETE CASCADE
             );
@@ -129,7 +149,39 @@ impl KnowledgeGraph {
                 FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
             );
 
+            CREATE TABLE IF NOT EXISTS test_links (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                file_id INTEGER NOT NULL,
+                test_name TEXT NOT NULL,
+                symbol_name TEXT NOT NULL,
+                start_line INTEGER NOT NULL,
+                end_line INTEGER NOT NULL,
+                FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS exports (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                file_id INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                alias TEXT,
+                source TEXT,
+                start_line INTEGER NOT NULL,
+                end_line INTEGER NOT NULL,
+                FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS symbol_frequencies (
+                name TEXT PRIMARY KEY,
+                file_count INTEGER NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS workspace_packages (
+                name TEXT PRIMARY KEY,
+                entry_path TEXT NOT NULL
+            );
+
             CREATE INDEX IF NOT EXISTS idx_symbols_name ON symbols(name);
+            CREATE INDEX IF NOT EXISTS idx_symbols_qualified_name ON symbols(qualified_name);
             CREATE INDEX IF NOT EXISTS idx_symbols_kind ON symbols(kind);
             CREATE INDEX IF NOT EXISTS idx_symbols_file ON symbols(file_id);
             CREATE INDEX IF NOT EXISTS idx_references_from ON symbol_references(from_symbol_id);
@@ -138,13 +190,30 @@ impl KnowledgeGraph {
             CREATE INDEX IF NOT EXISTS idx_type_definitions_name ON type_definitions(name);
             CREATE INDEX IF NOT EXISTS idx_constants_name ON constants(name);
             CREATE INDEX IF NOT EXISTS idx_schemas_name ON schemas(name);
+            CREATE INDEX IF NOT EXISTS idx_test_links_symbol ON test_links(symbol_name);
+            CREATE INDEX IF NOT EXISTS idx_exports_file ON exports(file_id);
             "#,
         )?;
         Ok(())
     }
 
+    /// Add columns introduced after a database was first created.
+    fn migrate_symbols_table(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let columns: Vec<String> = conn
+            .prepare("SELECT name FROM pragma_table_info('symbols')")?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+
+        // A fresh database has no symbols table yet; the CREATE TABLE includes the column.
+        if !columns.is_empty() && !columns.iter().any(|c| c == "qualified_name") {
+            conn.execute("ALTER TABLE symbols ADD COLUMN qualified_name TEXT", [])?;
+        }
+        Ok(())
+    }
+
     /// Insert a file and its symbols into the graph
-    pub fn insert_file(&mut self, file_path: &str, parsed_file: &ParsedFileData) -> Result<i64> {
+    pub fn insert_file(&self, file_path: &str, parsed_file: &ParsedFileData) -> Result<i64> {
         let mut conn = self.conn.lock().unwrap();
         let tx = conn.transaction()?;
 
@@ -175,6 +244,21 @@ impl KnowledgeGraph {
             )?;
         }
 
+        // Insert exports
+        for export in &parsed_file.exports {
+            tx.execute(
+                "INSERT INTO exports (file_id, name, alias, source, start_line, end_line) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    file_id,
+                    export.name,
+                    export.alias,
+                    export.source,
+                    export.start_line,
+                    export.end_line
+                ],
+            )?;
+        }
+
         // Insert design tokens
         for token in &parsed_file.design_tokens {
             tx.execute(
@@ -236,6 +320,22 @@ impl KnowledgeGraph {
             )?;
         }
 
+        // Insert test links (one row per name a test references)
+        for test in &parsed_file.tests {
+            for reference in &test.references {
+                tx.execute(
+                    "INSERT INTO test_links (file_id, test_name, symbol_name, start_line, end_line) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        file_id,
+                        test.name,
+                        reference,
+                        test.start_line,
+                        test.end_line
+                    ],
+                )?;
+            }
+        }
+
         tx.commit()?;
         Ok(file_id)
     }
@@ -250,8 +350,8 @@ fn insert_symbol_recursive(
     let metadata_json = serde_json::to_string(&symbol.metadata)?;
 
     tx.execute(
-        "INSERT INTO symbols (file_id, name, kind, start_line, end_line, start_byte, end_byte, content, metadata, parent_id) 
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        "INSERT INTO symbols (file_id, name, kind, start_line, end_line, start_byte, end_byte, content, metadata, parent_id, qualified_name) 
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
         params![
             file_id,
             symbol.name,
@@ -262,7 +362,8 @@ fn insert_symbol_recursive(
             symbol.end_byte,
             symbol.content,
             metadata_json,
-            parent_id
+            parent_id,
+            symbol.qualified_name
         ],
     )?;
 
@@ -284,8 +385,72 @@ fn insert_symbol_recursive(
     Ok(symbol_id)
 }
 
+/// Tarjan's algorithm over an adjacency list; returns the nodes of each component
+fn strongly_connected_components(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    struct Tarjan<'a> {
+        edges: &'a [Vec<usize>],
+        index: Vec<Option<usize>>,
+        low_link: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        next_index: usize,
+        components: Vec<Vec<usize>>,
+    }
+
+    impl Tarjan<'_> {
+        fn visit(&mut self, node: usize) {
+            self.index[node] = Some(self.next_index);
+            self.low_link[node] = self.next_index;
+            self.next_index += 1;
+            self.stack.push(node);
+            self.on_stack[node] = true;
+
+            for &next in &self.edges[node] {
+                match self.index[next] {
+                    None => {
+                        self.visit(next);
+                        self.low_link[node] = self.low_link[node].min(self.low_link[next]);
+                    }
+                    Some(next_index) if self.on_stack[next] => {
+                        self.low_link[node] = self.low_link[node].min(next_index);
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            if Some(self.low_link[node]) == self.index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        edges,
+        index: vec![None; edges.len()],
+        low_link: vec![0; edges.len()],
+        on_stack: vec![false; edges.len()],
+        stack: Vec::new(),
+        next_index: 0,
+        components: Vec::new(),
+    };
+    for node in 0..edges.len() {
+        if tarjan.index[node].is_none() {
+            tarjan.visit(node);
+        }
+    }
+    tarjan.components
+}
+
 impl KnowledgeGraph {
-    /// Search for symbols by name (fuzzy match)
+    /// Search for symbols by bare or module-qualified name (fuzzy match)
     pub fn search_symbols(&self, query: &str) -> Result<Vec<SymbolSearchResult>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
@@ -293,8 +458,8 @@ impl KnowledgeGraph {
             SELECT s.id, s.name, s.kind, s.content, f.path, s.start_line, s.end_line, s.metadata
             FROM symbols s
             JOIN files f ON s.file_id = f.id
-            WHERE s.name LIKE ?1
-            ORDER BY s.name
+            WHERE s.name LIKE ?1 OR s.qualified_name LIKE ?1
+            ORDER BY s.name, f.path, s.start_line
             LIMIT 50
             "#,
         )?;
@@ -310,17 +475,20 @@ impl KnowledgeGraph {
                 start_line: row.get(5)?,
                 end_line: row.get(6)?,
                 metadata: row.get(7)?,
+                name_match_spans: Vec::new(),
+                match_spans: Vec::new(),
+                reexported_from: Vec::new(),
             })
         })?;
 
         let mut symbols = Vec::new();
         for result in results {
-            symbols.push(result?);
+            symbols.push(result?.with_match_spans(query));
         }
         Ok(symbols)
     }
 
-    /// Find symbols by exact name
+    /// Find symbols by exact bare or module-qualified name
     pub fn find_symbols_by_name(&self, name: &str) -> Result<Vec<SymbolSearchResult>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
@@ -328,7 +496,8 @@ impl KnowledgeGraph {
             SELECT s.id, s.name, s.kind, s.content, f.path, s.start_line, s.end_line, s.metadata
             FROM symbols s
             JOIN files f ON s.file_id = f.id
-            WHERE s.name = ?1
+            WHERE s.name = ?1 OR s.qualified_name = ?1
+            ORDER BY f.path, s.start_line
             "#,
         )?;
 
@@ -342,6 +511,9 @@ impl KnowledgeGraph {
                 start_line: row.get(5)?,
                 end_line: row.get(6)?,
                 metadata: row.get(7)?,
+                name_match_spans: Vec::new(),
+                match_spans: Vec::new(),
+                reexported_from: Vec::new(),
             })
         })?;
 
@@ -361,7 +533,7 @@ impl KnowledgeGraph {
             FROM symbols s
             JOIN files f ON s.file_id = f.id
             WHERE s.kind = ?1
-            ORDER BY s.name
+            ORDER BY s.name, f.path, s.start_line
             "#,
         )?;
 
@@ -375,6 +547,80 @@ impl KnowledgeGraph {
                 start_line: row.get(5)?,
                 end_line: row.get(6)?,
                 metadata: row.get(7)?,
+                name_match_spans: Vec::new(),
+                match_spans: Vec::new(),
+                reexported_from: Vec::new(),
+            })
+        })?;
+
+        let mut symbols = Vec::new();
+        for result in results {
+            symbols.push(result?);
+        }
+        Ok(symbols)
+    }
+
+    /// Symbols whose metadata carries `tag` (e.g. `form-validation`, `zod-schema`,
+    /// `nextjs-server`), matched exactly
+    pub fn symbols_with_tag(&self, tag: &str) -> Result<Vec<SymbolSearchResult>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT s.id, s.name, s.kind, s.content, f.path, s.start_line, s.end_line, s.metadata
+            FROM symbols s
+            JOIN files f ON s.file_id = f.id
+            WHERE s.metadata LIKE ?1
+            ORDER BY s.name, f.path, s.start_line
+            "#,
+        )?;
+
+        // The LIKE only narrows the scan; tags are compared exactly after decoding the metadata
+        let pattern = format!("%{}%", tag);
+        let results = stmt.query_map(params![pattern], |row| {
+            Ok(SymbolSearchResult {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                kind: row.get(2)?,
+                content: row.get(3)?,
+                file_path: row.get(4)?,
+                start_line: row.get(5)?,
+                end_line: row.get(6)?,
+                metadata: row.get(7)?,
+                name_match_spans: Vec::new(),
+                match_spans: Vec::new(),
+                reexported_from: Vec::new(),
+            })
+        })?;
+
+        let mut symbols = Vec::new();
+        for result in results {
+            let symbol = result?;
+            if symbol.tags().iter().any(|t| t == tag) {
+                symbols.push(symbol);
+            }
+        }
+        Ok(symbols)
+    }
+
+    /// Run a `QueryBuilder` search against the symbols table
+    pub fn query_symbols(&self, query: &QueryBuilder) -> Result<Vec<SymbolSearchResult>> {
+        let (sql, values) = query.build();
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&sql)?;
+
+        let results = stmt.query_map(rusqlite::params_from_iter(values.iter()), |row| {
+            Ok(SymbolSearchResult {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                kind: row.get(2)?,
+                content: row.get(3)?,
+                file_path: row.get(4)?,
+                start_line: row.get(5)?,
+                end_line: row.get(6)?,
+                metadata: row.get(7)?,
+                name_match_spans: Vec::new(),
+                match_spans: Vec::new(),
+                reexported_from: Vec::new(),
             })
         })?;
 
@@ -394,6 +640,7 @@ impl KnowledgeGraph {
             FROM design_tokens dt
             JOIN files f ON dt.file_id = f.id
             WHERE dt.name LIKE ?1
+            ORDER BY dt.name, f.path
             "#,
         )?;
 
@@ -425,6 +672,7 @@ impl KnowledgeGraph {
             JOIN files f ON s.file_id = f.id
             JOIN symbol_references r ON r.from_symbol_id = s.id
             WHERE r.to_symbol_name = ?1
+            ORDER BY f.path, s.name, s.start_line
             "#,
         )?;
 
@@ -438,6 +686,9 @@ impl KnowledgeGraph {
                 start_line: row.get(5)?,
                 end_line: row.get(6)?,
                 metadata: row.get(7)?,
+                name_match_spans: Vec::new(),
+                match_spans: Vec::new(),
+                reexported_from: Vec::new(),
             })
         })?;
 
@@ -487,6 +738,9 @@ impl KnowledgeGraph {
                 start_line: row.get(5)?,
                 end_line: row.get(6)?,
                 metadata: row.get(7)?,
+                name_match_spans: Vec::new(),
+                match_spans: Vec::new(),
+                reexported_from: Vec::new(),
             })
         })?;
 
@@ -497,6 +751,103 @@ impl KnowledgeGraph {
         Ok(symbols)
     }
 
+    /// Get the imports recorded for a file
+    pub fn get_file_imports(&self, file_path: &str) -> Result<Vec<ImportData>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT i.source, i.names, i.start_line, i.end_line
+            FROM imports i
+            JOIN files f ON i.file_id = f.id
+            WHERE f.path = ?1
+            ORDER BY i.start_line
+            "#,
+        )?;
+
+        let results = stmt.query_map(params![file_path], |row| {
+            let names: Option<String> = row.get(1)?;
+            Ok(ImportData {
+                source: row.get(0)?,
+                names: names
+                    .and_then(|n| serde_json::from_str(&n).ok())
+                    .unwrap_or_default(),
+                start_line: row.get(2)?,
+                end_line: row.get(3)?,
+            })
+        })?;
+
+        let mut imports = Vec::new();
+        for result in results {
+            imports.push(result?);
+        }
+        Ok(imports)
+    }
+
+    /// Import edges of `current_file`, for boosting the symbols it depends on. Imports of
+    /// workspace packages resolve to the sibling package's entry point.
+    pub fn import_context(&self, current_file: &str) -> Result<ImportContext> {
+        let workspace_packages = self.workspace_packages()?;
+        Ok(ImportContext::new(current_file, &self.get_file_imports(current_file)?)
+            .with_workspace_packages(&workspace_packages))
+    }
+
+    /// Replace the monorepo's workspace packages (package name → entry point path)
+    pub fn set_workspace_packages(&self, packages: &HashMap<String, String>) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM workspace_packages", [])?;
+        for (name, entry_path) in packages {
+            tx.execute(
+                "INSERT INTO workspace_packages (name, entry_path) VALUES (?1, ?2)",
+                params![name, entry_path],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Remove every file under the directory `dir` (relative, like indexed paths) with its
+    /// symbols, references and extracted data. Returns how many files were removed.
+    pub fn remove_files_under(&self, dir: &str) -> Result<usize> {
+        let pattern = format!("{}/%", dir.trim_end_matches('/'));
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let files_under = "SELECT id FROM files WHERE path LIKE ?1";
+        tx.execute(
+            &format!(
+                "DELETE FROM symbol_references WHERE from_symbol_id IN (SELECT id FROM symbols WHERE file_id IN ({}))",
+                files_under
+            ),
+            params![pattern],
+        )?;
+        for table in [
+            "symbols",
+            "imports",
+            "design_tokens",
+            "type_definitions",
+            "constants",
+            "schemas",
+            "test_links",
+            "exports",
+        ] {
+            tx.execute(
+                &format!("DELETE FROM {} WHERE file_id IN ({})", table, files_under),
+                params![pattern],
+            )?;
+        }
+        let removed = tx.execute("DELETE FROM files WHERE path LIKE ?1", params![pattern])?;
+        tx.commit()?;
+        Ok(removed)
+    }
+
+    /// Workspace packages stored by `set_workspace_packages`
+    pub fn workspace_packages(&self) -> Result<HashMap<String, String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT name, entry_path FROM workspace_packages")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
     /// Find type definitions by name
     pub fn find_type_definitions(&self, query: &str) -> Result<Vec<TypeDefinitionResult>> {
         let conn = self.conn.lock().unwrap();
@@ -506,6 +857,7 @@ impl KnowledgeGraph {
             FROM type_definitions td
             JOIN files f ON td.file_id = f.id
             WHERE td.name LIKE ?1
+            ORDER BY td.name, f.path
             "#,
         )?;
 
@@ -537,6 +889,7 @@ impl KnowledgeGraph {
             FROM constants c
             JOIN files f ON c.file_id = f.id
             WHERE c.name LIKE ?1
+            ORDER BY c.name, f.path
             "#,
         )?;
 
@@ -559,6 +912,50 @@ impl KnowledgeGraph {
         Ok(constants)
     }
 
+    /// Find tests that exercise a project symbol. Each link lists every project
+    /// symbol the test references, not just `symbol_name`.
+    pub fn tests_for(&self, symbol_name: &str) -> Result<Vec<TestLink>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT DISTINCT t.file_id, f.path, t.test_name, t.start_line
+            FROM test_links t
+            JOIN files f ON t.file_id = f.id
+            WHERE t.symbol_name = ?1
+              AND EXISTS (SELECT 1 FROM symbols s WHERE s.name = ?1)
+            ORDER BY f.path, t.start_line
+            "#,
+        )?;
+        let tests = stmt
+            .query_map(params![symbol_name], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?, row.get::<_, i64>(3)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut symbols_stmt = conn.prepare(
+            r#"
+            SELECT DISTINCT t.symbol_name
+            FROM test_links t
+            WHERE t.file_id = ?1 AND t.test_name = ?2 AND t.start_line = ?3
+              AND EXISTS (SELECT 1 FROM symbols s WHERE s.name = t.symbol_name)
+            ORDER BY t.symbol_name
+            "#,
+        )?;
+
+        let mut links = Vec::new();
+        for (file_id, file, test_name, start_line) in tests {
+            let tested_symbols = symbols_stmt
+                .query_map(params![file_id, test_name, start_line], |row| row.get(0))?
+                .collect::<rusqlite::Result<Vec<String>>>()?;
+            links.push(TestLink {
+                test_name,
+                tested_symbols,
+                file,
+            });
+        }
+        Ok(links)
+    }
+
     /// Count total symbols in the graph
     pub fn count_symbols(&self) -> Result<usize> {
         let conn = self.conn.lock().unwrap();
@@ -570,6 +967,100 @@ impl KnowledgeGraph {
         Ok(count as usize)
     }
 
+    /// Recompute how many files define, reference or import each symbol name. Run once after
+    /// all files are inserted; returns the number of distinct names.
+    pub fn compute_document_frequencies(&self) -> Result<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let mut files_by_name: HashMap<String, HashSet<i64>> = HashMap::new();
+        {
+            let mut stmt = conn.prepare(
+                r#"
+                SELECT name, file_id FROM symbols
+                UNION
+                SELECT r.to_symbol_name, s.file_id
+                FROM symbol_references r
+                JOIN symbols s ON r.from_symbol_id = s.id
+                "#,
+            )?;
+            let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?;
+            for row in rows {
+                let (name, file_id) = row?;
+                files_by_name.entry(name).or_default().insert(file_id);
+            }
+
+            let mut stmt = conn.prepare("SELECT file_id, names FROM imports")?;
+            let rows = stmt.query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, Option<String>>(1)?))
+            })?;
+            for row in rows {
+                let (file_id, names) = row?;
+                let names: Vec<String> = names
+                    .and_then(|n| serde_json::from_str(&n).ok())
+                    .unwrap_or_default();
+                for name in names {
+                    files_by_name.entry(name).or_default().insert(file_id);
+                }
+            }
+        }
+
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM symbol_frequencies", [])?;
+        for (name, files) in &files_by_name {
+            tx.execute(
+                "INSERT INTO symbol_frequencies (name, file_count) VALUES (?1, ?2)",
+                params![name, files.len() as i64],
+            )?;
+        }
+        tx.commit()?;
+        Ok(files_by_name.len())
+    }
+
+    /// Load the document frequencies stored by `compute_document_frequencies`
+    pub fn document_frequencies(&self) -> Result<DocumentFrequencies> {
+        let total_files = self.count_files()?;
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT name, file_count FROM symbol_frequencies")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?;
+        let mut file_counts = HashMap::new();
+        for row in rows {
+            let (name, count) = row?;
+            file_counts.insert(name, count as usize);
+        }
+        Ok(DocumentFrequencies::new(file_counts, total_files))
+    }
+
+    /// Groups of files that import each other in a cycle, each sorted by path
+    pub fn import_cycles(&self) -> Result<Vec<Vec<String>>> {
+        let files: Vec<String> = {
+            let conn = self.conn.lock().unwrap();
+            let mut stmt = conn.prepare("SELECT path FROM files ORDER BY path")?;
+            let paths = stmt.query_map([], |row| row.get(0))?;
+            paths.collect::<rusqlite::Result<_>>()?
+        };
+        let index: HashMap<&str, usize> = files.iter().enumerate().map(|(i, f)| (f.as_str(), i)).collect();
+
+        let workspace_packages = self.workspace_packages()?;
+        let mut edges: Vec<Vec<usize>> = Vec::with_capacity(files.len());
+        for file in &files {
+            let imported = ImportContext::new(file, &self.get_file_imports(file)?)
+                .with_workspace_packages(&workspace_packages)
+                .imported_files(&files);
+            edges.push(imported.iter().map(|f| index[f]).collect());
+        }
+
+        let mut cycles: Vec<Vec<String>> = strongly_connected_components(&edges)
+            .into_iter()
+            .filter(|component| component.len() > 1 || edges[component[0]].contains(&component[0]))
+            .map(|component| {
+                let mut cycle: Vec<String> = component.into_iter().map(|i| files[i].clone()).collect();
+                cycle.sort();
+                cycle
+            })
+            .collect();
+        cycles.sort();
+        Ok(cycles)
+    }
+
     /// Count total files in the graph
     pub fn count_files(&self) -> Result<usize> {
         let conn = self.conn.lock().unwrap();
@@ -590,6 +1081,7 @@ impl KnowledgeGraph {
             FROM schemas s
             JOIN files f ON s.file_id = f.id
             WHERE s.name LIKE ?1
+            ORDER BY s.name, f.path
             "#,
         )?;
 
@@ -623,6 +1115,92 @@ pub struct SymbolSearchResult {
     pub start_line: i64,
     pub end_line: i64,
     pub metadata: Option<String>,
+    /// Byte ranges in `name` matching the query tokens (keyword search only)
+    #[serde(default)]
+    pub name_match_spans: Vec<(usize, usize)>,
+    /// Byte ranges in `content` matching the query tokens (keyword search only)
+    #[serde(default)]
+    pub match_spans: Vec<(usize, usize)>,
+    /// Barrel files that re-export this symbol, set when `collapse_reexports` folded their
+    /// hits into this definition
+    #[serde(default)]
+    pub reexported_from: Vec<String>,
+}
+
+impl SymbolSearchResult {
+    /// Record where the whitespace-separated tokens of `query` occur in the name and content
+    pub fn with_match_spans(mut self, query: &str) -> Self {
+        self.name_match_spans = match_spans(&self.name, query);
+        self.match_spans = match_spans(&self.content, query);
+        self
+    }
+
+    /// The parser metadata. Metadata may be stored as a JSON object or as a JSON string
+    /// wrapping one, depending on the indexing path.
+    pub(crate) fn metadata_value(&self) -> Option<serde_json::Value> {
+        let value = self.metadata.as_deref().and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())?;
+        match value {
+            serde_json::Value::String(inner) => serde_json::from_str(&inner).ok(),
+            value => Some(value),
+        }
+    }
+
+    /// Tags from the parser metadata
+    pub fn tags(&self) -> Vec<String> {
+        let Some(value) = self.metadata_value() else {
+            return Vec::new();
+        };
+        value
+            .get("tags")
+            .and_then(|tags| tags.as_array())
+            .map(|tags| tags.iter().filter_map(|t| t.as_str().map(str::to_string)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Base priority stamped at index time from the file's path (`IndexConfig::path_priorities`)
+    pub fn priority(&self) -> Option<f32> {
+        self.metadata_value()?.get("priority")?.as_f64().map(|p| p as f32)
+    }
+
+    /// Platform of a platform-specific file (`ios` for `Button.ios.tsx`), recorded at index time
+    pub fn platform(&self) -> Option<String> {
+        self.metadata_value()?.get("platform")?.as_str().map(str::to_string)
+    }
+
+    /// Whether the parser tagged the symbol deprecated (`miow_parsers::DEPRECATED_TAG`)
+    pub fn is_deprecated(&self) -> bool {
+        self.tags().iter().any(|t| t == "deprecated")
+    }
+}
+
+/// Sorted, non-overlapping byte ranges of case-insensitive occurrences of each query token
+pub fn match_spans(text: &str, query: &str) -> Vec<(usize, usize)> {
+    let haystack = text.to_ascii_lowercase();
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+    for token in query.split_whitespace() {
+        let token = token.to_ascii_lowercase();
+        spans.extend(haystack.match_indices(&token).map(|(start, m)| (start, start + m.len())));
+    }
+    spans.sort_unstable();
+
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(spans.len());
+    for (start, end) in spans {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Search results grouped by file path, files in path order and results in their original
+/// order within each file
+pub fn group_by_file(results: Vec<SymbolSearchResult>) -> BTreeMap<String, Vec<SymbolSearchResult>> {
+    let mut groups: BTreeMap<String, Vec<SymbolSearchResult>> = BTreeMap::new();
+    for result in results {
+        groups.entry(result.file_path.clone()).or_default().push(result);
+    }
+    groups
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -634,6 +1212,14 @@ pub struct DesignTokenResult {
     pub file_path: String,
 }
 
+/// A test and the project symbols it exercises
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestLink {
+    pub test_name: String,
+    pub tested_symbols: Vec<String>,
+    pub file: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TypeDefinitionResult {
     pub name: String,
@@ -663,3 +1249,202 @@ pub struct SchemaResult {
     pub start_line: i64,
     pub end_line: i64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::relationship_inference::{LLMProvider, LLMResponse};
+    use super::*;
+
+    /// Shared no-op LLM used by module tests that only need a provider to construct helpers.
+    pub(crate) struct MockLLM;
+
+    #[async_trait::async_trait]
+    impl LLMProvider for MockLLM {
+        async fn generate(&self, _prompt: &str) -> Result<LLMResponse> {
+            Ok(LLMResponse {
+                content: String::new(),
+            })
+        }
+    }
+
+    fn file_data(symbols: Vec<SymbolData>, tests: Vec<TestCaseData>) -> ParsedFileData {
+        ParsedFileData {
+            symbols,
+            imports: vec![],
+            design_tokens: vec![],
+            type_definitions: vec![],
+            constants: vec![],
+            schemas: vec![],
+            language: "tsx".to_string(),
+            exports: vec![],
+            tests,
+        }
+    }
+
+    #[test]
+    fn test_tests_for_links_describe_block_to_symbol() {
+        let graph = KnowledgeGraph::in_memory().unwrap();
+        let button = SymbolData {
+            name: "Button".to_string(),
+            kind: "Component".to_string(),
+            start_line: 1,
+            end_line: 3,
+            start_byte: 0,
+            end_byte: 40,
+            content: "export function Button() { return <button />; }".to_string(),
+            metadata: "{}".to_string(),
+            style_tags: None,
+            children: vec![],
+            references: vec![],
+            qualified_name: None,
+        };
+        graph.insert_file("src/Button.tsx", &file_data(vec![button], vec![])).unwrap();
+        graph
+            .insert_file(
+                "src/Button.test.tsx",
+                &file_data(
+                    vec![],
+                    vec![TestCaseData {
+                        name: "Button".to_string(),
+                        references: vec!["Button".to_string(), "render".to_string()],
+                        start_line: 4,
+                        end_line: 9,
+                    }],
+                ),
+            )
+            .unwrap();
+
+        let links = graph.tests_for("Button").unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].test_name, "Button");
+        assert_eq!(links[0].file, "src/Button.test.tsx");
+        // `render` is not a project symbol
+        assert_eq!(links[0].tested_symbols, vec!["Button".to_string()]);
+
+        assert!(graph.tests_for("render").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_qualified_names_disambiguate_search() {
+        let graph = KnowledgeGraph::in_memory().unwrap();
+        let config = |qualified: &str| SymbolData {
+            name: "Config".to_string(),
+            kind: "Struct".to_string(),
+            start_line: 1,
+            end_line: 1,
+            start_byte: 0,
+            end_byte: 19,
+            content: "pub struct Config;".to_string(),
+            metadata: "{}".to_string(),
+            style_tags: None,
+            children: vec![],
+            references: vec![],
+            qualified_name: Some(qualified.to_string()),
+        };
+        graph.insert_file("src/server.rs", &file_data(vec![config("server::Config")], vec![])).unwrap();
+        graph.insert_file("src/db.rs", &file_data(vec![config("db::Config")], vec![])).unwrap();
+
+        assert_eq!(graph.find_symbols_by_name("Config").unwrap().len(), 2);
+        let server = graph.find_symbols_by_name("server::Config").unwrap();
+        assert_eq!(server.len(), 1);
+        assert_eq!(server[0].file_path, "src/server.rs");
+        assert_eq!(graph.search_symbols("db::Con").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_symbols_with_tag_matches_exact_tags() {
+        let graph = KnowledgeGraph::in_memory().unwrap();
+        let tagged = |name: &str, tags: &[&str]| SymbolData {
+            name: name.to_string(),
+            kind: "Function".to_string(),
+            start_line: 1,
+            end_line: 1,
+            start_byte: 0,
+            end_byte: 10,
+            content: format!("function {}() {{}}", name),
+            metadata: serde_json::json!({ "tags": tags }).to_string(),
+            style_tags: None,
+            children: vec![],
+            references: vec![],
+            qualified_name: None,
+        };
+        graph
+            .insert_file(
+                "src/forms.ts",
+                &file_data(
+                    vec![
+                        tagged("validateEmail", &["form-validation", "zod-schema"]),
+                        tagged("submitForm", &["form-validation-legacy"]),
+                        tagged("formatDate", &[]),
+                    ],
+                    vec![],
+                ),
+            )
+            .unwrap();
+
+        let names = |tag: &str| -> Vec<String> {
+            graph.symbols_with_tag(tag).unwrap().into_iter().map(|s| s.name).collect()
+        };
+        assert_eq!(names("form-validation"), vec!["validateEmail".to_string()]);
+        assert_eq!(names("zod-schema"), vec!["validateEmail".to_string()]);
+        assert!(names("nextjs-server").is_empty());
+    }
+
+    #[test]
+    fn test_search_reports_match_spans() {
+        let graph = KnowledgeGraph::in_memory().unwrap();
+        let content = "export function useLoginForm() { return login(); }";
+        let symbol = SymbolData {
+            name: "useLoginForm".to_string(),
+            kind: "Function".to_string(),
+            start_line: 1,
+            end_line: 1,
+            start_byte: 0,
+            end_byte: content.len(),
+            content: content.to_string(),
+            metadata: "{}".to_string(),
+            style_tags: None,
+            children: vec![],
+            references: vec![],
+            qualified_name: None,
+        };
+        graph.insert_file("src/hooks.ts", &file_data(vec![symbol], vec![])).unwrap();
+
+        let results = graph.search_symbols("login").unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name_match_spans, vec![(3, 8)]);
+        assert_eq!(&results[0].name[3..8], "Login");
+        assert_eq!(results[0].match_spans, vec![(19, 24), (40, 45)]);
+        assert_eq!(&content[40..45], "login");
+        assert_eq!(match_spans("LoginLogin form", "login form"), vec![(0, 10), (11, 15)]);
+    }
+    #[test]
+    fn test_group_by_file_keeps_same_file_results_together() {
+        let graph = KnowledgeGraph::in_memory().unwrap();
+        let symbol = |name: &str| SymbolData {
+            name: name.to_string(),
+            kind: "Component".to_string(),
+            start_line: 1,
+            end_line: 1,
+            start_byte: 0,
+            end_byte: 10,
+            content: format!("function {}() {{}}", name),
+            metadata: "{}".to_string(),
+            style_tags: None,
+            children: vec![],
+            references: vec![],
+            qualified_name: None,
+        };
+        graph
+            .insert_file("src/components/Button.tsx", &file_data(vec![symbol("Button"), symbol("ButtonGroup")], vec![]))
+            .unwrap();
+        graph.insert_file("src/components/IconButton.tsx", &file_data(vec![symbol("IconButton")], vec![])).unwrap();
+
+        let groups = group_by_file(graph.search_symbols("Button").unwrap());
+        assert_eq!(groups.len(), 2);
+        let names: Vec<&str> = groups["src/components/Button.tsx"].iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["Button", "ButtonGroup"]);
+        assert_eq!(groups["src/components/IconButton.tsx"].len(), 1);
+    }
+}
@@ -0,0 +1,123 @@
+//! A symbol's neighborhood: the helpers it calls and the types it uses, followed along
+//! reference edges. Adding these to a search hit makes it self-contained context instead of
+//! code calling functions the reader never sees.
+
+use crate::{KnowledgeGraph, SymbolSearchResult};
+use anyhow::Result;
+use std::collections::HashSet;
+
+/// A symbol reached from the expanded one
+#[derive(Debug, Clone)]
+pub struct Neighbor {
+    pub symbol: SymbolSearchResult,
+    /// Reference hops from the expanded symbol; 1 for direct references
+    pub depth: usize,
+    /// Name of the symbol whose reference led here
+    pub referenced_by: String,
+}
+
+impl KnowledgeGraph {
+    /// Symbols referenced by `symbol`, then by those, up to `depth` hops, nearest first. A
+    /// name defined in the referencing symbol's own file resolves there; otherwise every
+    /// definition of it is taken, with barrel re-exports folded into what they re-export.
+    pub fn expand_neighborhood(&self, symbol: &SymbolSearchResult, depth: usize) -> Result<Vec<Neighbor>> {
+        let mut seen: HashSet<i64> = HashSet::from([symbol.id]);
+        let mut neighbors = Vec::new();
+        let mut frontier = vec![symbol.clone()];
+
+        for hop in 1..=depth {
+            let mut next = Vec::new();
+            for from in &frontier {
+                for name in self.get_symbol_dependencies(from.id)? {
+                    // Recursive functions reference themselves
+                    if name == from.name {
+                        continue;
+                    }
+                    for target in self.resolve_reference(&name, &from.file_path)? {
+                        if seen.insert(target.id) {
+                            next.push(target.clone());
+                            neighbors.push(Neighbor {
+                                symbol: target,
+                                depth: hop,
+                                referenced_by: from.name.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+        Ok(neighbors)
+    }
+
+    /// Definitions a reference to `name` from `file_path` may point at
+    fn resolve_reference(&self, name: &str, file_path: &str) -> Result<Vec<SymbolSearchResult>> {
+        let candidates = self.find_symbols_by_name(name)?;
+        if let Some(local) = candidates.iter().find(|s| s.file_path == file_path) {
+            return Ok(vec![local.clone()]);
+        }
+        self.collapse_reexports(candidates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{KnowledgeGraph, ParsedFileData, SymbolData};
+
+    fn symbol(name: &str, kind: &str, references: &[&str]) -> SymbolData {
+        SymbolData {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            start_line: 1,
+            end_line: 1,
+            content: format!("function {}() {{}}", name),
+            metadata: "{}".to_string(),
+            references: references.iter().map(|r| r.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn file(symbols: Vec<SymbolData>) -> ParsedFileData {
+        ParsedFileData {
+            symbols,
+            language: "typescript".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_expanding_a_function_brings_in_the_helper_it_calls() {
+        let graph = KnowledgeGraph::in_memory().unwrap();
+        graph
+            .insert_file(
+                "src/checkout.ts",
+                &file(vec![
+                    symbol("checkout", "Function", &["Cart", "checkout", "formatPrice"]),
+                    symbol("Cart", "Interface", &[]),
+                ]),
+            )
+            .unwrap();
+        graph
+            .insert_file("src/price.ts", &file(vec![symbol("formatPrice", "Function", &["roundCents"])]))
+            .unwrap();
+        graph
+            .insert_file("src/round.ts", &file(vec![symbol("roundCents", "Function", &[])]))
+            .unwrap();
+
+        let checkout = graph.find_symbols_by_name("checkout").unwrap().remove(0);
+        let direct = graph.expand_neighborhood(&checkout, 1).unwrap();
+        let names: Vec<&str> = direct.iter().map(|n| n.symbol.name.as_str()).collect();
+        assert_eq!(names, vec!["Cart", "formatPrice"]);
+        assert!(direct.iter().all(|n| n.depth == 1 && n.referenced_by == "checkout"));
+
+        let deeper = graph.expand_neighborhood(&checkout, 2).unwrap();
+        let round = deeper.iter().find(|n| n.symbol.name == "roundCents").unwrap();
+        assert_eq!((round.depth, round.referenced_by.as_str()), (2, "formatPrice"));
+        assert_eq!(round.symbol.file_path, "src/round.ts");
+
+        assert!(graph.expand_neighborhood(&checkout, 0).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,127 @@
+//! Platform-specific definitions of one logical symbol. `Button.ios.tsx` and
+//! `Button.android.tsx` both define `Button`, and the bundler picks one per platform; search
+//! returns them as labelled variants of a single symbol rather than as unrelated duplicates.
+
+use crate::{KnowledgeGraph, SymbolSearchResult};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Platforms that fall back to a `.native` file when they have no file of their own
+const NATIVE_PLATFORMS: [&str; 2] = ["ios", "android"];
+
+/// One logical symbol and its definitions across platform-specific files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlatformVariants {
+    pub name: String,
+    /// Module path every variant resolves as (`src/Button.tsx` for `src/Button.ios.tsx`)
+    pub logical_path: String,
+    /// Definitions in file path order; `SymbolSearchResult::platform` labels each, `None` being
+    /// the shared file without a suffix
+    pub variants: Vec<SymbolSearchResult>,
+}
+
+impl PlatformVariants {
+    /// Platforms with a dedicated definition
+    pub fn platforms(&self) -> Vec<String> {
+        self.variants.iter().filter_map(|v| v.platform()).collect()
+    }
+
+    /// The definition the bundler resolves for `platform`: its own file, else `.native` for
+    /// iOS/Android, else the shared file
+    pub fn for_platform(&self, platform: &str) -> Option<&SymbolSearchResult> {
+        let with_platform = |wanted: &str| self.variants.iter().find(|v| v.platform().as_deref() == Some(wanted));
+        with_platform(platform)
+            .or_else(|| NATIVE_PLATFORMS.contains(&platform).then(|| with_platform("native")).flatten())
+            .or_else(|| self.variants.iter().find(|v| v.platform().is_none()))
+    }
+}
+
+/// `file_path` without the `.<platform>` suffix of its file name
+fn logical_path(file_path: &str, platform: Option<&str>) -> String {
+    let Some(platform) = platform else {
+        return file_path.to_string();
+    };
+    let marker = format!(".{}.", platform);
+    match file_path.rfind(&marker) {
+        Some(at) => format!("{}{}", &file_path[..at], &file_path[at + marker.len() - 1..]),
+        None => file_path.to_string(),
+    }
+}
+
+/// Group results by name and logical module, keeping first-seen order. Symbols from files
+/// without platform variants end up in groups of one.
+pub fn group_platform_variants(results: Vec<SymbolSearchResult>) -> Vec<PlatformVariants> {
+    let mut groups: Vec<PlatformVariants> = Vec::new();
+    for result in results {
+        let logical_path = logical_path(&result.file_path, result.platform().as_deref());
+        match groups
+            .iter_mut()
+            .find(|group| group.name == result.name && group.logical_path == logical_path)
+        {
+            Some(group) => group.variants.push(result),
+            None => groups.push(PlatformVariants {
+                name: result.name.clone(),
+                logical_path,
+                variants: vec![result],
+            }),
+        }
+    }
+    groups
+}
+
+impl KnowledgeGraph {
+    /// Definitions of `name` grouped per logical module. With `platform`, each group keeps only
+    /// the variant that platform resolves (see `PlatformVariants::for_platform`); groups with
+    /// nothing for it are dropped.
+    pub fn find_platform_variants(&self, name: &str, platform: Option<&str>) -> Result<Vec<PlatformVariants>> {
+        let mut groups = group_platform_variants(self.find_symbols_by_name(name)?);
+        if let Some(platform) = platform {
+            for group in &mut groups {
+                group.variants = group.for_platform(platform).cloned().into_iter().collect();
+            }
+            groups.retain(|group| !group.variants.is_empty());
+        }
+        Ok(groups)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParsedFileData, SymbolData};
+
+    fn component_file(platform: &str) -> ParsedFileData {
+        ParsedFileData {
+            symbols: vec![SymbolData {
+                name: "Button".to_string(),
+                kind: "Component".to_string(),
+                start_line: 1,
+                end_line: 1,
+                end_byte: 40,
+                content: "export function Button() { return <Pressable />; }".to_string(),
+                metadata: format!(r#"{{"tags":["platform:{0}"],"platform":"{0}"}}"#, platform),
+                ..Default::default()
+            }],
+            language: "tsx".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_ios_and_android_variants_group_under_one_symbol() {
+        let graph = KnowledgeGraph::in_memory().unwrap();
+        graph.insert_file("src/components/Button.ios.tsx", &component_file("ios")).unwrap();
+        graph.insert_file("src/components/Button.android.tsx", &component_file("android")).unwrap();
+
+        let groups = graph.find_platform_variants("Button", None).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].logical_path, "src/components/Button.tsx");
+        assert_eq!(groups[0].platforms(), vec!["android".to_string(), "ios".to_string()]);
+
+        let ios = graph.find_platform_variants("Button", Some("ios")).unwrap();
+        assert_eq!(ios[0].variants.len(), 1);
+        assert_eq!(ios[0].variants[0].file_path, "src/components/Button.ios.tsx");
+        // No web file and no shared fallback
+        assert!(graph.find_platform_variants("Button", Some("web")).unwrap().is_empty());
+    }
+}
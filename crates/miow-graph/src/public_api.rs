@@ -0,0 +1,277 @@
+//! The public API of a package: what `import { ... } from '@org/ui'` can reach. That's the
+//! export list of the package entry, with barrel re-exports followed to the files that
+//! define each symbol.
+
+use crate::reexports::{module_candidates, MODULE_EXTENSIONS};
+use crate::{ExportData, KnowledgeGraph, SymbolSearchResult};
+use anyhow::{Context, Result};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// How many `export *` barrels are followed before giving up
+const MAX_EXPORT_STAR_DEPTH: usize = 5;
+
+/// Conditions of a conditional `exports` entry, most source-like first
+const EXPORT_CONDITIONS: [&str; 6] = ["source", "import", "module", "default", "require", "types"];
+
+/// Output directories whose files are compiled from a sibling `src/`
+const BUILD_DIRS: [&str; 4] = ["dist", "build", "lib", "out"];
+
+/// Entry points tried when `package.json` declares none that is indexed
+const CONVENTIONAL_ENTRIES: [&str; 2] = ["src/index", "index"];
+
+/// A name the package exports, and the symbol behind it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicExport {
+    /// Name importers use
+    pub name: String,
+    /// Where the symbol is defined; `None` for namespace re-exports (`export * as utils`)
+    /// and names re-exported from outside the project
+    pub definition: Option<SymbolSearchResult>,
+}
+
+/// Declared entry points of a `package.json`: the `.` export (resolving conditions), then
+/// `module` and `main`
+fn declared_entries(manifest: &Value) -> Vec<String> {
+    let mut entries = Vec::new();
+    match &manifest["exports"] {
+        Value::Object(map) if map.contains_key(".") => export_targets(&map["."], &mut entries),
+        // Subpath-less exports are the root entry's conditions
+        Value::Object(map) if !map.keys().any(|k| k.starts_with('.')) => {
+            export_targets(&manifest["exports"], &mut entries)
+        }
+        exports => export_targets(exports, &mut entries),
+    }
+    for field in ["module", "main"] {
+        if let Some(entry) = manifest[field].as_str() {
+            entries.push(entry.to_string());
+        }
+    }
+    entries
+}
+
+fn export_targets(target: &Value, entries: &mut Vec<String>) {
+    match target {
+        Value::String(path) => entries.push(path.clone()),
+        Value::Array(fallbacks) => fallbacks.iter().for_each(|t| export_targets(t, entries)),
+        Value::Object(conditions) => EXPORT_CONDITIONS
+            .iter()
+            .filter_map(|condition| conditions.get(*condition))
+            .for_each(|t| export_targets(t, entries)),
+        _ => {}
+    }
+}
+
+/// Source files an entry may be built from, relative to the package: the entry itself, then
+/// its extensionless stem with source extensions, also under `src/` for build output
+fn source_candidates(entry: &str) -> Vec<String> {
+    let entry = entry.trim_start_matches("./");
+    let stem = [".d.ts", ".mjs", ".cjs", ".js", ".jsx", ".ts", ".tsx"]
+        .iter()
+        .find_map(|ext| entry.strip_suffix(ext))
+        .unwrap_or(entry);
+    let mut stems = vec![stem.to_string()];
+    if let Some((dir, rest)) = stem.split_once('/') {
+        if BUILD_DIRS.contains(&dir) {
+            stems.push(format!("src/{}", rest));
+        }
+    }
+    let mut candidates = vec![entry.to_string()];
+    for stem in stems {
+        candidates.extend(MODULE_EXTENSIONS.iter().map(|ext| format!("{}.{}", stem, ext)));
+        candidates.extend(MODULE_EXTENSIONS.iter().map(|ext| format!("{}/index.{}", stem, ext)));
+    }
+    candidates
+}
+
+impl KnowledgeGraph {
+    /// The exports of the package in `package_root` (relative to `root_path`, like indexed
+    /// paths), from the entry its `package.json` declares through `main`, `module` or
+    /// `exports`. Re-exports are followed to their definitions, so each name comes with the
+    /// file that actually defines it.
+    pub fn package_public_api(&self, root_path: &Path, package_root: &str) -> Result<Vec<PublicExport>> {
+        let manifest_path = root_path.join(package_root).join("package.json");
+        let manifest: Value = serde_json::from_str(
+            &fs::read_to_string(&manifest_path).with_context(|| format!("Failed to read {:?}", manifest_path))?,
+        )
+        .with_context(|| format!("Invalid package manifest {:?}", manifest_path))?;
+
+        let package_root = package_root.trim_end_matches('/');
+        let candidates: Vec<String> = declared_entries(&manifest)
+            .iter()
+            .map(String::as_str)
+            .chain(CONVENTIONAL_ENTRIES)
+            .flat_map(source_candidates)
+            .map(|candidate| match package_root {
+                "" | "." => candidate,
+                root => format!("{}/{}", root, candidate),
+            })
+            .collect();
+        let Some(entry) = self.existing_files(&candidates)?.into_iter().next() else {
+            anyhow::bail!("No indexed entry point for package {:?}", package_root);
+        };
+
+        let mut public = Vec::new();
+        self.collect_exports(&entry, 0, &mut HashSet::new(), &mut public)?;
+        Ok(public)
+    }
+
+    /// Exports of `file_path` in source order
+    fn file_exports(&self, file_path: &str) -> Result<Vec<ExportData>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT e.name, e.alias, e.source, e.start_line, e.end_line
+            FROM exports e
+            JOIN files f ON e.file_id = f.id
+            WHERE f.path = ?1
+            ORDER BY e.start_line, e.id
+            "#,
+        )?;
+        let rows = stmt.query_map(params![file_path], |row| {
+            Ok(ExportData {
+                name: row.get(0)?,
+                alias: row.get(1)?,
+                source: row.get(2)?,
+                start_line: row.get::<_, i64>(3)? as usize,
+                end_line: row.get::<_, i64>(4)? as usize,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Add the exports of `file_path` not yet in `seen` to `public`. Named exports shadow
+    /// names reaching the file through `export *`, so those are expanded last.
+    fn collect_exports(
+        &self,
+        file_path: &str,
+        depth: usize,
+        seen: &mut HashSet<String>,
+        public: &mut Vec<PublicExport>,
+    ) -> Result<()> {
+        let (stars, named): (Vec<_>, Vec<_>) = self
+            .file_exports(file_path)?
+            .into_iter()
+            .partition(|export| export.name == "*" && export.alias.is_none());
+
+        for export in named {
+            let name = export.alias.clone().unwrap_or_else(|| export.name.clone());
+            if !seen.insert(name.clone()) {
+                continue;
+            }
+            let definition = match (&export.source, export.name.as_str()) {
+                (Some(_), "*") => None,
+                (Some(_), _) => self.reexport_definition(&name, file_path)?,
+                (None, _) => self
+                    .find_symbols_by_name(&export.name)?
+                    .into_iter()
+                    .find(|symbol| symbol.file_path == file_path),
+            };
+            public.push(PublicExport { name, definition });
+        }
+
+        if depth >= MAX_EXPORT_STAR_DEPTH {
+            return Ok(());
+        }
+        for star in stars {
+            let Some(source) = &star.source else { continue };
+            let Some(target) = self.existing_files(&module_candidates(file_path, source))?.into_iter().next() else {
+                continue;
+            };
+            // `export *` never forwards the target's default export
+            let had_default = seen.contains("default");
+            seen.insert("default".to_string());
+            self.collect_exports(&target, depth + 1, seen, public)?;
+            if !had_default {
+                seen.remove("default");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParsedFileData, SymbolData};
+
+    fn file(symbols: Vec<SymbolData>, exports: Vec<ExportData>) -> ParsedFileData {
+        ParsedFileData {
+            symbols,
+            language: "tsx".to_string(),
+            exports,
+            ..Default::default()
+        }
+    }
+
+    fn component(name: &str) -> SymbolData {
+        let content = format!("export function {}() {{ return null; }}", name);
+        SymbolData {
+            name: name.to_string(),
+            kind: "Component".to_string(),
+            start_line: 3,
+            end_line: 5,
+            end_byte: content.len(),
+            content,
+            metadata: "{}".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn export(name: &str, alias: Option<&str>, source: Option<&str>) -> ExportData {
+        ExportData {
+            name: name.to_string(),
+            alias: alias.map(str::to_string),
+            source: source.map(str::to_string),
+            start_line: 1,
+            end_line: 1,
+        }
+    }
+
+    #[test]
+    fn test_barrel_reexports_resolve_to_definitions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let package = temp_dir.path().join("packages/ui");
+        fs::create_dir_all(&package).unwrap();
+        fs::write(
+            package.join("package.json"),
+            r#"{"name":"@org/ui","main":"dist/index.js","exports":{".":{"types":"./dist/index.d.ts","import":"./dist/index.mjs"}}}"#,
+        )
+        .unwrap();
+
+        let graph = KnowledgeGraph::in_memory().unwrap();
+        for name in ["Button", "Card"] {
+            graph
+                .insert_file(
+                    &format!("packages/ui/src/{}.tsx", name),
+                    &file(vec![component(name)], vec![export(name, None, None)]),
+                )
+                .unwrap();
+        }
+        graph
+            .insert_file(
+                "packages/ui/src/index.ts",
+                &file(
+                    vec![],
+                    vec![export("Button", None, Some("./Button")), export("Card", Some("Tile"), Some("./Card"))],
+                ),
+            )
+            .unwrap();
+
+        let api = graph.package_public_api(temp_dir.path(), "packages/ui").unwrap();
+        let names: Vec<&str> = api.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["Button", "Tile"]);
+
+        let button = api[0].definition.as_ref().unwrap();
+        assert_eq!(button.file_path, "packages/ui/src/Button.tsx");
+        assert_eq!(button.start_line, 3);
+        let tile = api[1].definition.as_ref().unwrap();
+        assert_eq!((tile.name.as_str(), tile.file_path.as_str()), ("Card", "packages/ui/src/Card.tsx"));
+
+        assert!(graph.package_public_api(temp_dir.path(), "packages/missing").is_err());
+    }
+}
@@ -3,6 +3,39 @@
 
 use anyhow::Result;
 
+/// Stored `kind` values, i.e. `Debug`-formatted `SymbolType` and `TypeKind` variants
+const STORED_KINDS: &[&str] = &[
+    "File", "Module", "Namespace", "Package", "Class", "Method", "Property", "Field",
+    "Constructor", "Enum", "Interface", "Function", "Variable", "Constant", "String", "Number",
+    "Boolean", "Array", "Object", "Key", "Null", "EnumMember", "Struct", "Event", "Operator",
+    "TypeParameter", "Component", "Hook", "TypeAlias", "Union", "Intersection",
+];
+
+/// Expand a search kind hint (as emitted by the router) into the stored kinds it covers.
+///
+/// Category hints map to several kinds ("type" → Interface, TypeAlias, Enum, ...); anything
+/// else matches a stored kind case-insensitively. Returns an empty list for generic hints.
+pub fn stored_kinds_for_hint(hint: &str) -> Vec<String> {
+    let hint = hint.trim().to_lowercase();
+    let kinds: &[&str] = match hint.as_str() {
+        "" | "any" | "all" => &[],
+        "type" | "types" => &["Interface", "TypeAlias", "Enum", "Struct", "Union", "Intersection"],
+        "component" | "components" => &["Component"],
+        "hook" | "hooks" => &["Hook"],
+        "schema" | "schemas" | "constant" | "constants" => &["Constant", "Variable"],
+        "style" | "styles" => &["Constant", "Variable", "Component"],
+        "api" | "helper" | "helpers" | "function" | "functions" => &["Function", "Method"],
+        "class" | "classes" => &["Class", "Struct"],
+        other => {
+            return match STORED_KINDS.iter().find(|k| k.to_lowercase() == other) {
+                Some(kind) => vec![kind.to_string()],
+                None => vec![other.to_string()],
+            }
+        }
+    };
+    kinds.iter().map(|k| k.to_string()).collect()
+}
+
 /// Query builder for complex symbol searches
 pub struct QueryBuilder {
     conditions: Vec<String>,
@@ -23,9 +56,16 @@ impl QueryBuilder {
         self
     }
 
+    /// Restrict to symbols matching a kind hint such as "component" or "type".
+    /// Generic hints ("any", empty) add no condition.
     pub fn with_kind(mut self, kind: &str) -> Self {
-        self.conditions.push("s.kind = ?".to_string());
-        self.params.push(kind.to_string());
+        let kinds = stored_kinds_for_hint(kind);
+        if kinds.is_empty() {
+            return self;
+        }
+        let placeholders = vec!["?"; kinds.len()].join(", ");
+        self.conditions.push(format!("s.kind IN ({})", placeholders));
+        self.params.extend(kinds);
         self
     }
 
@@ -37,10 +77,11 @@ impl QueryBuilder {
         };
 
         let query = format!(
-            "SELECT s.id, s.name, s.kind, s.content, f.path, s.start_line, s.end_line \
+            "SELECT s.id, s.name, s.kind, s.content, f.path, s.start_line, s.end_line, s.metadata \
              FROM symbols s \
              JOIN files f ON s.file_id = f.id \
-             {}",
+             {} \
+             ORDER BY s.name, f.path, s.start_line",
             where_clause
         );
 
@@ -53,3 +94,46 @@ impl Default for QueryBuilder {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{KnowledgeGraph, ParsedFileData, SymbolData};
+
+    fn symbol(name: &str, kind: &str) -> SymbolData {
+        SymbolData {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            start_line: 1,
+            end_line: 1,
+            end_byte: 10,
+            content: format!("{} {}", kind, name),
+            metadata: "{}".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_type_hint_matches_interface_row() {
+        let graph = KnowledgeGraph::in_memory().unwrap();
+        let parsed = ParsedFileData {
+            symbols: vec![symbol("User", "Interface"), symbol("UserCard", "Component")],
+            language: "typescript".to_string(),
+            ..Default::default()
+        };
+        graph.insert_file("src/user.ts", &parsed).unwrap();
+
+        let types = graph.query_symbols(&QueryBuilder::new().with_kind("type")).unwrap();
+        let names: Vec<&str> = types.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["User"]);
+
+        let components = graph
+            .query_symbols(&QueryBuilder::new().with_name("User").with_kind("component"))
+            .unwrap();
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].name, "UserCard");
+
+        let any = graph.query_symbols(&QueryBuilder::new().with_kind("any")).unwrap();
+        assert_eq!(any.len(), 2);
+    }
+}
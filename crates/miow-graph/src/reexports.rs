@@ -0,0 +1,211 @@
+//! Barrel-file re-exports. `index.ts` re-exporting `Button` from `./Button` makes the name
+//! searchable in two files, but only one of them has the component; a re-export hit is
+//! folded into the definition it points at so context gets the real code.
+
+use crate::{KnowledgeGraph, SymbolSearchResult};
+use anyhow::Result;
+use rusqlite::params;
+use std::collections::HashMap;
+
+/// How many barrel files a re-export is followed through before giving up
+const MAX_REEXPORT_DEPTH: usize = 5;
+
+/// Extensions a relative module specifier may resolve to, in resolution order
+pub(crate) const MODULE_EXTENSIONS: [&str; 4] = ["ts", "tsx", "js", "jsx"];
+
+/// A re-export that can provide a name: the name it has in `source`, and the module specifier
+struct ReExport {
+    original: String,
+    source: String,
+}
+
+/// Candidate file paths for the relative module `source` imported from `from_file`. Bare
+/// specifiers (packages, path aliases) can't be resolved and yield nothing.
+pub(crate) fn module_candidates(from_file: &str, source: &str) -> Vec<String> {
+    if !source.starts_with('.') {
+        return Vec::new();
+    }
+    let mut parts: Vec<&str> = from_file.split('/').collect();
+    parts.pop();
+    for segment in source.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            other => parts.push(other),
+        }
+    }
+    let base = parts.join("/");
+    let mut candidates = vec![base.clone()];
+    candidates.extend(MODULE_EXTENSIONS.iter().map(|ext| format!("{}.{}", base, ext)));
+    candidates.extend(MODULE_EXTENSIONS.iter().map(|ext| format!("{}/index.{}", base, ext)));
+    candidates
+}
+
+impl KnowledgeGraph {
+    /// Re-exports in `file_path` that could provide `name`: explicit ones first, then
+    /// `export *`
+    fn reexports_providing(&self, file_path: &str, name: &str) -> Result<Vec<ReExport>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT e.name, e.source
+            FROM exports e
+            JOIN files f ON e.file_id = f.id
+            WHERE f.path = ?1 AND e.source IS NOT NULL
+              AND (e.alias = ?2 OR (e.alias IS NULL AND e.name = ?2) OR e.name = '*')
+            ORDER BY e.name = '*', e.start_line
+            "#,
+        )?;
+        let rows = stmt.query_map(params![file_path, name], |row| {
+            let original: String = row.get(0)?;
+            Ok(ReExport {
+                // `export { default as Button }` and `export *` keep the importer-facing name;
+                // the definition is looked up under it
+                original: if original == "default" || original == "*" { name.to_string() } else { original },
+                source: row.get(1)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Indexed file paths among `candidates`, in candidate order
+    pub(crate) fn existing_files(&self, candidates: &[String]) -> Result<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT 1 FROM files WHERE path = ?1")?;
+        let mut existing = Vec::new();
+        for candidate in candidates {
+            if stmt.exists(params![candidate])? {
+                existing.push(candidate.clone());
+            }
+        }
+        Ok(existing)
+    }
+
+    /// The definition `name` in `file_path` re-exports, following chains of barrel files.
+    /// `None` when `file_path` doesn't re-export `name` or the source can't be resolved.
+    pub fn reexport_definition(&self, name: &str, file_path: &str) -> Result<Option<SymbolSearchResult>> {
+        self.reexport_definition_at(name, file_path, 0)
+    }
+
+    fn reexport_definition_at(&self, name: &str, file_path: &str, depth: usize) -> Result<Option<SymbolSearchResult>> {
+        if depth >= MAX_REEXPORT_DEPTH {
+            return Ok(None);
+        }
+        for reexport in self.reexports_providing(file_path, name)? {
+            for file in self.existing_files(&module_candidates(file_path, &reexport.source))? {
+                // The target may itself be a barrel re-exporting the name further
+                if let Some(definition) = self.reexport_definition_at(&reexport.original, &file, depth + 1)? {
+                    return Ok(Some(definition));
+                }
+                let definition = self
+                    .find_symbols_by_name(&reexport.original)?
+                    .into_iter()
+                    .find(|symbol| symbol.file_path == file);
+                if definition.is_some() {
+                    return Ok(definition);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Replace re-export hits with the symbols they re-export, keeping result order. The
+    /// definition records each barrel file in `reexported_from`; a definition reached twice
+    /// (directly and through a barrel, or through two barrels) appears once.
+    pub fn collapse_reexports(&self, results: Vec<SymbolSearchResult>) -> Result<Vec<SymbolSearchResult>> {
+        let mut collapsed: Vec<SymbolSearchResult> = Vec::new();
+        let mut positions: HashMap<i64, usize> = HashMap::new();
+        for result in results {
+            let (symbol, barrel) = match self.reexport_definition(&result.name, &result.file_path)? {
+                Some(definition) => (definition, Some(result.file_path)),
+                None => (result, None),
+            };
+            let position = *positions.entry(symbol.id).or_insert_with(|| {
+                collapsed.push(symbol);
+                collapsed.len() - 1
+            });
+            if let Some(barrel) = barrel {
+                let reexported_from = &mut collapsed[position].reexported_from;
+                if !reexported_from.contains(&barrel) {
+                    reexported_from.push(barrel);
+                }
+            }
+        }
+        Ok(collapsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ExportData, ParsedFileData, SymbolData};
+
+    fn file(symbols: Vec<SymbolData>, exports: Vec<ExportData>) -> ParsedFileData {
+        ParsedFileData {
+            symbols,
+            language: "tsx".to_string(),
+            exports,
+            ..Default::default()
+        }
+    }
+
+    fn symbol(name: &str, content: &str) -> SymbolData {
+        SymbolData {
+            name: name.to_string(),
+            kind: "Component".to_string(),
+            start_line: 1,
+            end_line: 1,
+            end_byte: content.len(),
+            content: content.to_string(),
+            metadata: "{}".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn reexport(name: &str, source: &str) -> ExportData {
+        ExportData {
+            name: name.to_string(),
+            alias: None,
+            source: Some(source.to_string()),
+            start_line: 1,
+            end_line: 1,
+        }
+    }
+
+    #[test]
+    fn test_reexport_hit_collapses_into_definition() {
+        let graph = KnowledgeGraph::in_memory().unwrap();
+        let definition = "export function Button() { return <button />; }";
+        graph
+            .insert_file("src/components/Button.tsx", &file(vec![symbol("Button", definition)], vec![]))
+            .unwrap();
+        // A one-line re-export indexed as a symbol of its own
+        graph
+            .insert_file(
+                "src/components/index.ts",
+                &file(
+                    vec![symbol("Button", "export { Button } from './Button';")],
+                    vec![reexport("Button", "./Button")],
+                ),
+            )
+            .unwrap();
+        graph
+            .insert_file("src/index.ts", &file(vec![], vec![reexport("*", "./components")]))
+            .unwrap();
+
+        let hits = graph.search_symbols("Button").unwrap();
+        assert_eq!(hits.len(), 2);
+        let collapsed = graph.collapse_reexports(hits).unwrap();
+        assert_eq!(collapsed.len(), 1);
+        assert_eq!(collapsed[0].file_path, "src/components/Button.tsx");
+        assert_eq!(collapsed[0].content, definition);
+        assert_eq!(collapsed[0].reexported_from, vec!["src/components/index.ts".to_string()]);
+
+        // `export *` chains through the nested barrel to the same definition
+        let through_root = graph.reexport_definition("Button", "src/index.ts").unwrap().unwrap();
+        assert_eq!(through_root.file_path, "src/components/Button.tsx");
+        assert!(graph.reexport_definition("Button", "src/components/Button.tsx").unwrap().is_none());
+    }
+}
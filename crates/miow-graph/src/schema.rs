@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 /// Data structures for inserting into the knowledge graph
 /// These mirror the parser types but are simplified for storage
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ParsedFileData {
     pub symbols: Vec<SymbolData>,
     pub imports: Vec<ImportData>,
@@ -12,9 +12,13 @@ pub struct ParsedFileData {
     pub constants: Vec<ConstantData>,
     pub schemas: Vec<SchemaData>,
     pub language: String,
+    #[serde(default)]
+    pub exports: Vec<ExportData>,
+    #[serde(default)]
+    pub tests: Vec<TestCaseData>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SymbolData {
     pub name: String,
     pub kind: String,
@@ -27,6 +31,9 @@ pub struct SymbolData {
     pub style_tags: Option<String>, // Comma-separated style tags
     pub children: Vec<SymbolData>,
     pub references: Vec<String>,
+    /// Module-qualified name (e.g. `config::Settings`), searchable alongside `name`
+    #[serde(default)]
+    pub qualified_name: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,6 +44,18 @@ pub struct ImportData {
     pub end_line: usize,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportData {
+    /// Exported name in the source module, or `*` for `export * from`
+    pub name: String,
+    /// Name the file exports it under, when renamed (`export { Card as Tile }`)
+    pub alias: Option<String>,
+    /// Module a re-export comes from; `None` for the file's own declarations
+    pub source: Option<String>,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DesignTokenData {
     pub token_type: String,
@@ -73,3 +92,11 @@ pub struct SchemaData {
     pub start_line: usize,
     pub end_line: usize,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestCaseData {
+    pub name: String,
+    pub references: Vec<String>,
+    pub start_line: usize,
+    pub end_line: usize,
+}
@@ -81,9 +81,13 @@ impl SemanticGraphSearch {
             });
         }
         
-        // Sort by combined score
+        // Sort by combined score, ties by path then name
         semantic_results.sort_by(|a, b| {
-            b.combined_score.partial_cmp(&a.combined_score).unwrap()
+            b.combined_score
+                .partial_cmp(&a.combined_score)
+                .unwrap()
+                .then_with(|| a.file_path.cmp(&b.file_path))
+                .then_with(|| a.name.cmp(&b.name))
         });
         
         Ok(semantic_results)
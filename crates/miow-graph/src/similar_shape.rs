@@ -0,0 +1,202 @@
+//! Structural similarity: symbols of the same kind whose signatures look alike (the same props
+//! or parameters, similarly typed, returning the same thing), whatever they are called. This
+//! is what "refactor X to look like the others" needs; name similarity finds the wrong peers.
+
+use crate::{KnowledgeGraph, SymbolSearchResult};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Candidates scoring below this are not considered similar
+pub const MIN_SHAPE_SIMILARITY: f32 = 0.5;
+
+/// Share of the blended score taken by embedding similarity when a vector store is present
+pub const DEFAULT_EMBEDDING_WEIGHT: f32 = 0.3;
+
+/// A symbol shaped like the one searched for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarShape {
+    pub symbol: SymbolSearchResult,
+    /// Signature similarity in 0..=1
+    pub shape_score: f32,
+    /// `shape_score`, blended with embedding similarity when that is known
+    pub score: f32,
+}
+
+/// The parts of a signature compared: props for components, parameters otherwise
+#[derive(Debug, Default)]
+struct Shape {
+    inputs: Vec<(String, Option<String>)>,
+    return_type: Option<String>,
+}
+
+impl Shape {
+    fn of(symbol: &SymbolSearchResult) -> Self {
+        let Some(metadata) = symbol.metadata_value() else {
+            return Self::default();
+        };
+        let inputs = |field: &str| -> Vec<(String, Option<String>)> {
+            metadata[field]
+                .as_array()
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|item| {
+                            let name = item["name"].as_str()?.to_string();
+                            let type_annotation = item["type_annotation"].as_str().map(normalize_type);
+                            Some((name, type_annotation))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let props = inputs("props");
+        Self {
+            inputs: if props.is_empty() { inputs("parameters") } else { props },
+            return_type: metadata["return_type"].as_str().map(normalize_type),
+        }
+    }
+
+    /// 0..=1: input count, input names, input types and return type, weighted in that order
+    /// of specificity
+    fn similarity(&self, other: &Shape) -> f32 {
+        let (a, b) = (self.inputs.len(), other.inputs.len());
+        let count = 1.0 - a.abs_diff(b) as f32 / a.max(b).max(1) as f32;
+        let names = jaccard(self.inputs.iter().map(|(n, _)| n.as_str()), other.inputs.iter().map(|(n, _)| n.as_str()));
+        let typed = |shape: &Shape| -> Vec<String> {
+            shape
+                .inputs
+                .iter()
+                .map(|(n, t)| format!("{}:{}", n, t.as_deref().unwrap_or("")))
+                .collect()
+        };
+        let types = jaccard(
+            typed(self).iter().map(String::as_str),
+            typed(other).iter().map(String::as_str),
+        );
+        let returns = if self.return_type == other.return_type { 1.0 } else { 0.0 };
+        0.2 * count + 0.35 * names + 0.3 * types + 0.15 * returns
+    }
+}
+
+/// Type text without whitespace or trailing separators, so `string | null` matches `string|null`
+fn normalize_type(type_annotation: &str) -> String {
+    type_annotation
+        .trim_start_matches(':')
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .trim_end_matches([';', ','])
+        .to_string()
+}
+
+/// Jaccard similarity of two sets; two empty sets are identical
+fn jaccard<'a>(a: impl Iterator<Item = &'a str>, b: impl Iterator<Item = &'a str>) -> f32 {
+    let a: HashSet<&str> = a.collect();
+    let b: HashSet<&str> = b.collect();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    a.intersection(&b).count() as f32 / a.union(&b).count() as f32
+}
+
+/// Blend embedding similarity (by graph symbol id, 0..=1) into `results` with `weight` and
+/// re-rank them. Results without an embedding score keep their shape score.
+pub fn blend_embedding_similarity(results: &mut [SimilarShape], embedding_scores: &HashMap<i64, f32>, weight: f32) {
+    let weight = weight.clamp(0.0, 1.0);
+    for result in results.iter_mut() {
+        if let Some(embedding) = embedding_scores.get(&result.symbol.id) {
+            result.score = (1.0 - weight) * result.shape_score + weight * embedding;
+        }
+    }
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+}
+
+impl KnowledgeGraph {
+    /// Symbols of `symbol`'s kind with a similar signature, most similar first. Components
+    /// compare props, functions and methods their parameters; both compare return types.
+    pub fn find_similar_shape(&self, symbol: &SymbolSearchResult) -> Result<Vec<SimilarShape>> {
+        let shape = Shape::of(symbol);
+        let mut similar: Vec<SimilarShape> = self
+            .find_symbols_by_kind(&symbol.kind)?
+            .into_iter()
+            .filter(|candidate| candidate.id != symbol.id)
+            .filter_map(|candidate| {
+                let shape_score = shape.similarity(&Shape::of(&candidate));
+                (shape_score >= MIN_SHAPE_SIMILARITY).then_some(SimilarShape {
+                    symbol: candidate,
+                    shape_score,
+                    score: shape_score,
+                })
+            })
+            .collect();
+        similar.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.symbol.name.cmp(&b.symbol.name)));
+        Ok(similar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParsedFileData, SymbolData};
+    use serde_json::{json, Value};
+
+    fn component(name: &str, props: &[(&str, &str)]) -> SymbolData {
+        let props: Vec<Value> = props
+            .iter()
+            .map(|(name, ty)| json!({ "name": name, "type_annotation": ty, "is_required": true }))
+            .collect();
+        SymbolData {
+            name: name.to_string(),
+            kind: "Component".to_string(),
+            start_line: 1,
+            end_line: 10,
+            content: format!("export function {}(props) {{ return null; }}", name),
+            metadata: json!({ "props": props, "parameters": [], "return_type": "JSX.Element" }).to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn file(symbols: Vec<SymbolData>) -> ParsedFileData {
+        ParsedFileData {
+            symbols,
+            language: "tsx".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_components_with_similar_props_are_similar() {
+        let graph = KnowledgeGraph::in_memory().unwrap();
+        graph
+            .insert_file(
+                "src/UserCard.tsx",
+                &file(vec![component("UserCard", &[("title", "string"), ("avatarUrl", "string"), ("onClick", "() => void")])]),
+            )
+            .unwrap();
+        graph
+            .insert_file(
+                "src/TeamCard.tsx",
+                &file(vec![component("TeamCard", &[("title", "string"), ("avatarUrl", "string | null"), ("onClick", "() => void")])]),
+            )
+            .unwrap();
+        graph
+            .insert_file(
+                "src/DataGrid.tsx",
+                &file(vec![component("DataGrid", &[("rows", "Row[]"), ("columns", "Column[]")])]),
+            )
+            .unwrap();
+
+        let user_card = graph.find_symbols_by_name("UserCard").unwrap().remove(0);
+        let similar = graph.find_similar_shape(&user_card).unwrap();
+        let names: Vec<&str> = similar.iter().map(|s| s.symbol.name.as_str()).collect();
+        assert_eq!(names, vec!["TeamCard"]);
+        assert!(similar[0].shape_score > 0.8);
+
+        // Embedding similarity shifts the score without replacing the shape score
+        let mut blended = similar.clone();
+        blend_embedding_similarity(&mut blended, &HashMap::from([(similar[0].symbol.id, 0.0)]), 0.5);
+        assert_eq!(blended[0].shape_score, similar[0].shape_score);
+        assert!((blended[0].score - similar[0].shape_score * 0.5).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,264 @@
+use super::*;
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::json;
+
+/// Messages API root, overridable for proxies and compatible servers
+const DEFAULT_BASE_URL: &str = "https://api.anthropic.com/v1";
+
+/// `anthropic-version` header sent with every request
+const API_VERSION: &str = "2023-06-01";
+
+/// Model `from_env` uses
+const DEFAULT_MODEL: &str = "claude-sonnet-4-0";
+
+/// Calls fail on the first error; wrap the client in a `RetryingLLMProvider` to retry
+/// rate limits and transient server errors.
+pub struct AnthropicClient {
+    client: Client,
+    api_key: String,
+    model: String,
+    temperature: f32,
+    max_tokens: usize,
+    base_url: String,
+}
+
+impl AnthropicClient {
+    pub fn new(config: LLMConfig) -> Result<Self> {
+        if config.api_key.is_empty() {
+            anyhow::bail!("Anthropic API key is required");
+        }
+        if config.model.trim().is_empty() {
+            anyhow::bail!("Anthropic model name is required");
+        }
+
+        Ok(Self {
+            client: Client::new(),
+            api_key: config.api_key,
+            model: config.model,
+            temperature: config.temperature,
+            max_tokens: config.max_tokens,
+            base_url: DEFAULT_BASE_URL.to_string(),
+        })
+    }
+
+    pub fn from_env() -> Result<Self> {
+        let api_key = std::env::var("ANTHROPIC_API_KEY")
+            .context("ANTHROPIC_API_KEY environment variable not set")?;
+
+        // `LLMConfig::default()` names a Gemini model
+        Self::new(LLMConfig {
+            api_key,
+            model: DEFAULT_MODEL.to_string(),
+            ..Default::default()
+        })
+    }
+
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    fn build_request_body(&self, messages: Vec<Message>, options: &GenerationOptions) -> serde_json::Value {
+        // System prompts go to the top-level `system` field; adjacent turns with the same
+        // role are merged so `user` and `assistant` alternate
+        let mut system_parts = Vec::new();
+        let mut turns: Vec<(&str, String)> = Vec::new();
+        for message in messages {
+            let role = match message.role {
+                Role::System => {
+                    system_parts.push(message.content);
+                    continue;
+                }
+                Role::User => "user",
+                Role::Assistant => "assistant",
+            };
+
+            match turns.last_mut() {
+                Some((last_role, text)) if *last_role == role => {
+                    text.push_str("\n\n");
+                    text.push_str(&message.content);
+                }
+                _ => turns.push((role, message.content)),
+            }
+        }
+
+        let messages: Vec<serde_json::Value> = turns
+            .into_iter()
+            .map(|(role, content)| json!({ "role": role, "content": content }))
+            .collect();
+
+        let mut body = json!({
+            "model": self.model,
+            "messages": messages,
+            "max_tokens": options.max_tokens.unwrap_or(self.max_tokens),
+            "temperature": options.temperature.unwrap_or(self.temperature),
+        });
+        if !system_parts.is_empty() {
+            body["system"] = json!(system_parts.join("\n\n"));
+        }
+        body
+    }
+
+    /// Text blocks of a Messages API response, with its stop reason and token usage
+    fn parse_response(json: &serde_json::Value) -> LLMResponse {
+        let content: String = json["content"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|block| block["type"] == "text")
+            .filter_map(|block| block["text"].as_str())
+            .collect();
+
+        let usage = json["usage"].as_object().map(|u| {
+            let prompt_tokens = u.get("input_tokens").and_then(|t| t.as_u64()).unwrap_or(0) as usize;
+            let completion_tokens = u.get("output_tokens").and_then(|t| t.as_u64()).unwrap_or(0) as usize;
+            Usage {
+                prompt_tokens,
+                completion_tokens,
+                total_tokens: prompt_tokens + completion_tokens,
+            }
+        });
+
+        LLMResponse {
+            content,
+            finish_reason: json["stop_reason"].as_str().map(|s| s.to_string()),
+            usage,
+        }
+    }
+}
+
+#[async_trait]
+impl LLMProvider for AnthropicClient {
+    async fn generate(&self, prompt: &str) -> Result<LLMResponse> {
+        let messages = vec![Message {
+            role: Role::User,
+            content: prompt.to_string(),
+        }];
+        self.generate_with_context(messages).await
+    }
+
+    async fn generate_with_context(&self, messages: Vec<Message>) -> Result<LLMResponse> {
+        self.generate_with_options(messages, GenerationOptions::default()).await
+    }
+
+    async fn generate_with_options(
+        &self,
+        messages: Vec<Message>,
+        options: GenerationOptions,
+    ) -> Result<LLMResponse> {
+        let url = format!("{}/messages", self.base_url.trim_end_matches('/'));
+
+        let body = self.build_request_body(messages, &options);
+
+        let response = self
+            .client
+            .post(url)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", API_VERSION)
+            .json(&body)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ProviderHttpError {
+                provider: "Anthropic",
+                status: status.as_u16(),
+                body: response.text().await.unwrap_or_default(),
+            }
+            .into());
+        }
+
+        let json: serde_json::Value = response.json().await?;
+        Ok(Self::parse_response(&json))
+    }
+
+    async fn stream_generate(
+        &self,
+        _prompt: &str,
+    ) -> Result<Box<dyn futures::Stream<Item = Result<String>> + Unpin>> {
+        anyhow::bail!("Streaming not yet implemented for Anthropic")
+    }
+
+    async fn generate_multi_step(&self, steps: Vec<String>, context: &str) -> Result<LLMResponse> {
+        let mut final_content = String::new();
+
+        for (i, step_prompt) in steps.iter().enumerate() {
+            let full_prompt = format!("Step {}/{}: {}\nContext: {}", i + 1, steps.len(), step_prompt, context);
+            let response = self.generate(&full_prompt).await?;
+            final_content += &format!("Step {}: {}\n", i + 1, response.content);
+        }
+
+        Ok(LLMResponse {
+            content: final_content,
+            finish_reason: None,
+            usage: None,
+        })
+    }
+
+    async fn generate_with_framework(&self, prompt: &str, framework: &str, lang: &str) -> Result<LLMResponse> {
+        let enhanced_prompt = format!(
+            "You are an expert {} developer using {} framework.\n\n{}",
+            lang, framework, prompt
+        );
+        self.generate(&enhanced_prompt).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_prompt_is_top_level_and_usage_is_read() {
+        let client = AnthropicClient::new(LLMConfig {
+            api_key: "test-key".to_string(),
+            model: DEFAULT_MODEL.to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(AnthropicClient::new(LLMConfig {
+            api_key: "test-key".to_string(),
+            model: String::new(),
+            ..Default::default()
+        })
+        .is_err());
+        assert_eq!(ModelLimits::for_model(DEFAULT_MODEL).max_input_tokens, 200_000);
+        let messages = crate::with_few_shot(
+            vec![
+                Message { role: Role::System, content: "Answer in JSON.".to_string() },
+                Message { role: Role::User, content: "Plan a login page".to_string() },
+            ],
+            &[crate::FewShotExample::new("Plan a settings page", r#"{"global_intent":"create_settings_page"}"#)],
+        );
+
+        let body = client.build_request_body(messages, &GenerationOptions::deterministic().with_max_tokens(256));
+        assert_eq!(body["model"], DEFAULT_MODEL);
+        assert_eq!(body["system"], "Answer in JSON.");
+        assert_eq!(body["max_tokens"].as_u64(), Some(256));
+        assert_eq!(body["temperature"].as_f64(), Some(0.0));
+        let roles: Vec<&str> = body["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["role"].as_str().unwrap())
+            .collect();
+        assert_eq!(roles, vec!["user", "assistant", "user"]);
+
+        let response = AnthropicClient::parse_response(&json!({
+            "content": [
+                { "type": "thinking", "thinking": "..." },
+                { "type": "text", "text": "{\"global_intent\":" },
+                { "type": "text", "text": "\"create_login_page\"}" }
+            ],
+            "stop_reason": "end_turn",
+            "usage": { "input_tokens": 120, "output_tokens": 14 }
+        }));
+        assert_eq!(response.content, r#"{"global_intent":"create_login_page"}"#);
+        assert_eq!(response.finish_reason.as_deref(), Some("end_turn"));
+        let usage = response.usage.unwrap();
+        assert_eq!((usage.prompt_tokens, usage.completion_tokens, usage.total_tokens), (120, 14, 134));
+    }
+}
@@ -1,18 +1,18 @@
-use crate::{LLMConfig, LLMProvider, LLMResponse, Message, Role, LLMCache};
+use crate::{GenerationOptions, LLMConfig, LLMProvider, LLMResponse, Message, Role, LLMCache, ProviderHttpError, RetryPolicy};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use futures::{Stream, StreamExt};
 use serde_json::json;
-use tracing::{debug, info, warn, error};
-use tokio::time::{sleep, Duration};
-use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
+use std::collections::VecDeque;
+use std::time::Instant;
 
 pub struct GeminiClient {
     api_key: String,
     model: String,
     temperature: f32,
     client: reqwest::Client,
-    max_retries: u32,
-    base_delay: Duration,
+    retry_policy: RetryPolicy,
     cache: LLMCache,
 }
 
@@ -27,8 +27,7 @@ impl GeminiClient {
             model: config.model,
             temperature: config.temperature,
             client: reqwest::Client::new(),
-            max_retries: 5,
-            base_delay: Duration::from_secs(2),
+            retry_policy: RetryPolicy::default(),
             cache: LLMCache::new(),
         })
     }
@@ -43,75 +42,135 @@ impl GeminiClient {
         })
     }
 
-    fn generate_jitter(&self) -> Duration {
-        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
-        let seed = now.as_nanos() as u64 % 1000;
-        Duration::from_millis(seed)
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
     }
 
-    async fn call_api(&self, messages: Vec<Message>) -> Result<String> {
-        let url = format!(
-            "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-            self.model, self.api_key
-        );
-
-        debug!("Calling Gemini API with model: {}", self.model);
+    /// Whether the model accepts `responseMimeType`/`responseSchema`; Gemini 1.0 models don't
+    fn supports_json_mode(&self) -> bool {
+        let model = self.model.trim_start_matches("models/");
+        !(model.starts_with("gemini-1.0") || model == "gemini-pro" || model.starts_with("gemini-pro-"))
+    }
 
-        let mut contents = Vec::new();
+    fn build_request_body(&self, messages: Vec<Message>, options: &GenerationOptions) -> serde_json::Value {
+        // System prompts go to `systemInstruction`; the conversation alternates
+        // `user`/`model` turns, so adjacent messages with the same role are merged.
+        let mut system_parts = Vec::new();
+        let mut turns: Vec<(&str, String)> = Vec::new();
         for message in messages {
             let role = match message.role {
-                Role::System => "model",
+                Role::System => {
+                    system_parts.push(message.content);
+                    continue;
+                }
                 Role::User => "user",
                 Role::Assistant => "model",
             };
 
-            contents.push(json!({
-                "role": role,
-                "parts": [{
-                    "text": message.content
-                }]
-            }));
+            match turns.last_mut() {
+                Some((last_role, text)) if *last_role == role => {
+                    text.push_str("\n\n");
+                    text.push_str(&message.content);
+                }
+                _ => turns.push((role, message.content)),
+            }
         }
 
-        let request_body = json!({
-            "contents": contents,
-            "generationConfig": {
-                "temperature": self.temperature,
-                "topK": 40,
-                "topP": 0.95,
-            }
+        let contents: Vec<serde_json::Value> = turns
+            .into_iter()
+            .map(|(role, text)| {
+                json!({
+                    "role": role,
+                    "parts": [{
+                        "text": text
+                    }]
+                })
+            })
+            .collect();
+
+        let mut generation_config = json!({
+            "temperature": options.temperature.unwrap_or(self.temperature),
+            "topK": 40,
+            "topP": 0.95,
         });
+        if let Some(max_tokens) = options.max_tokens {
+            generation_config["maxOutputTokens"] = json!(max_tokens);
+        }
+        if options.json_output {
+            if self.supports_json_mode() {
+                generation_config["responseMimeType"] = json!("application/json");
+                if let Some(schema) = &options.json_schema {
+                    generation_config["responseSchema"] = schema.clone();
+                }
+            } else {
+                debug!("{} has no JSON mode; requesting plain text", self.model);
+            }
+        }
 
-        let mut attempt = 0;
+        let mut body = json!({
+            "contents": contents,
+            "generationConfig": generation_config
+        });
+        if !system_parts.is_empty() {
+            body["systemInstruction"] = json!({
+                "parts": [{
+                    "text": system_parts.join("\n\n")
+                }]
+            });
+        }
+        body
+    }
 
-        while attempt <= self.max_retries {
-            let start_time = Instant::now();
-            let jitter = self.generate_jitter();
+    async fn call_api(&self, messages: Vec<Message>, options: &GenerationOptions) -> Result<String> {
+        let url = format!(
+            "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
+            self.model, self.api_key
+        );
 
-            match self.perform_api_call(&url, &request_body).await {
-                Ok(response_text) => {
-                    info!("Gemini API call successful on attempt {} (took {:?})", attempt + 1, start_time.elapsed());
-                    return Ok(response_text);
-                }
-                Err(e) => {
-                    attempt += 1;
-                    warn!("Gemini API call failed on attempt {}: {}", attempt, e);
+        debug!("Calling Gemini API with model: {}", self.model);
 
-                    if attempt > self.max_retries {
-                        error!("All {} retry attempts failed for Gemini API", self.max_retries);
-                        return Err(e);
-                    }
+        let request_body = self.build_request_body(messages, options);
 
-                    let backoff_delay = self.base_delay * 2u32.pow(attempt - 1);
-                    let total_delay = backoff_delay + jitter;
+        let start_time = Instant::now();
+        let response_text = self
+            .retry_policy
+            .run("Gemini API", || self.perform_api_call(&url, &request_body))
+            .await?;
+        info!("Gemini API call successful (took {:?})", start_time.elapsed());
+        Ok(response_text)
+    }
 
-                    warn!("Retrying in {:?} (attempt {}/{}, jitter: {:?})", total_delay, attempt, self.max_retries, jitter);
-                    sleep(total_delay).await;
+    /// Open a `:streamGenerateContent` SSE response. Only opening the connection is retried;
+    /// once text has started arriving a failure ends the stream.
+    async fn open_stream(&self, messages: Vec<Message>, options: &GenerationOptions) -> Result<reqwest::Response> {
+        let url = format!(
+            "https://generativelanguage.googleapis.com/v1beta/models/{}:streamGenerateContent?alt=sse&key={}",
+            self.model, self.api_key
+        );
+        let request_body = self.build_request_body(messages, options);
+
+        self.retry_policy
+            .run("Gemini streaming API", || async {
+                let response = self
+                    .client
+                    .post(&url)
+                    .json(&request_body)
+                    .send()
+                    .await
+                    .context("Failed to send request to Gemini API")?;
+                if !response.status().is_success() {
+                    let status = response.status();
+                    return Err(ProviderHttpError {
+                        provider: "Gemini",
+                        status: status.as_u16(),
+                        body: response.text().await.unwrap_or_default(),
+                    }
+                    .into());
                 }
-            }
-        }
-
-        anyhow::bail!("Unexpected error after retries")
+                Ok(response)
+            })
+            .await
     }
 
     async fn perform_api_call(&self, url: &str, request_body: &serde_json::Value) -> Result<String> {
@@ -125,13 +184,12 @@ impl GeminiClient {
 
         if !response.status().is_success() {
             let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            
-            if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                anyhow::bail!("Gemini API error ({}): {}. This is retryable.", status, error_text);
-            } else {
-                anyhow::bail!("Gemini API error ({}): {}", status, error_text);
+            return Err(ProviderHttpError {
+                provider: "Gemini",
+                status: status.as_u16(),
+                body: response.text().await.unwrap_or_default(),
             }
+            .into());
         }
 
         let response_json: serde_json::Value = response
@@ -168,7 +226,7 @@ impl LLMProvider for GeminiClient {
             content: prompt.to_string(),
         }];
 
-        let text = self.call_api(messages).await?;
+        let text = self.call_api(messages, &GenerationOptions::default()).await?;
 
         // Cache the result
         if let Err(e) = self.cache.set(prompt, &self.model, &text).await {
@@ -183,9 +241,26 @@ impl LLMProvider for GeminiClient {
     }
 
     async fn generate_with_context(&self, messages: Vec<Message>) -> Result<LLMResponse> {
+        self.generate_with_options(messages, GenerationOptions::default()).await
+    }
+
+    /// Gemini 2.5 models spend output tokens on thinking, so the reply is not capped.
+    async fn health_check(&self) -> Result<()> {
+        let response = self
+            .generate_with_options(crate::health_check_messages(), GenerationOptions::deterministic())
+            .await
+            .context("Gemini health check request failed")?;
+        crate::ensure_healthy_response(&response)
+    }
+
+    async fn generate_with_options(
+        &self,
+        messages: Vec<Message>,
+        options: GenerationOptions,
+    ) -> Result<LLMResponse> {
         info!("Generating response with Gemini (with context)");
 
-        let text = self.call_api(messages).await?;
+        let text = self.call_api(messages, &options).await?;
 
         Ok(LLMResponse {
             content: text,
@@ -194,12 +269,23 @@ impl LLMProvider for GeminiClient {
         })
     }
 
+    /// Text fragments as Gemini produces them. If the connection drops midway, the fragments
+    /// that arrived are yielded before the error.
     async fn stream_generate(
         &self,
-        _prompt: &str,
+        prompt: &str,
     ) -> Result<Box<dyn futures::Stream<Item = Result<String>> + Unpin>> {
-        // TODO: Implement streaming for Gemini
-        unimplemented!("Streaming not yet implemented for Gemini")
+        info!("Streaming response with Gemini");
+
+        let messages = vec![Message {
+            role: Role::User,
+            content: prompt.to_string(),
+        }];
+        let response = self.open_stream(messages, &GenerationOptions::default()).await?;
+        let bytes = response
+            .bytes_stream()
+            .map(|chunk| chunk.context("Gemini stream interrupted"));
+        Ok(Box::new(Box::pin(sse_text_stream(bytes))))
     }
 
     async fn generate_multi_step(&self, steps: Vec<String>, context: &str) -> Result<LLMResponse> {
@@ -227,6 +313,95 @@ impl LLMProvider for GeminiClient {
     }
 }
 
+/// Incremental decoder for Gemini's server-sent events: buffers bytes until an event is
+/// complete and turns each `data:` payload into the text it carries
+#[derive(Default)]
+struct SseDecoder {
+    buffer: Vec<u8>,
+}
+
+impl SseDecoder {
+    /// Text fragments (or API errors) of the events completed by `chunk`
+    fn push(&mut self, chunk: &[u8]) -> Vec<Result<String>> {
+        self.buffer.extend_from_slice(chunk);
+        let mut fragments = Vec::new();
+        while let Some((end, separator)) = event_end(&self.buffer) {
+            let event: Vec<u8> = self.buffer.drain(..end + separator).collect();
+            fragments.extend(Self::decode_event(&event[..end]));
+        }
+        fragments
+    }
+
+    /// Whatever event the stream ended on without a trailing blank line
+    fn finish(&mut self) -> Option<Result<String>> {
+        let event = std::mem::take(&mut self.buffer);
+        Self::decode_event(&event)
+    }
+
+    fn decode_event(event: &[u8]) -> Option<Result<String>> {
+        let event = String::from_utf8_lossy(event);
+        let data: Vec<&str> = event
+            .lines()
+            .filter_map(|line| line.strip_prefix("data:"))
+            .map(str::trim_start)
+            .collect();
+        if data.is_empty() {
+            return None;
+        }
+        let payload: serde_json::Value = match serde_json::from_str(&data.join("\n")) {
+            Ok(payload) => payload,
+            Err(e) => return Some(Err(anyhow::anyhow!("Failed to parse Gemini stream event: {}", e))),
+        };
+        if let Some(error) = payload.get("error") {
+            return Some(Err(anyhow::anyhow!("Gemini stream error: {}", error)));
+        }
+        payload["candidates"][0]["content"]["parts"][0]["text"]
+            .as_str()
+            .filter(|text| !text.is_empty())
+            .map(|text| Ok(text.to_string()))
+    }
+}
+
+/// Offset and separator length of the first blank line ending an event
+fn event_end(buffer: &[u8]) -> Option<(usize, usize)> {
+    (0..buffer.len()).find_map(|i| {
+        if buffer[i..].starts_with(b"\r\n\r\n") {
+            Some((i, 4))
+        } else if buffer[i..].starts_with(b"\n\n") {
+            Some((i, 2))
+        } else {
+            None
+        }
+    })
+}
+
+/// Decode an SSE byte stream into text fragments. Fragments decoded before a transport error
+/// are yielded first; the error ends the stream.
+fn sse_text_stream<B: AsRef<[u8]>>(bytes: impl Stream<Item = Result<B>> + Unpin) -> impl Stream<Item = Result<String>> {
+    let state = (bytes, SseDecoder::default(), VecDeque::new(), false);
+    futures::stream::unfold(state, |(mut bytes, mut decoder, mut pending, mut done)| async move {
+        loop {
+            if let Some(fragment) = pending.pop_front() {
+                return Some((fragment, (bytes, decoder, pending, done)));
+            }
+            if done {
+                return None;
+            }
+            match bytes.next().await {
+                Some(Ok(chunk)) => pending.extend(decoder.push(chunk.as_ref())),
+                Some(Err(e)) => {
+                    done = true;
+                    pending.push_back(Err(e));
+                }
+                None => {
+                    done = true;
+                    pending.extend(decoder.finish());
+                }
+            }
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -238,4 +413,112 @@ mod tests {
         let response = client.generate("Say hello!").await;
         assert!(response.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_stream_yields_partial_text_before_error() {
+        let event = |text: &str| format!("data: {}\r\n\r\n", json!({ "candidates": [{ "content": { "parts": [{ "text": text }] } }] }));
+        let first = event("Hello, ");
+        // The second event is split across chunks, in the middle of a multi-byte character
+        let second = event("wörld");
+        let split = second.find('ö').unwrap() + 1;
+        let chunks: Vec<Result<Vec<u8>>> = vec![
+            Ok(first.into_bytes()),
+            Ok(second.as_bytes()[..split].to_vec()),
+            Ok(second.as_bytes()[split..].to_vec()),
+            Err(anyhow::anyhow!("connection reset")),
+        ];
+
+        let items: Vec<Result<String>> = sse_text_stream(futures::stream::iter(chunks)).collect().await;
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].as_ref().unwrap(), "Hello, ");
+        assert_eq!(items[1].as_ref().unwrap(), "wörld");
+        assert!(items[2].as_ref().unwrap_err().to_string().contains("connection reset"));
+
+        // A final event without the trailing blank line still counts
+        let unterminated: Vec<Result<Vec<u8>>> = vec![Ok(event("done").trim_end().as_bytes().to_vec())];
+        let items: Vec<Result<String>> = sse_text_stream(futures::stream::iter(unterminated)).collect().await;
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].as_ref().unwrap(), "done");
+    }
+
+    #[test]
+    fn test_generation_options_reach_request_body() {
+        let client = GeminiClient::new(LLMConfig {
+            api_key: "test-key".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        let messages = vec![Message {
+            role: Role::User,
+            content: "hi".to_string(),
+        }];
+
+        let body = client.build_request_body(messages.clone(), &GenerationOptions::default());
+        assert_eq!(body["generationConfig"]["temperature"].as_f64(), Some(0.7f32 as f64));
+        assert!(body["generationConfig"].get("maxOutputTokens").is_none());
+
+        let options = GenerationOptions::deterministic().with_max_tokens(256);
+        let body = client.build_request_body(messages, &options);
+        assert_eq!(body["generationConfig"]["temperature"].as_f64(), Some(0.0));
+        assert_eq!(body["generationConfig"]["maxOutputTokens"].as_u64(), Some(256));
+    }
+
+    #[test]
+    fn test_json_output_sets_mime_type_and_schema() {
+        let schema = json!({
+            "type": "OBJECT",
+            "properties": { "keep_indices": { "type": "ARRAY", "items": { "type": "INTEGER" } } },
+            "required": ["keep_indices"]
+        });
+        let options = GenerationOptions::deterministic().with_json_output(Some(schema.clone()));
+        let messages = vec![Message {
+            role: Role::User,
+            content: "Which items?".to_string(),
+        }];
+
+        let client = GeminiClient::new(LLMConfig {
+            api_key: "test-key".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        let body = client.build_request_body(messages.clone(), &options);
+        assert_eq!(body["generationConfig"]["responseMimeType"], "application/json");
+        assert_eq!(body["generationConfig"]["responseSchema"], schema);
+
+        // Gemini 1.0 has no JSON mode: the request stays plain text
+        let legacy = GeminiClient::new(LLMConfig {
+            api_key: "test-key".to_string(),
+            model: "gemini-pro".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        let body = legacy.build_request_body(messages, &options);
+        assert!(body["generationConfig"].get("responseMimeType").is_none());
+        assert!(body["generationConfig"].get("responseSchema").is_none());
+    }
+
+    #[test]
+    fn test_few_shot_turns_map_to_model_role() {
+        let client = GeminiClient::new(LLMConfig {
+            api_key: "test-key".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        let messages = crate::with_few_shot(
+            vec![
+                Message { role: Role::System, content: "Answer in JSON.".to_string() },
+                Message { role: Role::User, content: "Plan a login page".to_string() },
+            ],
+            &[crate::FewShotExample::new("Plan a settings page", r#"{"global_intent":"create_settings_page"}"#)],
+        );
+
+        let body = client.build_request_body(messages, &GenerationOptions::default());
+
+        assert_eq!(body["systemInstruction"]["parts"][0]["text"], "Answer in JSON.");
+        let contents = body["contents"].as_array().unwrap();
+        let roles: Vec<&str> = contents.iter().map(|c| c["role"].as_str().unwrap()).collect();
+        assert_eq!(roles, vec!["user", "model", "user"]);
+        assert_eq!(contents[1]["parts"][0]["text"], r#"{"global_intent":"create_settings_page"}"#);
+        assert_eq!(contents[2]["parts"][0]["text"], "Plan a login page");
+    }
 }
@@ -0,0 +1,172 @@
+//! Parsing JSON out of model responses. Models wrap JSON in markdown fences often enough that
+//! every caller strips them; when parsing still fails, the error carries the offending text so
+//! malformed output can be diagnosed from the logs alone. A response cut off at the token
+//! limit can still be partly salvaged: the complete elements before the cutoff are kept.
+
+use anyhow::{anyhow, Result};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tracing::warn;
+
+/// Characters of the raw response included in a parse error
+const ERROR_SNIPPET_CHARS: usize = 200;
+
+/// `response` with surrounding whitespace and markdown code fences removed
+pub fn strip_json_fences(response: &str) -> &str {
+    response
+        .trim()
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim()
+}
+
+/// Parse a model response as `T`, tolerating markdown fences. On failure the error names the
+/// line and column serde stopped at and includes a truncated snippet of the response.
+pub fn parse_llm_json<T: DeserializeOwned>(raw: &str) -> Result<T> {
+    let clean = strip_json_fences(raw);
+    serde_json::from_str(clean).map_err(|e| {
+        anyhow!(
+            "Invalid JSON in LLM response at line {}, column {}: {} (response: {:?})",
+            e.line(),
+            e.column(),
+            e,
+            snippet(clean)
+        )
+    })
+}
+
+/// Like `parse_llm_json`, but a response truncated mid-value gets a second chance through
+/// `salvage_truncated_json` before the original error is returned
+pub fn parse_llm_json_lenient<T: DeserializeOwned>(raw: &str) -> Result<T> {
+    let error = match parse_llm_json(raw) {
+        Ok(value) => return Ok(value),
+        Err(e) => e,
+    };
+    let Some(salvaged) = salvage_truncated_json(raw) else {
+        return Err(error);
+    };
+    match serde_json::from_value(salvaged) {
+        Ok(value) => {
+            warn!("{:#}; recovered the complete elements before the cutoff", error);
+            Ok(value)
+        }
+        Err(_) => Err(error),
+    }
+}
+
+/// The complete part of a truncated JSON object or array: everything up to the last complete
+/// element or member, with the containers still open at that point closed. `None` when no
+/// element completed before the cutoff.
+pub fn salvage_truncated_json(raw: &str) -> Option<Value> {
+    // Prose that merely mentions a bracket isn't a cut-off JSON answer
+    let text = strip_json_fences(raw);
+    if !text.starts_with(['{', '[']) {
+        return None;
+    }
+
+    // Points right after a complete element, with the closers its open containers need.
+    // Only the outermost object may be left incomplete: a shorter array is still a valid
+    // list, but a nested object cut short would be missing fields.
+    let mut cuts: Vec<(usize, String)> = Vec::new();
+    let mut cut_at = |end: usize, open: &[char]| {
+        if !open[1..].contains(&'}') {
+            cuts.push((end, open.iter().rev().collect()));
+        }
+    };
+    let mut open: Vec<char> = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' => open.push('}'),
+            '[' => open.push(']'),
+            '}' | ']' => {
+                open.pop();
+                if open.is_empty() {
+                    // The outermost value is complete; nothing was cut off
+                    return serde_json::from_str(&text[..=i]).ok();
+                }
+                cut_at(i + 1, &open);
+            }
+            ',' if !open.is_empty() => cut_at(i, &open),
+            _ => {}
+        }
+    }
+
+    cuts.into_iter()
+        .rev()
+        .find_map(|(end, closers)| serde_json::from_str(&format!("{}{}", &text[..end], closers)).ok())
+}
+
+fn snippet(text: &str) -> String {
+    match text.char_indices().nth(ERROR_SNIPPET_CHARS) {
+        Some((cut, _)) => format!("{}…", &text[..cut]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Decision {
+        keep: Vec<usize>,
+    }
+
+    #[test]
+    fn test_fenced_json_parses() {
+        let decision: Decision = parse_llm_json("```json\n{\"keep\": [0, 2]}\n```").unwrap();
+        assert_eq!(decision.keep, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_array_truncated_mid_third_element_keeps_first_two() {
+        let raw = r#"```json
+[
+  {"file_path": "src/a.ts", "content": "export const a = [1, 2];"},
+  {"file_path": "src/b.ts", "content": "export const b = \"}\";"},
+  {"file_path": "src/c.ts", "content": "export const c = "#;
+        assert!(parse_llm_json::<Value>(raw).is_err());
+
+        let salvaged = salvage_truncated_json(raw).unwrap();
+        let paths: Vec<&str> = salvaged
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["file_path"].as_str().unwrap())
+            .collect();
+        assert_eq!(paths, vec!["src/a.ts", "src/b.ts"]);
+
+        // Nested inside an object, the complete members are kept too
+        let decision: Decision = parse_llm_json_lenient(r#"{"keep": [0, 2, 5"#).unwrap();
+        assert_eq!(decision.keep, vec![0, 2]);
+        assert!(salvage_truncated_json(r#"[{"file_path": "src/a"#).is_none());
+        assert!(salvage_truncated_json("Use `[1, 2]` here").is_none());
+    }
+
+    #[test]
+    fn test_parse_error_includes_snippet_and_position() {
+        let raw = "Sure! Here is the plan: {\"keep\": [0, 2]}";
+        let err = parse_llm_json::<Decision>(raw).unwrap_err().to_string();
+        assert!(err.contains("line 1, column 1"), "{}", err);
+        assert!(err.contains("Sure! Here is the plan"), "{}", err);
+
+        let long = format!("{{\"keep\": [{}", "1, ".repeat(500));
+        let err = parse_llm_json::<Decision>(&long).unwrap_err().to_string();
+        assert!(err.contains('…'));
+        assert!(err.len() < long.len());
+    }
+}
@@ -1,29 +1,157 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
+use miow_common::MiowError;
+use miow_prompt::{CharRatioEstimator, TokenEstimator};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::sync::Arc;
 
+mod anthropic;
 mod gemini;
+pub mod json;
 mod openai;
 pub mod question_loop;
 pub mod cache;
+pub mod retry;
 
+pub use anthropic::AnthropicClient;
 pub use gemini::GeminiClient;
 pub use openai::OpenAIClient;
 pub use question_loop::*;
 pub use cache::LLMCache;
+pub use retry::{ProviderHttpError, RetryPolicy, RetryingLLMProvider};
+pub use json::{parse_llm_json, parse_llm_json_lenient, salvage_truncated_json, strip_json_fences};
 
 /// LLM provider trait
 #[async_trait]
 pub trait LLMProvider: Send + Sync {
     async fn generate(&self, prompt: &str) -> Result<LLMResponse>;
     async fn generate_with_context(&self, messages: Vec<Message>) -> Result<LLMResponse>;
+    /// Like `generate_with_context`, but with per-call overrides (e.g. temperature 0 for JSON output).
+    /// Providers that don't support overrides fall back to their configured defaults.
+    async fn generate_with_options(
+        &self,
+        messages: Vec<Message>,
+        options: GenerationOptions,
+    ) -> Result<LLMResponse> {
+        let _ = options;
+        self.generate_with_context(messages).await
+    }
     async fn stream_generate(
         &self,
         prompt: &str,
     ) -> Result<Box<dyn futures::Stream<Item = Result<String>> + Unpin>>;
     async fn generate_multi_step(&self, steps: Vec<String>, context: &str) -> Result<LLMResponse>;
     async fn generate_with_framework(&self, prompt: &str, framework: &str, lang: &str) -> Result<LLMResponse>;
+    /// Verify the API key and model work with a tiny request, so a run fails up front
+    /// instead of mid-pipeline.
+    async fn health_check(&self) -> Result<()> {
+        let options = GenerationOptions::deterministic().with_max_tokens(HEALTH_CHECK_MAX_TOKENS);
+        let response = self
+            .generate_with_options(health_check_messages(), options)
+            .await
+            .context("LLM health check request failed")?;
+        ensure_healthy_response(&response)
+    }
+    /// `generate_with_options`, but fails with `MiowError::Llm` before any network call when
+    /// the messages are estimated to exceed the model's input window.
+    async fn generate_within_limits(
+        &self,
+        messages: Vec<Message>,
+        options: GenerationOptions,
+        limits: &ModelLimits,
+    ) -> Result<LLMResponse> {
+        check_input_limits(&messages, limits)?;
+        self.generate_with_options(messages, options).await
+    }
+}
+
+/// Token windows of a model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelLimits {
+    pub max_input_tokens: usize,
+    pub max_output_tokens: usize,
+}
+
+impl ModelLimits {
+    pub fn new(max_input_tokens: usize, max_output_tokens: usize) -> Self {
+        Self {
+            max_input_tokens,
+            max_output_tokens,
+        }
+    }
+
+    /// Gemini's 1M-token window for Gemini 1.5+ models and Claude's 200k window; otherwise a
+    /// conservative 128k/4k, which matches GPT-4o.
+    pub fn for_model(model: &str) -> Self {
+        if model.starts_with("gemini-1.5") || model.starts_with("gemini-2") {
+            Self::new(1_048_576, 8_192)
+        } else if model.starts_with("claude") {
+            Self::new(200_000, 8_192)
+        } else {
+            Self::new(128_000, 4_096)
+        }
+    }
+}
+
+/// Framing tokens each message costs on top of its content (role markers, separators)
+const MESSAGE_OVERHEAD_TOKENS: usize = 4;
+
+/// Estimated input tokens of `messages`, using the default character-ratio estimator
+pub fn estimate_tokens(messages: &[Message]) -> usize {
+    estimate_tokens_with(messages, &CharRatioEstimator::default())
+}
+
+/// Estimated input tokens of `messages` under a custom estimator
+pub fn estimate_tokens_with(messages: &[Message], estimator: &dyn TokenEstimator) -> usize {
+    messages
+        .iter()
+        .map(|m| estimator.estimate(&m.content) + MESSAGE_OVERHEAD_TOKENS)
+        .sum()
+}
+
+/// Reject `messages` that won't fit the input window of `limits`
+pub fn check_input_limits(messages: &[Message], limits: &ModelLimits) -> Result<()> {
+    let estimated = estimate_tokens(messages);
+    if estimated > limits.max_input_tokens {
+        return Err(MiowError::Llm(format!(
+            "input exceeds {} tokens (estimated {} tokens across {} messages)",
+            limits.max_input_tokens,
+            estimated,
+            messages.len()
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// Output cap for the default health check request
+pub const HEALTH_CHECK_MAX_TOKENS: usize = 16;
+
+/// The "reply OK" request sent by `LLMProvider::health_check`
+pub fn health_check_messages() -> Vec<Message> {
+    vec![Message {
+        role: Role::User,
+        content: "Reply with OK.".to_string(),
+    }]
+}
+
+pub(crate) fn ensure_healthy_response(response: &LLMResponse) -> Result<()> {
+    if response.content.trim().is_empty() {
+        anyhow::bail!("LLM health check returned an empty response");
+    }
+    Ok(())
+}
+
+/// First provider whose health check passes, for picking a live primary among fallbacks.
+pub async fn first_healthy(providers: &[Arc<dyn LLMProvider>]) -> Option<Arc<dyn LLMProvider>> {
+    for provider in providers {
+        match provider.health_check().await {
+            Ok(()) => return Some(provider.clone()),
+            Err(e) => tracing::warn!("Skipping unhealthy LLM provider: {:#}", e),
+        }
+    }
+    None
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -53,6 +181,47 @@ pub enum Role {
     Assistant,
 }
 
+/// A user request paired with an ideal assistant answer, used to prime a provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FewShotExample {
+    pub user: String,
+    pub assistant: String,
+}
+
+impl FewShotExample {
+    pub fn new(user: impl Into<String>, assistant: impl Into<String>) -> Self {
+        Self {
+            user: user.into(),
+            assistant: assistant.into(),
+        }
+    }
+}
+
+/// Insert few-shot user/assistant pairs after the leading system messages, so the
+/// real request follows the examples.
+pub fn with_few_shot(messages: Vec<Message>, examples: &[FewShotExample]) -> Vec<Message> {
+    let split = messages
+        .iter()
+        .position(|m| !matches!(m.role, Role::System))
+        .unwrap_or(messages.len());
+
+    let mut out = Vec::with_capacity(messages.len() + examples.len() * 2);
+    let mut rest = messages.into_iter();
+    out.extend(rest.by_ref().take(split));
+    for example in examples {
+        out.push(Message {
+            role: Role::User,
+            content: example.user.clone(),
+        });
+        out.push(Message {
+            role: Role::Assistant,
+            content: example.assistant.clone(),
+        });
+    }
+    out.extend(rest);
+    out
+}
+
 #[derive(Debug, Clone)]
 pub struct LLMConfig {
     pub api_key: String,
@@ -61,6 +230,46 @@ pub struct LLMConfig {
     pub max_tokens: usize,
 }
 
+/// Per-call overrides for a single generation request.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GenerationOptions {
+    pub temperature: Option<f32>,
+    pub max_tokens: Option<usize>,
+    /// Ask for a JSON response. Providers with a JSON mode constrain the output to JSON;
+    /// others generate text as usual, so callers still parse with `parse_llm_json`.
+    pub json_output: bool,
+    /// Schema the JSON response must match, where supported (Gemini `responseSchema`, an
+    /// OpenAPI-style subset: `type`, `properties`, `items`, `enum`, `required`)
+    pub json_schema: Option<serde_json::Value>,
+}
+
+impl GenerationOptions {
+    /// Temperature 0, for calls whose output is parsed as JSON.
+    pub fn deterministic() -> Self {
+        Self {
+            temperature: Some(0.0),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    pub fn with_max_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Request JSON output, constrained to `schema` when given
+    pub fn with_json_output(mut self, schema: Option<serde_json::Value>) -> Self {
+        self.json_output = true;
+        self.json_schema = schema;
+        self
+    }
+}
+
 impl Default for LLMConfig {
     fn default() -> Self {
         Self {
@@ -108,7 +317,7 @@ Respond in JSON format:
         ];
 
         let response = self.provider.generate_with_context(messages).await?;
-        let analysis: IntentAnalysis = serde_json::from_str(&response.content)?;
+        let analysis: IntentAnalysis = parse_llm_json(&response.content)?;
 
         Ok(analysis)
     }
@@ -135,7 +344,7 @@ Respond with a JSON array of strings."#,
         );
 
         let response = self.provider.generate(&system_prompt).await?;
-        let queries: Vec<String> = serde_json::from_str(&response.content)?;
+        let queries: Vec<String> = parse_llm_json(&response.content)?;
 
         Ok(queries)
     }
@@ -186,7 +395,7 @@ pub struct IntentAnalysis {
     pub questions: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GatheredContext {
     pub components: Vec<ContextItem>,
     pub helpers: Vec<ContextItem>,
@@ -208,4 +417,103 @@ pub struct ContextItem {
     pub props: Vec<String>,
     #[serde(default)]
     pub references: Vec<String>,
+    /// Markers such as "summarized" describing how the content was transformed
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Replies with a fixed text, or fails like a provider with a bad API key.
+    struct StubProvider {
+        reply: Option<&'static str>,
+    }
+
+    #[async_trait]
+    impl LLMProvider for StubProvider {
+        async fn generate(&self, _prompt: &str) -> Result<LLMResponse> {
+            match self.reply {
+                Some(content) => Ok(LLMResponse {
+                    content: content.to_string(),
+                    finish_reason: Some("stop".to_string()),
+                    usage: None,
+                }),
+                None => anyhow::bail!("401 Unauthorized: invalid API key"),
+            }
+        }
+
+        async fn generate_with_context(&self, _messages: Vec<Message>) -> Result<LLMResponse> {
+            self.generate("").await
+        }
+
+        async fn stream_generate(
+            &self,
+            _prompt: &str,
+        ) -> Result<Box<dyn futures::Stream<Item = Result<String>> + Unpin>> {
+            Ok(Box::new(futures::stream::empty()))
+        }
+
+        async fn generate_multi_step(&self, _steps: Vec<String>, _context: &str) -> Result<LLMResponse> {
+            self.generate("").await
+        }
+
+        async fn generate_with_framework(&self, _prompt: &str, _framework: &str, _lang: &str) -> Result<LLMResponse> {
+            self.generate("").await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health_check_passes_and_fails() {
+        assert!(StubProvider { reply: Some("OK") }.health_check().await.is_ok());
+        assert!(StubProvider { reply: Some("  ") }.health_check().await.is_err());
+
+        let err = StubProvider { reply: None }.health_check().await.unwrap_err();
+        assert!(format!("{:#}", err).contains("invalid API key"));
+    }
+
+    #[tokio::test]
+    async fn test_oversized_input_rejected_before_sending() {
+        let messages = vec![
+            Message {
+                role: Role::System,
+                content: "You are a code assistant.".to_string(),
+            },
+            Message {
+                role: Role::User,
+                content: "x".repeat(400),
+            },
+        ];
+        assert_eq!(estimate_tokens(&messages), 6 + 100 + 2 * MESSAGE_OVERHEAD_TOKENS);
+
+        // A provider with a bad key proves the guard fails before any request is made
+        let provider = StubProvider { reply: None };
+        let limits = ModelLimits::new(64, 16);
+        let err = provider
+            .generate_within_limits(messages.clone(), GenerationOptions::default(), &limits)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<MiowError>(),
+            Some(MiowError::Llm(message)) if message.starts_with("input exceeds 64 tokens")
+        ));
+
+        let roomy = ModelLimits::new(1_000, 16);
+        let err = provider
+            .generate_within_limits(messages, GenerationOptions::default(), &roomy)
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("invalid API key"));
+    }
+
+    #[tokio::test]
+    async fn test_first_healthy_skips_dead_providers() {
+        let dead: Arc<dyn LLMProvider> = Arc::new(StubProvider { reply: None });
+        let live: Arc<dyn LLMProvider> = Arc::new(StubProvider { reply: Some("OK") });
+
+        let picked = first_healthy(&[dead.clone(), live.clone()]).await.unwrap();
+        assert!(Arc::ptr_eq(&picked, &live));
+        assert!(first_healthy(&[dead]).await.is_none());
+    }
 }
@@ -4,10 +4,16 @@ use async_trait::async_trait;
 use reqwest::Client;
 use serde_json::json;
 
+/// Chat completions API root, overridable for compatible servers
+const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+
+/// Calls fail on the first error; wrap the client in a `RetryingLLMProvider` to retry
+/// rate limits and transient server errors.
 pub struct OpenAIClient {
     client: Client,
     api_key: String,
     model: String,
+    base_url: String,
 }
 
 impl OpenAIClient {
@@ -16,6 +22,7 @@ impl OpenAIClient {
             client: Client::new(),
             api_key,
             model: "gpt-4-turbo-preview".to_string(),
+            base_url: DEFAULT_BASE_URL.to_string(),
         }
     }
 
@@ -23,21 +30,13 @@ impl OpenAIClient {
         self.model = model;
         self
     }
-}
 
-#[async_trait]
-impl LLMProvider for OpenAIClient {
-    async fn generate(&self, prompt: &str) -> Result<LLMResponse> {
-        let messages = vec![Message {
-            role: Role::User,
-            content: prompt.to_string(),
-        }];
-        self.generate_with_context(messages).await
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url;
+        self
     }
 
-    async fn generate_with_context(&self, messages: Vec<Message>) -> Result<LLMResponse> {
-        let url = "https://api.openai.com/v1/chat/completions";
-
+    fn build_request_body(&self, messages: Vec<Message>, options: &GenerationOptions) -> serde_json::Value {
         let openai_messages: Vec<serde_json::Value> = messages
             .into_iter()
             .map(|msg| {
@@ -53,12 +52,41 @@ impl LLMProvider for OpenAIClient {
             })
             .collect();
 
-        let body = json!({
+        let mut body = json!({
             "model": self.model,
             "messages": openai_messages,
-            "temperature": 0.7,
-            "max_tokens": 4096,
+            "temperature": options.temperature.unwrap_or(0.7),
+            "max_tokens": options.max_tokens.unwrap_or(4096),
         });
+        if options.json_output {
+            body["response_format"] = json!({ "type": "json_object" });
+        }
+        body
+    }
+}
+
+#[async_trait]
+impl LLMProvider for OpenAIClient {
+    async fn generate(&self, prompt: &str) -> Result<LLMResponse> {
+        let messages = vec![Message {
+            role: Role::User,
+            content: prompt.to_string(),
+        }];
+        self.generate_with_context(messages).await
+    }
+
+    async fn generate_with_context(&self, messages: Vec<Message>) -> Result<LLMResponse> {
+        self.generate_with_options(messages, GenerationOptions::default()).await
+    }
+
+    async fn generate_with_options(
+        &self,
+        messages: Vec<Message>,
+        options: GenerationOptions,
+    ) -> Result<LLMResponse> {
+        let url = format!("{}/chat/completions", self.base_url.trim_end_matches('/'));
+
+        let body = self.build_request_body(messages, &options);
 
         let response = self
             .client
@@ -68,6 +96,16 @@ impl LLMProvider for OpenAIClient {
             .send()
             .await?;
 
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ProviderHttpError {
+                provider: "OpenAI",
+                status: status.as_u16(),
+                body: response.text().await.unwrap_or_default(),
+            }
+            .into());
+        }
+
         let json: serde_json::Value = response.json().await?;
 
         let content = json["choices"][0]["message"]["content"]
@@ -122,3 +160,48 @@ impl LLMProvider for OpenAIClient {
         self.generate(&enhanced_prompt).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generation_options_reach_request_body() {
+        let client = OpenAIClient::new("test-key".to_string());
+        let messages = vec![Message {
+            role: Role::User,
+            content: "hi".to_string(),
+        }];
+
+        let body = client.build_request_body(messages.clone(), &GenerationOptions::default());
+        assert_eq!(body["temperature"].as_f64(), Some(0.7f32 as f64));
+        assert_eq!(body["max_tokens"].as_u64(), Some(4096));
+
+        let body = client.build_request_body(messages, &GenerationOptions::deterministic());
+        assert_eq!(body["temperature"].as_f64(), Some(0.0));
+    }
+
+    #[test]
+    fn test_few_shot_turns_keep_assistant_role() {
+        let client = OpenAIClient::new("test-key".to_string());
+        let messages = crate::with_few_shot(
+            vec![
+                Message { role: Role::System, content: "Answer in JSON.".to_string() },
+                Message { role: Role::User, content: "Plan a login page".to_string() },
+            ],
+            &[crate::FewShotExample::new("Plan a settings page", r#"{"global_intent":"create_settings_page"}"#)],
+        );
+
+        let body = client.build_request_body(messages, &GenerationOptions::default());
+
+        let roles: Vec<&str> = body["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["role"].as_str().unwrap())
+            .collect();
+        assert_eq!(roles, vec!["system", "user", "assistant", "user"]);
+        assert_eq!(body["messages"][2]["content"], r#"{"global_intent":"create_settings_page"}"#);
+        assert_eq!(body["messages"][3]["content"], "Plan a login page");
+    }
+}
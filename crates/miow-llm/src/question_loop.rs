@@ -2,10 +2,25 @@ use anyhow::{Context, Result};
 use miow_graph::{KnowledgeGraph, SymbolSearchResult};
 use miow_vector::{VectorStore, SymbolSearchResult as VectorResult};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
 use tracing::{debug, info, warn};
 
-use crate::{LLMProvider, Message, Role};
+use crate::{parse_llm_json, LLMProvider, Message, Role};
+
+/// Result count for the first, strict vector query.
+const STRICT_VECTOR_LIMIT: usize = 10;
+/// Result count for the relaxed retry when the strict query finds nothing.
+const RELAXED_VECTOR_LIMIT: usize = 25;
+
+/// Filler words dropped from a query when relaxing it to its keywords.
+const RELAXED_STOP_WORDS: &[&str] = &[
+    "a", "an", "the", "is", "are", "there", "any", "for", "of", "to", "in", "on", "with",
+    "that", "which", "existing", "component", "components", "function", "functions", "struct",
+    "type", "types", "class", "interface", "schema", "hook", "helper", "constant",
+];
 
 /// Critical question for context gathering
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,12 +61,58 @@ pub struct QuestionAnswer {
     pub confidence: f32,
 }
 
+/// Search results and verifications of one run, keyed by normalized query, so a query repeated
+/// by several questions or retries doesn't re-hit the vector store and the LLM verifier
+#[derive(Debug, Default)]
+struct QueryCache {
+    searches: RwLock<HashMap<String, Vec<SymbolSearchResult>>>,
+    /// Keyed by (question, normalized query): the results for a query are cached, so the
+    /// verdict on them only depends on which question they answer
+    verifications: RwLock<HashMap<(String, String), VerificationResult>>,
+    hits: AtomicUsize,
+}
+
+impl QueryCache {
+    fn search(&self, query: &str) -> Option<Vec<SymbolSearchResult>> {
+        let cached = self.searches.read().unwrap().get(&normalize_query(query)).cloned();
+        self.count_hit(cached)
+    }
+
+    fn insert_search(&self, query: &str, results: &[SymbolSearchResult]) {
+        self.searches.write().unwrap().insert(normalize_query(query), results.to_vec());
+    }
+
+    fn verification(&self, question: &CriticalQuestion) -> Option<VerificationResult> {
+        let cached = self.verifications.read().unwrap().get(&Self::verification_key(question)).cloned();
+        self.count_hit(cached)
+    }
+
+    fn insert_verification(&self, question: &CriticalQuestion, verification: &VerificationResult) {
+        self.verifications
+            .write()
+            .unwrap()
+            .insert(Self::verification_key(question), verification.clone());
+    }
+
+    fn verification_key(question: &CriticalQuestion) -> (String, String) {
+        (question.question.clone(), normalize_query(&question.search_query))
+    }
+
+    fn count_hit<T>(&self, cached: Option<T>) -> Option<T> {
+        if cached.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        }
+        cached
+    }
+}
+
 /// Question loop executor with rollback capability
 pub struct QuestionLoop {
     llm: Arc<dyn LLMProvider>,
     vector_store: Option<Arc<VectorStore>>,
     graph: Arc<KnowledgeGraph>,
     max_retries: usize,
+    cache: QueryCache,
 }
 
 impl QuestionLoop {
@@ -65,8 +126,14 @@ impl QuestionLoop {
             vector_store,
             graph,
             max_retries: 3,
+            cache: QueryCache::default(),
         }
     }
+
+    /// How many searches and verifications were answered from the per-run cache
+    pub fn cache_hits(&self) -> usize {
+        self.cache.hits.load(Ordering::Relaxed)
+    }
     
     /// Execute all questions and gather verified context
     pub async fn execute_questions(
@@ -175,15 +242,23 @@ impl QuestionLoop {
         Ok(QuestionResult::NotFound)
     }
     
-    /// Search for symbols using vector store and/or knowledge graph
+    /// Search for symbols using vector store and/or knowledge graph, once per normalized query
     async fn search(&self, query: &str) -> Result<Vec<SymbolSearchResult>> {
+        if let Some(cached) = self.cache.search(query) {
+            info!("   [CACHE] Reusing {} results for '{}'", cached.len(), query);
+            return Ok(cached);
+        }
+
         let mut results = Vec::new();
         
         // Try vector search first if available (semantic understanding like Cursor)
         if let Some(vector_store) = &self.vector_store {
             info!("   [VECTOR_SEARCH] Searching for: '{}'", query);
             let vector_start = std::time::Instant::now();
-            match vector_store.search_similar(query, 10).await {
+            let vector_search = vector_search_with_relaxation(query, |q, limit| async move {
+                vector_store.search_similar(&q, limit).await
+            });
+            match vector_search.await {
                 Ok(vector_results) => {
                     let vector_duration = vector_start.elapsed();
                     info!("   [VECTOR_SEARCH] Found {} results in {:?}", vector_results.len(), vector_duration);
@@ -217,6 +292,7 @@ impl QuestionLoop {
             }
         }
         
+        self.cache.insert_search(query, &results);
         Ok(results)
     }
     
@@ -226,6 +302,11 @@ impl QuestionLoop {
         question: &CriticalQuestion,
         results: &[SymbolSearchResult],
     ) -> Result<VerificationResult> {
+        if let Some(cached) = self.cache.verification(question) {
+            info!("   [CACHE] Reusing verification for '{}'", question.search_query);
+            return Ok(cached);
+        }
+
         let results_summary: Vec<String> = results
             .iter()
             .take(5)
@@ -261,21 +342,17 @@ Return ONLY the JSON."#,
         let llm_duration = llm_start.elapsed();
         info!("   [LLM] Response received in {:?} ({} chars)", llm_duration, response.content.len());
         
-        // Parse JSON response
-        let clean = response.content
-            .trim()
-            .trim_start_matches("```json")
-            .trim_start_matches("```")
-            .trim_end_matches("```")
-            .trim();
-        
-        let verification: VerificationResult = serde_json::from_str(clean)
-            .unwrap_or(VerificationResult {
-                is_correct: !results.is_empty(),
-                reason: "Failed to parse verification response".to_string(),
-                suggestion: None,
+        let verification: VerificationResult = parse_llm_json(&response.content)
+            .unwrap_or_else(|e| {
+                warn!("   [LLM] {:#}", e);
+                VerificationResult {
+                    is_correct: !results.is_empty(),
+                    reason: "Failed to parse verification response".to_string(),
+                    suggestion: None,
+                }
             });
         
+        self.cache.insert_verification(question, &verification);
         Ok(verification)
     }
     
@@ -309,21 +386,17 @@ Return ONLY the JSON."#,
         let reformulate_duration = reformulate_start.elapsed();
         info!("   [LLM] Reformulation response received in {:?}", reformulate_duration);
         
-        let clean = response.content
-            .trim()
-            .trim_start_matches("```json")
-            .trim_start_matches("```")
-            .trim_end_matches("```")
-            .trim();
-        
-        if let Ok(json) = serde_json::from_str::<serde_json::Value>(clean) {
-            if let Some(new_query) = json["new_query"].as_str() {
-                debug!("🔄 Reformulated: '{}' → '{}'", question.search_query, new_query);
-                return Ok(CriticalQuestion {
-                    search_query: new_query.to_string(),
-                    ..question
-                });
+        match parse_llm_json::<serde_json::Value>(&response.content) {
+            Ok(json) => {
+                if let Some(new_query) = json["new_query"].as_str() {
+                    debug!("🔄 Reformulated: '{}' → '{}'", question.search_query, new_query);
+                    return Ok(CriticalQuestion {
+                        search_query: new_query.to_string(),
+                        ..question
+                    });
+                }
             }
+            Err(e) => warn!("   [LLM] Reformulation: {:#}", e),
         }
         
         // Fallback: Try common variations
@@ -340,6 +413,43 @@ Return ONLY the JSON."#,
     }
 }
 
+/// Run a strict vector query and, if it finds nothing, a relaxed keyword-only
+/// query with a larger result count before giving up on semantic search.
+async fn vector_search_with_relaxation<F, Fut>(query: &str, search: F) -> Result<Vec<VectorResult>>
+where
+    F: Fn(String, usize) -> Fut,
+    Fut: Future<Output = Result<Vec<VectorResult>>>,
+{
+    let strict = search(query.to_string(), STRICT_VECTOR_LIMIT).await?;
+    if !strict.is_empty() {
+        return Ok(strict);
+    }
+
+    let relaxed = relax_query(query);
+    info!("   [VECTOR_SEARCH] No hits, retrying relaxed query: '{}'", relaxed);
+    search(relaxed, RELAXED_VECTOR_LIMIT).await
+}
+
+/// Cache key for a query: lowercased with whitespace collapsed
+fn normalize_query(query: &str) -> String {
+    query.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Reduce a query to its distinctive keywords, e.g. "Is there a UserProfile component?" → "UserProfile".
+fn relax_query(query: &str) -> String {
+    let keywords: Vec<&str> = query
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|word| !word.is_empty())
+        .filter(|word| !RELAXED_STOP_WORDS.contains(&word.to_lowercase().as_str()))
+        .collect();
+
+    if keywords.is_empty() {
+        query.trim().to_string()
+    } else {
+        keywords.join(" ")
+    }
+}
+
 /// Generate language-specific critical questions
 pub async fn generate_critical_questions(
     llm: &dyn LLMProvider,
@@ -384,14 +494,7 @@ Return ONLY the JSON array."#,
     
     let response = llm.generate(&prompt).await?;
     
-    let clean = response.content
-        .trim()
-        .trim_start_matches("```json")
-        .trim_start_matches("```")
-        .trim_end_matches("```")
-        .trim();
-    
-    let questions: Vec<serde_json::Value> = serde_json::from_str(clean)
+    let questions: Vec<serde_json::Value> = parse_llm_json(&response.content)
         .context("Failed to parse questions from LLM")?;
     
     let mut critical_questions = Vec::new();
@@ -436,4 +539,134 @@ mod tests {
         
         assert_eq!(q.priority, Priority::Critical);
     }
+
+    #[test]
+    fn test_relax_query() {
+        assert_eq!(relax_query("Is there a UserProfile component?"), "UserProfile");
+        assert_eq!(relax_query("auth_guard function for routes"), "auth_guard routes");
+        assert_eq!(relax_query("the component"), "the component");
+    }
+
+    #[tokio::test]
+    async fn test_relaxed_vector_search_after_empty_strict_query() {
+        let calls = std::sync::Mutex::new(Vec::new());
+        let results = vector_search_with_relaxation("Is there a UserProfile component", |q, limit| {
+            calls.lock().unwrap().push((q.clone(), limit));
+            async move {
+                if q == "UserProfile" {
+                    Ok(vec![VectorResult {
+                        symbol: miow_vector::SymbolVector {
+                            id: "1".to_string(),
+                            name: "UserProfile".to_string(),
+                            kind: "component".to_string(),
+                            content: "export function UserProfile() {}".to_string(),
+                            file_path: "src/UserProfile.tsx".to_string(),
+                            metadata: "{}".to_string(),
+                            embed_text: None,
+                        },
+                        score: 0.4,
+                    }])
+                } else {
+                    Ok(vec![])
+                }
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].symbol.name, "UserProfile");
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].1, STRICT_VECTOR_LIMIT);
+        assert_eq!(calls[1], ("UserProfile".to_string(), RELAXED_VECTOR_LIMIT));
+    }
+
+    struct CountingVerifier {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl LLMProvider for CountingVerifier {
+        async fn generate(&self, _prompt: &str) -> Result<crate::LLMResponse> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(crate::LLMResponse {
+                content: r#"{"is_correct": true, "reason": "matches"}"#.to_string(),
+                finish_reason: Some("stop".to_string()),
+                usage: None,
+            })
+        }
+
+        async fn generate_with_context(&self, _messages: Vec<crate::Message>) -> Result<crate::LLMResponse> {
+            self.generate("").await
+        }
+
+        async fn stream_generate(
+            &self,
+            _prompt: &str,
+        ) -> Result<Box<dyn futures::Stream<Item = Result<String>> + Unpin>> {
+            Ok(Box::new(futures::stream::empty()))
+        }
+
+        async fn generate_multi_step(&self, _steps: Vec<String>, _context: &str) -> Result<crate::LLMResponse> {
+            self.generate("").await
+        }
+
+        async fn generate_with_framework(&self, _prompt: &str, _framework: &str, _lang: &str) -> Result<crate::LLMResponse> {
+            self.generate("").await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repeated_query_hits_cache() {
+        let graph = KnowledgeGraph::in_memory().unwrap();
+        graph
+            .insert_file(
+                "src/components/Button.tsx",
+                &miow_graph::ParsedFileData {
+                    symbols: vec![miow_graph::SymbolData {
+                        name: "Button".to_string(),
+                        kind: "Component".to_string(),
+                        start_line: 1,
+                        end_line: 1,
+                        start_byte: 0,
+                        end_byte: 30,
+                        content: "export function Button() {}".to_string(),
+                        metadata: "{}".to_string(),
+                        style_tags: None,
+                        children: vec![],
+                        references: vec![],
+                        qualified_name: None,
+                    }],
+                    imports: vec![],
+                    design_tokens: vec![],
+                    type_definitions: vec![],
+                    constants: vec![],
+                    schemas: vec![],
+                    language: "tsx".to_string(),
+                    exports: vec![],
+                    tests: vec![],
+                },
+            )
+            .unwrap();
+        let llm = Arc::new(CountingVerifier { calls: AtomicUsize::new(0) });
+        let question_loop = QuestionLoop::new(llm.clone(), None, Arc::new(graph));
+
+        let question = |search_query: &str| CriticalQuestion {
+            question: "Is there a Button component?".to_string(),
+            search_query: search_query.to_string(),
+            expected_type: "component".to_string(),
+            priority: Priority::Critical,
+        };
+        let answers = question_loop
+            .execute_questions(vec![question("Button"), question("  button ")])
+            .await
+            .unwrap();
+
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers[1].symbols[0].name, "Button");
+        // The second question reuses both the search results and the verification
+        assert_eq!(question_loop.cache_hits(), 2);
+        assert_eq!(llm.calls.load(Ordering::SeqCst), 1);
+    }
 }
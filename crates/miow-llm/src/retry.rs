@@ -0,0 +1,242 @@
+//! Retrying transient provider failures the same way for every provider: rate limits, 5xx
+//! gateway errors and timeouts are retried with exponential backoff, anything else (bad
+//! key, invalid request) fails right away.
+
+use crate::{GenerationOptions, LLMProvider, LLMResponse, Message};
+use anyhow::Result;
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::time::{sleep, Duration};
+use tracing::{error, warn};
+
+/// HTTP statuses worth retrying: rate limiting and transient server or gateway failures
+const RETRYABLE_STATUSES: [u16; 5] = [429, 500, 502, 503, 504];
+
+/// A non-success HTTP response from a provider's API
+#[derive(Debug, thiserror::Error)]
+#[error("{provider} API error ({status}): {body}")]
+pub struct ProviderHttpError {
+    pub provider: &'static str,
+    pub status: u16,
+    pub body: String,
+}
+
+/// How often and how patiently failed calls are retried
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each one after
+    pub base_delay: Duration,
+    /// Upper bound of the random delay added to each backoff, so clients don't retry in step
+    pub max_jitter: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            base_delay: Duration::from_secs(2),
+            max_jitter: Duration::from_secs(1),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: u32, base_delay: Duration) -> Self {
+        Self {
+            max_retries,
+            base_delay,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_max_jitter(mut self, max_jitter: Duration) -> Self {
+        self.max_jitter = max_jitter;
+        self
+    }
+
+    /// Whether a response with this HTTP status may succeed when sent again
+    pub fn is_retryable(&self, status: u16) -> bool {
+        RETRYABLE_STATUSES.contains(&status)
+    }
+
+    /// Whether a failed call may succeed when made again: a retryable HTTP status, or a
+    /// request that timed out or couldn't connect
+    pub fn is_retryable_error(&self, error: &anyhow::Error) -> bool {
+        error.chain().any(|cause| {
+            if let Some(http) = cause.downcast_ref::<ProviderHttpError>() {
+                return self.is_retryable(http.status);
+            }
+            if let Some(request) = cause.downcast_ref::<reqwest::Error>() {
+                return request.is_timeout()
+                    || request.is_connect()
+                    || request.status().is_some_and(|status| self.is_retryable(status.as_u16()));
+            }
+            false
+        })
+    }
+
+    /// Backoff before retry number `retry` (1-based), jitter included
+    pub fn delay(&self, retry: u32) -> Duration {
+        let backoff = self.base_delay * 2u32.saturating_pow(retry.saturating_sub(1));
+        let jitter_ms = self.max_jitter.as_millis() as u64;
+        if jitter_ms == 0 {
+            return backoff;
+        }
+        let seed = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos() as u64;
+        backoff + Duration::from_millis(seed % jitter_ms)
+    }
+
+    /// Run `call` until it succeeds, fails with a non-retryable error, or retries run out
+    pub async fn run<T, F, Fut>(&self, label: &str, mut call: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut retry = 0;
+        loop {
+            match call().await {
+                Ok(value) => return Ok(value),
+                Err(e) if retry < self.max_retries && self.is_retryable_error(&e) => {
+                    retry += 1;
+                    let delay = self.delay(retry);
+                    warn!("{} call failed ({}), retrying in {:?} ({}/{})", label, e, delay, retry, self.max_retries);
+                    sleep(delay).await;
+                }
+                Err(e) => {
+                    if retry > 0 {
+                        error!("{} call failed after {} retries: {}", label, retry, e);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+    }
+}
+
+/// Wraps any provider so its calls are retried under one `RetryPolicy`. Providers that retry
+/// internally (`GeminiClient`) don't need it.
+pub struct RetryingLLMProvider {
+    inner: Arc<dyn LLMProvider>,
+    policy: RetryPolicy,
+}
+
+impl RetryingLLMProvider {
+    pub fn new(inner: Arc<dyn LLMProvider>) -> Self {
+        Self {
+            inner,
+            policy: RetryPolicy::default(),
+        }
+    }
+
+    pub fn with_policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
+
+#[async_trait]
+impl LLMProvider for RetryingLLMProvider {
+    async fn generate(&self, prompt: &str) -> Result<LLMResponse> {
+        self.policy.run("LLM", || self.inner.generate(prompt)).await
+    }
+
+    async fn generate_with_context(&self, messages: Vec<Message>) -> Result<LLMResponse> {
+        self.policy
+            .run("LLM", || self.inner.generate_with_context(messages.clone()))
+            .await
+    }
+
+    async fn generate_with_options(
+        &self,
+        messages: Vec<Message>,
+        options: GenerationOptions,
+    ) -> Result<LLMResponse> {
+        self.policy
+            .run("LLM", || self.inner.generate_with_options(messages.clone(), options.clone()))
+            .await
+    }
+
+    async fn stream_generate(
+        &self,
+        prompt: &str,
+    ) -> Result<Box<dyn futures::Stream<Item = Result<String>> + Unpin>> {
+        // The boxed stream isn't `Send`, so it can't be held across a backoff; streams aren't
+        // retried
+        self.inner.stream_generate(prompt).await
+    }
+
+    async fn generate_multi_step(&self, steps: Vec<String>, context: &str) -> Result<LLMResponse> {
+        self.policy
+            .run("LLM", || self.inner.generate_multi_step(steps.clone(), context))
+            .await
+    }
+
+    async fn generate_with_framework(&self, prompt: &str, framework: &str, lang: &str) -> Result<LLMResponse> {
+        self.policy
+            .run("LLM", || self.inner.generate_with_framework(prompt, framework, lang))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OpenAIClient, Role};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const COMPLETION: &str = r#"{"choices":[{"message":{"content":"hello"},"finish_reason":"stop"}]}"#;
+
+    /// Answers the first request with 503 and every later one with a completion
+    async fn flaky_server(requests: Arc<AtomicUsize>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else { return };
+                let mut buffer = vec![0u8; 64 * 1024];
+                let _ = socket.read(&mut buffer).await;
+                let (status, body) = match requests.fetch_add(1, Ordering::SeqCst) {
+                    0 => ("503 Service Unavailable", r#"{"error":"overloaded"}"#),
+                    _ => ("200 OK", COMPLETION),
+                };
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{}/v1", address)
+    }
+
+    #[tokio::test]
+    async fn test_openai_503_is_retried() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let base_url = flaky_server(requests.clone()).await;
+        let messages = vec![Message {
+            role: Role::User,
+            content: "hi".to_string(),
+        }];
+
+        // Unwrapped, the 503 surfaces as a retryable error
+        let client = OpenAIClient::new("test-key".to_string()).with_base_url(base_url.clone());
+        let err = client.generate_with_context(messages.clone()).await.unwrap_err();
+        let policy = RetryPolicy::new(2, Duration::from_millis(10)).with_max_jitter(Duration::ZERO);
+        assert!(policy.is_retryable_error(&err));
+        assert!(!policy.is_retryable(400));
+
+        requests.store(0, Ordering::SeqCst);
+        let retrying = RetryingLLMProvider::new(Arc::new(client)).with_policy(policy);
+        let response = retrying.generate_with_context(messages).await.unwrap();
+        assert_eq!(response.content, "hello");
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+}
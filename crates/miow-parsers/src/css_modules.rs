@@ -0,0 +1,236 @@
+//! CSS modules. A component styled with `import styles from './Button.module.css'` only says
+//! `styles.button`; what that looks like is in the `.button` rule of the module. Each class of a
+//! `.module.css` file becomes a `CSSModuleClass` design token holding its rules, and components
+//! record the `styles.foo` classes they use so the two can be joined in the graph.
+
+use crate::types::*;
+use anyhow::Result;
+use regex::Regex;
+use std::collections::BTreeMap;
+
+/// Extensions of stylesheets bundlers treat as CSS modules
+const CSS_MODULE_EXTENSIONS: [&str; 4] = [".module.css", ".module.scss", ".module.sass", ".module.less"];
+
+/// Whether `file_path` names a CSS module stylesheet
+pub fn is_css_module(file_path: &str) -> bool {
+    CSS_MODULE_EXTENSIONS.iter().any(|ext| file_path.ends_with(ext))
+}
+
+/// Parse a CSS module into one `CSSModuleClass` token per class name. The token value is every
+/// rule whose selector mentions the class, in source order; its context is the first selector.
+pub fn parse_css_module(content: &str) -> Result<ParsedFile> {
+    let class_pattern = Regex::new(r"\.(-?[_a-zA-Z][_a-zA-Z0-9-]*)").unwrap();
+    let css = strip_comments(content);
+
+    let mut classes: BTreeMap<String, DesignToken> = BTreeMap::new();
+    for rule in rules(&css) {
+        if rule.selector.starts_with('@') {
+            continue;
+        }
+        let text = format!("{} {{{}}}", rule.selector, rule.body);
+        for capture in class_pattern.captures_iter(&rule.selector) {
+            let name = capture[1].to_string();
+            classes
+                .entry(name.clone())
+                .and_modify(|token| {
+                    if !token.value.contains(&text) {
+                        token.value.push('\n');
+                        token.value.push_str(&text);
+                    }
+                })
+                .or_insert_with(|| DesignToken {
+                    token_type: DesignTokenType::CSSModuleClass,
+                    name,
+                    value: text.clone(),
+                    context: rule.selector.clone(),
+                    range: range_of(content, rule.start, rule.end),
+                    language: None,
+                });
+        }
+    }
+
+    let mut design_tokens: Vec<DesignToken> = classes.into_values().collect();
+    design_tokens.sort_by_key(|token| token.range.start_byte);
+
+    Ok(ParsedFile {
+        symbols: vec![],
+        imports: vec![],
+        exports: vec![],
+        design_tokens,
+        type_definitions: vec![],
+        constants: vec![],
+        schemas: vec![],
+        language: "css".to_string(),
+        tests: vec![],
+        routes: vec![],
+        examples: vec![],
+    })
+}
+
+/// Record on each symbol the CSS module classes it reads through a default import of a
+/// `.module.css` file (`styles.button`, `styles['primary-button']`), in source order
+pub fn link_css_module_classes(symbols: &mut [Symbol], imports: &[Import]) {
+    let bindings: Vec<(Regex, &str)> = imports
+        .iter()
+        .filter(|import| is_css_module(&import.source))
+        .flat_map(|import| {
+            import
+                .names
+                .iter()
+                .filter(|name| name.is_default || name.is_namespace)
+                .map(move |name| (name.alias.as_deref().unwrap_or(&name.name), import.source.as_str()))
+        })
+        .map(|(binding, source)| {
+            let binding = regex::escape(binding);
+            let pattern = format!(
+                r#"\b{binding}(?:\.([_a-zA-Z][_a-zA-Z0-9]*)|\[\s*['"]([^'"]+)['"]\s*\])"#
+            );
+            (Regex::new(&pattern).unwrap(), source)
+        })
+        .collect();
+    if bindings.is_empty() {
+        return;
+    }
+
+    for symbol in symbols.iter_mut() {
+        let mut used: Vec<CssModuleClassRef> = Vec::new();
+        for (pattern, source) in &bindings {
+            for capture in pattern.captures_iter(&symbol.content) {
+                let Some(class_name) = capture.get(1).or_else(|| capture.get(2)) else {
+                    continue;
+                };
+                let class_ref = CssModuleClassRef {
+                    module: source.to_string(),
+                    class_name: class_name.as_str().to_string(),
+                };
+                if !used.contains(&class_ref) {
+                    used.push(class_ref);
+                }
+            }
+        }
+        symbol.metadata.css_module_classes = used;
+        link_css_module_classes(&mut symbol.children, imports);
+    }
+}
+
+/// A rule's selector (or at-rule prelude) and body, with the byte span of the whole rule
+struct Rule {
+    selector: String,
+    body: String,
+    start: usize,
+    end: usize,
+}
+
+/// Every rule in `css`, nested ones included (SCSS nesting, rules inside `@media`). A nested
+/// rule's selector is its own, not the resolved `&`-joined one.
+fn rules(css: &str) -> Vec<Rule> {
+    let bytes = css.as_bytes();
+    let mut rules = Vec::new();
+    // Byte offsets of the open braces and of where each enclosing selector began
+    let mut open: Vec<(usize, usize)> = Vec::new();
+    let mut selector_start = 0;
+
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'{' => {
+                open.push((selector_start, i));
+                selector_start = i + 1;
+            }
+            b'}' => {
+                if let Some((start, brace)) = open.pop() {
+                    let selector = css[start..brace].trim();
+                    rules.push(Rule {
+                        selector: selector.split_whitespace().collect::<Vec<_>>().join(" "),
+                        body: css[brace + 1..i].trim().to_string(),
+                        start: start + (css[start..brace].len() - css[start..brace].trim_start().len()),
+                        end: i + 1,
+                    });
+                }
+                selector_start = i + 1;
+            }
+            b';' => selector_start = i + 1,
+            _ => {}
+        }
+    }
+    rules.sort_by_key(|rule| rule.start);
+    rules
+}
+
+/// `content` with `/* */` comments blanked out, keeping byte offsets and line numbers
+fn strip_comments(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        let end = rest[start + 2..].find("*/").map_or(rest.len(), |e| start + 2 + e + 2);
+        for c in rest[start..end].chars() {
+            match c {
+                '\n' => out.push('\n'),
+                _ => out.extend(std::iter::repeat_n(' ', c.len_utf8())),
+            }
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn range_of(content: &str, start: usize, end: usize) -> Range {
+    let line_of = |offset: usize| content[..offset].matches('\n').count() + 1;
+    let col_of = |offset: usize| offset - content[..offset].rfind('\n').map_or(0, |n| n + 1);
+    Range {
+        start_line: line_of(start),
+        end_line: line_of(end),
+        start_byte: start,
+        end_byte: end,
+        start_col: col_of(start),
+        end_col: col_of(end),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_styles_button_links_to_button_class() {
+        let css = r#"
+/* Primary action */
+.button {
+  padding: 8px 16px;
+  border-radius: 6px;
+}
+
+.button:hover, .active { opacity: 0.9; }
+
+@media (max-width: 600px) {
+  .button { width: 100%; }
+}
+"#;
+        let module = parse_css_module(css).unwrap();
+        let names: Vec<&str> = module.design_tokens.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["button", "active"]);
+        let button = &module.design_tokens[0];
+        assert!(matches!(button.token_type, DesignTokenType::CSSModuleClass));
+        assert!(button.value.starts_with(".button {padding: 8px 16px;"), "{}", button.value);
+        assert!(button.value.contains(".button:hover, .active {opacity: 0.9;}"));
+        assert!(button.value.contains("width: 100%"));
+        assert_eq!(button.range.start_line, 3);
+
+        let tsx = r#"
+import styles from './Button.module.css';
+
+export function Button({ active }) {
+  return <button className={active ? styles['active'] : styles.button}>Go</button>;
+}
+"#;
+        let component = crate::parse_typescript(tsx, true).unwrap();
+        let used: Vec<(&str, &str)> = component.symbols[0]
+            .metadata
+            .css_module_classes
+            .iter()
+            .map(|c| (c.module.as_str(), c.class_name.as_str()))
+            .collect();
+        assert_eq!(used, [("./Button.module.css", "active"), ("./Button.module.css", "button")]);
+    }
+}
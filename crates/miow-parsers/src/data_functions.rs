@@ -0,0 +1,170 @@
+//! Route data functions: the `loader`/`action` exports of a Remix route, Next's
+//! `getServerSideProps` and `"use server"` actions, SvelteKit's `load`. They sit next to the
+//! page they feed and are where the frontend meets the backend, so each one is tagged and
+//! linked to the page: the page references it, and it records the page as its route symbol.
+
+use crate::types::*;
+
+/// Tag of functions that load a route's data before it renders
+pub const DATA_LOADER_TAG: &str = "data-loader";
+
+/// Tag of functions that handle a route's form submissions and mutations
+pub const SERVER_ACTION_TAG: &str = "server-action";
+
+/// Exports frameworks call to load a route's data
+const DATA_LOADER_EXPORTS: [&str; 7] = [
+    "loader",
+    "clientLoader",
+    "getServerSideProps",
+    "getStaticProps",
+    "getStaticPaths",
+    "generateStaticParams",
+    "load",
+];
+
+/// Exports frameworks call with a route's submissions
+const SERVER_ACTION_EXPORTS: [&str; 3] = ["action", "clientAction", "actions"];
+
+/// Whether `source` starts with a `"use server"` directive, making every exported function in
+/// it a server action
+fn is_server_module(source: &str) -> bool {
+    source
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with("//"))
+        .is_some_and(is_use_server)
+}
+
+fn is_use_server(statement: &str) -> bool {
+    let statement = statement.trim_end_matches(';');
+    statement == "'use server'" || statement == "\"use server\""
+}
+
+/// Whether a function's body opens with a `"use server"` directive
+fn has_use_server_directive(content: &str) -> bool {
+    content
+        .split_once('{')
+        .and_then(|(_, body)| body.lines().map(str::trim).find(|line| !line.is_empty()))
+        .is_some_and(is_use_server)
+}
+
+/// The page a route module renders: its default-exported component, or its only component
+fn route_symbol(symbols: &[Symbol], exports: &[Export]) -> Option<String> {
+    let default_export = exports
+        .iter()
+        .find(|export| export.is_default && export.source.is_none() && export.name != "default")
+        .map(|export| export.name.clone());
+    if default_export.is_some() {
+        return default_export;
+    }
+    let mut components = symbols.iter().filter(|symbol| matches!(symbol.kind, SymbolType::Component));
+    match (components.next(), components.next()) {
+        (Some(page), None) => Some(page.name.clone()),
+        _ => None,
+    }
+}
+
+/// Tag the data loaders and server actions among a module's exported functions, and link each
+/// to the module's page
+pub(crate) fn tag_data_functions(symbols: &mut [Symbol], exports: &[Export], source: &str) {
+    let server_module = is_server_module(source);
+    let exported = |name: &str| {
+        exports
+            .iter()
+            .any(|export| export.source.is_none() && !export.is_type && export.alias.as_deref().unwrap_or(&export.name) == name)
+    };
+
+    let mut data_functions = Vec::new();
+    for symbol in symbols.iter_mut() {
+        if !matches!(symbol.kind, SymbolType::Function | SymbolType::Variable) || !exported(&symbol.name) {
+            continue;
+        }
+        let is_function = matches!(symbol.kind, SymbolType::Function);
+        let tag = if DATA_LOADER_EXPORTS.contains(&symbol.name.as_str()) {
+            DATA_LOADER_TAG
+        } else if SERVER_ACTION_EXPORTS.contains(&symbol.name.as_str())
+            || (is_function && (server_module || has_use_server_directive(&symbol.content)))
+        {
+            SERVER_ACTION_TAG
+        } else {
+            continue;
+        };
+        if !symbol.metadata.tags.iter().any(|t| t == tag) {
+            symbol.metadata.tags.push(tag.to_string());
+        }
+        data_functions.push(symbol.name.clone());
+    }
+
+    let Some(page) = route_symbol(symbols, exports).filter(|_| !data_functions.is_empty()) else {
+        return;
+    };
+    for symbol in symbols.iter_mut() {
+        if data_functions.contains(&symbol.name) {
+            symbol.metadata.route_symbol = Some(page.clone());
+        } else if symbol.name == page {
+            symbol.references.extend(data_functions.iter().cloned());
+            symbol.references.sort();
+            symbol.references.dedup();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TypeScriptParser;
+
+    #[test]
+    fn test_remix_loader_and_action_link_to_the_route() {
+        let content = r#"
+import { json, redirect } from "@remix-run/node";
+import { useLoaderData } from "@remix-run/react";
+
+export async function loader({ params }: LoaderFunctionArgs) {
+  return json(await getNote(params.id));
+}
+
+export const action = async ({ request }: ActionFunctionArgs) => {
+  await updateNote(await request.formData());
+  return redirect("/notes");
+};
+
+function formatTitle(title: string) {
+  return title.trim();
+}
+
+export default function NoteRoute() {
+  const note = useLoaderData<typeof loader>();
+  return <h1>{formatTitle(note.title)}</h1>;
+}
+"#;
+
+        let parsed = TypeScriptParser::new().parse(content, true).unwrap();
+        let symbol = |name: &str| parsed.symbols.iter().find(|s| s.name == name).unwrap();
+
+        let loader = symbol("loader");
+        assert!(loader.metadata.tags.contains(&DATA_LOADER_TAG.to_string()));
+        assert_eq!(loader.metadata.route_symbol.as_deref(), Some("NoteRoute"));
+        let action = symbol("action");
+        assert!(action.metadata.tags.contains(&SERVER_ACTION_TAG.to_string()));
+        assert_eq!(action.metadata.route_symbol.as_deref(), Some("NoteRoute"));
+
+        // The page references its data layer, so expanding it brings both in
+        let page = symbol("NoteRoute");
+        assert!(page.references.contains(&"loader".to_string()));
+        assert!(page.references.contains(&"action".to_string()));
+
+        let helper = symbol("formatTitle");
+        assert!(helper.metadata.tags.is_empty());
+        assert!(helper.metadata.route_symbol.is_none());
+    }
+
+    #[test]
+    fn test_use_server_module_exports_are_server_actions() {
+        let content = "'use server';\n\nexport async function createPost(data: FormData) {\n  await db.post.create({ data });\n}\n\nasync function audit() {}\n";
+        let parsed = TypeScriptParser::new().parse(content, false).unwrap();
+        let tags = |name: &str| parsed.symbols.iter().find(|s| s.name == name).unwrap().metadata.tags.clone();
+        assert_eq!(tags("createPost"), vec![SERVER_ACTION_TAG.to_string()]);
+        assert!(tags("audit").is_empty());
+    }
+}
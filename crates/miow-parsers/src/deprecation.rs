@@ -0,0 +1,104 @@
+use crate::{Symbol, SymbolType};
+
+/// Tag added to `SymbolMetadata::tags` of symbols marked deprecated
+pub const DEPRECATED_TAG: &str = "deprecated";
+
+/// JSDoc `@deprecated`, Java/TS `@Deprecated`, Rust `#[deprecated]` and PEP 702 `@deprecated(...)`
+const DEPRECATION_MARKERS: [&str; 3] = ["@deprecated", "@Deprecated", "#[deprecated"];
+
+/// Tag `symbols` (and their children) that are marked deprecated, either by an annotation
+/// in the comments/attributes/decorators directly above or at the start of the symbol, or,
+/// for functions, by emitting a Python `DeprecationWarning`.
+pub fn tag_deprecated(symbols: &mut [Symbol], source: &str) {
+    for symbol in symbols {
+        if is_deprecated(symbol, source) && !symbol.metadata.tags.iter().any(|t| t == DEPRECATED_TAG) {
+            symbol.metadata.tags.push(DEPRECATED_TAG.to_string());
+        }
+        tag_deprecated(&mut symbol.children, source);
+    }
+}
+
+fn is_deprecated(symbol: &Symbol, source: &str) -> bool {
+    let mut annotations = leading_annotations(source, symbol.range.start_byte);
+    annotations.extend(symbol.content.lines().map(str::trim).take_while(|line| is_annotation(line)));
+    if annotations
+        .iter()
+        .any(|line| DEPRECATION_MARKERS.iter().any(|marker| line.contains(marker)))
+    {
+        return true;
+    }
+
+    matches!(symbol.kind, SymbolType::Function | SymbolType::Method)
+        && symbol.content.contains("DeprecationWarning")
+}
+
+/// Comment, attribute and decorator lines: `//`, `/* */`, `#`, `#[...]`, `@decorator`
+fn is_annotation(line: &str) -> bool {
+    line.starts_with("//")
+        || line.starts_with("/*")
+        || line.starts_with('*')
+        || line.starts_with('#')
+        || line.starts_with('@')
+}
+
+/// The block of annotation lines immediately preceding `start_byte`, nearest first
+pub(crate) fn leading_annotations(source: &str, start_byte: usize) -> Vec<&str> {
+    let before = source.get(..start_byte).unwrap_or_default();
+    let mut lines = before.lines().rev().map(str::trim).peekable();
+
+    // Text on the symbol's own line, e.g. `export ` ahead of `function`
+    if !before.ends_with('\n') && lines.peek().is_some_and(|line| !line.ends_with("*/")) {
+        lines.next();
+    }
+
+    let mut block = Vec::new();
+    let mut in_block_comment = false;
+    for line in lines {
+        // Walking upwards, a block comment runs from its `*/` line to its `/*` line
+        if in_block_comment || line.ends_with("*/") {
+            in_block_comment = !line.starts_with("/*");
+        } else if !is_annotation(line) {
+            break;
+        }
+        block.push(line);
+    }
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_python, parse_rust, parse_typescript};
+
+    fn tagged(symbols: &[crate::Symbol], name: &str) -> bool {
+        symbols
+            .iter()
+            .find(|s| s.name == name)
+            .unwrap_or_else(|| panic!("missing symbol {}", name))
+            .metadata
+            .tags
+            .iter()
+            .any(|t| t == super::DEPRECATED_TAG)
+    }
+
+    #[test]
+    fn test_deprecation_detected_across_languages() {
+        let ts = parse_typescript(
+            "/**\n * Formats a price.\n * @deprecated use formatMoney\n */\nexport function formatPrice(n: number) { return n; }\n\nexport function formatMoney(n: number) { return n; }\n",
+            false,
+        )
+        .unwrap();
+        assert!(tagged(&ts.symbols, "formatPrice"));
+        assert!(!tagged(&ts.symbols, "formatMoney"));
+
+        let rs = parse_rust("#[deprecated(note = \"use new_api\")]\npub fn old_api() {}\n\npub fn new_api() {}\n").unwrap();
+        assert!(tagged(&rs.symbols, "old_api"));
+        assert!(!tagged(&rs.symbols, "new_api"));
+
+        let py = parse_python(
+            "import warnings\n\ndef legacy():\n    warnings.warn(\"use modern\", DeprecationWarning)\n\ndef modern():\n    pass\n",
+        )
+        .unwrap();
+        assert!(tagged(&py.symbols, "legacy"));
+        assert!(!tagged(&py.symbols, "modern"));
+    }
+}
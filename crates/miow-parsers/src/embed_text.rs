@@ -0,0 +1,74 @@
+//! Compact embedding text for a symbol: its name, signature and doc comment. Bodies of
+//! functions that do the same thing can differ a lot, so embedding what a symbol *is* rather
+//! than how it is implemented tends to retrieve better for "find a function that does X".
+
+use crate::deprecation::leading_annotations;
+use crate::Symbol;
+
+/// The signature is taken from at most this many leading lines
+const MAX_SIGNATURE_LINES: usize = 12;
+
+/// `name`, signature and doc comment of `symbol`, one per line; `source` is the file the
+/// symbol was parsed from, used to find the comment directly above it.
+pub fn signature_embed_text(symbol: &Symbol, source: &str) -> String {
+    let mut parts = vec![symbol.name.clone(), signature(&symbol.content)];
+    if let Some(doc) = doc_comment(symbol, source) {
+        parts.push(doc);
+    }
+    parts.retain(|part| !part.is_empty());
+    parts.join("\n")
+}
+
+/// Reduce parsed symbols to their signatures for `ParseMode::SignaturesOnly`: bodies,
+/// children and references are dropped
+pub(crate) fn keep_signatures_only(symbols: &mut [Symbol]) {
+    for symbol in symbols {
+        symbol.content = signature(&symbol.content);
+        symbol.children.clear();
+        symbol.references.clear();
+    }
+}
+
+/// Leading lines of a symbol up to where its body opens
+fn signature(content: &str) -> String {
+    let mut lines = Vec::new();
+    for line in content.lines().take(MAX_SIGNATURE_LINES) {
+        let trimmed = line.trim_end();
+        if let Some(open) = trimmed.find('{').filter(|_| trimmed.ends_with('{')) {
+            lines.push(trimmed[..open].trim_end());
+            break;
+        }
+        lines.push(trimmed);
+        if trimmed.ends_with("=>") || trimmed.ends_with(':') {
+            break;
+        }
+    }
+    lines.join("\n").trim().to_string()
+}
+
+/// The parser-extracted docstring, else the comment block directly above the symbol with
+/// comment markers stripped
+fn doc_comment(symbol: &Symbol, source: &str) -> Option<String> {
+    if let Some(doc) = symbol.metadata.documentation.as_ref().or(symbol.metadata.jsdoc.as_ref()) {
+        return Some(doc.trim().to_string());
+    }
+
+    let mut lines: Vec<&str> = leading_annotations(source, symbol.range.start_byte)
+        .into_iter()
+        .filter(|line| !line.starts_with("#[") && !line.starts_with('@'))
+        .map(|line| {
+            line.trim_start_matches("/**")
+                .trim_start_matches("/*")
+                .trim_end_matches("*/")
+                .trim_start_matches("///")
+                .trim_start_matches("//!")
+                .trim_start_matches("//")
+                .trim_start_matches('*')
+                .trim_start_matches('#')
+                .trim()
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+    lines.reverse();
+    (!lines.is_empty()).then(|| lines.join(" "))
+}
@@ -0,0 +1,395 @@
+//! Mixed-language files: `.vue` and `.astro` components and markdown. Each embedded block
+//! (`<script>`, `<style>`, Astro frontmatter, fenced code) is parsed as its own language and
+//! everything it yields is tagged with that language, so a `<style>` token reports `css`
+//! rather than the container's `vue`. Ranges are shifted back into the container file.
+
+use crate::types::*;
+use crate::{parse_python_with_mode, parse_rust_file_with_mode, parse_typescript_with_mode};
+use anyhow::Result;
+
+/// Extensions `parse_embedded` understands
+pub const EMBEDDED_EXTENSIONS: [&str; 4] = ["vue", "astro", "md", "mdx"];
+
+/// One block of another language inside a container file
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbeddedBlock {
+    /// Canonical language name (`typescript`, `tsx`, `javascript`, `css`, `scss`, ...)
+    pub language: String,
+    pub content: String,
+    /// Byte offset of `content` within the container file
+    pub start_byte: usize,
+}
+
+/// Parse a mixed-language file, tagging symbols and tokens with their block's language
+pub fn parse_embedded(content: &str, extension: &str, mode: ParseMode) -> Result<ParsedFile> {
+    let mut parsed = ParsedFile {
+        symbols: Vec::new(),
+        imports: Vec::new(),
+        exports: Vec::new(),
+        design_tokens: Vec::new(),
+        type_definitions: Vec::new(),
+        constants: Vec::new(),
+        schemas: Vec::new(),
+        language: container_language(extension).to_string(),
+        tests: Vec::new(),
+        routes: Vec::new(),
+        examples: Vec::new(),
+    };
+
+    for block in embedded_blocks(content, extension) {
+        let offset = Offset::of(content, block.start_byte);
+        if is_stylesheet(&block.language) {
+            if mode == ParseMode::Full {
+                parsed.design_tokens.extend(css_variables(&block, &offset, content));
+            }
+            continue;
+        }
+        let Some(mut inner) = parse_block(&block, mode)? else {
+            continue;
+        };
+        for symbol in &mut inner.symbols {
+            tag_symbol(symbol, &block.language, &offset);
+        }
+        for token in &mut inner.design_tokens {
+            offset.shift(&mut token.range);
+            token.language = Some(block.language.clone());
+        }
+        for type_def in &mut inner.type_definitions {
+            offset.shift(&mut type_def.range);
+        }
+        for route in &mut inner.routes {
+            offset.shift(&mut route.range);
+        }
+        for example in &mut inner.examples {
+            offset.shift(&mut example.range);
+        }
+        parsed.symbols.append(&mut inner.symbols);
+        parsed.imports.append(&mut inner.imports);
+        parsed.exports.append(&mut inner.exports);
+        parsed.design_tokens.append(&mut inner.design_tokens);
+        parsed.type_definitions.append(&mut inner.type_definitions);
+        parsed.constants.append(&mut inner.constants);
+        parsed.schemas.append(&mut inner.schemas);
+        parsed.tests.append(&mut inner.tests);
+        parsed.routes.append(&mut inner.routes);
+        parsed.examples.append(&mut inner.examples);
+    }
+    Ok(parsed)
+}
+
+/// The embedded blocks of a container file, in source order
+pub fn embedded_blocks(content: &str, extension: &str) -> Vec<EmbeddedBlock> {
+    match extension {
+        "vue" => tag_blocks(content, 0),
+        "astro" => {
+            let mut blocks = Vec::new();
+            let mut markup_start = 0;
+            if let Some((block, end)) = astro_frontmatter(content) {
+                blocks.push(block);
+                markup_start = end;
+            }
+            blocks.extend(tag_blocks(content, markup_start));
+            blocks
+        }
+        "md" | "mdx" => fenced_blocks(content),
+        _ => Vec::new(),
+    }
+}
+
+fn container_language(extension: &str) -> &str {
+    match extension {
+        "md" | "mdx" => "markdown",
+        other => other,
+    }
+}
+
+fn is_stylesheet(language: &str) -> bool {
+    matches!(language, "css" | "scss" | "sass" | "less")
+}
+
+fn parse_block(block: &EmbeddedBlock, mode: ParseMode) -> Result<Option<ParsedFile>> {
+    let parsed = match block.language.as_str() {
+        "typescript" | "javascript" => parse_typescript_with_mode(&block.content, false, mode)?,
+        "tsx" | "jsx" => parse_typescript_with_mode(&block.content, true, mode)?,
+        "rust" => parse_rust_file_with_mode(&block.content, "", mode)?,
+        "python" => parse_python_with_mode(&block.content, mode)?,
+        _ => return Ok(None),
+    };
+    Ok(Some(parsed))
+}
+
+fn tag_symbol(symbol: &mut Symbol, language: &str, offset: &Offset) {
+    offset.shift(&mut symbol.range);
+    symbol.metadata.language = Some(language.to_string());
+    for child in &mut symbol.children {
+        tag_symbol(child, language, offset);
+    }
+}
+
+/// `<script>` and `<style>` elements at or after `from`
+fn tag_blocks(content: &str, from: usize) -> Vec<EmbeddedBlock> {
+    let mut blocks = Vec::new();
+    let mut cursor = from;
+    while let Some((tag, open)) = next_block_tag(content, cursor) {
+        let Some(attrs_end) = content[open..].find('>').map(|i| open + i) else {
+            break;
+        };
+        let attributes = &content[open + tag.len() + 1..attrs_end];
+        let body_start = attrs_end + 1;
+        let close = format!("</{}>", tag);
+        let Some(body_end) = content[body_start..].find(&close).map(|i| body_start + i) else {
+            break;
+        };
+        let lang = attribute(attributes, "lang");
+        let language = match tag {
+            "script" => script_language(lang.as_deref().unwrap_or("js")),
+            _ => style_language(lang.as_deref().unwrap_or("css")),
+        };
+        blocks.push(EmbeddedBlock {
+            language: language.to_string(),
+            content: content[body_start..body_end].to_string(),
+            start_byte: body_start,
+        });
+        cursor = body_end + close.len();
+    }
+    blocks
+}
+
+/// The nearest `<script` or `<style` opening tag at or after `from`
+fn next_block_tag(content: &str, from: usize) -> Option<(&'static str, usize)> {
+    ["script", "style"]
+        .into_iter()
+        .filter_map(|tag| {
+            let rest = &content[from..];
+            let mut search = 0;
+            while let Some(i) = rest[search..].find(&format!("<{}", tag)) {
+                let at = search + i;
+                // `<scripts>` or `<style-guide>` aren't the elements we want
+                let next = rest[at + tag.len() + 1..].chars().next();
+                if matches!(next, Some(c) if c == '>' || c.is_whitespace()) {
+                    return Some((tag, from + at));
+                }
+                search = at + 1;
+            }
+            None
+        })
+        .min_by_key(|(_, at)| *at)
+}
+
+/// Value of `name="..."` (or single-quoted) in an opening tag's attribute text
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    let at = attributes.find(&format!("{}=", name))?;
+    let value = &attributes[at + name.len() + 1..];
+    let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let value = &value[1..];
+    value.find(quote).map(|end| value[..end].to_string())
+}
+
+/// The `---` fenced frontmatter script at the top of an Astro file, and where it ends
+fn astro_frontmatter(content: &str) -> Option<(EmbeddedBlock, usize)> {
+    let leading = content.len() - content.trim_start().len();
+    let rest = content[leading..].strip_prefix("---")?;
+    let body_start = leading + 3;
+    let body_len = rest.find("\n---")?;
+    let block = EmbeddedBlock {
+        language: "typescript".to_string(),
+        content: rest[..body_len + 1].to_string(),
+        start_byte: body_start,
+    };
+    Some((block, body_start + body_len + 4))
+}
+
+/// Fenced code blocks (```` ```lang ````) whose info string names a language we parse
+fn fenced_blocks(content: &str) -> Vec<EmbeddedBlock> {
+    let mut blocks = Vec::new();
+    let mut open: Option<(String, usize)> = None;
+    let mut line_start = 0;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim();
+        match &open {
+            None if trimmed.starts_with("```") => {
+                let info = trimmed.trim_start_matches('`').split_whitespace().next().unwrap_or("");
+                open = Some((info.to_lowercase(), line_start + line.len()));
+            }
+            Some((info, body_start)) if trimmed.starts_with("```") => {
+                if let Some(language) = fence_language(info) {
+                    blocks.push(EmbeddedBlock {
+                        language: language.to_string(),
+                        content: content[*body_start..line_start].to_string(),
+                        start_byte: *body_start,
+                    });
+                }
+                open = None;
+            }
+            _ => {}
+        }
+        line_start += line.len();
+    }
+    blocks
+}
+
+fn script_language(lang: &str) -> &'static str {
+    match lang {
+        "ts" | "typescript" => "typescript",
+        "tsx" => "tsx",
+        "jsx" => "jsx",
+        _ => "javascript",
+    }
+}
+
+fn style_language(lang: &str) -> &'static str {
+    match lang {
+        "scss" => "scss",
+        "sass" => "sass",
+        "less" => "less",
+        _ => "css",
+    }
+}
+
+fn fence_language(info: &str) -> Option<&'static str> {
+    let language = match info {
+        "ts" | "typescript" => "typescript",
+        "tsx" => "tsx",
+        "js" | "javascript" => "javascript",
+        "jsx" => "jsx",
+        "rs" | "rust" => "rust",
+        "py" | "python" => "python",
+        "css" => "css",
+        "scss" => "scss",
+        _ => return None,
+    };
+    Some(language)
+}
+
+/// `--name: value` custom properties declared in a stylesheet block
+fn css_variables(block: &EmbeddedBlock, offset: &Offset, source: &str) -> Vec<DesignToken> {
+    let mut tokens = Vec::new();
+    let text = &block.content;
+    let mut search = 0;
+    while let Some(i) = text[search..].find("--") {
+        let start = search + i;
+        search = start + 2;
+        let Some(colon) = text[start..].find(':').map(|c| start + c) else {
+            break;
+        };
+        let name = &text[start..colon];
+        if name.len() <= 2 || !name[2..].chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+            continue;
+        }
+        let end = text[colon..].find([';', '}']).map_or(text.len(), |e| colon + e);
+        let range = offset.span(start, end, source);
+        tokens.push(DesignToken {
+            token_type: DesignTokenType::CSSVariable,
+            name: name.to_string(),
+            value: text[colon + 1..end].trim().to_string(),
+            context: "style block".to_string(),
+            range,
+            language: Some(block.language.clone()),
+        });
+        search = end;
+    }
+    tokens
+}
+
+/// Where a block starts within its container, for mapping block-relative ranges back
+struct Offset {
+    byte: usize,
+    /// 0-based line of the block start
+    line: usize,
+    /// Column of the block start on that line
+    col: usize,
+}
+
+impl Offset {
+    fn of(source: &str, byte: usize) -> Self {
+        let before = &source[..byte];
+        Self {
+            byte,
+            line: before.matches('\n').count(),
+            col: byte - before.rfind('\n').map_or(0, |i| i + 1),
+        }
+    }
+
+    /// Shift a 1-based, block-relative range into the container
+    fn shift(&self, range: &mut Range) {
+        if range.start_line <= 1 {
+            range.start_col += self.col;
+        }
+        if range.end_line <= 1 {
+            range.end_col += self.col;
+        }
+        range.start_line += self.line;
+        range.end_line += self.line;
+        range.start_byte += self.byte;
+        range.end_byte += self.byte;
+    }
+
+    /// Container range of the block-relative byte span `start..end`
+    fn span(&self, start: usize, end: usize, source: &str) -> Range {
+        let start = Offset::of(source, self.byte + start);
+        let end = Offset::of(source, self.byte + end);
+        Range {
+            start_line: start.line + 1,
+            end_line: end.line + 1,
+            start_byte: start.byte,
+            end_byte: end.byte,
+            start_col: start.col,
+            end_col: end.col,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VUE: &str = r#"<template>
+  <button class="btn">{{ label }}</button>
+</template>
+
+<script setup lang="ts">
+export function formatLabel(label: string): string {
+  return label.trim();
+}
+</script>
+
+<style scoped>
+.btn {
+  --brand-color: #ff6600;
+  color: var(--brand-color);
+}
+</style>
+"#;
+
+    #[test]
+    fn test_vue_blocks_report_their_own_language() {
+        let parsed = parse_embedded(VUE, "vue", ParseMode::Full).unwrap();
+        assert_eq!(parsed.language, "vue");
+
+        let symbol = parsed.symbols.iter().find(|s| s.name == "formatLabel").unwrap();
+        assert_eq!(parsed.symbol_language(symbol), "typescript");
+        assert_eq!(symbol.range.start_line, 6);
+        assert!(VUE[symbol.range.start_byte..].starts_with("function formatLabel"));
+
+        let token = parsed.design_tokens.iter().find(|t| t.name == "--brand-color").unwrap();
+        assert_eq!(parsed.token_language(token), "css");
+        assert_eq!(token.value, "#ff6600");
+        assert_eq!(token.range.start_line, 13);
+    }
+
+    #[test]
+    fn test_astro_frontmatter_and_markdown_fences() {
+        let astro = "---\nconst title = 'Home';\n---\n<h1>{title}</h1>\n<style lang=\"scss\">h1 { --gap: 4px; }</style>\n";
+        let blocks = embedded_blocks(astro, "astro");
+        let languages: Vec<&str> = blocks.iter().map(|b| b.language.as_str()).collect();
+        assert_eq!(languages, vec!["typescript", "scss"]);
+        assert!(blocks[0].content.contains("const title"));
+
+        let markdown = "# Usage\n\n```rust\npub fn add(a: i32, b: i32) -> i32 { a + b }\n```\n\n```text\nignored\n```\n";
+        let parsed = parse_embedded(markdown, "md", ParseMode::Full).unwrap();
+        assert_eq!(parsed.language, "markdown");
+        let add = parsed.symbols.iter().find(|s| s.name == "add").unwrap();
+        assert_eq!(parsed.symbol_language(add), "rust");
+        assert_eq!(add.range.start_line, 4);
+    }
+}
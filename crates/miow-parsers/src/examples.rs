@@ -0,0 +1,184 @@
+//! JSDoc/TSDoc comments and their `@example` blocks. An example shows how an API is meant
+//! to be called, which is often better context than the implementation, so each one becomes
+//! a chunk of its own, linked to the symbol it documents.
+
+use crate::types::{Symbol, UsageExample};
+use tree_sitter::Node;
+
+/// Tag for chunks holding a doc comment's `@example` code
+pub const USAGE_EXAMPLE_TAG: &str = "usage-example";
+
+/// Fill `SymbolMetadata::jsdoc` of `symbols` (and their children) from the `/** ... */`
+/// comment directly above each, with comment markers stripped
+pub(crate) fn attach_jsdoc(symbols: &mut [Symbol], source: &str) {
+    for symbol in symbols {
+        if symbol.metadata.jsdoc.is_none() {
+            symbol.metadata.jsdoc = jsdoc_block(source, symbol.range.start_byte).map(clean_jsdoc);
+        }
+        attach_jsdoc(&mut symbol.children, source);
+    }
+}
+
+/// The `@example` blocks in the JSDoc of `symbols` (and their children), in source order
+pub(crate) fn usage_examples(symbols: &[Symbol]) -> Vec<UsageExample> {
+    let mut examples = Vec::new();
+    for symbol in symbols {
+        if let Some(jsdoc) = &symbol.metadata.jsdoc {
+            examples.extend(jsdoc_examples(jsdoc).into_iter().map(|(language, code)| UsageExample {
+                symbol: symbol.name.clone(),
+                code,
+                language,
+                range: symbol.range.clone(),
+            }));
+        }
+        examples.extend(usage_examples(&symbol.children));
+    }
+    examples
+}
+
+/// JSDoc of an interface member, from the `/** ... */` comment node right above it
+pub(crate) fn member_jsdoc(member: &Node, source: &str) -> Option<String> {
+    let comment = member.prev_named_sibling().filter(|n| n.kind() == "comment")?;
+    // A trailing comment of the previous member, on that member's line
+    let previous = comment.prev_named_sibling();
+    if previous.is_some_and(|p| p.end_position().row == comment.start_position().row) {
+        return None;
+    }
+    jsdoc_comment_text(comment.utf8_text(source.as_bytes()).ok()?)
+}
+
+/// Cleaned text of a `/** ... */` comment, `None` for other comments or empty docs
+fn jsdoc_comment_text(comment: &str) -> Option<String> {
+    let body = comment.trim().strip_prefix("/**")?.strip_suffix("*/")?;
+    Some(clean_jsdoc(body)).filter(|text| !text.is_empty())
+}
+
+/// Body of the doc comment ending right before `start_byte`, between `/**` and `*/`
+fn jsdoc_block(source: &str, start_byte: usize) -> Option<&str> {
+    let before = source.get(..start_byte)?;
+    // Text on the symbol's own line, e.g. `export ` ahead of `function`
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let before = if before[line_start..].trim_end().ends_with("*/") {
+        before
+    } else {
+        &before[..line_start]
+    };
+    let body = before.trim_end().strip_suffix("*/")?;
+    let open = body.rfind("/**")?;
+    Some(&body[open + 3..])
+}
+
+/// Strip the leading ` * ` of each comment line, keeping indentation after it
+fn clean_jsdoc(body: &str) -> String {
+    body.lines()
+        .map(|line| {
+            let line = line.trim_start();
+            let line = line.strip_prefix('*').unwrap_or(line);
+            line.strip_prefix(' ').unwrap_or(line).trim_end()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// `(fence language, code)` of each `@example` in a cleaned JSDoc comment. An example runs
+/// until the next block tag; fenced code is taken from inside the fence, unfenced examples
+/// whole.
+fn jsdoc_examples(jsdoc: &str) -> Vec<(Option<String>, String)> {
+    let mut blocks: Vec<Vec<&str>> = Vec::new();
+    let mut in_example = false;
+    let mut in_fence = false;
+    for line in jsdoc.lines() {
+        let trimmed = line.trim();
+        if !in_fence && trimmed.starts_with('@') {
+            in_example = trimmed.starts_with("@example");
+            if in_example {
+                blocks.push(Vec::new());
+                // Code may start on the tag line itself
+                let rest = trimmed["@example".len()..].trim();
+                if !rest.is_empty() && !rest.starts_with("<caption>") {
+                    blocks.last_mut().unwrap().push(rest);
+                }
+            }
+            continue;
+        }
+        if in_example {
+            if trimmed.starts_with("```") {
+                in_fence = !in_fence;
+            }
+            blocks.last_mut().unwrap().push(line);
+        }
+    }
+
+    blocks
+        .into_iter()
+        .filter_map(|lines| {
+            let (language, code) = unfence(&lines);
+            let code = code.trim_matches('\n').to_string();
+            (!code.trim().is_empty()).then_some((language, code))
+        })
+        .collect()
+}
+
+/// The fence language and code of the first fenced block in `lines`, else all of `lines`
+fn unfence(lines: &[&str]) -> (Option<String>, String) {
+    let Some(open) = lines.iter().position(|l| l.trim().starts_with("```")) else {
+        return (None, lines.join("\n"));
+    };
+    let info = lines[open].trim().trim_start_matches('`').trim();
+    let language = (!info.is_empty()).then(|| info.to_string());
+    let body = &lines[open + 1..];
+    let close = body.iter().position(|l| l.trim().starts_with("```")).unwrap_or(body.len());
+    (language, body[..close].join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse_typescript;
+
+    const DOCUMENTED: &str = r#"/**
+ * Format a price in the given currency.
+ *
+ * @param cents - Amount in cents
+ * @example
+ * ```ts
+ * formatPrice(1999, 'EUR');
+ * // => "€19.99"
+ * ```
+ * @returns The formatted price
+ */
+export function formatPrice(cents: number, currency: string): string {
+  const amount = cents / 100;
+  return new Intl.NumberFormat('en', { style: 'currency', currency }).format(amount);
+}
+
+export function undocumented() {}
+"#;
+
+    #[test]
+    fn test_example_becomes_its_own_chunk() {
+        let parsed = parse_typescript(DOCUMENTED, false).unwrap();
+        let symbol = parsed.symbols.iter().find(|s| s.name == "formatPrice").unwrap();
+        let jsdoc = symbol.metadata.jsdoc.as_deref().unwrap();
+        assert!(jsdoc.starts_with("Format a price in the given currency."));
+
+        assert_eq!(parsed.examples.len(), 1);
+        let example = &parsed.examples[0];
+        assert_eq!(example.symbol, "formatPrice");
+        assert_eq!(example.language.as_deref(), Some("ts"));
+        assert_eq!(example.code, "formatPrice(1999, 'EUR');\n// => \"€19.99\"");
+        assert_eq!(example.range.start_line, symbol.range.start_line);
+
+        let undocumented = parsed.symbols.iter().find(|s| s.name == "undocumented").unwrap();
+        assert!(undocumented.metadata.jsdoc.is_none());
+    }
+
+    #[test]
+    fn test_unfenced_examples_run_until_the_next_tag() {
+        let examples = super::jsdoc_examples("Sum.\n@example add(1, 2)\n@example\nconst total = add(\n  1,\n  2,\n);\n@returns the sum");
+        let code: Vec<&str> = examples.iter().map(|(_, code)| code.as_str()).collect();
+        assert_eq!(code, vec!["add(1, 2)", "const total = add(\n  1,\n  2,\n);"]);
+        assert!(examples.iter().all(|(language, _)| language.is_none()));
+    }
+}
@@ -0,0 +1,384 @@
+use crate::deprecation::tag_deprecated;
+use crate::embed_text::keep_signatures_only;
+use crate::types::*;
+use anyhow::{Context, Result};
+use tree_sitter::{Node, Parser};
+
+pub struct GoParser {
+    mode: ParseMode,
+}
+
+impl GoParser {
+    pub fn new() -> Self {
+        Self { mode: ParseMode::Full }
+    }
+
+    pub fn with_mode(mut self, mode: ParseMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn parse(&self, content: &str) -> Result<ParsedFile> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(tree_sitter_go::language())
+            .context("Failed to set Go language")?;
+
+        let tree = parser
+            .parse(content, None)
+            .context("Failed to parse Go content")?;
+
+        let root_node = tree.root_node();
+
+        let mut symbols = self.extract_symbols(&root_node, content)?;
+        tag_deprecated(&mut symbols, content);
+        let imports = self.extract_imports(&root_node, content)?;
+        if self.mode == ParseMode::SignaturesOnly {
+            keep_signatures_only(&mut symbols);
+        }
+
+        Ok(ParsedFile {
+            symbols,
+            imports,
+            exports: vec![], // Go exports are implicit (capitalized names)
+            design_tokens: vec![],
+            type_definitions: vec![],
+            constants: vec![],
+            schemas: vec![],
+            language: "go".to_string(),
+            tests: vec![],
+            routes: vec![],
+            examples: vec![],
+        })
+    }
+
+    fn extract_symbols(&self, node: &Node, source: &str) -> Result<Vec<Symbol>> {
+        let mut symbols = Vec::new();
+        let mut cursor = node.walk();
+
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                "function_declaration" => symbols.push(self.function(&child, source, SymbolType::Function)?),
+                "method_declaration" => {
+                    let mut method = self.function(&child, source, SymbolType::Method)?;
+                    if let Some(receiver) = child.child_by_field_name("receiver") {
+                        method.metadata.extends = self.receiver_type(&receiver, source).into_iter().collect();
+                    }
+                    symbols.push(method);
+                }
+                "type_declaration" => {
+                    let mut spec_cursor = child.walk();
+                    for spec in child.children(&mut spec_cursor) {
+                        if spec.kind() == "type_spec" {
+                            if let Some(symbol) = self.type_spec(&spec, &child, source)? {
+                                symbols.push(symbol);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(symbols)
+    }
+
+    /// A `func` declaration, with or without a receiver
+    fn function(&self, node: &Node, source: &str, kind: SymbolType) -> Result<Symbol> {
+        let name = self
+            .get_child_text(node, "name", source)
+            .unwrap_or_else(|| "anonymous".to_string());
+        let mut metadata = self.metadata(&name, node, source);
+        if let Some(parameters) = node.child_by_field_name("parameters") {
+            metadata.parameters = self.parameters(&parameters, source)?;
+        }
+        metadata.return_type = self.get_child_text(node, "result", source);
+        if let Some(type_parameters) = node.child_by_field_name("type_parameters") {
+            metadata.generic_params = self.parameters(&type_parameters, source)?.into_iter().map(|p| p.name).collect();
+        }
+
+        Ok(Symbol {
+            name,
+            kind,
+            range: Range::of(node),
+            content: node.utf8_text(source.as_bytes())?.to_string(),
+            metadata,
+            children: vec![],
+            references: vec![],
+        })
+    }
+
+    /// Structs and interfaces; other named types (`type ID string`) aren't symbols
+    fn type_spec(&self, spec: &Node, declaration: &Node, source: &str) -> Result<Option<Symbol>> {
+        let Some(type_node) = spec.child_by_field_name("type") else {
+            return Ok(None);
+        };
+        let name = self.get_child_text(spec, "name", source).unwrap_or_default();
+        let (kind, children) = match type_node.kind() {
+            "struct_type" => (SymbolType::Struct, self.struct_fields(&type_node, source)?),
+            "interface_type" => (SymbolType::Interface, self.interface_methods(&type_node, source)?),
+            _ => return Ok(None),
+        };
+        // A lone `type X struct` is documented above the `type` keyword; a grouped spec above itself
+        let outer = if declaration.named_child_count() == 1 { declaration } else { spec };
+        let mut metadata = self.metadata(&name, outer, source);
+        if let Some(type_parameters) = spec.child_by_field_name("type_parameters") {
+            metadata.generic_params = self.parameters(&type_parameters, source)?.into_iter().map(|p| p.name).collect();
+        }
+
+        Ok(Some(Symbol {
+            name,
+            kind,
+            range: Range::of(outer),
+            content: outer.utf8_text(source.as_bytes())?.to_string(),
+            metadata,
+            children,
+            references: vec![],
+        }))
+    }
+
+    fn struct_fields(&self, struct_type: &Node, source: &str) -> Result<Vec<Symbol>> {
+        let mut fields = Vec::new();
+        let mut cursor = struct_type.walk();
+        let Some(list) = struct_type.children(&mut cursor).find(|n| n.kind() == "field_declaration_list") else {
+            return Ok(fields);
+        };
+
+        let mut list_cursor = list.walk();
+        for field in list.children(&mut list_cursor) {
+            if field.kind() != "field_declaration" {
+                continue;
+            }
+            let type_annotation = self.get_child_text(&field, "type", source);
+            let mut name_cursor = field.walk();
+            let mut names: Vec<String> = field
+                .children_by_field_name("name", &mut name_cursor)
+                .map(|n| n.utf8_text(source.as_bytes()).map(str::to_string))
+                .collect::<Result<_, _>>()?;
+            // An embedded field (`sync.Mutex`, `*Base`) is named after its type
+            if names.is_empty() {
+                if let Some(embedded) = &type_annotation {
+                    let embedded = embedded.trim_start_matches('*');
+                    names.push(embedded.rsplit('.').next().unwrap_or(embedded).to_string());
+                }
+            }
+            for name in names {
+                let mut metadata = self.metadata(&name, &field, source);
+                metadata.return_type = type_annotation.clone();
+                fields.push(Symbol {
+                    name,
+                    kind: SymbolType::Field,
+                    range: Range::of(&field),
+                    content: field.utf8_text(source.as_bytes())?.to_string(),
+                    metadata,
+                    children: vec![],
+                    references: vec![],
+                });
+            }
+        }
+        Ok(fields)
+    }
+
+    fn interface_methods(&self, interface_type: &Node, source: &str) -> Result<Vec<Symbol>> {
+        let mut methods = Vec::new();
+        let mut cursor = interface_type.walk();
+        for spec in interface_type.children(&mut cursor) {
+            if spec.kind() == "method_spec" {
+                methods.push(self.function(&spec, source, SymbolType::Method)?);
+            }
+        }
+        Ok(methods)
+    }
+
+    /// Type a method is declared on: `Server` for both `(s Server)` and `(s *Server)`, without
+    /// type arguments
+    fn receiver_type(&self, receiver: &Node, source: &str) -> Option<String> {
+        let mut cursor = receiver.walk();
+        let parameter = receiver
+            .children(&mut cursor)
+            .find(|n| n.kind() == "parameter_declaration")?;
+        let type_text = self.get_child_text(&parameter, "type", source)?;
+        let type_name = type_text.trim_start_matches('*');
+        Some(type_name.split('[').next().unwrap_or(type_name).trim().to_string())
+    }
+
+    fn parameters(&self, list: &Node, source: &str) -> Result<Vec<Parameter>> {
+        let mut params = Vec::new();
+        let mut cursor = list.walk();
+        for declaration in list.children(&mut cursor) {
+            if !matches!(declaration.kind(), "parameter_declaration" | "variadic_parameter_declaration") {
+                continue;
+            }
+            let type_annotation = self.get_child_text(&declaration, "type", source);
+            let mut name_cursor = declaration.walk();
+            let names: Vec<String> = declaration
+                .children_by_field_name("name", &mut name_cursor)
+                .map(|n| n.utf8_text(source.as_bytes()).map(str::to_string))
+                .collect::<Result<_, _>>()?;
+            // Unnamed parameters (`func(int, string)`) are listed by type
+            let names = if names.is_empty() { vec![type_annotation.clone().unwrap_or_default()] } else { names };
+            for name in names {
+                params.push(Parameter {
+                    name,
+                    type_annotation: type_annotation.clone(),
+                    default_value: None,
+                    is_optional: declaration.kind() == "variadic_parameter_declaration",
+                });
+            }
+        }
+        Ok(params)
+    }
+
+    /// Visibility from the name's case (Go exports capitalized names) and the `//` comment
+    /// block directly above `node`
+    fn metadata(&self, name: &str, node: &Node, source: &str) -> SymbolMetadata {
+        let exported = name.chars().next().is_some_and(char::is_uppercase);
+        SymbolMetadata {
+            access_modifier: Some(if exported { "public" } else { "private" }.to_string()),
+            documentation: self.doc_comment(node, source),
+            ..Default::default()
+        }
+    }
+
+    fn doc_comment(&self, node: &Node, source: &str) -> Option<String> {
+        let mut lines = Vec::new();
+        let mut expected_row = node.start_position().row;
+        let mut sibling = node.prev_sibling();
+        while let Some(comment) = sibling.filter(|s| s.kind() == "comment" && s.end_position().row + 1 == expected_row) {
+            let text = comment.utf8_text(source.as_bytes()).ok()?;
+            lines.push(text.trim_start_matches("//").trim().to_string());
+            expected_row = comment.start_position().row;
+            sibling = comment.prev_sibling();
+        }
+        if lines.is_empty() {
+            return None;
+        }
+        lines.reverse();
+        Some(lines.join("\n"))
+    }
+
+    fn extract_imports(&self, node: &Node, source: &str) -> Result<Vec<Import>> {
+        let mut imports = Vec::new();
+        let mut cursor = node.walk();
+
+        for child in node.children(&mut cursor) {
+            if child.kind() != "import_declaration" {
+                continue;
+            }
+            let mut specs = Vec::new();
+            let mut spec_cursor = child.walk();
+            for sub in child.children(&mut spec_cursor) {
+                match sub.kind() {
+                    "import_spec" => specs.push(sub),
+                    "import_spec_list" => {
+                        let mut list_cursor = sub.walk();
+                        specs.extend(sub.children(&mut list_cursor).filter(|n| n.kind() == "import_spec"));
+                    }
+                    _ => {}
+                }
+            }
+
+            for spec in specs {
+                let path = self
+                    .get_child_text(&spec, "path", source)
+                    .map(|p| p.trim_matches(|c| c == '"' || c == '`').to_string())
+                    .unwrap_or_default();
+                // The package is bound under its last path segment unless renamed
+                let package = path.rsplit('/').next().unwrap_or(&path).to_string();
+                imports.push(Import {
+                    names: vec![ImportName {
+                        name: package,
+                        alias: self.get_child_text(&spec, "name", source),
+                        is_default: false,
+                        is_namespace: true,
+                        is_type: false,
+                    }],
+                    source: path,
+                    range: Range::of(&spec),
+                });
+            }
+        }
+        Ok(imports)
+    }
+
+    fn get_child_text(&self, node: &Node, field: &str, source: &str) -> Option<String> {
+        node.child_by_field_name(field)
+            .map(|n| n.utf8_text(source.as_bytes()).unwrap().to_string())
+    }
+}
+
+impl Default for GoParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_receiver_methods_and_visibility() {
+        let code = r#"package server
+
+import (
+	"net/http"
+	log "github.com/sirupsen/logrus"
+)
+
+// Server handles requests.
+type Server struct {
+	Addr    string
+	handler http.Handler
+	sync.Mutex
+}
+
+type Store interface {
+	Get(id string) (Item, error)
+}
+
+// Handle serves one request.
+func (s *Server) Handle(w http.ResponseWriter, r *http.Request) {
+	log.Info("handling")
+}
+
+func newServer(addr string) *Server {
+	return &Server{Addr: addr}
+}
+"#;
+        let parsed = GoParser::new().parse(code).unwrap();
+        let find = |name: &str| parsed.symbols.iter().find(|s| s.name == name).unwrap();
+
+        let server = find("Server");
+        assert_eq!(server.kind, SymbolType::Struct);
+        assert_eq!(server.metadata.documentation.as_deref(), Some("Server handles requests."));
+        let fields: Vec<(&str, Option<&str>)> = server
+            .children
+            .iter()
+            .map(|f| (f.name.as_str(), f.metadata.access_modifier.as_deref()))
+            .collect();
+        assert_eq!(fields, [("Addr", Some("public")), ("handler", Some("private")), ("Mutex", Some("public"))]);
+
+        let store = find("Store");
+        assert_eq!(store.kind, SymbolType::Interface);
+        assert_eq!(store.children[0].name, "Get");
+        assert_eq!(store.children[0].kind, SymbolType::Method);
+
+        let handle = find("Handle");
+        assert_eq!(handle.kind, SymbolType::Method);
+        assert_eq!(handle.metadata.extends, ["Server"]);
+        assert_eq!(handle.metadata.access_modifier.as_deref(), Some("public"));
+        assert_eq!(handle.metadata.parameters.len(), 2);
+        assert_eq!(handle.metadata.documentation.as_deref(), Some("Handle serves one request."));
+
+        let new_server = find("newServer");
+        assert_eq!(new_server.kind, SymbolType::Function);
+        assert_eq!(new_server.metadata.access_modifier.as_deref(), Some("private"));
+        assert_eq!(new_server.metadata.return_type.as_deref(), Some("*Server"));
+        assert!(new_server.metadata.extends.is_empty());
+
+        let sources: Vec<&str> = parsed.imports.iter().map(|i| i.source.as_str()).collect();
+        assert_eq!(sources, ["net/http", "github.com/sirupsen/logrus"]);
+        assert_eq!(parsed.imports[1].names[0].alias.as_deref(), Some("log"));
+    }
+}
@@ -0,0 +1,211 @@
+//! JSON files are configuration or data, not code. Well-known config files contribute their
+//! top-level keys as config constants (plus `scripts` and `engines` for `package.json`); large
+//! data-only files such as fixtures are classified as data so the indexer can skip them.
+
+use crate::types::*;
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::path::Path;
+
+/// File names whose top-level keys describe project configuration
+const WELL_KNOWN_CONFIG_FILES: &[&str] = &[
+    "package.json",
+    "tsconfig.json",
+    "jsconfig.json",
+    "composer.json",
+    "components.json",
+    "turbo.json",
+    "nx.json",
+    "lerna.json",
+    "vercel.json",
+    "deno.json",
+    "app.json",
+    "manifest.json",
+    "babel.config.json",
+    ".eslintrc.json",
+    ".prettierrc.json",
+];
+
+/// `package.json` sections whose entries are surfaced individually
+const PACKAGE_JSON_SECTIONS: [&str; 2] = ["scripts", "engines"];
+
+/// JSON files at least this large that aren't known config are treated as data
+pub const DATA_JSON_MIN_BYTES: usize = 64 * 1024;
+
+/// What a JSON file holds, decided from its name and size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonRole {
+    /// A well-known config file whose keys are worth indexing
+    Config,
+    /// A large data-only file (fixtures, dumps, lockfiles) that should be skipped
+    Data,
+    /// Any other JSON; read but contributes nothing
+    Other,
+}
+
+/// Classify a JSON file by its path and content
+pub fn classify_json(file_path: &str, content: &str) -> JsonRole {
+    let file_name = Path::new(file_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let is_tsconfig_variant = file_name.starts_with("tsconfig.") && file_name.ends_with(".json");
+    if WELL_KNOWN_CONFIG_FILES.contains(&file_name.as_str()) || is_tsconfig_variant {
+        JsonRole::Config
+    } else if content.len() >= DATA_JSON_MIN_BYTES {
+        JsonRole::Data
+    } else {
+        JsonRole::Other
+    }
+}
+
+/// Parse a JSON file, recording the keys of well-known config files as config constants.
+/// Non-config JSON yields an empty `ParsedFile` rather than a parse failure.
+pub fn parse_json_config(content: &str, file_path: &str) -> Result<ParsedFile> {
+    let mut parsed = ParsedFile {
+        symbols: Vec::new(),
+        imports: Vec::new(),
+        exports: Vec::new(),
+        design_tokens: Vec::new(),
+        type_definitions: Vec::new(),
+        constants: Vec::new(),
+        schemas: Vec::new(),
+        language: "json".to_string(),
+        tests: Vec::new(),
+        routes: Vec::new(),
+        examples: Vec::new(),
+    };
+    if classify_json(file_path, content) != JsonRole::Config {
+        return Ok(parsed);
+    }
+
+    let value: Value = serde_json::from_str(&strip_line_comments(content))
+        .with_context(|| format!("invalid JSON config {}", file_path))?;
+    let Value::Object(root) = value else {
+        return Ok(parsed);
+    };
+
+    for (key, value) in &root {
+        parsed
+            .constants
+            .push(config_constant(key, value, find_key(content, key, 0)));
+    }
+
+    if file_path.ends_with("package.json") {
+        for section in PACKAGE_JSON_SECTIONS {
+            let Some(Value::Object(entries)) = root.get(section) else {
+                continue;
+            };
+            let section_start = find_key(content, section, 0).start_byte;
+            for (key, value) in entries {
+                let range = find_key(content, key, section_start);
+                parsed
+                    .constants
+                    .push(config_constant(&format!("{}.{}", section, key), value, range));
+            }
+        }
+    }
+
+    // serde_json maps iterate in key order; report keys in source order instead
+    parsed.constants.sort_by_key(|c| c.range.start_byte);
+    Ok(parsed)
+}
+
+fn config_constant(name: &str, value: &Value, range: Range) -> Constant {
+    Constant {
+        name: name.to_string(),
+        value: describe_value(value),
+        type_annotation: Some("json".to_string()),
+        category: ConstantCategory::Config,
+        range,
+    }
+}
+
+/// Scalars verbatim; objects and arrays as a short shape description
+fn describe_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Object(map) => format!("{{{} keys}}", map.len()),
+        Value::Array(items) => format!("[{} items]", items.len()),
+        other => other.to_string(),
+    }
+}
+
+/// Range of the first `"key"` at or after `from`; the file start if not found
+fn find_key(content: &str, key: &str, from: usize) -> Range {
+    let needle = format!("\"{}\"", key);
+    let start = content
+        .get(from..)
+        .and_then(|rest| rest.find(&needle))
+        .map(|offset| from + offset)
+        .unwrap_or(0);
+    let line = content[..start].matches('\n').count() + 1;
+    let col = start - content[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    Range {
+        start_line: line,
+        end_line: line,
+        start_byte: start,
+        end_byte: start + needle.len(),
+        start_col: col,
+        end_col: col + needle.len(),
+    }
+}
+
+/// tsconfig-style files allow `//` comment lines; blank them so byte offsets stay stable
+fn strip_line_comments(content: &str) -> String {
+    content
+        .split('\n')
+        .map(|line| {
+            if line.trim_start().starts_with("//") {
+                " ".repeat(line.len())
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_json_scripts_and_engines_extracted() {
+        let package_json = r#"{
+  "name": "shop",
+  "scripts": {
+    "dev": "next dev",
+    "build": "next build",
+    "lint": "eslint ."
+  },
+  "engines": { "node": ">=18" },
+  "dependencies": { "next": "14.0.0" }
+}"#;
+        let parsed = parse_json_config(package_json, "package.json").unwrap();
+        let names: Vec<&str> = parsed.constants.iter().map(|c| c.name.as_str()).collect();
+
+        for key in ["name", "scripts", "engines", "dependencies"] {
+            assert!(names.contains(&key), "missing top-level key {}", key);
+        }
+        let scripts: Vec<&str> = names
+            .iter()
+            .filter_map(|n| n.strip_prefix("scripts."))
+            .collect();
+        assert_eq!(scripts, vec!["dev", "build", "lint"]);
+
+        let node = parsed.constants.iter().find(|c| c.name == "engines.node").unwrap();
+        assert_eq!(node.value, ">=18");
+        let build = parsed.constants.iter().find(|c| c.name == "scripts.build").unwrap();
+        assert_eq!(build.value, "next build");
+        assert_eq!(build.range.start_line, 5);
+
+        // Fixtures are data and contribute nothing
+        let fixture = format!("[{}]", vec!["{\"id\": 1}"; 8000].join(","));
+        assert_eq!(classify_json("tests/fixtures/orders.json", &fixture), JsonRole::Data);
+        assert!(parse_json_config(&fixture, "tests/fixtures/orders.json")
+            .unwrap()
+            .constants
+            .is_empty());
+    }
+}
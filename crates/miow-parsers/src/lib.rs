@@ -1,37 +1,105 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
+pub mod go;
 pub mod python;
 pub mod rust;
 pub mod types;
 pub mod typescript;
+pub mod typescript_enhanced;
 pub mod style_analyzer;
 pub mod semantic;
 pub mod pattern_discovery;
+pub mod deprecation;
+pub mod json_config;
+pub mod embed_text;
+pub mod tailwind_config;
+pub mod platform;
+pub mod embedded;
+pub mod routes;
+pub mod examples;
+pub mod env_example;
+pub mod data_functions;
+pub mod css_modules;
+pub mod zod;
 
+pub use go::GoParser;
 pub use python::PythonParser;
 pub use rust::RustParser;
 pub use types::*;
-pub use typescript::TypeScriptParser;
+pub use typescript::{is_test_file, TypeScriptParser};
 pub use style_analyzer::{StyleAnalyzer, StyleAnalysis};
 pub use semantic::{SemanticAnalyzer, SemanticInfo, BestPractice, ComplianceStatus};
 pub use pattern_discovery::{PatternDiscovery, DiscoveredPattern};
+pub use deprecation::{tag_deprecated, DEPRECATED_TAG};
+pub use embed_text::signature_embed_text;
+pub use json_config::{classify_json, parse_json_config, JsonRole, DATA_JSON_MIN_BYTES};
+pub use tailwind_config::{extract_tailwind_tokens, is_tailwind_config, parse_tailwind_config};
+pub use platform::{logical_path, platform_of, tag_platform, PLATFORM_TAG_PREFIX};
+pub use examples::USAGE_EXAMPLE_TAG;
+pub use data_functions::{DATA_LOADER_TAG, SERVER_ACTION_TAG};
+pub use css_modules::{is_css_module, link_css_module_classes, parse_css_module};
+pub use env_example::{env_var_names, is_env_example, ENV_EXAMPLE_FILES};
+pub use embedded::{embedded_blocks, parse_embedded, EmbeddedBlock, EMBEDDED_EXTENSIONS};
 
 /// Parse a TypeScript/TSX file and extract symbols
 pub fn parse_typescript(content: &str, is_tsx: bool) -> Result<ParsedFile> {
-    let parser = TypeScriptParser::new();
+    parse_typescript_with_mode(content, is_tsx, ParseMode::Full)
+}
+
+/// Parse a TypeScript/TSX file, extracting as much as `mode` asks for
+pub fn parse_typescript_with_mode(content: &str, is_tsx: bool, mode: ParseMode) -> Result<ParsedFile> {
+    let parser = TypeScriptParser::new().with_mode(mode);
     parser.parse(content, is_tsx)
 }
 
+/// Parse a TypeScript/TSX file, extracting test cases when `file_path` is a test file
+pub fn parse_typescript_file(content: &str, is_tsx: bool, file_path: &str) -> Result<ParsedFile> {
+    parse_typescript_file_with_mode(content, is_tsx, file_path, ParseMode::Full)
+}
+
+/// Parse a TypeScript/TSX file with test-file detection, extracting as much as `mode` asks for
+pub fn parse_typescript_file_with_mode(content: &str, is_tsx: bool, file_path: &str, mode: ParseMode) -> Result<ParsedFile> {
+    let parser = TypeScriptParser::new().with_mode(mode);
+    parser.parse_with_path(content, is_tsx, file_path)
+}
+
 /// Parse a Rust file and extract symbols
 pub fn parse_rust(content: &str) -> Result<ParsedFile> {
     let parser = RustParser::new();
     parser.parse(content)
 }
 
+/// Parse a Rust file, qualifying symbols with the module path derived from `file_path`
+pub fn parse_rust_file(content: &str, file_path: &str) -> Result<ParsedFile> {
+    parse_rust_file_with_mode(content, file_path, ParseMode::Full)
+}
+
+/// Parse a Rust file with module-path qualification, extracting as much as `mode` asks for
+pub fn parse_rust_file_with_mode(content: &str, file_path: &str, mode: ParseMode) -> Result<ParsedFile> {
+    let parser = RustParser::new().with_mode(mode);
+    parser.parse_with_path(content, file_path)
+}
+
 /// Parse a Python file and extract symbols
 pub fn parse_python(content: &str) -> Result<ParsedFile> {
-    let parser = PythonParser::new();
+    parse_python_with_mode(content, ParseMode::Full)
+}
+
+/// Parse a Python file, extracting as much as `mode` asks for
+pub fn parse_python_with_mode(content: &str, mode: ParseMode) -> Result<ParsedFile> {
+    let parser = PythonParser::new().with_mode(mode);
+    parser.parse(content)
+}
+
+/// Parse a Go file and extract symbols
+pub fn parse_go(content: &str) -> Result<ParsedFile> {
+    parse_go_with_mode(content, ParseMode::Full)
+}
+
+/// Parse a Go file, extracting as much as `mode` asks for
+pub fn parse_go_with_mode(content: &str, mode: ParseMode) -> Result<ParsedFile> {
+    let parser = GoParser::new().with_mode(mode);
     parser.parse(content)
 }
 
@@ -39,6 +107,28 @@ pub fn parse_python(content: &str) -> Result<ParsedFile> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_reexports_record_their_source_module() {
+        let code = r#"
+export { Button } from './Button';
+export { Card as Tile } from './Card';
+export * from './utils';
+export type { ButtonProps } from './Button';
+export const VERSION = '1.0';
+export default Button;
+"#;
+        let parsed = parse_typescript(code, false).unwrap();
+        let find = |name: &str| parsed.exports.iter().find(|e| e.name == name).unwrap();
+
+        assert_eq!(find("Button").source.as_deref(), Some("./Button"));
+        assert!(!find("Button").is_default);
+        assert_eq!(find("Card").alias.as_deref(), Some("Tile"));
+        assert_eq!(find("*").source.as_deref(), Some("./utils"));
+        assert!(find("ButtonProps").is_type);
+        assert_eq!(find("VERSION").source, None);
+        assert!(parsed.exports.iter().any(|e| e.name == "Button" && e.is_default && e.source.is_none()));
+    }
+
     #[test]
     fn test_parse_simple_component() {
         let code = r#"
@@ -53,4 +143,37 @@ mod tests {
         assert!(!parsed.symbols.is_empty());
         assert_eq!(parsed.symbols[0].name, "Button");
     }
+
+    #[test]
+    fn test_signatures_only_keeps_declarations_without_bodies() {
+        let code = r#"
+            import { formatDate } from './dates';
+
+            export function DateLabel({ date }: { date: Date }) {
+                const label = formatDate(date);
+                return <span className="text-sm text-gray-500">{label}</span>;
+            }
+
+            export class Store {
+                load(id: string) {
+                    return fetchItem(id);
+                }
+            }
+        "#;
+
+        let full = parse_typescript(code, true).unwrap();
+        let quick = parse_typescript_with_mode(code, true, ParseMode::SignaturesOnly).unwrap();
+
+        let names = |parsed: &ParsedFile| parsed.symbols.iter().map(|s| s.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&quick), names(&full));
+        assert_eq!(quick.imports.len(), full.imports.len());
+        assert!(!full.design_tokens.is_empty());
+        assert!(quick.design_tokens.is_empty());
+
+        let label = quick.symbols.iter().find(|s| s.name == "DateLabel").unwrap();
+        assert!(label.content.starts_with("function DateLabel"), "{}", label.content);
+        assert!(!label.content.contains("formatDate(date)"));
+        assert!(label.references.is_empty());
+        assert!(quick.symbols.iter().all(|s| s.children.is_empty()));
+    }
 }
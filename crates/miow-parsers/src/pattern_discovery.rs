@@ -215,7 +215,7 @@ Focus on patterns that appear in multiple files. Ignore one-off code."#,
             response
         };
         
-        let patterns: Vec<DiscoveredPattern> = serde_json::from_str(json_str)
+        let patterns: Vec<DiscoveredPattern> = miow_llm::parse_llm_json(json_str)
             .context("Failed to parse patterns response")?;
         
         // Filter by confidence
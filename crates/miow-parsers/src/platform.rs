@@ -0,0 +1,69 @@
+//! Platform-specific files. React Native and other cross-platform bundlers resolve
+//! `Button.ios.tsx`, `Button.android.tsx` and `Button.web.ts` to the same `Button` module, picking
+//! one per platform. Symbols from such files record their platform so search can group the
+//! variants under one logical symbol instead of showing unrelated duplicates.
+
+use crate::types::*;
+use std::path::Path;
+
+/// File-name suffixes bundlers resolve per platform
+pub const PLATFORM_SUFFIXES: [&str; 6] = ["ios", "android", "web", "native", "windows", "macos"];
+
+/// Prefix of the tag recording a symbol's platform, e.g. `platform:ios`
+pub const PLATFORM_TAG_PREFIX: &str = "platform:";
+
+/// Platform suffix of `file_path` (`ios` for `Button.ios.tsx`), if it has one
+pub fn platform_of(file_path: &str) -> Option<&'static str> {
+    let stem = Path::new(file_path).file_stem()?.to_str()?;
+    let (_, suffix) = stem.rsplit_once('.')?;
+    PLATFORM_SUFFIXES.iter().copied().find(|platform| *platform == suffix)
+}
+
+/// `file_path` with its platform suffix removed (`Button.ios.tsx` → `Button.tsx`): the module
+/// every variant resolves as
+pub fn logical_path(file_path: &str) -> String {
+    match platform_of(file_path) {
+        Some(platform) => {
+            let marker = format!(".{}.", platform);
+            match file_path.rfind(&marker) {
+                Some(at) => format!("{}{}", &file_path[..at], &file_path[at + marker.len() - 1..]),
+                None => file_path.to_string(),
+            }
+        }
+        None => file_path.to_string(),
+    }
+}
+
+/// Record the platform of `file_path` on every symbol (and nested symbol) of `parsed`, both as
+/// `metadata.platform` and as a `platform:<name>` tag. Files without a suffix are untouched.
+pub fn tag_platform(parsed: &mut ParsedFile, file_path: &str) {
+    fn tag_symbol(symbol: &mut Symbol, platform: &str) {
+        symbol.metadata.platform = Some(platform.to_string());
+        symbol.metadata.tags.push(format!("{}{}", PLATFORM_TAG_PREFIX, platform));
+        for child in &mut symbol.children {
+            tag_symbol(child, platform);
+        }
+    }
+    if let Some(platform) = platform_of(file_path) {
+        for symbol in &mut parsed.symbols {
+            tag_symbol(symbol, platform);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_platform_suffix_detection() {
+        assert_eq!(platform_of("src/components/Button.ios.tsx"), Some("ios"));
+        assert_eq!(platform_of("src/components/Button.web.ts"), Some("web"));
+        assert_eq!(platform_of("src/components/Button.tsx"), None);
+        assert_eq!(platform_of("src/config.test.ts"), None);
+
+        assert_eq!(logical_path("src/components/Button.android.tsx"), "src/components/Button.tsx");
+        assert_eq!(logical_path("src/ios.ios.ts"), "src/ios.ts");
+        assert_eq!(logical_path("src/components/Button.tsx"), "src/components/Button.tsx");
+    }
+}
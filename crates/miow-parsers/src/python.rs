@@ -1,9 +1,12 @@
+use crate::deprecation::tag_deprecated;
+use crate::embed_text::keep_signatures_only;
 use crate::types::*;
 use anyhow::{Context, Result};
 use tree_sitter::{Node, Parser, Query, QueryCursor};
 
 pub struct PythonParser {
     parser: Parser,
+    mode: ParseMode,
 }
 
 impl PythonParser {
@@ -13,7 +16,15 @@ impl PythonParser {
         parser
             .set_language(language)
             .expect("Error loading Python grammar");
-        Self { parser }
+        Self {
+            parser,
+            mode: ParseMode::Full,
+        }
+    }
+
+    pub fn with_mode(mut self, mode: ParseMode) -> Self {
+        self.mode = mode;
+        self
     }
 
     pub fn parse(&self, content: &str) -> Result<ParsedFile> {
@@ -28,8 +39,25 @@ impl PythonParser {
 
         let root_node = tree.root_node();
 
-        let symbols = self.extract_symbols(&root_node, content)?;
+        let mut symbols = self.extract_symbols(&root_node, content)?;
+        tag_deprecated(&mut symbols, content);
         let imports = self.extract_imports(&root_node, content)?;
+        if self.mode == ParseMode::SignaturesOnly {
+            keep_signatures_only(&mut symbols);
+            return Ok(ParsedFile {
+                symbols,
+                imports,
+                exports: vec![],
+                design_tokens: vec![],
+                type_definitions: vec![],
+                constants: vec![],
+                schemas: vec![],
+                language: "python".to_string(),
+                tests: vec![],
+                routes: vec![],
+                examples: vec![],
+            });
+        }
         let type_definitions = self.extract_type_definitions(&root_node, content)?;
         let constants = self.extract_constants(&root_node, content)?;
         let schemas = self.extract_schemas(&root_node, content)?;
@@ -43,6 +71,9 @@ impl PythonParser {
             constants,
             schemas,
             language: "python".to_string(),
+            tests: vec![],
+            routes: vec![],
+            examples: vec![],
         })
     }
 
@@ -68,7 +99,7 @@ impl PythonParser {
                 let name = self
                     .get_child_text(node, "name", source)
                     .unwrap_or_else(|| "Anonymous".to_string());
-                let range = self.get_range(node);
+                let range = Range::of(node);
                 let metadata = self.extract_metadata(node, source)?;
 
                 Ok(Some(Symbol {
@@ -85,7 +116,7 @@ impl PythonParser {
                 let name = self
                     .get_child_text(node, "name", source)
                     .unwrap_or_else(|| "anonymous".to_string());
-                let range = self.get_range(node);
+                let range = Range::of(node);
                 let metadata = self.extract_function_metadata(node, source)?;
 
                 Ok(Some(Symbol {
@@ -105,7 +136,7 @@ impl PythonParser {
                     Ok(Some(Symbol {
                         name,
                         kind: SymbolType::Variable,
-                        range: self.get_range(node),
+                        range: Range::of(node),
                         content: text.to_string(),
                         metadata: SymbolMetadata::default(),
                         children: vec![],
@@ -161,7 +192,7 @@ impl PythonParser {
                             members.push(Symbol {
                                 name,
                                 kind: symbol_kind,
-                                range: self.get_range(&func),
+                                range: Range::of(&func),
                                 content: child.utf8_text(source.as_bytes())?.to_string(),
                                 metadata,
                                 children: vec![],
@@ -277,42 +308,75 @@ impl PythonParser {
 
         for child in node.children(&mut cursor) {
             if child.kind() == "import_statement" {
-                // import x, y
-                let text = child.utf8_text(source.as_bytes())?;
-                imports.push(Import {
-                    source: text.to_string(), // Simplified
-                    names: vec![],
-                    range: self.get_range(&child),
-                });
+                // import x, y.z as w -- one import per module
+                let mut name_cursor = child.walk();
+                for name_node in child.children_by_field_name("name", &mut name_cursor) {
+                    let import_name = self.import_name(&name_node, source)?;
+                    imports.push(Import {
+                        source: import_name.name.clone(),
+                        names: vec![ImportName {
+                            is_namespace: true,
+                            ..import_name
+                        }],
+                        range: Range::of(&child),
+                    });
+                }
             } else if child.kind() == "import_from_statement" {
-                // from x import y
+                // from x import y, (a, b as c), *
                 let module_name = self
                     .get_child_text(&child, "module_name", source)
                     .unwrap_or_default();
+
+                let mut names = Vec::new();
+                let mut name_cursor = child.walk();
+                for name_node in child.children_by_field_name("name", &mut name_cursor) {
+                    names.push(self.import_name(&name_node, source)?);
+                }
+                let mut inner = child.walk();
+                if child.children(&mut inner).any(|c| c.kind() == "wildcard_import") {
+                    names.push(ImportName {
+                        name: "*".to_string(),
+                        alias: None,
+                        is_default: false,
+                        is_namespace: true,
+                        is_type: false,
+                    });
+                }
+
                 imports.push(Import {
                     source: module_name,
-                    names: vec![],
-                    range: self.get_range(&child),
+                    names,
+                    range: Range::of(&child),
                 });
             }
         }
         Ok(imports)
     }
 
+    /// Name from a `dotted_name` or `aliased_import` (`x as y`) node
+    fn import_name(&self, node: &Node, source: &str) -> Result<ImportName> {
+        let (name, alias) = if node.kind() == "aliased_import" {
+            (
+                self.get_child_text(node, "name", source).unwrap_or_default(),
+                self.get_child_text(node, "alias", source),
+            )
+        } else {
+            (node.utf8_text(source.as_bytes())?.to_string(), None)
+        };
+        Ok(ImportName {
+            name,
+            alias,
+            is_default: false,
+            is_namespace: false,
+            is_type: false,
+        })
+    }
+
     fn get_child_text(&self, node: &Node, field: &str, source: &str) -> Option<String> {
         node.child_by_field_name(field)
             .map(|n| n.utf8_text(source.as_bytes()).unwrap().to_string())
     }
 
-    fn get_range(&self, node: &Node) -> Range {
-        Range {
-            start_line: node.start_position().row + 1,
-            end_line: node.end_position().row + 1,
-            start_byte: node.start_byte(),
-            end_byte: node.end_byte(),
-        }
-    }
-
     fn extract_decorators(&self, node: &Node, source: &str) -> Result<Vec<String>> {
         let mut decorators = Vec::new();
         let mut cursor = node.walk();
@@ -415,6 +479,7 @@ impl PythonParser {
                                         type_annotation,
                                         is_optional: false,
                                         description: None,
+                                        value: None,
                                     });
                                 }
                             }
@@ -437,6 +502,7 @@ impl PythonParser {
                             type_annotation: signature,
                             is_optional: false,
                             description: None,
+                            value: None,
                         });
                     }
                     _ => {}
@@ -444,13 +510,24 @@ impl PythonParser {
             }
         }
 
+        // ORM columns are rarely annotated; type them from the field/column declaration
+        if let Some((ModelKind::Django | ModelKind::SqlAlchemy, fields)) = self.model_fields(node, source)? {
+            for property in &mut properties {
+                if let Some(field) = fields.iter().find(|f| f.name == property.name) {
+                    property.type_annotation = field.type_annotation.clone().unwrap_or_else(|| "Any".to_string());
+                    property.is_optional = field.is_optional;
+                    property.description = field.description.clone();
+                }
+            }
+        }
+
         Ok(Some(TypeDefinition {
             name,
             kind: TypeKind::Interface, // Python classes are like interfaces
             definition: node.utf8_text(source.as_bytes())?.to_string(),
             properties,
             generic_params,
-            range: self.get_range(node),
+            range: Range::of(node),
         }))
     }
 
@@ -471,7 +548,7 @@ impl PythonParser {
             definition: node.utf8_text(source.as_bytes())?.to_string(),
             properties: vec![],
             generic_params,
-            range: self.get_range(node),
+            range: Range::of(node),
         }))
     }
 
@@ -527,7 +604,7 @@ impl PythonParser {
                             value,
                             type_annotation,
                             category,
-                            range: self.get_range(&child),
+                            range: Range::of(&child),
                         });
                     }
                 }
@@ -542,66 +619,264 @@ impl PythonParser {
         let mut cursor = node.walk();
 
         for child in node.children(&mut cursor) {
-            if child.kind() == "class_definition" {
-                // Check if it's a Pydantic model
-                if let Some(superclasses) = child.child_by_field_name("superclasses") {
-                    let superclass_text = superclasses.utf8_text(source.as_bytes())?;
-                    
-                    if superclass_text.contains("BaseModel") || superclass_text.contains("pydantic") {
-                        let name = self
-                            .get_child_text(&child, "name", source)
-                            .unwrap_or_else(|| "Model".to_string());
-                        
-                        let mut fields = Vec::new();
-                        
-                        // Extract Pydantic fields
-                        if let Some(body) = child.child_by_field_name("body") {
-                            let mut body_cursor = body.walk();
-                            for body_child in body.children(&mut body_cursor) {
-                                if body_child.kind() == "expression_statement" {
-                                    if let Some(assignment) = body_child.child(0) {
-                                        if assignment.kind() == "assignment" {
-                                            if let Some(left) = assignment.child_by_field_name("left") {
-                                                let field_name = left.utf8_text(source.as_bytes())?.to_string();
-                                                
-                                                let type_annotation = assignment
-                                                    .child_by_field_name("type")
-                                                    .map(|n| n.utf8_text(source.as_bytes()).unwrap().to_string());
-                                                
-                                                let default_value = assignment
-                                                    .child_by_field_name("right")
-                                                    .map(|n| n.utf8_text(source.as_bytes()).unwrap().to_string());
-
-                                                fields.push(SchemaField {
-                                                    name: field_name,
-                                                    validation_rules: vec![],
-                                                    is_required: default_value.is_none(),
-                                                    default_value,
-                                                    type_annotation,
-                                                    is_optional: false,
-                                                    validators: vec![],
-                                                    description: None,
-                                                });
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
+            if child.kind() != "class_definition" {
+                continue;
+            }
+            let Some((kind, fields)) = self.model_fields(&child, source)? else {
+                continue;
+            };
+            let name = self
+                .get_child_text(&child, "name", source)
+                .unwrap_or_else(|| "Model".to_string());
+
+            schemas.push(ValidationSchema {
+                name,
+                schema_type: SchemaType::Other(kind.label().to_string()),
+                definition: child.utf8_text(source.as_bytes())?.to_string(),
+                fields,
+                range: Range::of(&child),
+            });
+        }
 
-                        schemas.push(ValidationSchema {
-                            name,
-                            schema_type: SchemaType::Other("Pydantic".to_string()),
-                            definition: child.utf8_text(source.as_bytes())?.to_string(),
-                            fields,
-                            range: self.get_range(&child),
-                        });
+        Ok(schemas)
+    }
+
+    /// Which kind of model `class` declares, from its base classes (or a `__tablename__`
+    /// for SQLAlchemy models inheriting from a project-specific `Base`)
+    fn model_kind(&self, class: &Node, source: &str) -> Option<ModelKind> {
+        let superclasses = class
+            .child_by_field_name("superclasses")
+            .and_then(|n| n.utf8_text(source.as_bytes()).ok())?;
+
+        if superclasses.contains("BaseModel") || superclasses.contains("pydantic") {
+            Some(ModelKind::Pydantic)
+        } else if superclasses.contains("models.Model") {
+            Some(ModelKind::Django)
+        } else if superclasses.contains("DeclarativeBase")
+            || superclasses.contains("db.Model")
+            || self.declares_tablename(class, source)
+        {
+            Some(ModelKind::SqlAlchemy)
+        } else {
+            None
+        }
+    }
+
+    fn declares_tablename(&self, class: &Node, source: &str) -> bool {
+        self.class_assignments(class)
+            .iter()
+            .any(|a| a.child_by_field_name("left").and_then(|l| l.utf8_text(source.as_bytes()).ok()) == Some("__tablename__"))
+    }
+
+    /// `name = ...` / `name: T = ...` statements directly in a class body
+    fn class_assignments<'t>(&self, class: &Node<'t>) -> Vec<Node<'t>> {
+        let Some(body) = class.child_by_field_name("body") else {
+            return Vec::new();
+        };
+        let mut cursor = body.walk();
+        body.children(&mut cursor)
+            .filter(|c| c.kind() == "expression_statement")
+            .filter_map(|c| c.child(0))
+            .filter(|a| a.kind() == "assignment")
+            .collect()
+    }
+
+    /// Fields of a Pydantic, Django or SQLAlchemy model class, or `None` for other classes
+    fn model_fields(&self, class: &Node, source: &str) -> Result<Option<(ModelKind, Vec<SchemaField>)>> {
+        let Some(kind) = self.model_kind(class, source) else {
+            return Ok(None);
+        };
+
+        let mut fields = Vec::new();
+        for assignment in self.class_assignments(class) {
+            let Some(left) = assignment.child_by_field_name("left") else {
+                continue;
+            };
+            let field_name = left.utf8_text(source.as_bytes())?.to_string();
+            let annotation = assignment
+                .child_by_field_name("type")
+                .map(|n| n.utf8_text(source.as_bytes()).unwrap().to_string());
+            let right = assignment.child_by_field_name("right");
+
+            let field = match kind {
+                ModelKind::Pydantic => {
+                    let default_value = right.map(|n| n.utf8_text(source.as_bytes()).unwrap().to_string());
+                    Some(SchemaField {
+                        name: field_name,
+                        validation_rules: vec![],
+                        is_required: default_value.is_none(),
+                        default_value,
+                        type_annotation: annotation,
+                        is_optional: false,
+                        validators: vec![],
+                        description: None,
+                    })
+                }
+                ModelKind::Django => right.and_then(|call| self.django_field(field_name, &call, source)),
+                ModelKind::SqlAlchemy => {
+                    right.and_then(|call| self.sqlalchemy_field(field_name, annotation.as_deref(), &call, source))
+                }
+            };
+            fields.extend(field);
+        }
+
+        Ok(Some((kind, fields)))
+    }
+
+    /// `name = models.CharField(max_length=100, blank=True)`: the field class is the type,
+    /// keyword arguments become rules, and a relation's target becomes `references=<Model>`
+    fn django_field(&self, name: String, call: &Node, source: &str) -> Option<SchemaField> {
+        let CallParts { callee, positional, keywords } = self.call_parts(call, source)?;
+        let field_type = callee.strip_prefix("models.")?.to_string();
+
+        let mut validation_rules = Vec::new();
+        if let Some(target) = positional.first().filter(|_| DJANGO_RELATIONS.contains(&field_type.as_str())) {
+            validation_rules.push(format!("references={}", target.trim_matches(|c| c == '"' || c == '\'')));
+        }
+        let mut description = None;
+        let mut default_value = None;
+        let mut is_optional = false;
+        for (key, value) in keywords {
+            match key.as_str() {
+                "default" => default_value = Some(value),
+                "help_text" | "verbose_name" => {
+                    description = Some(value.trim_matches(|c| c == '"' || c == '\'').to_string())
+                }
+                "null" | "blank" if value == "True" => {
+                    is_optional = true;
+                    validation_rules.push(format!("{}={}", key, value));
+                }
+                _ => validation_rules.push(format!("{}={}", key, value)),
+            }
+        }
+
+        Some(SchemaField {
+            name,
+            is_required: !is_optional && default_value.is_none(),
+            validators: validation_rules.clone(),
+            validation_rules,
+            default_value,
+            type_annotation: Some(field_type),
+            is_optional,
+            description,
+        })
+    }
+
+    /// `name = Column(String(50), nullable=False)` or
+    /// `name: Mapped[Optional[str]] = mapped_column(String(50))`: the column type (or the
+    /// `Mapped[...]` annotation) is the type, `ForeignKey(...)` becomes `foreign_key=<target>`
+    fn sqlalchemy_field(&self, name: String, annotation: Option<&str>, call: &Node, source: &str) -> Option<SchemaField> {
+        let CallParts { callee, positional, keywords } = self.call_parts(call, source)?;
+        let callee = callee.rsplit('.').next().unwrap_or(&callee);
+        if !matches!(callee, "Column" | "mapped_column") {
+            return None;
+        }
+
+        let mapped_type = annotation
+            .and_then(|a| a.strip_prefix("Mapped["))
+            .and_then(|a| a.strip_suffix(']'));
+        let mut validation_rules = Vec::new();
+        let mut column_type = None;
+        for arg in positional {
+            match arg.strip_prefix("ForeignKey(").and_then(|a| a.strip_suffix(')')) {
+                Some(target) => validation_rules.push(format!("foreign_key={}", target.trim_matches(|c| c == '"' || c == '\''))),
+                None if column_type.is_none() && !arg.starts_with('"') && !arg.starts_with('\'') => column_type = Some(arg),
+                None => {}
+            }
+        }
+
+        let mut default_value = None;
+        let mut description = None;
+        let mut nullable = None;
+        let mut primary_key = false;
+        for (key, value) in keywords {
+            match key.as_str() {
+                "default" | "server_default" => default_value = Some(value),
+                "doc" | "comment" => description = Some(value.trim_matches(|c| c == '"' || c == '\'').to_string()),
+                "nullable" => {
+                    nullable = Some(value == "True");
+                    validation_rules.push(format!("nullable={}", value));
+                }
+                _ => {
+                    primary_key |= key == "primary_key" && value == "True";
+                    validation_rules.push(format!("{}={}", key, value));
+                }
+            }
+        }
+        // Columns are nullable unless they're primary keys, `nullable=False`, or typed
+        // `Mapped[T]` without `Optional`
+        let is_optional = nullable.unwrap_or(match mapped_type {
+            Some(mapped) => mapped.starts_with("Optional[") || mapped.contains("None"),
+            None => !primary_key,
+        });
+
+        Some(SchemaField {
+            name,
+            is_required: !is_optional && default_value.is_none(),
+            validators: validation_rules.clone(),
+            validation_rules,
+            default_value,
+            type_annotation: column_type.or_else(|| mapped_type.map(str::to_string)),
+            is_optional,
+            description,
+        })
+    }
+
+    fn call_parts(&self, call: &Node, source: &str) -> Option<CallParts> {
+        if call.kind() != "call" {
+            return None;
+        }
+        let text = |n: Node| n.utf8_text(source.as_bytes()).unwrap_or("").to_string();
+        let callee = text(call.child_by_field_name("function")?);
+        let arguments = call.child_by_field_name("arguments")?;
+
+        let mut positional = Vec::new();
+        let mut keywords = Vec::new();
+        let mut cursor = arguments.walk();
+        for arg in arguments.named_children(&mut cursor) {
+            match arg.kind() {
+                "keyword_argument" => {
+                    if let (Some(key), Some(value)) = (arg.child_by_field_name("name"), arg.child_by_field_name("value")) {
+                        keywords.push((text(key), text(value)));
                     }
                 }
+                "comment" => {}
+                _ => positional.push(text(arg)),
             }
         }
+        Some(CallParts {
+            callee,
+            positional,
+            keywords,
+        })
+    }
+}
 
-        Ok(schemas)
+/// Callee text, positional argument texts and `(keyword, value)` pairs of a call
+struct CallParts {
+    callee: String,
+    positional: Vec<String>,
+    keywords: Vec<(String, String)>,
+}
+
+/// Django field classes whose first positional argument is the related model
+const DJANGO_RELATIONS: [&str; 3] = ["ForeignKey", "OneToOneField", "ManyToManyField"];
+
+/// Model frameworks whose classes are extracted as schemas
+#[derive(Debug, Clone, Copy)]
+enum ModelKind {
+    Pydantic,
+    Django,
+    SqlAlchemy,
+}
+
+impl ModelKind {
+    fn label(self) -> &'static str {
+        match self {
+            ModelKind::Pydantic => "Pydantic",
+            ModelKind::Django => "Django",
+            ModelKind::SqlAlchemy => "SQLAlchemy",
+        }
     }
 }
 
@@ -610,3 +885,131 @@ impl Default for PythonParser {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_import_parenthesized_with_alias() {
+        let parser = PythonParser::new();
+        let parsed = parser
+            .parse("from a.b import (\n    C,\n    D as E,\n)\n")
+            .unwrap();
+
+        assert_eq!(parsed.imports.len(), 1);
+        let import = &parsed.imports[0];
+        assert_eq!(import.source, "a.b");
+        let names: Vec<(&str, Option<&str>)> = import
+            .names
+            .iter()
+            .map(|n| (n.name.as_str(), n.alias.as_deref()))
+            .collect();
+        assert_eq!(names, vec![("C", None), ("D", Some("E"))]);
+    }
+
+    fn field<'a>(schema: &'a ValidationSchema, name: &str) -> &'a SchemaField {
+        schema.fields.iter().find(|f| f.name == name).unwrap_or_else(|| panic!("no field {}", name))
+    }
+
+    #[test]
+    fn test_django_model_fields_extracted() {
+        let parsed = PythonParser::new()
+            .parse(
+                r#"
+from django.db import models
+
+class Article(models.Model):
+    title = models.CharField(max_length=200, unique=True, help_text="Headline")
+    body = models.TextField(blank=True)
+    author = models.ForeignKey("auth.User", on_delete=models.CASCADE)
+    views = models.IntegerField(default=0)
+
+    class Meta:
+        ordering = ["-id"]
+
+    def __str__(self):
+        return self.title
+"#,
+            )
+            .unwrap();
+
+        let schema = &parsed.schemas[0];
+        assert_eq!(schema.name, "Article");
+        assert!(matches!(&schema.schema_type, SchemaType::Other(kind) if kind == "Django"));
+        assert_eq!(schema.fields.len(), 4);
+
+        let title = field(schema, "title");
+        assert_eq!(title.type_annotation.as_deref(), Some("CharField"));
+        assert_eq!(title.validation_rules, vec!["max_length=200", "unique=True"]);
+        assert_eq!(title.description.as_deref(), Some("Headline"));
+        assert!(title.is_required);
+        assert!(field(schema, "body").is_optional);
+        assert_eq!(
+            field(schema, "author").validation_rules,
+            vec!["references=auth.User", "on_delete=models.CASCADE"]
+        );
+        let views = field(schema, "views");
+        assert_eq!(views.default_value.as_deref(), Some("0"));
+        assert!(!views.is_required);
+
+        let type_def = parsed.type_definitions.iter().find(|t| t.name == "Article").unwrap();
+        let title_property = type_def.properties.iter().find(|p| p.name == "title").unwrap();
+        assert_eq!(title_property.type_annotation, "CharField");
+    }
+
+    #[test]
+    fn test_sqlalchemy_model_fields_extracted() {
+        let parsed = PythonParser::new()
+            .parse(
+                r#"
+from sqlalchemy import Column, ForeignKey, Integer, String
+from sqlalchemy.orm import Mapped, mapped_column
+
+class Order(Base):
+    __tablename__ = "orders"
+
+    id = Column(Integer, primary_key=True)
+    customer_id = Column(Integer, ForeignKey("customers.id"), nullable=False)
+    note = Column(String(500))
+    status: Mapped[str] = mapped_column(String(20), default="pending")
+    shipped_at: Mapped[Optional[datetime]] = mapped_column()
+"#,
+            )
+            .unwrap();
+
+        let schema = &parsed.schemas[0];
+        assert!(matches!(&schema.schema_type, SchemaType::Other(kind) if kind == "SQLAlchemy"));
+        let names: Vec<&str> = schema.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "customer_id", "note", "status", "shipped_at"]);
+
+        let id = field(schema, "id");
+        assert_eq!(id.type_annotation.as_deref(), Some("Integer"));
+        assert!(id.is_required);
+        let customer = field(schema, "customer_id");
+        assert_eq!(customer.validation_rules, vec!["foreign_key=customers.id", "nullable=False"]);
+        assert!(customer.is_required);
+        let note = field(schema, "note");
+        assert_eq!(note.type_annotation.as_deref(), Some("String(500)"));
+        assert!(note.is_optional);
+        let status = field(schema, "status");
+        assert_eq!(status.default_value.as_deref(), Some("\"pending\""));
+        assert!(!status.is_optional);
+        let shipped = field(schema, "shipped_at");
+        assert_eq!(shipped.type_annotation.as_deref(), Some("Optional[datetime]"));
+        assert!(shipped.is_optional);
+    }
+
+    #[test]
+    fn test_import_module_with_alias() {
+        let parser = PythonParser::new();
+        let parsed = parser.parse("import numpy as np\nimport os, sys\n").unwrap();
+
+        let sources: Vec<&str> = parsed.imports.iter().map(|i| i.source.as_str()).collect();
+        assert_eq!(sources, vec!["numpy", "os", "sys"]);
+        let numpy = &parsed.imports[0].names[0];
+        assert_eq!(numpy.name, "numpy");
+        assert_eq!(numpy.alias.as_deref(), Some("np"));
+        assert!(numpy.is_namespace);
+    }
+}
@@ -0,0 +1,347 @@
+//! HTTP routes and the middleware that guards them: NestJS controller decorators
+//! (`@Get(':id')` under `@UseGuards(AuthGuard)`), Express-style `router.post('/x', auth, handler)`
+//! calls and Axum `Router` chains with `.layer(...)`. Knowing the chain lets a worker answer
+//! "is the /admin route protected" without reading every handler.
+
+use crate::types::{ApiRoute, Range};
+use tree_sitter::Node;
+
+/// Methods of Express-style routers that register a route
+const EXPRESS_METHODS: [&str; 8] = ["get", "post", "put", "patch", "delete", "options", "head", "all"];
+
+/// NestJS decorators that contribute to a route's guard chain
+const NEST_MIDDLEWARE_DECORATORS: [&str; 2] = ["UseGuards", "UseInterceptors"];
+
+/// Axum router methods whose argument wraps every route added before them
+const AXUM_LAYER_METHODS: [&str; 2] = ["layer", "route_layer"];
+
+/// Routes declared in a TypeScript/JavaScript file, in source order
+pub(crate) fn typescript_routes(root: &Node, source: &str) -> Vec<ApiRoute> {
+    let mut routes = Vec::new();
+    // Middleware registered with `<router>.use(...)`, applied to that router's later routes
+    let mut router_middleware: Vec<(String, Vec<String>)> = Vec::new();
+    visit(*root, &mut |node| match node.kind() {
+        "class_declaration" => routes.extend(nest_routes(node, source)),
+        "call_expression" => {
+            if let Some(route) = express_route(node, source, &mut router_middleware) {
+                routes.push(route);
+            }
+        }
+        _ => {}
+    });
+    routes
+}
+
+/// Routes declared on Axum routers in a Rust file, in source order
+pub(crate) fn rust_routes(root: &Node, source: &str) -> Vec<ApiRoute> {
+    let mut routes = Vec::new();
+    visit(*root, &mut |node| {
+        // Start from the outermost call of each chain; inner calls are walked by `axum_chain`
+        let in_chain = node.parent().is_some_and(|parent| parent.kind() == "field_expression");
+        if node.kind() == "call_expression" && !in_chain && chain_method(node, source).is_some() {
+            routes.extend(axum_chain(node, source));
+        }
+    });
+    routes
+}
+
+fn visit<'a>(node: Node<'a>, f: &mut impl FnMut(Node<'a>)) {
+    f(node);
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        visit(child, f);
+    }
+}
+
+fn text<'a>(node: Node, source: &'a str) -> &'a str {
+    &source[node.byte_range()]
+}
+
+fn unquote(literal: &str) -> String {
+    literal.trim_matches(|c| c == '\'' || c == '"' || c == '`').to_string()
+}
+
+/// `prefix` and `path` joined with single slashes, always absolute
+fn join_path(prefix: &str, path: &str) -> String {
+    let joined: Vec<&str> = prefix.split('/').chain(path.split('/')).filter(|s| !s.is_empty()).collect();
+    format!("/{}", joined.join("/"))
+}
+
+/// Named arguments of a call, without comments
+fn arguments<'a>(call: Node<'a>) -> Vec<Node<'a>> {
+    let Some(args) = call.child_by_field_name("arguments") else {
+        return Vec::new();
+    };
+    let mut cursor = args.walk();
+    args.named_children(&mut cursor).filter(|arg| arg.kind() != "comment").collect()
+}
+
+/// Name and arguments of a `@Name(args)` decorator
+fn decorator_call<'a>(decorator: Node<'a>, source: &str) -> Option<(String, Vec<Node<'a>>)> {
+    let call = decorator.named_child(0).filter(|n| n.kind() == "call_expression")?;
+    let name = text(call.child_by_field_name("function")?, source).to_string();
+    Some((name, arguments(call)))
+}
+
+/// Decorators attached to a class, including those written before `export`
+fn class_decorators<'a>(class: Node<'a>) -> Vec<Node<'a>> {
+    let mut decorators = Vec::new();
+    let exported = class.parent().filter(|p| p.kind() == "export_statement");
+    for owner in exported.into_iter().chain(Some(class)) {
+        let mut cursor = owner.walk();
+        decorators.extend(owner.children_by_field_name("decorator", &mut cursor));
+    }
+    decorators
+}
+
+/// Argument texts of the middleware decorators among `decorators`
+fn nest_middleware(decorators: &[Node], source: &str) -> Vec<String> {
+    decorators
+        .iter()
+        .filter_map(|d| decorator_call(*d, source))
+        .filter(|(name, _)| NEST_MIDDLEWARE_DECORATORS.contains(&name.as_str()))
+        .flat_map(|(_, args)| args.into_iter().map(|arg| text(arg, source).to_string()))
+        .collect()
+}
+
+/// Handler methods of a NestJS `@Controller`, with class guards ahead of method guards
+fn nest_routes(class: Node, source: &str) -> Vec<ApiRoute> {
+    let decorators = class_decorators(class);
+    let Some((_, controller_args)) = decorators
+        .iter()
+        .filter_map(|d| decorator_call(*d, source))
+        .find(|(name, _)| name == "Controller")
+    else {
+        return Vec::new();
+    };
+    let prefix = controller_args
+        .first()
+        .filter(|arg| arg.kind() == "string")
+        .map(|arg| unquote(text(*arg, source)))
+        .unwrap_or_default();
+    let class_middleware = nest_middleware(&decorators, source);
+
+    let mut routes = Vec::new();
+    let Some(body) = class.child_by_field_name("body") else {
+        return routes;
+    };
+    // Method decorators are siblings that precede the method in the class body
+    let mut pending: Vec<Node> = Vec::new();
+    let mut cursor = body.walk();
+    for member in body.named_children(&mut cursor) {
+        match member.kind() {
+            "decorator" => pending.push(member),
+            "method_definition" => {
+                let route = pending.iter().filter_map(|d| decorator_call(*d, source)).find_map(|(name, args)| {
+                    let method = match name.as_str() {
+                        "Get" | "Post" | "Put" | "Patch" | "Delete" | "Options" | "Head" | "All" => name.to_uppercase(),
+                        _ => return None,
+                    };
+                    let path = args.first().filter(|a| a.kind() == "string").map(|a| unquote(text(*a, source)));
+                    Some((method, path.unwrap_or_default()))
+                });
+                if let Some((method, path)) = route {
+                    let mut middleware = class_middleware.clone();
+                    middleware.extend(nest_middleware(&pending, source));
+                    routes.push(ApiRoute {
+                        method,
+                        path: join_path(&prefix, &path),
+                        handler: member.child_by_field_name("name").map(|n| text(n, source).to_string()),
+                        middleware,
+                        range: Range::of(&member),
+                    });
+                }
+                pending.clear();
+            }
+            _ => pending.clear(),
+        }
+    }
+    routes
+}
+
+/// `router.post('/x', auth, handler)`: every argument between the path and the final handler
+/// is middleware, after whatever `router.use(...)` registered earlier
+fn express_route(call: Node, source: &str, router_middleware: &mut Vec<(String, Vec<String>)>) -> Option<ApiRoute> {
+    let function = call.child_by_field_name("function").filter(|f| f.kind() == "member_expression")?;
+    let router = text(function.child_by_field_name("object")?, source).to_string();
+    let method = text(function.child_by_field_name("property")?, source);
+    let args = arguments(call);
+
+    if method == "use" {
+        // Path-scoped `use('/admin', ...)` mounts a sub-router rather than guarding this one
+        if args.first().is_some_and(|a| a.kind() != "string" && a.kind() != "template_string") {
+            let texts = args.iter().map(|a| text(*a, source).to_string());
+            match router_middleware.iter_mut().find(|(name, _)| *name == router) {
+                Some((_, middleware)) => middleware.extend(texts),
+                None => router_middleware.push((router, texts.collect())),
+            }
+        }
+        return None;
+    }
+    if !EXPRESS_METHODS.contains(&method) || args.len() < 2 {
+        return None;
+    }
+    let path = args.first().filter(|a| a.kind() == "string" || a.kind() == "template_string")?;
+    let path = unquote(text(*path, source));
+    // `cache.get('key', fallback)` has the same shape; real routes start with `/` or `*`
+    if !path.starts_with('/') && path != "*" {
+        return None;
+    }
+
+    let handler = args.last()?;
+    let mut middleware: Vec<String> = router_middleware
+        .iter()
+        .find(|(name, _)| *name == router)
+        .map(|(_, middleware)| middleware.clone())
+        .unwrap_or_default();
+    middleware.extend(args[1..args.len() - 1].iter().map(|a| text(*a, source).to_string()));
+    Some(ApiRoute {
+        method: method.to_uppercase(),
+        path,
+        // Inline arrow functions have no name to point at
+        handler: matches!(handler.kind(), "identifier" | "member_expression").then(|| text(*handler, source).to_string()),
+        middleware,
+        range: Range::of(&call),
+    })
+}
+
+/// Method name of a `receiver.method(...)` call, when it's one of the Axum router methods
+fn chain_method<'a>(call: Node, source: &'a str) -> Option<&'a str> {
+    let function = call.child_by_field_name("function").filter(|f| f.kind() == "field_expression")?;
+    let method = text(function.child_by_field_name("field")?, source);
+    (method == "route" || AXUM_LAYER_METHODS.contains(&method)).then_some(method)
+}
+
+/// Routes of a `Router::new().route(..).layer(..)` chain. A layer wraps the routes added before
+/// it, so it's appended to each of those.
+fn axum_chain(call: Node, source: &str) -> Vec<ApiRoute> {
+    let Some(function) = call.child_by_field_name("function").filter(|f| f.kind() == "field_expression") else {
+        return Vec::new();
+    };
+    let mut routes = match function.child_by_field_name("value") {
+        Some(receiver) if receiver.kind() == "call_expression" => axum_chain(receiver, source),
+        _ => Vec::new(),
+    };
+    let args = arguments(call);
+    match chain_method(call, source) {
+        Some("route") => {
+            let (Some(path), Some(method_router)) = (args.first(), args.get(1)) else {
+                return routes;
+            };
+            let path = unquote(text(*path, source));
+            for (method, handler) in axum_method_router(*method_router, source) {
+                routes.push(ApiRoute {
+                    method,
+                    path: path.clone(),
+                    handler: Some(handler),
+                    middleware: Vec::new(),
+                    range: Range::of(&call),
+                });
+            }
+        }
+        Some(_) => {
+            if let Some(layer) = args.first() {
+                for route in &mut routes {
+                    route.middleware.push(text(*layer, source).to_string());
+                }
+            }
+        }
+        None => {}
+    }
+    routes
+}
+
+/// `(METHOD, handler)` pairs of a method router such as `get(list).post(create)`
+fn axum_method_router(node: Node, source: &str) -> Vec<(String, String)> {
+    if node.kind() != "call_expression" {
+        return Vec::new();
+    }
+    let Some(function) = node.child_by_field_name("function") else {
+        return Vec::new();
+    };
+    let (mut pairs, method) = match function.kind() {
+        "field_expression" => {
+            let inner = function.child_by_field_name("value").map(|v| axum_method_router(v, source)).unwrap_or_default();
+            (inner, function.child_by_field_name("field").map(|f| text(f, source)))
+        }
+        // `routing::get(handler)`
+        "scoped_identifier" => (Vec::new(), function.child_by_field_name("name").map(|n| text(n, source))),
+        "identifier" => (Vec::new(), Some(text(function, source))),
+        _ => (Vec::new(), None),
+    };
+    if let (Some(method), Some(handler)) = (method, arguments(node).first()) {
+        if EXPRESS_METHODS.contains(&method) || method == "any" {
+            pairs.push((method.to_uppercase(), text(*handler, source).to_string()));
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_rust, parse_typescript};
+
+    #[test]
+    fn test_nest_guards_combine_controller_and_method_decorators() {
+        let code = r#"
+@Controller('admin')
+@UseGuards(AuthGuard)
+export class AdminController {
+  @Get(':id')
+  @UseGuards(RolesGuard)
+  findOne(@Param('id') id: string) { return id; }
+
+  @Post()
+  create(@Body() dto: CreateDto) { return dto; }
+}
+"#;
+        let routes = parse_typescript(code, false).unwrap().routes;
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0].method, "GET");
+        assert_eq!(routes[0].path, "/admin/:id");
+        assert_eq!(routes[0].handler.as_deref(), Some("findOne"));
+        assert_eq!(routes[0].middleware, vec!["AuthGuard".to_string(), "RolesGuard".to_string()]);
+        assert_eq!(routes[1].path, "/admin");
+        assert_eq!(routes[1].middleware, vec!["AuthGuard".to_string()]);
+        assert!(routes.iter().all(|r| r.is_guarded()));
+    }
+
+    #[test]
+    fn test_express_middleware_chain_includes_router_use() {
+        let code = r#"
+const router = express.Router();
+router.get('/health', (req, res) => res.send('ok'));
+router.use(requestLogger);
+router.post('/admin/users', authMiddleware, validate(userSchema), createUser);
+cache.get('user', fallback);
+"#;
+        let routes = parse_typescript(code, false).unwrap().routes;
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0].path, "/health");
+        assert!(!routes[0].is_guarded());
+        assert_eq!(routes[0].handler, None);
+        assert_eq!(routes[1].method, "POST");
+        assert_eq!(routes[1].handler.as_deref(), Some("createUser"));
+        assert_eq!(
+            routes[1].middleware,
+            vec!["requestLogger".to_string(), "authMiddleware".to_string(), "validate(userSchema)".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_axum_layer_wraps_routes_added_before_it() {
+        let code = r#"
+fn app() -> Router {
+    Router::new()
+        .route("/admin", get(admin).post(create_admin))
+        .layer(from_fn(require_auth))
+        .route("/health", get(health))
+}
+"#;
+        let routes = parse_rust(code).unwrap().routes;
+        let summary: Vec<(&str, &str, usize)> =
+            routes.iter().map(|r| (r.method.as_str(), r.path.as_str(), r.middleware.len())).collect();
+        assert_eq!(summary, vec![("GET", "/admin", 1), ("POST", "/admin", 1), ("GET", "/health", 0)]);
+        assert_eq!(routes[0].middleware, vec!["from_fn(require_auth)".to_string()]);
+    }
+}
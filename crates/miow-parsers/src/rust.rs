@@ -1,9 +1,13 @@
+use crate::deprecation::tag_deprecated;
+use crate::embed_text::keep_signatures_only;
+use crate::routes::rust_routes;
 use crate::types::*;
 use anyhow::{Context, Result};
 use tree_sitter::{Node, Parser, Query, QueryCursor};
 
 pub struct RustParser {
     parser: Parser,
+    mode: ParseMode,
 }
 
 impl RustParser {
@@ -13,10 +17,24 @@ impl RustParser {
         parser
             .set_language(language)
             .expect("Error loading Rust grammar");
-        Self { parser }
+        Self {
+            parser,
+            mode: ParseMode::Full,
+        }
+    }
+
+    pub fn with_mode(mut self, mode: ParseMode) -> Self {
+        self.mode = mode;
+        self
     }
 
     pub fn parse(&self, content: &str) -> Result<ParsedFile> {
+        self.parse_with_path(content, "")
+    }
+
+    /// Parse a file and qualify its symbols with the module path derived from
+    /// `file_path` (relative to the crate, e.g. `src/config/mod.rs`) and inline `mod` nesting.
+    pub fn parse_with_path(&self, content: &str, file_path: &str) -> Result<ParsedFile> {
         let mut parser = Parser::new();
         parser
             .set_language(tree_sitter_rust::language())
@@ -28,8 +46,26 @@ impl RustParser {
 
         let root_node = tree.root_node();
 
-        let symbols = self.extract_symbols(&root_node, content)?;
+        let mut symbols = self.extract_symbols(&root_node, content)?;
+        qualify_symbols(&mut symbols, &module_path_from_file(file_path));
+        tag_deprecated(&mut symbols, content);
         let imports = self.extract_imports(&root_node, content)?;
+        if self.mode == ParseMode::SignaturesOnly {
+            keep_signatures_only(&mut symbols);
+            return Ok(ParsedFile {
+                symbols,
+                imports,
+                exports: vec![],
+                design_tokens: vec![],
+                type_definitions: vec![],
+                constants: vec![],
+                schemas: vec![],
+                language: "rust".to_string(),
+                tests: vec![],
+                routes: vec![],
+                examples: vec![],
+            });
+        }
         let type_definitions = self.extract_type_definitions(&root_node, content)?;
         let constants = self.extract_constants(&root_node, content)?;
 
@@ -42,6 +78,9 @@ impl RustParser {
             constants,
             schemas: vec![],          // Rust doesn't have runtime validation schemas like Zod
             language: "rust".to_string(),
+            tests: vec![],
+            routes: rust_routes(&root_node, content),
+            examples: vec![],
         })
     }
 
@@ -65,9 +104,9 @@ impl RustParser {
         match kind {
             "struct_item" => {
                 let name = self
-                    .get_child_text(node, "type_identifier", source)
+                    .get_child_text(node, "name", source)
                     .unwrap_or_else(|| "Anonymous".to_string());
-                let range = self.get_range(node);
+                let range = Range::of(node);
                 let metadata = self.extract_metadata(node, source)?;
 
                 Ok(Some(Symbol {
@@ -82,9 +121,9 @@ impl RustParser {
             }
             "enum_item" => {
                 let name = self
-                    .get_child_text(node, "type_identifier", source)
+                    .get_child_text(node, "name", source)
                     .unwrap_or_else(|| "Anonymous".to_string());
-                let range = self.get_range(node);
+                let range = Range::of(node);
                 let metadata = self.extract_metadata(node, source)?;
 
                 Ok(Some(Symbol {
@@ -99,9 +138,9 @@ impl RustParser {
             }
             "function_item" => {
                 let name = self
-                    .get_child_text(node, "identifier", source)
+                    .get_child_text(node, "name", source)
                     .unwrap_or_else(|| "anonymous".to_string());
-                let range = self.get_range(node);
+                let range = Range::of(node);
                 let metadata = self.extract_function_metadata(node, source)?;
 
                 Ok(Some(Symbol {
@@ -116,7 +155,7 @@ impl RustParser {
             }
             "impl_item" => {
                 let type_name = self
-                    .get_child_text(node, "type_identifier", source)
+                    .get_child_text(node, "type", source)
                     .unwrap_or_default();
                 let trait_name = if let Some(trait_node) = node.child_by_field_name("trait") {
                     trait_node.utf8_text(source.as_bytes())?.to_string()
@@ -133,7 +172,7 @@ impl RustParser {
                 Ok(Some(Symbol {
                     name,
                     kind: SymbolType::Class, // Mapping impl to Class-like structure for now
-                    range: self.get_range(node),
+                    range: Range::of(node),
                     content: text.to_string(),
                     metadata: SymbolMetadata::default(),
                     children: self.extract_impl_members(node, source)?,
@@ -142,12 +181,12 @@ impl RustParser {
             }
             "macro_definition" => {
                 let name = self
-                    .get_child_text(node, "identifier", source)
+                    .get_child_text(node, "name", source)
                     .unwrap_or_else(|| "anonymous".to_string());
                 Ok(Some(Symbol {
                     name,
                     kind: SymbolType::Function, // Macros are function-like
-                    range: self.get_range(node),
+                    range: Range::of(node),
                     content: text.to_string(),
                     metadata: SymbolMetadata::default(),
                     children: vec![],
@@ -156,23 +195,26 @@ impl RustParser {
             }
             "mod_item" => {
                 let name = self
-                    .get_child_text(node, "identifier", source)
+                    .get_child_text(node, "name", source)
                     .unwrap_or_else(|| "anonymous".to_string());
                 Ok(Some(Symbol {
                     name,
                     kind: SymbolType::Module,
-                    range: self.get_range(node),
+                    range: Range::of(node),
                     content: text.to_string(),
                     metadata: SymbolMetadata::default(),
-                    children: vec![], // Could recurse if inline mod
+                    children: match node.child_by_field_name("body") {
+                        Some(body) => self.extract_symbols(&body, source)?,
+                        None => vec![],
+                    },
                     references: vec![],
                 }))
             }
             "trait_item" => {
                 let name = self
-                    .get_child_text(node, "type_identifier", source)
+                    .get_child_text(node, "name", source)
                     .unwrap_or_else(|| "Anonymous".to_string());
-                let range = self.get_range(node);
+                let range = Range::of(node);
                 let mut metadata = self.extract_metadata(node, source)?;
                 
                 // Extract generic parameters
@@ -193,12 +235,12 @@ impl RustParser {
             "type_item" => {
                 // Type alias: type MyType = SomeType;
                 let name = self
-                    .get_child_text(node, "type_identifier", source)
+                    .get_child_text(node, "name", source)
                     .unwrap_or_else(|| "Anonymous".to_string());
                 Ok(Some(Symbol {
                     name,
                     kind: SymbolType::TypeParameter,
-                    range: self.get_range(node),
+                    range: Range::of(node),
                     content: text.to_string(),
                     metadata: SymbolMetadata::default(),
                     children: vec![],
@@ -207,7 +249,7 @@ impl RustParser {
             }
             "const_item" => {
                 let name = self
-                    .get_child_text(node, "identifier", source)
+                    .get_child_text(node, "name", source)
                     .unwrap_or_else(|| "CONST".to_string());
                 let mut metadata = self.extract_metadata(node, source)?;
                 
@@ -219,7 +261,7 @@ impl RustParser {
                 Ok(Some(Symbol {
                     name,
                     kind: SymbolType::Constant,
-                    range: self.get_range(node),
+                    range: Range::of(node),
                     content: text.to_string(),
                     metadata,
                     children: vec![],
@@ -228,7 +270,7 @@ impl RustParser {
             }
             "static_item" => {
                 let name = self
-                    .get_child_text(node, "identifier", source)
+                    .get_child_text(node, "name", source)
                     .unwrap_or_else(|| "STATIC".to_string());
                 let mut metadata = self.extract_metadata(node, source)?;
                 metadata.is_static = true;
@@ -240,7 +282,7 @@ impl RustParser {
                 Ok(Some(Symbol {
                     name,
                     kind: SymbolType::Variable,
-                    range: self.get_range(node),
+                    range: Range::of(node),
                     content: text.to_string(),
                     metadata,
                     children: vec![],
@@ -276,7 +318,7 @@ impl RustParser {
                     fields.push(Symbol {
                         name,
                         kind: SymbolType::Field,
-                        range: self.get_range(&child),
+                        range: Range::of(&child),
                         content: child.utf8_text(source.as_bytes())?.to_string(),
                         metadata,
                         children: vec![],
@@ -300,7 +342,7 @@ impl RustParser {
                     variants.push(Symbol {
                         name,
                         kind: SymbolType::EnumMember,
-                        range: self.get_range(&child),
+                        range: Range::of(&child),
                         content: child.utf8_text(source.as_bytes())?.to_string(),
                         metadata: SymbolMetadata::default(),
                         children: vec![],
@@ -326,7 +368,7 @@ impl RustParser {
                     members.push(Symbol {
                         name,
                         kind: SymbolType::Method,
-                        range: self.get_range(&child),
+                        range: Range::of(&child),
                         content: child.utf8_text(source.as_bytes())?.to_string(),
                         metadata,
                         children: vec![],
@@ -413,7 +455,7 @@ impl RustParser {
                 imports.push(Import {
                     source: path.to_string(),
                     names: vec![], // Rust imports are complex, just storing the path for now
-                    range: self.get_range(&child),
+                    range: Range::of(&child),
                 });
             }
         }
@@ -425,15 +467,6 @@ impl RustParser {
             .map(|n| n.utf8_text(source.as_bytes()).unwrap().to_string())
     }
 
-    fn get_range(&self, node: &Node) -> Range {
-        Range {
-            start_line: node.start_position().row + 1,
-            end_line: node.end_position().row + 1,
-            start_byte: node.start_byte(),
-            end_byte: node.end_byte(),
-        }
-    }
-
     fn extract_trait_members(&self, node: &Node, source: &str) -> Result<Vec<Symbol>> {
         let mut members = Vec::new();
         if let Some(body) = node.child_by_field_name("body") {
@@ -449,7 +482,7 @@ impl RustParser {
                         members.push(Symbol {
                             name,
                             kind: SymbolType::Method,
-                            range: self.get_range(&child),
+                            range: Range::of(&child),
                             content: child.utf8_text(source.as_bytes())?.to_string(),
                             metadata,
                             children: vec![],
@@ -458,13 +491,13 @@ impl RustParser {
                     }
                     "associated_type" => {
                         let name = self
-                            .get_child_text(&child, "type_identifier", source)
+                            .get_child_text(&child, "name", source)
                             .unwrap_or_else(|| "AssociatedType".to_string());
                         
                         members.push(Symbol {
                             name,
                             kind: SymbolType::TypeParameter,
-                            range: self.get_range(&child),
+                            range: Range::of(&child),
                             content: child.utf8_text(source.as_bytes())?.to_string(),
                             metadata: SymbolMetadata::default(),
                             children: vec![],
@@ -526,7 +559,7 @@ impl RustParser {
 
     fn extract_struct_type_def(&self, node: &Node, source: &str) -> Result<Option<TypeDefinition>> {
         let name = self
-            .get_child_text(node, "type_identifier", source)
+            .get_child_text(node, "name", source)
             .unwrap_or_else(|| "Anonymous".to_string());
         
         let mut properties = Vec::new();
@@ -555,6 +588,7 @@ impl RustParser {
                         type_annotation,
                         is_optional: false, // Rust doesn't have optional fields in the same way
                         description: None,
+                        value: None,
                     });
                 }
             }
@@ -566,13 +600,13 @@ impl RustParser {
             definition: node.utf8_text(source.as_bytes())?.to_string(),
             properties,
             generic_params,
-            range: self.get_range(node),
+            range: Range::of(node),
         }))
     }
 
     fn extract_enum_type_def(&self, node: &Node, source: &str) -> Result<Option<TypeDefinition>> {
         let name = self
-            .get_child_text(node, "type_identifier", source)
+            .get_child_text(node, "name", source)
             .unwrap_or_else(|| "Anonymous".to_string());
         
         let mut properties = Vec::new();
@@ -599,6 +633,7 @@ impl RustParser {
                         type_annotation: variant_type,
                         is_optional: false,
                         description: None,
+                        value: None,
                     });
                 }
             }
@@ -610,13 +645,13 @@ impl RustParser {
             definition: node.utf8_text(source.as_bytes())?.to_string(),
             properties,
             generic_params,
-            range: self.get_range(node),
+            range: Range::of(node),
         }))
     }
 
     fn extract_type_alias(&self, node: &Node, source: &str) -> Result<Option<TypeDefinition>> {
         let name = self
-            .get_child_text(node, "type_identifier", source)
+            .get_child_text(node, "name", source)
             .unwrap_or_else(|| "Anonymous".to_string());
         
         let mut generic_params = Vec::new();
@@ -632,13 +667,13 @@ impl RustParser {
             definition: node.utf8_text(source.as_bytes())?.to_string(),
             properties: vec![],
             generic_params,
-            range: self.get_range(node),
+            range: Range::of(node),
         }))
     }
 
     fn extract_trait_type_def(&self, node: &Node, source: &str) -> Result<Option<TypeDefinition>> {
         let name = self
-            .get_child_text(node, "type_identifier", source)
+            .get_child_text(node, "name", source)
             .unwrap_or_else(|| "Anonymous".to_string());
         
         let mut properties = Vec::new();
@@ -666,11 +701,12 @@ impl RustParser {
                             type_annotation: signature,
                             is_optional: false,
                             description: None,
+                            value: None,
                         });
                     }
                     "associated_type" => {
                         let type_name = self
-                            .get_child_text(&child, "type_identifier", source)
+                            .get_child_text(&child, "name", source)
                             .unwrap_or_else(|| "AssociatedType".to_string());
                         
                         properties.push(TypeProperty {
@@ -678,6 +714,7 @@ impl RustParser {
                             type_annotation: "type".to_string(),
                             is_optional: false,
                             description: None,
+                            value: None,
                         });
                     }
                     _ => {}
@@ -691,7 +728,7 @@ impl RustParser {
             definition: node.utf8_text(source.as_bytes())?.to_string(),
             properties,
             generic_params,
-            range: self.get_range(node),
+            range: Range::of(node),
         }))
     }
 
@@ -703,7 +740,7 @@ impl RustParser {
             match child.kind() {
                 "const_item" => {
                     let name = self
-                        .get_child_text(&child, "identifier", source)
+                        .get_child_text(&child, "name", source)
                         .unwrap_or_else(|| "CONST".to_string());
                     
                     let type_annotation = child
@@ -720,12 +757,12 @@ impl RustParser {
                         value,
                         type_annotation,
                         category: ConstantCategory::Other,
-                        range: self.get_range(&child),
+                        range: Range::of(&child),
                     });
                 }
                 "static_item" => {
                     let name = self
-                        .get_child_text(&child, "identifier", source)
+                        .get_child_text(&child, "name", source)
                         .unwrap_or_else(|| "STATIC".to_string());
                     
                     let type_annotation = child
@@ -742,7 +779,7 @@ impl RustParser {
                         value,
                         type_annotation,
                         category: ConstantCategory::Config,
-                        range: self.get_range(&child),
+                        range: Range::of(&child),
                     });
                 }
                 _ => {}
@@ -758,3 +795,111 @@ impl Default for RustParser {
         Self::new()
     }
 }
+
+/// Module path of a Rust source file, e.g. `src/config/settings.rs` -> `config::settings`.
+/// Crate roots (`lib.rs`, `main.rs`) and `mod.rs` files map to their directory.
+pub fn module_path_from_file(file_path: &str) -> String {
+    let normalized = file_path.replace('\\', "/");
+    let relative = match normalized.rfind("src/") {
+        Some(idx) => &normalized[idx + 4..],
+        None => normalized.as_str(),
+    };
+
+    let mut segments: Vec<&str> = relative
+        .trim_end_matches(".rs")
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+    if matches!(segments.last(), Some(&"mod") | Some(&"lib") | Some(&"main")) {
+        segments.pop();
+    }
+    segments.join("::")
+}
+
+/// Fill `metadata.qualified_name` for every item below `prefix`. Inline modules extend the
+/// prefix for their children and impl methods are qualified with the implementing type.
+fn qualify_symbols(symbols: &mut [Symbol], prefix: &str) {
+    let qualify = |name: &str| {
+        if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}::{}", prefix, name)
+        }
+    };
+
+    for symbol in symbols {
+        match symbol.kind {
+            SymbolType::Module => {
+                let path = qualify(&symbol.name);
+                qualify_symbols(&mut symbol.children, &path);
+                symbol.metadata.qualified_name = Some(path);
+            }
+            // Impl blocks are named `impl Type` / `impl Trait for Type`
+            SymbolType::Class => {
+                let header = symbol.name.trim_start_matches("impl ");
+                let type_name = header.rsplit(" for ").next().unwrap_or(header).trim();
+                if !type_name.is_empty() {
+                    qualify_symbols(&mut symbol.children, &qualify(type_name));
+                }
+            }
+            _ => {
+                if !prefix.is_empty() {
+                    symbol.metadata.qualified_name = Some(qualify(&symbol.name));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_path_from_file() {
+        assert_eq!(module_path_from_file("src/lib.rs"), "");
+        assert_eq!(module_path_from_file("crates/app/src/config/mod.rs"), "config");
+        assert_eq!(module_path_from_file("src/config/settings.rs"), "config::settings");
+    }
+
+    #[test]
+    fn test_nested_mod_symbols_are_qualified() {
+        let content = r#"
+pub struct Config;
+
+mod server {
+    pub struct Config {
+        port: u16,
+    }
+
+    impl Config {
+        pub fn new() -> Self { Config { port: 80 } }
+    }
+
+    pub mod tls {
+        pub fn load() {}
+    }
+}
+"#;
+        let parsed = RustParser::new().parse_with_path(content, "src/net/mod.rs").unwrap();
+
+        let top = parsed.symbols.iter().find(|s| s.name == "Config").unwrap();
+        assert_eq!(top.metadata.qualified_name.as_deref(), Some("net::Config"));
+
+        let server = parsed.symbols.iter().find(|s| s.name == "server").unwrap();
+        assert_eq!(server.metadata.qualified_name.as_deref(), Some("net::server"));
+        let nested = server.children.iter().find(|s| s.name == "Config").unwrap();
+        assert_eq!(nested.metadata.qualified_name.as_deref(), Some("net::server::Config"));
+
+        let new = server
+            .children
+            .iter()
+            .find(|s| s.name == "impl Config")
+            .and_then(|imp| imp.children.iter().find(|m| m.name == "new"))
+            .unwrap();
+        assert_eq!(new.metadata.qualified_name.as_deref(), Some("net::server::Config::new"));
+
+        let tls = server.children.iter().find(|s| s.name == "tls").unwrap();
+        assert_eq!(tls.children[0].metadata.qualified_name.as_deref(), Some("net::server::tls::load"));
+    }
+}
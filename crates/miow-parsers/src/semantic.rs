@@ -220,7 +220,7 @@ Respond ONLY with valid JSON."#,
             response
         };
         
-        serde_json::from_str(json_str)
+        miow_llm::parse_llm_json(json_str)
             .context("Failed to parse LLM response as SemanticInfo")
     }
     
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use miow_llm::LLMProvider;
+use miow_llm::{parse_llm_json, LLMProvider};
 use std::sync::Arc;
 
 /// Style analyzer - extracts coding patterns and style information
@@ -72,15 +72,7 @@ Return ONLY the JSON, no explanation."#,
         
         let response = llm.generate(&prompt).await?;
         
-        // Try to parse JSON response
-        let clean_response = response.content
-            .trim()
-            .trim_start_matches("```json")
-            .trim_start_matches("```")
-            .trim_end_matches("```")
-            .trim();
-        
-        match serde_json::from_str::<serde_json::Value>(clean_response) {
+        match parse_llm_json::<serde_json::Value>(&response.content) {
             Ok(json) => {
                 let naming = json["naming_convention"]
                     .as_array()
@@ -104,8 +96,9 @@ Return ONLY the JSON, no explanation."#,
                     code_samples: samples,
                 })
             }
-            Err(_) => {
+            Err(e) => {
                 // Fallback to pattern-based if JSON parsing fails
+                tracing::warn!("Style analysis: {:#}", e);
                 Ok(self.analyze_patterns(code_samples, language))
             }
         }
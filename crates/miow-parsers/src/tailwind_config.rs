@@ -0,0 +1,323 @@
+//! Design tokens from a Tailwind config. Class names such as `bg-primary` only say a token is
+//! used; the theme in `tailwind.config.{js,ts}` says what it is. Colors, spacing and font sizes
+//! under `theme` and `theme.extend` become `DesignToken`s with their resolved values, following
+//! nested palettes, top-level constants, member access and spreads of local objects.
+
+use crate::types::*;
+use crate::typescript::TypeScriptParser;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+use tree_sitter::{Node, Parser};
+
+/// Theme sections extracted, with the token type each produces
+const THEME_SECTIONS: [(&str, DesignTokenType); 3] = [
+    ("colors", DesignTokenType::Color),
+    ("spacing", DesignTokenType::Spacing),
+    ("fontSize", DesignTokenType::FontSize),
+];
+
+/// How many identifier/member hops are followed when resolving a value
+const MAX_RESOLVE_DEPTH: usize = 8;
+
+/// Whether `file_path` names a Tailwind config file
+pub fn is_tailwind_config(file_path: &str) -> bool {
+    let file_name = Path::new(file_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    file_name
+        .strip_prefix("tailwind.config.")
+        .is_some_and(|ext| matches!(ext, "js" | "cjs" | "mjs" | "ts" | "cts" | "mts"))
+}
+
+/// Parse a Tailwind config as TypeScript and add its theme tokens to `design_tokens`
+pub fn parse_tailwind_config(content: &str) -> Result<ParsedFile> {
+    let mut parsed = TypeScriptParser::new().parse(content, false)?;
+    parsed.design_tokens.extend(extract_tailwind_tokens(content)?);
+    Ok(parsed)
+}
+
+/// Theme colors, spacing and font sizes of a Tailwind config. Nested palettes are flattened
+/// Tailwind-style (`brand: { DEFAULT, 500 }` → `brand`, `brand-500`); values that can't be
+/// resolved statically keep their source text.
+pub fn extract_tailwind_tokens(content: &str) -> Result<Vec<DesignToken>> {
+    let mut parser = Parser::new();
+    parser
+        .set_language(tree_sitter_typescript::language_typescript())
+        .context("Failed to set TypeScript language")?;
+    let tree = parser
+        .parse(content, None)
+        .context("Failed to parse Tailwind config")?;
+
+    let extractor = ThemeExtractor::new(tree.root_node(), content);
+    let Some(theme) = extractor.find_theme(tree.root_node()) else {
+        return Ok(Vec::new());
+    };
+
+    let mut tokens = Vec::new();
+    for (section, token_type) in THEME_SECTIONS {
+        if let Some(object) = extractor.property(theme, section) {
+            extractor.flatten(object, "", &format!("tailwind.theme.{}", section), &token_type, &mut tokens, 0);
+        }
+        if let Some(object) = extractor.property(theme, "extend").and_then(|extend| extractor.property(extend, section)) {
+            extractor.flatten(object, "", &format!("tailwind.theme.extend.{}", section), &token_type, &mut tokens, 0);
+        }
+    }
+    Ok(tokens)
+}
+
+/// A value in the config, after following identifiers and member access
+enum Resolved<'a> {
+    Object(Node<'a>),
+    Value(String),
+}
+
+struct ThemeExtractor<'a> {
+    source: &'a str,
+    /// Top-level `const`/`let`/`var` initializers by name
+    declarations: HashMap<String, Node<'a>>,
+}
+
+impl<'a> ThemeExtractor<'a> {
+    fn new(root: Node<'a>, source: &'a str) -> Self {
+        let mut declarations = HashMap::new();
+        let mut cursor = root.walk();
+        for statement in root.named_children(&mut cursor) {
+            let declaration = match statement.kind() {
+                "export_statement" => statement.child_by_field_name("declaration"),
+                _ => Some(statement),
+            };
+            let Some(declaration) = declaration.filter(|d| matches!(d.kind(), "lexical_declaration" | "variable_declaration")) else {
+                continue;
+            };
+            let mut declarators = declaration.walk();
+            for declarator in declaration.named_children(&mut declarators) {
+                if let (Some(name), Some(value)) = (declarator.child_by_field_name("name"), declarator.child_by_field_name("value")) {
+                    declarations.insert(text(name, source).to_string(), value);
+                }
+            }
+        }
+        Self { source, declarations }
+    }
+
+    /// The first object with a `theme` property, wherever the config object is declared
+    fn find_theme(&self, node: Node<'a>) -> Option<Node<'a>> {
+        if node.kind() == "object" {
+            if let Some(theme) = self.property(node, "theme") {
+                return Some(theme);
+            }
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node<'a>> = node.named_children(&mut cursor).collect();
+        children.into_iter().find_map(|child| self.find_theme(child))
+    }
+
+    /// Object value of property `key` of `object`; the last definition wins
+    fn property(&self, object: Node<'a>, key: &str) -> Option<Node<'a>> {
+        let mut found = None;
+        self.for_each_entry(object, 0, &mut |name, value| {
+            if name == key {
+                found = Some(value);
+            }
+        });
+        match self.resolve(found?, 0)? {
+            Resolved::Object(object) => Some(object),
+            Resolved::Value(_) => None,
+        }
+    }
+
+    /// Visit `(key, value)` of every pair and shorthand property, expanding spreads of
+    /// resolvable objects in place
+    fn for_each_entry(&self, object: Node<'a>, depth: usize, visit: &mut dyn FnMut(String, Node<'a>)) {
+        let mut cursor = object.walk();
+        for entry in object.named_children(&mut cursor) {
+            match entry.kind() {
+                "pair" => {
+                    if let (Some(key), Some(value)) = (entry.child_by_field_name("key"), entry.child_by_field_name("value")) {
+                        visit(unquote(text(key, self.source)), value);
+                    }
+                }
+                "shorthand_property_identifier" => visit(text(entry, self.source).to_string(), entry),
+                "spread_element" if depth < MAX_RESOLVE_DEPTH => {
+                    if let Some(Resolved::Object(spread)) = entry.named_child(0).and_then(|arg| self.resolve(arg, depth + 1)) {
+                        self.for_each_entry(spread, depth + 1, visit);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn resolve(&self, node: Node<'a>, depth: usize) -> Option<Resolved<'a>> {
+        if depth > MAX_RESOLVE_DEPTH {
+            return None;
+        }
+        let raw = text(node, self.source);
+        match node.kind() {
+            "object" => Some(Resolved::Object(node)),
+            "string" => Some(Resolved::Value(unquote(raw))),
+            "template_string" if !has_child_kind(node, "template_substitution") => Some(Resolved::Value(unquote(raw))),
+            // `fontSize: ['2rem', { lineHeight: '2.5rem' }]`: the size comes first
+            "array" => node.named_child(0).and_then(|first| self.resolve(first, depth + 1)),
+            "parenthesized_expression" | "as_expression" | "satisfies_expression" => {
+                node.named_child(0).and_then(|inner| self.resolve(inner, depth + 1))
+            }
+            "identifier" | "shorthand_property_identifier" => match self.declarations.get(raw) {
+                Some(value) => self.resolve(*value, depth + 1),
+                None => Some(Resolved::Value(raw.to_string())),
+            },
+            "member_expression" | "subscript_expression" => {
+                let field = node
+                    .child_by_field_name("property")
+                    .or_else(|| node.child_by_field_name("index"))
+                    .map(|key| unquote(text(key, self.source)));
+                let target = node
+                    .child_by_field_name("object")
+                    .and_then(|object| self.resolve(object, depth + 1));
+                match (target, field) {
+                    (Some(Resolved::Object(object)), Some(field)) => {
+                        let mut value = None;
+                        self.for_each_entry(object, depth + 1, &mut |name, entry| {
+                            if name == field {
+                                value = Some(entry);
+                            }
+                        });
+                        match value {
+                            Some(value) => self.resolve(value, depth + 1),
+                            None => Some(Resolved::Value(raw.to_string())),
+                        }
+                    }
+                    _ => Some(Resolved::Value(raw.to_string())),
+                }
+            }
+            _ => Some(Resolved::Value(raw.to_string())),
+        }
+    }
+
+    /// Emit a token for every leaf under `object`, prefixing nested keys with `prefix`
+    fn flatten(
+        &self,
+        object: Node<'a>,
+        prefix: &str,
+        context: &str,
+        token_type: &DesignTokenType,
+        tokens: &mut Vec<DesignToken>,
+        depth: usize,
+    ) {
+        let mut entries = Vec::new();
+        self.for_each_entry(object, depth, &mut |name, value| entries.push((name, value)));
+        for (key, value) in entries {
+            let name = match (prefix.is_empty(), key.as_str()) {
+                (true, _) => key.clone(),
+                (false, "DEFAULT") => prefix.to_string(),
+                (false, _) => format!("{}-{}", prefix, key),
+            };
+            match self.resolve(value, depth) {
+                Some(Resolved::Object(nested)) if depth < MAX_RESOLVE_DEPTH => {
+                    self.flatten(nested, &name, context, token_type, tokens, depth + 1);
+                }
+                Some(Resolved::Value(value_text)) => {
+                    tokens.retain(|t: &DesignToken| !(t.name == name && t.context == context));
+                    tokens.push(DesignToken {
+                        token_type: token_type.clone(),
+                        name,
+                        value: value_text,
+                        context: context.to_string(),
+                        range: Range::of(&value.parent().unwrap_or(value)),
+                        language: None,
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn text<'a>(node: Node, source: &'a str) -> &'a str {
+    &source[node.byte_range()]
+}
+
+fn has_child_kind(node: Node, kind: &str) -> bool {
+    let mut cursor = node.walk();
+    let found = node.named_children(&mut cursor).any(|child| child.kind() == kind);
+    found
+}
+
+fn unquote(raw: &str) -> String {
+    raw.trim_matches(|c| c == '"' || c == '\'' || c == '`').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token<'t>(tokens: &'t [DesignToken], name: &str) -> &'t DesignToken {
+        tokens
+            .iter()
+            .find(|t| t.name == name)
+            .unwrap_or_else(|| panic!("no token {} in {:?}", name, tokens.iter().map(|t| &t.name).collect::<Vec<_>>()))
+    }
+
+    #[test]
+    fn test_custom_colors_resolve_to_values() {
+        let config = r##"
+const defaultTheme = require('tailwindcss/defaultTheme');
+const brand = '#1d4ed8';
+const palette = { accent: { light: '#fde68a', dark: '#b45309' } };
+
+/** @type {import('tailwindcss').Config} */
+module.exports = {
+  content: ['./src/**/*.{ts,tsx}'],
+  theme: {
+    extend: {
+      colors: {
+        primary: { DEFAULT: brand, 500: "#3b82f6", foreground: 'hsl(0 0% 100%)' },
+        danger: `#dc2626`,
+        warning: palette.accent.dark,
+        ...palette,
+        border: defaultTheme.colors.gray[200],
+      },
+      spacing: { '18': '4.5rem' },
+      fontSize: { hero: ['3.5rem', { lineHeight: '1.1' }] },
+    },
+  },
+};
+"##;
+        let tokens = extract_tailwind_tokens(config).unwrap();
+
+        let primary = token(&tokens, "primary");
+        assert_eq!(primary.value, "#1d4ed8");
+        assert!(matches!(primary.token_type, DesignTokenType::Color));
+        assert_eq!(primary.context, "tailwind.theme.extend.colors");
+        assert_eq!(token(&tokens, "primary-500").value, "#3b82f6");
+        assert_eq!(token(&tokens, "primary-foreground").value, "hsl(0 0% 100%)");
+        assert_eq!(token(&tokens, "danger").value, "#dc2626");
+        assert_eq!(token(&tokens, "warning").value, "#b45309");
+        assert_eq!(token(&tokens, "accent-light").value, "#fde68a");
+        // Not resolvable locally: the expression is kept
+        assert_eq!(token(&tokens, "border").value, "defaultTheme.colors.gray[200]");
+
+        let spacing = token(&tokens, "18");
+        assert_eq!(spacing.value, "4.5rem");
+        assert!(matches!(spacing.token_type, DesignTokenType::Spacing));
+        assert_eq!(token(&tokens, "hero").value, "3.5rem");
+    }
+
+    #[test]
+    fn test_typescript_config_and_file_detection() {
+        let config = r#"
+import type { Config } from 'tailwindcss';
+
+export default {
+  theme: { colors: { ink: '#111827' } },
+} satisfies Config;
+"#;
+        let parsed = parse_tailwind_config(config).unwrap();
+        assert_eq!(token(&parsed.design_tokens, "ink").context, "tailwind.theme.colors");
+
+        assert!(is_tailwind_config("web/tailwind.config.ts"));
+        assert!(is_tailwind_config("tailwind.config.cjs"));
+        assert!(!is_tailwind_config("src/tailwind.ts"));
+    }
+}
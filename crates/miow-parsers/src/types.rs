@@ -12,6 +12,26 @@ pub struct ParsedFile {
     pub constants: Vec<Constant>,
     pub schemas: Vec<ValidationSchema>,
     pub language: String,
+    #[serde(default)]
+    pub tests: Vec<TestCase>,
+    /// HTTP routes the file declares, with their middleware (see `crate::routes`)
+    #[serde(default)]
+    pub routes: Vec<ApiRoute>,
+    /// `@example` code from doc comments (see `crate::examples`)
+    #[serde(default)]
+    pub examples: Vec<UsageExample>,
+}
+
+impl ParsedFile {
+    /// Language `symbol` is written in: its embedded block's language, else the file's
+    pub fn symbol_language<'a>(&'a self, symbol: &'a Symbol) -> &'a str {
+        symbol.metadata.language.as_deref().unwrap_or(&self.language)
+    }
+
+    /// Language `token` is written in: its embedded block's language, else the file's
+    pub fn token_language<'a>(&'a self, token: &'a DesignToken) -> &'a str {
+        token.language.as_deref().unwrap_or(&self.language)
+    }
 }
 
 /// A generic symbol (class, function, interface, variable, etc.)
@@ -26,15 +46,43 @@ pub struct Symbol {
     pub references: Vec<String>, // Names of other symbols referenced by this one
 }
 
+/// A test block (e.g. `describe('Button', ...)`) and the names it references
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestCase {
+    pub name: String,
+    pub references: Vec<String>,
+    pub range: Range,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Range {
     pub start_line: usize,
     pub end_line: usize,
     pub start_byte: usize,
     pub end_byte: usize,
+    /// 0-based column of the first character on `start_line`
+    #[serde(default)]
+    pub start_col: usize,
+    /// 0-based column just past the last character on `end_line`
+    #[serde(default)]
+    pub end_col: usize,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+impl Range {
+    /// Span of a tree-sitter node, with 1-based lines
+    pub fn of(node: &tree_sitter::Node) -> Self {
+        Range {
+            start_line: node.start_position().row + 1,
+            end_line: node.end_position().row + 1,
+            start_byte: node.start_byte(),
+            end_byte: node.end_byte(),
+            start_col: node.start_position().column,
+            end_col: node.end_position().column,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum SymbolType {
     File,
     Module,
@@ -87,6 +135,38 @@ pub struct SymbolMetadata {
     pub props: Vec<PropDefinition>,
     pub hooks_used: Vec<String>,
     pub state_variables: Vec<String>,
+    /// Module-qualified name, e.g. `config::Settings` (Rust only)
+    #[serde(default)]
+    pub qualified_name: Option<String>,
+    /// Platform of a platform-specific file such as `Button.ios.tsx` (see `crate::platform`)
+    #[serde(default)]
+    pub platform: Option<String>,
+    /// Language of the embedded block the symbol came from in a mixed-language file (the
+    /// `<script lang="ts">` of a `.vue` file, a fenced block in markdown); `None` means the
+    /// file's own `ParsedFile::language`
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Page or route component a data loader or server action serves (see
+    /// `crate::data_functions`)
+    #[serde(default)]
+    pub route_symbol: Option<String>,
+    /// Accessibility attributes (`aria-*`, `role`, `alt`) used in a component's JSX, in
+    /// source order without duplicates
+    #[serde(default)]
+    pub a11y_attributes: Vec<String>,
+    /// Classes of imported CSS modules the symbol uses (`styles.button`), in source order
+    /// (see `crate::css_modules`)
+    #[serde(default)]
+    pub css_module_classes: Vec<CssModuleClassRef>,
+}
+
+/// A class read off an imported CSS module, e.g. `styles.button` with
+/// `import styles from './Button.module.css'`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CssModuleClassRef {
+    /// Import specifier of the module, as written
+    pub module: String,
+    pub class_name: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -131,6 +211,53 @@ pub struct Export {
     pub is_default: bool,
     pub is_type: bool,
     pub range: Range,
+    /// Module a re-export comes from (`export { Button } from './Button'`); `None` for
+    /// exports of the file's own declarations. `name` is `*` for `export * from`.
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+/// An HTTP route and the middleware/guards that run before its handler
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiRoute {
+    /// Upper-case HTTP method (`GET`, `POST`, `ALL`, ...)
+    pub method: String,
+    pub path: String,
+    /// Handler function or method name; `None` for inline closures
+    pub handler: Option<String>,
+    /// Guards, middleware and layers in the order they apply, outermost first
+    pub middleware: Vec<String>,
+    pub range: Range,
+}
+
+impl ApiRoute {
+    /// Whether anything runs before the handler
+    pub fn is_guarded(&self) -> bool {
+        !self.middleware.is_empty()
+    }
+}
+
+/// Code from a doc comment's `@example` block, showing how a symbol is meant to be called
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageExample {
+    /// Name of the documented symbol
+    pub symbol: String,
+    pub code: String,
+    /// Fence language (`ts`, `tsx`, ...); `None` for unfenced examples
+    pub language: Option<String>,
+    /// Range of the documented symbol
+    pub range: Range,
+}
+
+/// How much of a file the parsers extract
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParseMode {
+    /// Symbols with bodies and references, plus imports, types, constants, schemas, tokens
+    #[default]
+    Full,
+    /// Top-level declarations only: names, kinds, signatures and imports. Much faster; enough
+    /// for a planning-level symbol map, with full parses done for the files actually selected.
+    SignaturesOnly,
 }
 
 /// Design tokens (colors, spacing, etc.)
@@ -141,6 +268,9 @@ pub struct DesignToken {
     pub value: String,
     pub context: String, // Where it was found (e.g., className, style prop)
     pub range: Range,
+    /// Language of the embedded block the token came from, like `SymbolMetadata::language`
+    #[serde(default)]
+    pub language: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -160,6 +290,8 @@ pub enum DesignTokenType {
     Animation,
     Transition,
     Opacity,
+    /// A class of a CSS module; the value holds the rules that style it
+    CSSModuleClass,
 }
 
 /// JSDoc documentation
@@ -209,6 +341,8 @@ pub enum TypeKind {
     Enum,
     Union,
     Intersection,
+    /// A TypeScript `namespace` (or `module X`) block
+    Namespace,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -217,6 +351,9 @@ pub struct TypeProperty {
     pub type_annotation: String,
     pub is_optional: bool,
     pub description: Option<String>,
+    /// Initializer of an enum member (`"ACTIVE"` is stored unquoted), if known
+    #[serde(default)]
+    pub value: Option<String>,
 }
 
 /// Constant or configuration value
@@ -265,6 +402,20 @@ pub struct SchemaField {
     // New fields for enhanced Zod support
     pub type_annotation: Option<String>,
     pub is_optional: bool,
+    /// Zod fields list their call chain as written, e.g. `["string", "min(8)", "optional"]`
     pub validators: Vec<String>,
     pub description: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_without_columns_deserializes() {
+        let range: Range =
+            serde_json::from_str(r#"{"start_line":3,"end_line":5,"start_byte":10,"end_byte":42}"#).unwrap();
+        assert_eq!(range.start_col, 0);
+        assert_eq!(range.end_col, 0);
+    }
+}
@@ -1,18 +1,51 @@
+use crate::deprecation::tag_deprecated;
+use crate::embed_text::keep_signatures_only;
+use crate::examples::{attach_jsdoc, member_jsdoc, usage_examples};
+use crate::data_functions::tag_data_functions;
+use crate::css_modules::link_css_module_classes;
+use crate::routes::typescript_routes;
 use crate::types::*;
+use crate::zod::zod_schemas;
 use anyhow::{Context, Result};
 use tree_sitter::{Node, Parser, Query, QueryCursor};
 
+/// Type annotations that mark a variable as a React component (`const Card: FC<Props> = ...`)
+const COMPONENT_TYPE_NAMES: [&str; 7] = [
+    "FC",
+    "VFC",
+    "FunctionComponent",
+    "ComponentType",
+    "NextPage",
+    "ForwardRefExoticComponent",
+    "MemoExoticComponent",
+];
+
 pub struct TypeScriptParser {
     parser: Parser,
+    mode: ParseMode,
 }
 
 impl TypeScriptParser {
     pub fn new() -> Self {
         let mut parser = Parser::new();
-        Self { parser }
+        Self {
+            parser,
+            mode: ParseMode::Full,
+        }
+    }
+
+    pub fn with_mode(mut self, mode: ParseMode) -> Self {
+        self.mode = mode;
+        self
     }
 
     pub fn parse(&self, content: &str, is_tsx: bool) -> Result<ParsedFile> {
+        self.parse_with_path(content, is_tsx, "")
+    }
+
+    /// Parse a file, extracting `describe`/`it`/`test` blocks only when `file_path` names a
+    /// test file (see [`is_test_file`])
+    pub fn parse_with_path(&self, content: &str, is_tsx: bool, file_path: &str) -> Result<ParsedFile> {
         let mut parser = Parser::new();
 
         let language = if is_tsx {
@@ -31,13 +64,41 @@ impl TypeScriptParser {
 
         let root_node = tree.root_node();
 
-        let symbols = self.extract_symbols(&root_node, content, is_tsx)?;
+        let mut symbols = self.extract_symbols(&root_node, content, is_tsx)?;
+        self.apply_default_props(&root_node, content, &mut symbols)?;
+        tag_deprecated(&mut symbols, content);
+        attach_jsdoc(&mut symbols, content);
         let imports = self.extract_imports(&root_node, content)?;
+        link_css_module_classes(&mut symbols, &imports);
         let exports = self.extract_exports(&root_node, content)?;
+        tag_data_functions(&mut symbols, &exports, content);
+        if self.mode == ParseMode::SignaturesOnly {
+            keep_signatures_only(&mut symbols);
+            return Ok(ParsedFile {
+                symbols,
+                imports,
+                exports,
+                design_tokens: vec![],
+                type_definitions: vec![],
+                constants: vec![],
+                schemas: vec![],
+                language: if is_tsx { "tsx" } else { "typescript" }.to_string(),
+                tests: vec![],
+                routes: vec![],
+                examples: vec![],
+            });
+        }
         let design_tokens = self.extract_design_tokens(&root_node, content)?;
         let type_definitions = self.extract_type_definitions(&root_node, content)?;
         let constants = self.extract_constants(&root_node, content)?;
         let schemas = self.extract_validation_schemas(&root_node, content)?;
+        let tests = if is_test_file(file_path) {
+            self.extract_test_cases(&root_node, content)?
+        } else {
+            vec![]
+        };
+        let routes = typescript_routes(&root_node, content);
+        let examples = usage_examples(&symbols);
 
         Ok(ParsedFile {
             symbols,
@@ -52,21 +113,91 @@ impl TypeScriptParser {
             } else {
                 "typescript".to_string()
             },
+            tests,
+            routes,
+            examples,
         })
     }
 
+    /// Extract top-level `describe`/`it`/`test` blocks along with the names they reference,
+    /// so tests can be linked back to the symbols they exercise.
+    fn extract_test_cases(&self, node: &Node, source: &str) -> Result<Vec<TestCase>> {
+        let mut tests = Vec::new();
+        self.collect_test_cases(node, source, &mut tests)?;
+        Ok(tests)
+    }
+
+    fn collect_test_cases(&self, node: &Node, source: &str, tests: &mut Vec<TestCase>) -> Result<()> {
+        if node.kind() == "call_expression" {
+            if let Some(name) = self.test_block_name(node, source) {
+                let mut references: Vec<String> = self
+                    .extract_references(node, source)?
+                    .into_iter()
+                    .filter(|r| !is_test_framework_name(r))
+                    .collect();
+
+                // The block title usually names the unit under test, e.g. describe('Button')
+                references.extend(
+                    name.split(|c: char| !c.is_alphanumeric() && c != '_')
+                        .filter(|word| word.chars().any(|c| c.is_uppercase()))
+                        .map(String::from),
+                );
+                references.sort();
+                references.dedup();
+
+                tests.push(TestCase {
+                    name,
+                    references,
+                    range: Range::of(node),
+                });
+                return Ok(());
+            }
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.collect_test_cases(&child, source, tests)?;
+        }
+        Ok(())
+    }
+
+    /// Title of a `describe('...')`, `it.only('...')` or `test('...')` call, if `node` is one.
+    fn test_block_name(&self, node: &Node, source: &str) -> Option<String> {
+        let function = node.child_by_field_name("function")?;
+        let callee = match function.kind() {
+            "identifier" => function,
+            "member_expression" => function.child_by_field_name("object")?,
+            _ => return None,
+        };
+        let callee = callee.utf8_text(source.as_bytes()).ok()?;
+        if !matches!(callee, "describe" | "it" | "test") {
+            return None;
+        }
+
+        let arguments = node.child_by_field_name("arguments")?;
+        let title = arguments.named_child(0)?;
+        if !matches!(title.kind(), "string" | "template_string") {
+            return None;
+        }
+        let title = title.utf8_text(source.as_bytes()).ok()?;
+        Some(title.trim_matches(|c| c == '\'' || c == '"' || c == '`').to_string())
+    }
+
     fn extract_symbols(&self, root_node: &Node, source: &str, is_tsx: bool) -> Result<Vec<Symbol>> {
         let mut symbols = Vec::new();
         let mut cursor = root_node.walk();
 
         for child in root_node.children(&mut cursor) {
-            if let Some(symbol) = self.process_node(&child, source, is_tsx)? {
+            if let Some(mut symbol) = self.process_node(&child, source, is_tsx)? {
+                if symbol.kind == SymbolType::Component {
+                    symbol.metadata.a11y_attributes = self.a11y_attributes(&child, source);
+                }
                 symbols.push(symbol);
             }
         }
 
         // Additional extraction for common UI components in JSX/TSX
-        if is_tsx {
+        if is_tsx && self.mode == ParseMode::Full {
             if let Ok(ui_symbols) = self.extract_ui_components(root_node, source) {
                 symbols.extend(ui_symbols);
             }
@@ -122,7 +253,7 @@ impl TypeScriptParser {
                 };
 
                 // Get full component code by expanding context
-                let range = self.get_range_expanded(&node, 5); // Expand 5 lines for context
+                let range = self.get_range_expanded(&node, source, 5); // Expand 5 lines for context
                 let content = self.extract_node_content_with_context(&node, source, &range);
 
                 let mut metadata = SymbolMetadata::default();
@@ -171,14 +302,23 @@ impl TypeScriptParser {
         None
     }
 
-    fn get_range_expanded(&self, node: &Node, lines: usize) -> Range {
-        let start_row = node.start_position().row.saturating_sub(lines as usize);
-        let end_row = node.end_position().row + lines as usize;
+    /// `node`'s lines plus up to `lines` more on either side, starting at the first column of the
+    /// first line and ending at the end of the last
+    fn get_range_expanded(&self, node: &Node, source: &str, lines: usize) -> Range {
+        let line_count = source.lines().count().max(1);
+        let start_row = node.start_position().row.saturating_sub(lines);
+        let end_row = (node.end_position().row + lines).min(line_count - 1);
         Range {
             start_line: start_row + 1,
             end_line: end_row + 1,
             start_byte: node.start_byte(),
             end_byte: node.end_byte(),
+            start_col: if lines == 0 { node.start_position().column } else { 0 },
+            end_col: if lines == 0 {
+                node.end_position().column
+            } else {
+                source.lines().nth(end_row).map_or(0, str::len)
+            },
         }
     }
 
@@ -200,7 +340,7 @@ impl TypeScriptParser {
                 let name = self
                     .get_child_text(node, "name", source)
                     .unwrap_or_else(|| "anonymous".to_string());
-                let range = self.get_range(node);
+                let range = Range::of(node);
                 let metadata = self.extract_function_metadata(node, source)?;
 
                 // Check if it's a component (starts with Uppercase and returns JSX)
@@ -225,12 +365,20 @@ impl TypeScriptParser {
                 let name = self
                     .get_child_text(node, "name", source)
                     .unwrap_or_else(|| "Anonymous".to_string());
-                let range = self.get_range(node);
-                let metadata = SymbolMetadata::default(); // TODO: Extract class metadata
+                let range = Range::of(node);
+                let metadata = SymbolMetadata {
+                    generic_params: self.extract_generic_params(node, source)?,
+                    ..Default::default()
+                };
+                let symbol_type = if self.extends_component_class(node, source) {
+                    SymbolType::Component
+                } else {
+                    SymbolType::Class
+                };
 
                 Ok(Some(Symbol {
                     name,
-                    kind: SymbolType::Class,
+                    kind: symbol_type,
                     range,
                     content: text.to_string(),
                     metadata,
@@ -242,7 +390,7 @@ impl TypeScriptParser {
                 let name = self
                     .get_child_text(node, "name", source)
                     .unwrap_or_else(|| "Anonymous".to_string());
-                let range = self.get_range(node);
+                let range = Range::of(node);
 
                 Ok(Some(Symbol {
                     name,
@@ -285,15 +433,20 @@ impl TypeScriptParser {
                     let value_node = child.child_by_field_name("value");
 
                     if let Some(value_node) = value_node {
-                        // Check if it's an arrow function
-                        if value_node.kind() == "arrow_function" {
-                            let range = self.get_range(node);
-                            let metadata =
-                                self.extract_arrow_function_metadata(&value_node, source)?;
+                        // Check if it's an arrow function, possibly wrapped in memo/forwardRef
+                        let function_node = self.unwrap_component_wrapper(&value_node, source);
+                        if matches!(function_node.kind(), "arrow_function" | "function_expression" | "function") {
+                            let range = Range::of(node);
+                            let mut metadata =
+                                self.extract_arrow_function_metadata(&function_node, source)?;
 
                             let symbol_type = if self.is_component_name(&name)
-                                && self.returns_jsx(&value_node, source)
+                                && (self.returns_jsx(&function_node, source)
+                                    || self.is_typed_as_component(&child, source))
                             {
+                                if let Some(first_param) = metadata.parameters.first() {
+                                    metadata.props = self.extract_props_from_param(first_param, &function_node, source)?;
+                                }
                                 SymbolType::Component
                             } else {
                                 SymbolType::Function
@@ -312,12 +465,16 @@ impl TypeScriptParser {
                     }
 
                     // Regular variable
+                    let mut metadata = SymbolMetadata::default();
+                    if value_node.is_some_and(|v| self.creates_state_store(&v, source)) {
+                        metadata.tags.push("state-management".to_string());
+                    }
                     return Ok(Some(Symbol {
                         name,
                         kind: SymbolType::Variable,
-                        range: self.get_range(node),
+                        range: Range::of(node),
                         content: node.utf8_text(source.as_bytes())?.to_string(),
-                        metadata: SymbolMetadata::default(),
+                        metadata,
                         children: vec![],
                         references: self.extract_references(node, source)?,
                     }));
@@ -327,6 +484,73 @@ impl TypeScriptParser {
         Ok(None)
     }
 
+    /// Whether `value` calls a store factory, e.g. Zustand `create<T>()(...)`,
+    /// Redux Toolkit `createSlice(...)` or `React.createContext(...)`
+    fn creates_state_store(&self, value: &Node, source: &str) -> bool {
+        if value.kind() != "call_expression" {
+            return false;
+        }
+        let mut callee = *value;
+        while callee.kind() == "call_expression" {
+            match callee.child_by_field_name("function") {
+                Some(function) => callee = function,
+                None => return false,
+            }
+        }
+        let name_node = match callee.kind() {
+            "identifier" => Some(callee),
+            "member_expression" => callee.child_by_field_name("property"),
+            _ => None,
+        };
+        let Some(name) = name_node.and_then(|n| n.utf8_text(source.as_bytes()).ok()) else {
+            return false;
+        };
+        if STATE_STORE_FACTORIES.contains(&name) {
+            return true;
+        }
+        // `create` alone is too common (`Object.create`, `axios.create`) to mean a store
+        // unless it is Zustand's
+        callee.kind() == "identifier" && self.zustand_create_binding(value, source).as_deref() == Some(name)
+    }
+
+    /// Local name Zustand's `create` is imported under in the file containing `node`
+    fn zustand_create_binding(&self, node: &Node, source: &str) -> Option<String> {
+        let mut root = *node;
+        while let Some(parent) = root.parent() {
+            root = parent;
+        }
+        let mut cursor = root.walk();
+        let import = root.children(&mut cursor).find(|child| {
+            child.kind() == "import_statement"
+                && child
+                    .child_by_field_name("source")
+                    .and_then(|s| s.utf8_text(source.as_bytes()).ok())
+                    .is_some_and(|s| s.trim_matches(|c| c == '"' || c == '\'') == "zustand")
+        })?;
+        let mut cursor = import.walk();
+        let clause = import.children(&mut cursor).find(|child| child.kind() == "import_clause")?;
+
+        let mut cursor = clause.walk();
+        for child in clause.children(&mut cursor) {
+            match child.kind() {
+                // zustand v3 default export
+                "identifier" => return Some(child.utf8_text(source.as_bytes()).ok()?.to_string()),
+                "named_imports" => {
+                    let mut specifiers = child.walk();
+                    for specifier in child.children(&mut specifiers).filter(|n| n.kind() == "import_specifier") {
+                        let imported = specifier.child_by_field_name("name")?;
+                        if imported.utf8_text(source.as_bytes()).ok()? == "create" {
+                            let local = specifier.child_by_field_name("alias").unwrap_or(imported);
+                            return Some(local.utf8_text(source.as_bytes()).ok()?.to_string());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
     fn extract_class_members(&self, node: &Node, source: &str) -> Result<Vec<Symbol>> {
         let mut members = Vec::new();
         if let Some(body) = node.child_by_field_name("body") {
@@ -341,7 +565,7 @@ impl TypeScriptParser {
                         members.push(Symbol {
                             name,
                             kind: SymbolType::Method,
-                            range: self.get_range(&child),
+                            range: Range::of(&child),
                             content: child.utf8_text(source.as_bytes())?.to_string(),
                             metadata: SymbolMetadata::default(),
                             children: vec![],
@@ -355,7 +579,7 @@ impl TypeScriptParser {
                         members.push(Symbol {
                             name,
                             kind: SymbolType::Field,
-                            range: self.get_range(&child),
+                            range: Range::of(&child),
                             content: child.utf8_text(source.as_bytes())?.to_string(),
                             metadata: SymbolMetadata::default(),
                             children: vec![],
@@ -381,7 +605,7 @@ impl TypeScriptParser {
                     members.push(Symbol {
                         name,
                         kind: SymbolType::Property,
-                        range: self.get_range(&child),
+                        range: Range::of(&child),
                         content: child.utf8_text(source.as_bytes())?.to_string(),
                         metadata: SymbolMetadata::default(),
                         children: vec![],
@@ -413,11 +637,27 @@ impl TypeScriptParser {
             metadata.return_type = Some(return_type.utf8_text(source.as_bytes())?.to_string());
         }
 
+        metadata.generic_params = self.extract_generic_params(node, source)?;
         metadata.is_async = node.utf8_text(source.as_bytes())?.starts_with("async");
 
         Ok(metadata)
     }
 
+    /// Type parameters of a generic declaration as written, with their constraints and
+    /// defaults (`T extends Entity`, `K = string`)
+    fn extract_generic_params(&self, node: &Node, source: &str) -> Result<Vec<String>> {
+        let mut params = Vec::new();
+        if let Some(type_params) = node.child_by_field_name("type_parameters") {
+            let mut cursor = type_params.walk();
+            for param in type_params.named_children(&mut cursor) {
+                if param.kind() == "type_parameter" {
+                    params.push(param.utf8_text(source.as_bytes())?.to_string());
+                }
+            }
+        }
+        Ok(params)
+    }
+
     fn is_component_node(&self, node: &Node, source: &str) -> bool {
         if let Some(name_node) = node.child_by_field_name("name") {
              if let Ok(name) = name_node.utf8_text(source.as_bytes()) {
@@ -430,29 +670,21 @@ impl TypeScriptParser {
     fn extract_props_from_param(&self, param: &Parameter, _node: &Node, _source: &str) -> Result<Vec<PropDefinition>> {
         let mut props = Vec::new();
 
-        // Case 1: Destructured props: ({ name, age }: Props)
+        // Case 1: Destructured props: ({ name, size = 'md' }: Props). A prop with a default
+        // is optional.
         if param.name.starts_with('{') {
-             // Simple regex to find property names in { prop1, prop2: alias }
-             // Matches words followed by comma or closing brace, ignoring values
-             let re = regex::Regex::new(r"(\w+)(?:\s*[:=]\s*[^,}]+)?").unwrap();
-             
-             // Remove braces
-             let content = param.name.trim_matches(|c| c == '{' || c == '}');
-             
-             for cap in re.captures_iter(content) {
-                 if let Some(name) = cap.get(1) {
-                     props.push(PropDefinition {
-                         name: name.as_str().to_string(),
-                         type_annotation: None, 
-                         is_required: true, // Assume required by default in destructuring unless = value
-                         default_value: None,
-                         description: None,
-                         validation: None,
-                     });
-                 }
-             }
+            for (name, default_value) in destructured_props(&param.name) {
+                props.push(PropDefinition {
+                    name,
+                    type_annotation: None,
+                    is_required: default_value.is_none(),
+                    default_value,
+                    description: None,
+                    validation: None,
+                });
+            }
         }
-        
+
         // Case 2: Named props with type annotation (props: MyProps)
         if let Some(_type_name) = &param.type_annotation {
              // We can't resolve the type definition here easily, but we can record that 
@@ -464,6 +696,61 @@ impl TypeScriptParser {
         Ok(props)
     }
 
+    /// Apply legacy `Button.defaultProps = { size: 'md' }` assignments to the props of the
+    /// components they name: each listed prop gets its default and becomes optional
+    fn apply_default_props(&self, root: &Node, source: &str, symbols: &mut [Symbol]) -> Result<()> {
+        let mut cursor = root.walk();
+        for statement in root.children(&mut cursor) {
+            let Some(assignment) = statement.named_child(0).filter(|_| statement.kind() == "expression_statement")
+            else {
+                continue;
+            };
+            if assignment.kind() != "assignment_expression" {
+                continue;
+            }
+            let (Some(left), Some(right)) =
+                (assignment.child_by_field_name("left"), assignment.child_by_field_name("right"))
+            else {
+                continue;
+            };
+            let property = self.get_child_text(&left, "property", source);
+            if left.kind() != "member_expression" || property.as_deref() != Some("defaultProps") || right.kind() != "object" {
+                continue;
+            }
+            let Some(component) = self.get_child_text(&left, "object", source) else {
+                continue;
+            };
+            let Some(symbol) = symbols.iter_mut().find(|s| s.name == component) else {
+                continue;
+            };
+
+            let mut pairs = right.walk();
+            for pair in right.named_children(&mut pairs).filter(|n| n.kind() == "pair") {
+                let (Some(key), Some(value)) = (pair.child_by_field_name("key"), pair.child_by_field_name("value"))
+                else {
+                    continue;
+                };
+                let name = unquote(key.utf8_text(source.as_bytes())?).to_string();
+                let value = value.utf8_text(source.as_bytes())?.to_string();
+                match symbol.metadata.props.iter_mut().find(|p| p.name == name) {
+                    Some(prop) => {
+                        prop.is_required = false;
+                        prop.default_value.get_or_insert(value);
+                    }
+                    None => symbol.metadata.props.push(PropDefinition {
+                        name,
+                        type_annotation: None,
+                        is_required: false,
+                        default_value: Some(value),
+                        description: None,
+                        validation: None,
+                    }),
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn extract_arrow_function_metadata(&self, node: &Node, source: &str) -> Result<SymbolMetadata> {
         let mut metadata = SymbolMetadata::default();
 
@@ -483,6 +770,7 @@ impl TypeScriptParser {
             }
         }
 
+        metadata.generic_params = self.extract_generic_params(node, source)?;
         metadata.is_async = node.utf8_text(source.as_bytes())?.starts_with("async");
 
         Ok(metadata)
@@ -550,7 +838,12 @@ impl TypeScriptParser {
                 let mut names = Vec::new();
 
                 // Handle import clause
-                if let Some(clause) = child.child_by_field_name("clause") {
+                let clause = child.child_by_field_name("clause").or_else(|| {
+                    let mut cursor2 = child.walk();
+                    let clause = child.children(&mut cursor2).find(|c| c.kind() == "import_clause");
+                    clause
+                });
+                if let Some(clause) = clause {
                     // Default import?
                     let mut cursor2 = clause.walk();
                     for sub in clause.children(&mut cursor2) {
@@ -562,6 +855,18 @@ impl TypeScriptParser {
                                 is_namespace: false,
                                 is_type: false,
                             });
+                        } else if sub.kind() == "namespace_import" {
+                            let mut cursor3 = sub.walk();
+                            let binding = sub.children(&mut cursor3).find(|n| n.kind() == "identifier");
+                            if let Some(binding) = binding {
+                                names.push(ImportName {
+                                    name: binding.utf8_text(source.as_bytes())?.to_string(),
+                                    alias: None,
+                                    is_default: false,
+                                    is_namespace: true,
+                                    is_type: false,
+                                });
+                            }
                         } else if sub.kind() == "named_imports" {
                             let mut cursor3 = sub.walk();
                             for spec in sub.children(&mut cursor3) {
@@ -586,7 +891,7 @@ impl TypeScriptParser {
                 imports.push(Import {
                     source: source_path,
                     names,
-                    range: self.get_range(&child),
+                    range: Range::of(&child),
                 });
             }
         }
@@ -595,13 +900,91 @@ impl TypeScriptParser {
 
     fn extract_exports(&self, node: &Node, source: &str) -> Result<Vec<Export>> {
         let mut exports = Vec::new();
-        // TODO: Implement robust export extraction
-        // For now, we rely on symbols being marked as exported if we were to add that flag
-        // But here we want explicit export statements
+        let mut cursor = node.walk();
+
+        for child in node.children(&mut cursor) {
+            if child.kind() != "export_statement" {
+                continue;
+            }
+            let range = Range::of(&child);
+            let module = self
+                .get_child_text(&child, "source", source)
+                .map(|s| s.trim_matches(|c| c == '\'' || c == '"').to_string());
+            let mut is_default = false;
+            let mut is_type = false;
+            let mut names: Vec<(String, Option<String>)> = Vec::new();
+
+            let mut cursor2 = child.walk();
+            for sub in child.children(&mut cursor2) {
+                match sub.kind() {
+                    "default" => is_default = true,
+                    "type" => is_type = true,
+                    // `export * from './utils'`
+                    "*" => names.push(("*".to_string(), None)),
+                    // `export * as utils from './utils'`
+                    "namespace_export" => {
+                        let alias = sub
+                            .named_child(0)
+                            .map(|n| n.utf8_text(source.as_bytes()).map(str::to_string))
+                            .transpose()?;
+                        names.push(("*".to_string(), alias));
+                    }
+                    "export_clause" => {
+                        let mut cursor3 = sub.walk();
+                        for spec in sub.children(&mut cursor3) {
+                            if spec.kind() == "export_specifier" {
+                                let name = self.get_child_text(&spec, "name", source).unwrap_or_default();
+                                let alias = self.get_child_text(&spec, "alias", source);
+                                names.push((name, alias));
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            if let Some(declaration) = child.child_by_field_name("declaration") {
+                is_type |= matches!(declaration.kind(), "interface_declaration" | "type_alias_declaration");
+                if let Some(name) = self.get_child_text(&declaration, "name", source) {
+                    names.push((name, None));
+                } else {
+                    let mut cursor3 = declaration.walk();
+                    for declarator in declaration.children(&mut cursor3) {
+                        if declarator.kind() == "variable_declarator" {
+                            if let Some(name) = self.get_child_text(&declarator, "name", source) {
+                                names.push((name, None));
+                            }
+                        }
+                    }
+                }
+            } else if let Some(value) = child.child_by_field_name("value") {
+                // `export default Button;` names the symbol, anything else is anonymous
+                let name = match value.kind() {
+                    "identifier" => value.utf8_text(source.as_bytes())?.to_string(),
+                    _ => "default".to_string(),
+                };
+                names.push((name, None));
+            }
+
+            for (name, alias) in names {
+                exports.push(Export {
+                    name,
+                    alias,
+                    is_default,
+                    is_type,
+                    range: range.clone(),
+                    source: module.clone(),
+                });
+            }
+        }
+
         Ok(exports)
     }
 
     fn extract_references(&self, node: &Node, source: &str) -> Result<Vec<String>> {
+        if self.mode == ParseMode::SignaturesOnly {
+            return Ok(Vec::new());
+        }
         let mut references = Vec::new();
         let mut cursor = node.walk();
 
@@ -724,7 +1107,8 @@ impl TypeScriptParser {
                                 name: class.to_string(),
                                 value: class.to_string(),
                                 context: "className".to_string(),
-                                range: self.get_range(&capture.node),
+                                range: Range::of(&capture.node),
+                                language: None,
                             });
                         }
                     }
@@ -742,15 +1126,6 @@ impl TypeScriptParser {
             .map(|n| n.utf8_text(source.as_bytes()).unwrap().to_string())
     }
 
-    fn get_range(&self, node: &Node) -> Range {
-        Range {
-            start_line: node.start_position().row + 1,
-            end_line: node.end_position().row + 1,
-            start_byte: node.start_byte(),
-            end_byte: node.end_byte(),
-        }
-    }
-
     fn is_component_name(&self, name: &str) -> bool {
         name.chars()
             .next()
@@ -759,18 +1134,121 @@ impl TypeScriptParser {
     }
 
     fn returns_jsx(&self, node: &Node, source: &str) -> bool {
-        // Heuristic: check if the function body contains JSX elements
-        // A better way is to traverse the body and look for return statements with JSX
+        // JSX only parses as such in TSX; plain TS falls back to a text heuristic
+        if self.contains_jsx(node) {
+            return true;
+        }
         let text = node.utf8_text(source.as_bytes()).unwrap_or("");
         text.contains("return <") || text.contains("=> <") || text.contains("=> ( <")
     }
 
+    fn contains_jsx(&self, node: &Node) -> bool {
+        if matches!(node.kind(), "jsx_element" | "jsx_self_closing_element" | "jsx_fragment") {
+            return true;
+        }
+        let mut cursor = node.walk();
+        let found = node.named_children(&mut cursor).any(|child| self.contains_jsx(&child));
+        found
+    }
+
+    /// `aria-*`, `role` and `alt` attribute names on JSX elements under `node`
+    fn a11y_attributes(&self, node: &Node, source: &str) -> Vec<String> {
+        let mut attributes = Vec::new();
+        self.collect_a11y_attributes(node, source, &mut attributes);
+        attributes
+    }
+
+    fn collect_a11y_attributes(&self, node: &Node, source: &str, attributes: &mut Vec<String>) {
+        if node.kind() == "jsx_attribute" {
+            let name = node
+                .named_child(0)
+                .and_then(|n| n.utf8_text(source.as_bytes()).ok())
+                .unwrap_or("");
+            if (name.starts_with("aria-") || name == "role" || name == "alt")
+                && !attributes.iter().any(|a| a == name)
+            {
+                attributes.push(name.to_string());
+            }
+            return;
+        }
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            self.collect_a11y_attributes(&child, source, attributes);
+        }
+    }
+
+    /// Whether a variable declarator is annotated as a component type, e.g. `React.FC<Props>`
+    fn is_typed_as_component(&self, declarator: &Node, source: &str) -> bool {
+        let Some(annotation) = declarator.child_by_field_name("type") else {
+            return false;
+        };
+        let text = annotation.utf8_text(source.as_bytes()).unwrap_or("");
+        let type_name = text.trim_start_matches(':').trim().split('<').next().unwrap_or("");
+        let type_name = type_name.rsplit('.').next().unwrap_or(type_name).trim();
+        COMPONENT_TYPE_NAMES.contains(&type_name)
+    }
+
+    /// The function inside `memo(...)`, `forwardRef(...)` or `React.memo(...)`, else `node`
+    fn unwrap_component_wrapper<'t>(&self, node: &Node<'t>, source: &str) -> Node<'t> {
+        if node.kind() != "call_expression" {
+            return *node;
+        }
+        let callee = node
+            .child_by_field_name("function")
+            .and_then(|f| f.utf8_text(source.as_bytes()).ok())
+            .unwrap_or("");
+        let callee = callee.rsplit('.').next().unwrap_or(callee);
+        if !matches!(callee, "memo" | "forwardRef") {
+            return *node;
+        }
+        node.child_by_field_name("arguments")
+            .and_then(|args| args.named_child(0))
+            .map(|inner| self.unwrap_component_wrapper(&inner, source))
+            .unwrap_or(*node)
+    }
+
+    /// Whether a class extends `Component`/`PureComponent` (optionally `React.`-qualified)
+    fn extends_component_class(&self, class: &Node, source: &str) -> bool {
+        let mut cursor = class.walk();
+        let heritage = class.named_children(&mut cursor).find(|c| c.kind() == "class_heritage");
+        let Some(heritage) = heritage else {
+            return false;
+        };
+        let text = heritage.utf8_text(source.as_bytes()).unwrap_or("");
+        let Some(base) = text.trim().strip_prefix("extends") else {
+            return false;
+        };
+        let base = base.trim().split(|c: char| c == '<' || c.is_whitespace()).next().unwrap_or("");
+        matches!(base, "Component" | "PureComponent" | "React.Component" | "React.PureComponent")
+    }
+
     /// Extract ALL type definitions (interfaces, type aliases, enums)
     fn extract_type_definitions(&self, node: &Node, source: &str) -> Result<Vec<TypeDefinition>> {
         let mut types = Vec::new();
+        self.collect_type_definitions(node, source, &mut types)?;
+        Ok(types)
+    }
+
+    /// Type declarations among `node`'s statements, including those inside namespaces,
+    /// `declare global { }` and `declare module '...' { }` blocks. Those repeat an existing
+    /// interface's name to merge members into it; the graph presents the merged shape.
+    fn collect_type_definitions(&self, node: &Node, source: &str, types: &mut Vec<TypeDefinition>) -> Result<()> {
         let mut cursor = node.walk();
 
         for child in node.children(&mut cursor) {
+            // `export enum X {}` wraps the declaration in an export statement
+            let child = match child.kind() {
+                "export_statement" => match child.child_by_field_name("declaration") {
+                    Some(declaration) => declaration,
+                    None => continue,
+                },
+                // A top-level `namespace X {}` parses as an expression statement
+                "expression_statement" => match child.named_child(0) {
+                    Some(expression) if expression.kind() == "internal_module" => expression,
+                    _ => continue,
+                },
+                _ => child,
+            };
             match child.kind() {
                 "interface_declaration" => {
                     if let Some(type_def) = self.extract_interface(&child, source)? {
@@ -787,16 +1265,39 @@ impl TypeScriptParser {
                         types.push(type_def);
                     }
                 }
+                // `declare global { ... }` and `declare module 'x' { ... }`
+                "ambient_declaration" => self.collect_type_definitions(&child, source, types)?,
+                "statement_block" => self.collect_type_definitions(&child, source, types)?,
+                "internal_module" | "module" => {
+                    // `declare module 'x'` augments another module rather than declaring a
+                    // namespace of its own
+                    let is_namespace = child
+                        .child_by_field_name("name")
+                        .is_some_and(|name| name.kind() != "string");
+                    if is_namespace {
+                        types.push(TypeDefinition {
+                            name: self.get_child_text(&child, "name", source).unwrap_or_default(),
+                            kind: TypeKind::Namespace,
+                            definition: child.utf8_text(source.as_bytes())?.to_string(),
+                            properties: vec![],
+                            generic_params: vec![],
+                            range: Range::of(&child),
+                        });
+                    }
+                    if let Some(body) = child.child_by_field_name("body") {
+                        self.collect_type_definitions(&body, source, types)?;
+                    }
+                }
                 _ => {}
             }
         }
 
-        Ok(types)
+        Ok(())
     }
 
     fn extract_interface(&self, node: &Node, source: &str) -> Result<Option<TypeDefinition>> {
         let name = self
-            .get_child_text(node, "type_identifier", source)
+            .get_child_text(node, "name", source)
             .unwrap_or_default();
         let definition = node.utf8_text(source.as_bytes())?.to_string();
 
@@ -806,7 +1307,7 @@ impl TypeScriptParser {
             for child in body.children(&mut cursor) {
                 if child.kind() == "property_signature" {
                     let prop_name = self
-                        .get_child_text(&child, "property_identifier", source)
+                        .get_child_text(&child, "name", source)
                         .unwrap_or_default();
                     let type_annotation = child
                         .child_by_field_name("type")
@@ -818,7 +1319,8 @@ impl TypeScriptParser {
                         name: prop_name,
                         type_annotation,
                         is_optional,
-                        description: None,
+                        description: member_jsdoc(&child, source),
+                        value: None,
                     });
                 }
             }
@@ -829,14 +1331,14 @@ impl TypeScriptParser {
             kind: TypeKind::Interface,
             definition,
             properties,
-            generic_params: vec![],
-            range: self.get_range(node),
+            generic_params: self.extract_generic_params(node, source)?,
+            range: Range::of(node),
         }))
     }
 
     fn extract_type_alias(&self, node: &Node, source: &str) -> Result<Option<TypeDefinition>> {
         let name = self
-            .get_child_text(node, "type_identifier", source)
+            .get_child_text(node, "name", source)
             .unwrap_or_default();
         let definition = node.utf8_text(source.as_bytes())?.to_string();
 
@@ -845,36 +1347,101 @@ impl TypeScriptParser {
             kind: TypeKind::TypeAlias,
             definition,
             properties: vec![],
-            generic_params: vec![],
-            range: self.get_range(node),
+            generic_params: self.extract_generic_params(node, source)?,
+            range: Range::of(node),
         }))
     }
 
     fn extract_enum_type(&self, node: &Node, source: &str) -> Result<Option<TypeDefinition>> {
         let name = self
-            .get_child_text(node, "identifier", source)
+            .get_child_text(node, "name", source)
             .unwrap_or_default();
         let definition = node.utf8_text(source.as_bytes())?.to_string();
+        let properties = match node.child_by_field_name("body") {
+            Some(body) => self.extract_enum_members(&body, source)?,
+            None => vec![],
+        };
 
         Ok(Some(TypeDefinition {
             name,
             kind: TypeKind::Enum,
             definition,
-            properties: vec![],
+            properties,
             generic_params: vec![],
-            range: self.get_range(node),
+            range: Range::of(node),
         }))
     }
 
+    /// Members of a (`const`) enum body in declaration order. `type_annotation` is "string" or
+    /// "number"; `value` is the unquoted string, the explicit or auto-incremented number, or the
+    /// source text of a computed initializer.
+    fn extract_enum_members(&self, body: &Node, source: &str) -> Result<Vec<TypeProperty>> {
+        let mut members = Vec::new();
+        // Members without an initializer continue from the previous numeric value
+        let mut next_value: Option<i64> = Some(0);
+        let mut cursor = body.walk();
+
+        for child in body.named_children(&mut cursor) {
+            let (name_node, initializer) = match child.kind() {
+                "property_identifier" | "string" => (child, None),
+                "enum_assignment" => match child.child_by_field_name("name") {
+                    Some(name) => (name, child.child_by_field_name("value")),
+                    None => continue,
+                },
+                _ => continue,
+            };
+            let name = unquote(name_node.utf8_text(source.as_bytes())?).to_string();
+
+            let (type_annotation, value) = match initializer {
+                None => {
+                    let value = next_value;
+                    next_value = value.map(|v| v + 1);
+                    ("number", value.map(|v| v.to_string()))
+                }
+                Some(init) => {
+                    let text = init.utf8_text(source.as_bytes())?;
+                    match init.kind() {
+                        "string" | "template_string" => {
+                            next_value = None;
+                            ("string", Some(unquote(text).to_string()))
+                        }
+                        _ => {
+                            next_value = text.parse::<i64>().ok().map(|v| v + 1);
+                            ("number", Some(text.to_string()))
+                        }
+                    }
+                }
+            };
+
+            members.push(TypeProperty {
+                name,
+                type_annotation: type_annotation.to_string(),
+                is_optional: false,
+                description: None,
+                value,
+            });
+        }
+
+        Ok(members)
+    }
+
     /// Extract ALL constants and configuration values
     fn extract_constants(&self, node: &Node, source: &str) -> Result<Vec<Constant>> {
         let mut constants = Vec::new();
         let mut cursor = node.walk();
 
         for child in node.children(&mut cursor) {
+            // `export const X = ...` wraps the declaration in an export statement
+            let child = match child.kind() {
+                "export_statement" => match child.child_by_field_name("declaration") {
+                    Some(declaration) => declaration,
+                    None => continue,
+                },
+                _ => child,
+            };
             if child.kind() == "lexical_declaration" || child.kind() == "variable_declaration" {
                 let text = child.utf8_text(source.as_bytes())?;
-                if text.starts_with("const") || text.starts_with("export const") {
+                if text.starts_with("const") {
                     if let Some(constant) =
                         self.extract_constant_from_declaration(&child, source)?
                     {
@@ -914,7 +1481,7 @@ impl TypeScriptParser {
                         value,
                         type_annotation: None,
                         category,
-                        range: self.get_range(node),
+                        range: Range::of(node),
                     }));
                 }
             }
@@ -959,133 +1526,153 @@ impl TypeScriptParser {
     }
 
     fn extract_zod_schemas(&self, node: &Node, source: &str) -> Result<Vec<ValidationSchema>> {
-        let mut schemas = Vec::new();
-        let mut cursor = node.walk();
-
-        for child in node.children(&mut cursor) {
-            if child.kind() == "lexical_declaration" || child.kind() == "variable_declaration" {
-                let text = child.utf8_text(source.as_bytes())?;
-                if text.contains("z.object") {
-                    if let Some(schema) =
-                        self.extract_zod_schema_from_declaration(&child, source)?
-                    {
-                        schemas.push(schema);
-                    }
-                }
-            }
-        }
-
-        Ok(schemas)
+        zod_schemas(node, source)
     }
+}
 
-    fn extract_zod_schema_from_declaration(
-        &self,
-        node: &Node,
-        source: &str,
-    ) -> Result<Option<ValidationSchema>> {
-        let mut cursor = node.walk();
-        for child in node.children(&mut cursor) {
-            if child.kind() == "variable_declarator" {
-                if let Some(name_node) = child.child_by_field_name("name") {
-                    let name = name_node.utf8_text(source.as_bytes())?.to_string();
-                    let definition = child.utf8_text(source.as_bytes())?.to_string();
-
-                    // Enhanced field extraction for Zod schemas
-                    let mut fields = Vec::new();
-                    if let Some(value_node) = child.child_by_field_name("value") {
-                        if value_node.kind() == "call_expression" {
-                            // Look for z.object({ ... })
-                            if let Some(arg_list) = value_node.child_by_field_name("arguments") {
-                                let mut arg_cursor = arg_list.walk();
-                                for arg in arg_list.children(&mut arg_cursor) {
-                                    if arg.kind() == "object" {
-                                        // Parse object literal for fields
-                                        fields = self.parse_zod_object_fields(&arg, source)?;
-                                    }
-                                }
-                            }
-                        }
-                    }
-
-                    return Ok(Some(ValidationSchema {
-                        name,
-                        schema_type: SchemaType::Zod,
-                        definition,
-                        fields,
-                        range: self.get_range(node),
-                    }));
-                }
-            }
-        }
-        Ok(None)
+impl Default for TypeScriptParser {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    fn parse_zod_object_fields(&self, node: &Node, source: &str) -> Result<Vec<SchemaField>> {
-        let mut fields = Vec::new();
-        let mut cursor = node.walk();
-
-        for child in node.children(&mut cursor) {
-            if child.kind() == "object_member" || child.kind() == "pair" {
-                let key_node = child.child_by_field_name("key");
-                let value_node = child.child_by_field_name("value");
+/// Call names that create a global state store or context
+/// (Zustand's `create` only counts when imported from `zustand`)
+const STATE_STORE_FACTORIES: [&str; 5] = [
+    "createStore",
+    "createSlice",
+    "configureStore",
+    "createContext",
+    "atom",
+];
+
+/// `Button.test.tsx`, `auth.spec.ts` or anything under a `__tests__/` directory
+pub fn is_test_file(file_path: &str) -> bool {
+    let normalized = file_path.replace('\\', "/");
+    let file_name = normalized.rsplit('/').next().unwrap_or_default();
+    file_name.contains(".test.") || file_name.contains(".spec.") || normalized.split('/').any(|dir| dir == "__tests__")
+}
 
-                if let (Some(key_node), Some(value_node)) = (key_node, value_node) {
-                    let field_name = key_node.utf8_text(source.as_bytes())?.trim_matches('"').to_string();
-                    let field_type = value_node.utf8_text(source.as_bytes())?.to_string();
-                    let is_optional = field_name.ends_with('?');
+fn is_test_framework_name(name: &str) -> bool {
+    matches!(
+        name,
+        "describe" | "it" | "test" | "expect" | "beforeEach" | "afterEach" | "beforeAll"
+            | "afterAll" | "jest" | "vi"
+    )
+}
 
-                    fields.push(SchemaField {
-                        name: field_name.trim_end_matches('?').to_string(),
-                        validation_rules: self.extract_zod_validators(&value_node, source)?,
-                        is_required: !is_optional,
-                        default_value: None,
-                        type_annotation: Some(field_type),
-                        is_optional,
-                        validators: self.extract_zod_validators(&value_node, source)?,
-                        description: None,
-                    });
-                }
+/// `(name, default)` of each property bound by an object destructuring pattern such as
+/// `{ label, size = 'md', onClick: handleClick, ...rest }`; rest elements are skipped
+fn destructured_props(pattern: &str) -> Vec<(String, Option<String>)> {
+    let inner = pattern.trim();
+    let inner = inner.strip_prefix('{').and_then(|p| p.strip_suffix('}')).unwrap_or(inner);
+    split_top_level(inner)
+        .into_iter()
+        .filter(|entry| !entry.is_empty() && !entry.starts_with("..."))
+        .filter_map(|entry| {
+            // `key: binding = default` or `key = default`
+            let key_end = entry.find([':', '=']).unwrap_or(entry.len());
+            let name = entry[..key_end].trim();
+            if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$') {
+                return None;
             }
-        }
+            let default = split_top_level_once(entry, '=').map(|d| d.to_string());
+            Some((name.to_string(), default))
+        })
+        .collect()
+}
 
-        Ok(fields)
+/// Split on commas outside brackets and string literals, trimming each part
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    for (i, _) in top_level_positions(text).filter(|(_, c)| *c == ',') {
+        parts.push(text[start..i].trim());
+        start = i + 1;
     }
+    parts.push(text[start..].trim());
+    parts
+}
 
-    fn extract_zod_validators(&self, node: &Node, source: &str) -> Result<Vec<String>> {
-        let mut validators = Vec::new();
-        let text = node.utf8_text(source.as_bytes())?;
+/// The text after the first `separator` outside brackets and string literals
+fn split_top_level_once(text: &str, separator: char) -> Option<&str> {
+    // `=>` in a default arrow function isn't an assignment
+    top_level_positions(text)
+        .find(|(i, c)| *c == separator && !text[i + 1..].starts_with('>'))
+        .map(|(i, _)| text[i + 1..].trim())
+}
 
-        // Common Zod validators
-        if text.contains(".min(") {
-            validators.push("min_length".to_string());
-        }
-        if text.contains(".max(") {
-            validators.push("max_length".to_string());
-        }
-        if text.contains(".email(") {
-            validators.push("email".to_string());
+/// Characters of `text` at bracket depth 0 and outside string literals, with byte offsets
+fn top_level_positions(text: &str) -> impl Iterator<Item = (usize, char)> + '_ {
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    text.char_indices().filter(move |(_, c)| {
+        match (quote, *c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'' | '`') => quote = Some(*c),
+            (None, '{' | '[' | '(') => depth += 1,
+            (None, '}' | ']' | ')') => depth = depth.saturating_sub(1),
+            (None, _) => return depth == 0,
         }
-        if text.contains(".required(") {
-            validators.push("required".to_string());
-        }
-        if text.contains(".regex(") {
-            validators.push("pattern".to_string());
-        }
-
-        Ok(validators)
-    }
+        false
+    })
 }
 
-impl Default for TypeScriptParser {
-    fn default() -> Self {
-        Self::new()
+/// Strip one pair of matching quotes or backticks from a string literal
+fn unquote(text: &str) -> &str {
+    for quote in ['"', '\'', '`'] {
+        if let Some(inner) = text.strip_prefix(quote).and_then(|t| t.strip_suffix(quote)) {
+            return inner;
+        }
     }
+    text
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_range_reports_start_column() {
+        let parser = TypeScriptParser::new();
+        let content = "const ready = true;\n    function refresh(): void {}\n";
+
+        let parsed = parser.parse(content, false).unwrap();
+        let symbol = parsed.symbols.iter().find(|s| s.name == "refresh").unwrap();
+
+        assert_eq!(symbol.range.start_line, 2);
+        assert_eq!(symbol.range.start_col, 4);
+        assert_eq!(symbol.range.end_col, 31);
+    }
+
+    #[test]
+    fn test_expanded_range_ends_at_end_of_last_line() {
+        let parser = TypeScriptParser::new();
+        let content = "import x from 'x';\n  const a = 1;\n  const b = Button();\n  const c = 3;\nexport default b;";
+        let tree = {
+            let mut ts = Parser::new();
+            ts.set_language(tree_sitter_typescript::language_typescript()).unwrap();
+            ts.parse(content, None).unwrap()
+        };
+        let button = content.find("Button").unwrap();
+        let call = tree.root_node().descendant_for_byte_range(button, button).and_then(|n| n.parent()).unwrap();
+        assert_eq!(call.kind(), "call_expression");
+
+        let range = parser.get_range_expanded(&call, content, 1);
+        assert_eq!((range.start_line, range.end_line), (2, 4));
+        assert_eq!((range.start_col, range.end_col), (0, "  const c = 3;".len()));
+        assert_eq!((range.start_byte, range.end_byte), (call.start_byte(), call.end_byte()));
+
+        // Clamped to the file
+        let range = parser.get_range_expanded(&call, content, 5);
+        assert_eq!((range.start_line, range.end_line), (1, 5));
+        assert_eq!(range.end_col, "export default b;".len());
+
+        let unexpanded = parser.get_range_expanded(&call, content, 0);
+        assert_eq!((unexpanded.start_col, unexpanded.end_col), (12, 20));
+    }
+
     #[test]
     fn test_extract_references() {
         let parser = TypeScriptParser::new();
@@ -1126,4 +1713,324 @@ mod tests {
         assert!(symbol.metadata.props.iter().any(|p| p.name == "title"));
         assert!(symbol.metadata.props.iter().any(|p| p.name == "isActive"));
     }
+
+    #[test]
+    fn test_destructuring_defaults_make_props_optional() {
+        let content = r#"
+            export const Button = ({ label, size = 'md', onClick: handleClick, icon = { name: 'x', size: 2 }, ...rest }: ButtonProps) => {
+                return <button onClick={handleClick}>{label}</button>;
+            };
+        "#;
+        let parsed = TypeScriptParser::new().parse(content, true).unwrap();
+        let button = parsed.symbols.iter().find(|s| s.name == "Button").unwrap();
+        let props: Vec<(&str, bool, Option<&str>)> = button
+            .metadata
+            .props
+            .iter()
+            .map(|p| (p.name.as_str(), p.is_required, p.default_value.as_deref()))
+            .collect();
+        assert_eq!(
+            props,
+            vec![
+                ("label", true, None),
+                ("size", false, Some("'md'")),
+                ("onClick", true, None),
+                ("icon", false, Some("{ name: 'x', size: 2 }")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_default_props_object_sets_defaults() {
+        let content = r#"
+            function Badge({ text, tone, rounded }) {
+                return <span>{text}</span>;
+            }
+            Badge.defaultProps = {
+                tone: 'neutral',
+                pill: false,
+            };
+        "#;
+        let parsed = TypeScriptParser::new().parse(content, true).unwrap();
+        let badge = parsed.symbols.iter().find(|s| s.name == "Badge").unwrap();
+        let prop = |name: &str| badge.metadata.props.iter().find(|p| p.name == name).unwrap();
+        assert!(prop("text").is_required);
+        assert!(!prop("tone").is_required);
+        assert_eq!(prop("tone").default_value.as_deref(), Some("'neutral'"));
+        // Listed only in defaultProps
+        assert!(!prop("pill").is_required);
+        assert_eq!(prop("pill").default_value.as_deref(), Some("false"));
+    }
+
+    #[test]
+    fn test_extract_test_cases() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+            import { render, screen } from '@testing-library/react';
+            import { Button } from './Button';
+
+            describe('Button', () => {
+                it('renders its label', () => {
+                    render(<Button label="Save" />);
+                    expect(screen.getByText('Save')).toBeTruthy();
+                });
+            });
+        "#;
+
+        let parsed = parser.parse_with_path(content, true, "src/Button.test.tsx").unwrap();
+
+        assert_eq!(parsed.tests.len(), 1);
+        let test = &parsed.tests[0];
+        assert_eq!(test.name, "Button");
+        assert!(test.references.contains(&"Button".to_string()));
+        assert!(test.references.contains(&"render".to_string()));
+        assert!(!test.references.contains(&"expect".to_string()));
+    }
+
+    #[test]
+    fn test_test_cases_only_extracted_from_test_files() {
+        let parser = TypeScriptParser::new();
+        let content = "describe('cart', () => { it('adds', () => addItem()); });";
+
+        assert!(parser.parse(content, false).unwrap().tests.is_empty());
+        assert!(parser.parse_with_path(content, false, "src/cart.ts").unwrap().tests.is_empty());
+        for path in ["src/cart.test.ts", "src/cart.spec.ts", "src/__tests__/cart.ts"] {
+            assert_eq!(parser.parse_with_path(content, false, path).unwrap().tests.len(), 1, "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_store_factories_tagged_as_state_management() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+import { create } from "zustand";
+export const authSlice = createSlice({ name: "auth", initialState, reducers: {} });
+export const useCartStore = create<CartState>()((set) => ({ items: [] }));
+export const ThemeContext = React.createContext(null);
+export const pageSize = 20;
+export const proto = Object.create(null);
+export const api = axios.create({ baseURL: "/api" });
+"#;
+
+        let parsed = parser.parse(content, false).unwrap();
+        let tagged = |name: &str| {
+            parsed
+                .symbols
+                .iter()
+                .find(|s| s.name == name)
+                .unwrap()
+                .metadata
+                .tags
+                .contains(&"state-management".to_string())
+        };
+
+        assert!(tagged("authSlice"));
+        assert!(tagged("useCartStore"));
+        assert!(tagged("ThemeContext"));
+        assert!(!tagged("pageSize"));
+        assert!(!tagged("proto"));
+        assert!(!tagged("api"));
+
+        // Without the Zustand import, `create` is just a function
+        let unrelated = parser.parse("export const user = create(defaults);\n", false).unwrap();
+        assert!(!unrelated.symbols[0].metadata.tags.contains(&"state-management".to_string()));
+    }
+
+    #[test]
+    fn test_a11y_attributes_recorded_on_components() {
+        let code = r#"
+export function IconButton({ label, onPress }: Props) {
+    return (
+        <div role="toolbar">
+            <button aria-label={label} aria-pressed="false" onClick={onPress}>
+                <img src="/icon.svg" alt="" />
+            </button>
+            <span aria-label="hint" className="sr-only" />
+        </div>
+    );
+}
+
+export function Plain() {
+    return <div className="plain" />;
+}
+"#;
+        let parsed = TypeScriptParser::new().parse(code, true).unwrap();
+        let a11y = |name: &str| parsed.symbols.iter().find(|s| s.name == name).unwrap().metadata.a11y_attributes.clone();
+
+        assert_eq!(a11y("IconButton"), vec!["role", "aria-label", "aria-pressed", "alt"]);
+        assert!(a11y("Plain").is_empty());
+    }
+
+    #[test]
+    fn test_components_detected_structurally_not_by_name() {
+        let code = r#"
+import React, { memo } from 'react';
+
+export function OrderSummaryPanel({ order }: Props) {
+    return <section>{order.id}</section>;
+}
+
+export const PricingTierCard: React.FC<TierProps> = ({ tier }) => renderTier(tier);
+
+export const InvoiceRow = memo(({ row }: RowProps) => <tr><td>{row.id}</td></tr>);
+
+export class LegacyWidget extends React.Component<WidgetProps> {
+    render() {
+        return <div />;
+    }
+}
+
+export class PaymentGateway {
+    charge(amount: number) {
+        return fetch(`/pay?amount=${amount}`);
+    }
+}
+
+export function ButtonGroupLogic(buttons: Button[]) {
+    return buttons.filter((b) => b.visible);
+}
+"#;
+        let parsed = TypeScriptParser::new().parse(code, true).unwrap();
+        let kind_of = |name: &str| {
+            &parsed.symbols.iter().find(|s| s.name == name).unwrap_or_else(|| panic!("no symbol {}", name)).kind
+        };
+
+        for component in ["OrderSummaryPanel", "PricingTierCard", "InvoiceRow", "LegacyWidget"] {
+            assert!(matches!(kind_of(component), SymbolType::Component), "{} should be a component", component);
+        }
+        assert!(matches!(kind_of("PaymentGateway"), SymbolType::Class));
+        assert!(matches!(kind_of("ButtonGroupLogic"), SymbolType::Function));
+    }
+
+    #[test]
+    fn test_enum_members_extracted_with_values() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+export const enum OrderStatus {
+    Pending = "PENDING",
+    Shipped = 'SHIPPED',
+    Delivered = "DELIVERED",
+}
+
+enum Priority { Low, High = 10, Urgent }
+"#;
+
+        let parsed = parser.parse(content, false).unwrap();
+        let members = |name: &str| -> Vec<(String, String, Option<String>)> {
+            parsed
+                .type_definitions
+                .iter()
+                .find(|t| t.name == name && matches!(t.kind, TypeKind::Enum))
+                .unwrap()
+                .properties
+                .iter()
+                .map(|p| (p.name.clone(), p.type_annotation.clone(), p.value.clone()))
+                .collect()
+        };
+        let member = |name: &str, ty: &str, value: &str| {
+            (name.to_string(), ty.to_string(), Some(value.to_string()))
+        };
+
+        assert_eq!(
+            members("OrderStatus"),
+            vec![
+                member("Pending", "string", "PENDING"),
+                member("Shipped", "string", "SHIPPED"),
+                member("Delivered", "string", "DELIVERED"),
+            ]
+        );
+        assert_eq!(
+            members("Priority"),
+            vec![
+                member("Low", "number", "0"),
+                member("High", "number", "10"),
+                member("Urgent", "number", "11"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_augmentations_and_namespaces_are_extracted() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+declare global {
+    interface Window { analytics: Analytics }
+}
+declare module 'express' {
+    interface Request { user?: User }
+}
+namespace Api {
+    export interface Response { status: number }
+}
+"#;
+
+        let parsed = parser.parse(content, false).unwrap();
+        let types: Vec<(&str, String)> = parsed
+            .type_definitions
+            .iter()
+            .map(|t| (t.name.as_str(), format!("{:?}", t.kind)))
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                ("Window", "Interface".to_string()),
+                ("Request", "Interface".to_string()),
+                ("Api", "Namespace".to_string()),
+                ("Response", "Interface".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generic_params_with_constraints() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+interface Repository<T extends Entity, K = string> {
+    find(id: K): Promise<T>;
+}
+
+type Page<T> = { items: T[]; next?: string };
+
+export function pluck<T, Key extends keyof T>(items: T[], key: Key): T[Key][] {
+    return items.map((item) => item[key]);
+}
+
+export const first = <T,>(items: T[]): T | undefined => items[0];
+"#;
+
+        let parsed = parser.parse(content, false).unwrap();
+        let type_params = |name: &str| parsed.type_definitions.iter().find(|t| t.name == name).unwrap().generic_params.clone();
+        assert_eq!(type_params("Repository"), vec!["T extends Entity", "K = string"]);
+        assert_eq!(type_params("Page"), vec!["T"]);
+
+        let symbol_params = |name: &str| parsed.symbols.iter().find(|s| s.name == name).unwrap().metadata.generic_params.clone();
+        assert_eq!(symbol_params("pluck"), vec!["T", "Key extends keyof T"]);
+        assert_eq!(symbol_params("first"), vec!["T"]);
+    }
+
+    #[test]
+    fn test_interface_property_jsdoc_becomes_description() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+interface ButtonProps {
+    /** Text shown inside the button */
+    label: string;
+    /**
+     * Visual style.
+     * Defaults to `primary`.
+     */
+    variant?: 'primary' | 'ghost';
+    size: number; // px
+    onClick: () => void;
+}
+"#;
+
+        let parsed = parser.parse(content, false).unwrap();
+        let props = &parsed.type_definitions.iter().find(|t| t.name == "ButtonProps").unwrap().properties;
+        let description = |name: &str| props.iter().find(|p| p.name == name).unwrap().description.clone();
+        assert_eq!(description("label").as_deref(), Some("Text shown inside the button"));
+        assert_eq!(description("variant").as_deref(), Some("Visual style.\nDefaults to `primary`."));
+        assert_eq!(description("size"), None);
+        assert_eq!(description("onClick"), None);
+    }
 }
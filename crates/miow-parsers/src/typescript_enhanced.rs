@@ -1,16 +1,14 @@
+use crate::examples::member_jsdoc;
 use crate::types::*;
+use crate::zod::zod_schemas;
 use anyhow::{Context, Result};
 use tree_sitter::{Node, Parser, Query, QueryCursor};
-use std::collections::HashMap;
 
-pub struct EnhancedTypeScriptParser {
-    parser: Parser,
-}
+pub struct EnhancedTypeScriptParser;
 
 impl EnhancedTypeScriptParser {
     pub fn new() -> Self {
-        let mut parser = Parser::new();
-        Self { parser }
+        Self
     }
 
     pub fn parse(&self, content: &str, is_tsx: bool) -> Result<ParsedFile> {
@@ -50,6 +48,9 @@ impl EnhancedTypeScriptParser {
             constants,
             schemas,
             language: if is_tsx { "tsx".to_string() } else { "typescript".to_string() },
+            tests: vec![],
+            routes: vec![],
+            examples: vec![],
         })
     }
 
@@ -85,7 +86,7 @@ impl EnhancedTypeScriptParser {
     }
 
     fn extract_interface(&self, node: &Node, source: &str) -> Result<Option<TypeDefinition>> {
-        let name = self.get_child_text(node, "type_identifier", source).unwrap_or_default();
+        let name = self.get_child_text(node, "name", source).unwrap_or_default();
         let definition = node.utf8_text(source.as_bytes())?.to_string();
         
         let mut properties = Vec::new();
@@ -93,7 +94,7 @@ impl EnhancedTypeScriptParser {
             let mut cursor = body.walk();
             for child in body.children(&mut cursor) {
                 if child.kind() == "property_signature" {
-                    let prop_name = self.get_child_text(&child, "property_identifier", source).unwrap_or_default();
+                    let prop_name = self.get_child_text(&child, "name", source).unwrap_or_default();
                     let type_annotation = child.child_by_field_name("type")
                         .map(|n| n.utf8_text(source.as_bytes()).unwrap().to_string())
                         .unwrap_or_default();
@@ -103,7 +104,8 @@ impl EnhancedTypeScriptParser {
                         name: prop_name,
                         type_annotation,
                         is_optional,
-                        description: None, // TODO: Extract from JSDoc
+                        description: member_jsdoc(&child, source),
+                        value: None,
                     });
                 }
             }
@@ -115,12 +117,12 @@ impl EnhancedTypeScriptParser {
             definition,
             properties,
             generic_params: vec![],
-            range: self.get_range(node),
+            range: Range::of(node),
         }))
     }
 
     fn extract_type_alias(&self, node: &Node, source: &str) -> Result<Option<TypeDefinition>> {
-        let name = self.get_child_text(node, "type_identifier", source).unwrap_or_default();
+        let name = self.get_child_text(node, "name", source).unwrap_or_default();
         let definition = node.utf8_text(source.as_bytes())?.to_string();
 
         Ok(Some(TypeDefinition {
@@ -129,12 +131,12 @@ impl EnhancedTypeScriptParser {
             definition,
             properties: vec![],
             generic_params: vec![],
-            range: self.get_range(node),
+            range: Range::of(node),
         }))
     }
 
     fn extract_enum_type(&self, node: &Node, source: &str) -> Result<Option<TypeDefinition>> {
-        let name = self.get_child_text(node, "identifier", source).unwrap_or_default();
+        let name = self.get_child_text(node, "name", source).unwrap_or_default();
         let definition = node.utf8_text(source.as_bytes())?.to_string();
 
         Ok(Some(TypeDefinition {
@@ -143,7 +145,7 @@ impl EnhancedTypeScriptParser {
             definition,
             properties: vec![],
             generic_params: vec![],
-            range: self.get_range(node),
+            range: Range::of(node),
         }))
     }
 
@@ -179,7 +181,7 @@ impl EnhancedTypeScriptParser {
                     let value = value_node.utf8_text(source.as_bytes())?.to_string();
                     
                     // Categorize the constant
-                    let category = self.categorize_constant(&name, &value);
+                    let category = self.categorize_constant(&name);
                     
                     // Skip if it's a function
                     if value_node.kind() == "arrow_function" || value_node.kind() == "function" {
@@ -191,7 +193,7 @@ impl EnhancedTypeScriptParser {
                         value,
                         type_annotation: None,
                         category,
-                        range: self.get_range(node),
+                        range: Range::of(node),
                     }));
                 }
             }
@@ -199,9 +201,8 @@ impl EnhancedTypeScriptParser {
         Ok(None)
     }
 
-    fn categorize_constant(&self, name: &str, value: &str) -> ConstantCategory {
+    fn categorize_constant(&self, name: &str) -> ConstantCategory {
         let name_lower = name.to_lowercase();
-        let value_lower = value.to_lowercase();
 
         if name_lower.contains("api") || name_lower.contains("endpoint") || name_lower.contains("url") {
             ConstantCategory::APIEndpoint
@@ -235,16 +236,12 @@ impl EnhancedTypeScriptParser {
     }
 
     fn extract_zod_schemas(&self, node: &Node, source: &str) -> Result<Vec<ValidationSchema>> {
-        let mut schemas = Vec::new();
-        // TODO: Implement Zod schema extraction using tree-sitter queries
-        // This is a placeholder - full implementation would parse z.object() calls
-        Ok(schemas)
+        zod_schemas(node, source)
     }
 
-    fn extract_yup_schemas(&self, node: &Node, source: &str) -> Result<Vec<ValidationSchema>> {
-        let mut schemas = Vec::new();
+    fn extract_yup_schemas(&self, _node: &Node, _source: &str) -> Result<Vec<ValidationSchema>> {
         // TODO: Implement Yup schema extraction
-        Ok(schemas)
+        Ok(vec![])
     }
 
     /// Enhanced design token extraction - extract ALL CSS variables, colors, etc.
@@ -285,7 +282,8 @@ impl EnhancedTypeScriptParser {
                             name: class.to_string(),
                             value: class.to_string(),
                             context: "className".to_string(),
-                            range: self.get_range(&capture.node),
+                            range: Range::of(&capture.node),
+                            language: None,
                         });
                     }
                 }
@@ -329,15 +327,6 @@ impl EnhancedTypeScriptParser {
             .map(|n| n.utf8_text(source.as_bytes()).unwrap().to_string())
     }
 
-    fn get_range(&self, node: &Node) -> Range {
-        Range {
-            start_line: node.start_position().row + 1,
-            end_line: node.end_position().row + 1,
-            start_byte: node.start_byte(),
-            end_byte: node.end_byte(),
-        }
-    }
-
     // Placeholder for existing methods - these would be kept from the original parser
     fn extract_symbols(&self, _node: &Node, _source: &str, _is_tsx: bool) -> Result<Vec<Symbol>> {
         Ok(vec![])
@@ -357,3 +346,36 @@ impl Default for EnhancedTypeScriptParser {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interface_property_jsdoc_becomes_description() {
+        let content = r#"
+interface CardProps {
+    /** Heading shown above the body */
+    title: string;
+    /**
+     * Called when the card is dismissed.
+     * Omit to hide the close button.
+     */
+    onClose?: () => void;
+    elevation: number; // 0-3
+    footer?: string;
+}
+"#;
+
+        let parsed = EnhancedTypeScriptParser::new().parse(content, true).unwrap();
+        let props = &parsed.type_definitions.iter().find(|t| t.name == "CardProps").unwrap().properties;
+        let description = |name: &str| props.iter().find(|p| p.name == name).unwrap().description.clone();
+        assert_eq!(description("title").as_deref(), Some("Heading shown above the body"));
+        assert_eq!(
+            description("onClose").as_deref(),
+            Some("Called when the card is dismissed.\nOmit to hide the close button.")
+        );
+        assert_eq!(description("elevation"), None);
+        assert_eq!(description("footer"), None);
+    }
+}
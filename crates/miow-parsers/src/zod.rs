@@ -0,0 +1,230 @@
+//! Zod schemas. `const SignupSchema = z.object({ email: z.string().email() })` is where a form's
+//! rules actually live, so each such declaration becomes a `ValidationSchema` with one field per
+//! key and the field's validator chain spelled out. Nested `z.object` and
+//! `z.array(z.object(...))` fields are flattened as `address.city` and `items[].sku`.
+
+use crate::types::*;
+use anyhow::{Context, Result};
+use tree_sitter::{Node, Query, QueryCursor};
+
+/// Declarators initialised by a call; those whose chain starts at `z.object` are schemas
+const ZOD_DECLARATION_QUERY: &str = r#"
+(variable_declarator
+  name: (identifier) @name
+  value: (call_expression) @schema)
+"#;
+
+/// Argument kinds kept verbatim in a validator, e.g. `min(8)` or `regex(/^\d+$/)`
+const LITERAL_ARGUMENTS: [&str; 8] = ["number", "string", "template_string", "regex", "true", "false", "null", "unary_expression"];
+
+/// Every `const X = z.object({...})` under `root`, in source order
+pub(crate) fn zod_schemas(root: &Node, source: &str) -> Result<Vec<ValidationSchema>> {
+    let query = Query::new(root.language(), ZOD_DECLARATION_QUERY).context("Failed to create Zod schema query")?;
+    let name_index = query.capture_index_for_name("name").unwrap_or(0);
+    let schema_index = query.capture_index_for_name("schema").unwrap_or(1);
+
+    let mut schemas = Vec::new();
+    let mut cursor = QueryCursor::new();
+    for m in cursor.matches(&query, *root, source.as_bytes()) {
+        let capture = |index| m.captures.iter().find(|c| c.index == index).map(|c| c.node);
+        let (Some(name), Some(schema)) = (capture(name_index), capture(schema_index)) else {
+            continue;
+        };
+        let chain = ZodChain::of(schema, source);
+        let Some(object) = chain.object_shape() else {
+            continue;
+        };
+        let Some(declarator) = name.parent() else {
+            continue;
+        };
+
+        let mut fields = Vec::new();
+        object_fields(object, "", source, &mut fields);
+        // The whole `const`/`let` statement, as the declarator alone drops the keyword
+        let declaration = declarator.parent().unwrap_or(declarator);
+        schemas.push(ValidationSchema {
+            name: name.utf8_text(source.as_bytes())?.to_string(),
+            schema_type: SchemaType::Zod,
+            definition: declaration.utf8_text(source.as_bytes())?.to_string(),
+            fields,
+            range: Range::of(&declaration),
+        });
+    }
+    Ok(schemas)
+}
+
+/// A method call of a chain: `min` and its `(8)` argument list
+struct Call<'a> {
+    name: String,
+    arguments: Node<'a>,
+}
+
+/// `z.string().min(8).optional()` split into the expression it starts from (`z`) and its
+/// calls in source order
+struct ZodChain<'a> {
+    base: String,
+    calls: Vec<Call<'a>>,
+}
+
+impl<'a> ZodChain<'a> {
+    fn of(expression: Node<'a>, source: &str) -> Self {
+        let mut calls = Vec::new();
+        let mut node = expression;
+        while node.kind() == "call_expression" {
+            let (Some(function), Some(arguments)) =
+                (node.child_by_field_name("function"), node.child_by_field_name("arguments"))
+            else {
+                break;
+            };
+            let (Some(object), Some(property)) =
+                (function.child_by_field_name("object"), function.child_by_field_name("property"))
+            else {
+                break;
+            };
+            calls.push(Call {
+                name: text(&property, source).to_string(),
+                arguments,
+            });
+            node = object;
+        }
+        calls.reverse();
+        ZodChain {
+            base: text(&node, source).to_string(),
+            calls,
+        }
+    }
+
+    /// The `z.<name>(...)` call the chain starts with
+    fn starts_with(&self, name: &str) -> Option<&Call<'a>> {
+        self.calls.first().filter(|call| self.base == "z" && call.name == name)
+    }
+
+    /// Object literal passed to a leading `z.object(...)`
+    fn object_shape(&self) -> Option<Node<'a>> {
+        let arguments = self.starts_with("object")?.arguments;
+        let mut cursor = arguments.walk();
+        let shape = arguments.named_children(&mut cursor).find(|arg| arg.kind() == "object");
+        shape
+    }
+
+    /// Each call as written when its arguments are literals (`min(8)`), else by name (`array`)
+    fn validators(&self, source: &str) -> Vec<String> {
+        self.calls
+            .iter()
+            .map(|call| {
+                let mut cursor = call.arguments.walk();
+                let mut arguments = call.arguments.named_children(&mut cursor).peekable();
+                let literal = arguments.peek().is_some() && arguments.all(|arg| LITERAL_ARGUMENTS.contains(&arg.kind()));
+                if literal {
+                    format!("{}{}", call.name, text(&call.arguments, source))
+                } else {
+                    call.name.clone()
+                }
+            })
+            .collect()
+    }
+
+    /// Source of the arguments of the last `.name(...)` call, without the parentheses
+    fn argument_of(&self, name: &str, source: &str) -> Option<String> {
+        let call = self.calls.iter().rev().find(|call| call.name == name)?;
+        let arguments = text(&call.arguments, source);
+        Some(arguments.trim_start_matches('(').trim_end_matches(')').trim().to_string())
+    }
+}
+
+/// Append a field for each key of a `z.object` shape, recursing into nested object and
+/// array-of-object fields with `prefix` extended by the key
+fn object_fields(shape: Node, prefix: &str, source: &str, fields: &mut Vec<SchemaField>) {
+    let mut cursor = shape.walk();
+    for pair in shape.named_children(&mut cursor).filter(|n| n.kind() == "pair") {
+        let (Some(key), Some(value)) = (pair.child_by_field_name("key"), pair.child_by_field_name("value")) else {
+            continue;
+        };
+        let name = format!("{}{}", prefix, text(&key, source).trim_matches(|c| c == '"' || c == '\''));
+        let chain = ZodChain::of(value, source);
+        let validators = chain.validators(source);
+        let is_optional = chain.calls.iter().any(|call| matches!(call.name.as_str(), "optional" | "nullish"));
+
+        fields.push(SchemaField {
+            name: name.clone(),
+            validation_rules: validators.clone(),
+            is_required: !is_optional,
+            default_value: chain.argument_of("default", source),
+            type_annotation: Some(text(&value, source).to_string()),
+            is_optional,
+            validators,
+            description: chain
+                .argument_of("describe", source)
+                .map(|d| d.trim_matches(|c| c == '"' || c == '\'' || c == '`').to_string()),
+        });
+
+        if let Some(nested) = chain.object_shape() {
+            object_fields(nested, &format!("{}.", name), source, fields);
+        } else if let Some(array) = chain.starts_with("array") {
+            let mut cursor = array.arguments.walk();
+            let element = array.arguments.named_children(&mut cursor).next();
+            if let Some(nested) = element.and_then(|e| ZodChain::of(e, source).object_shape()) {
+                object_fields(nested, &format!("{}[].", name), source, fields);
+            }
+        }
+    }
+}
+
+fn text<'s>(node: &Node, source: &'s str) -> &'s str {
+    node.utf8_text(source.as_bytes()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_zod_object_fields_and_validators() {
+        let content = r#"
+import { z } from 'zod';
+
+export const SignupSchema = z.object({
+  email: z.string().email(),
+  password: z.string().min(8, "Too short"),
+  nickname: z.string().max(20).optional(),
+  role: z.enum(["admin", "member"]).default("member"),
+  address: z.object({
+    city: z.string(),
+    zip: z.string().regex(/^\d{5}$/).nullish(),
+  }),
+  items: z.array(z.object({ sku: z.string(), qty: z.number().int().min(1) })),
+});
+
+const notASchema = z.string().email();
+"#;
+        let parsed = crate::parse_typescript(content, false).unwrap();
+        assert_eq!(parsed.schemas.len(), 1);
+        let schema = &parsed.schemas[0];
+        assert_eq!(schema.name, "SignupSchema");
+        assert_eq!(schema.range.start_line, 4);
+        assert!(schema.definition.starts_with("const SignupSchema = z.object({"), "{}", schema.definition);
+        assert_eq!(&content[schema.range.start_byte..schema.range.end_byte], schema.definition);
+
+        let names: Vec<&str> = schema.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["email", "password", "nickname", "role", "address", "address.city", "address.zip", "items", "items[].sku", "items[].qty"]
+        );
+        let field = |name: &str| schema.fields.iter().find(|f| f.name == name).unwrap();
+        assert_eq!(field("email").validators, ["string", "email"]);
+        assert_eq!(field("password").validators, ["string", r#"min(8, "Too short")"#]);
+        assert_eq!(field("nickname").validators, ["string", "max(20)", "optional"]);
+        assert!(field("nickname").is_optional && !field("nickname").is_required);
+        assert!(!field("email").is_optional && field("email").is_required);
+        assert_eq!(field("role").validators, ["enum", r#"default("member")"#]);
+        assert_eq!(field("role").default_value.as_deref(), Some(r#""member""#));
+        assert_eq!(field("address").validators, ["object"]);
+        assert!(field("address.zip").is_optional);
+        assert_eq!(field("items[].qty").validators, ["number", "int", "min(1)"]);
+
+        let enhanced = crate::typescript_enhanced::EnhancedTypeScriptParser::new().parse(content, false).unwrap();
+        assert_eq!(enhanced.schemas.len(), 1);
+        assert_eq!(enhanced.schemas[0].name, "SignupSchema");
+        let enhanced_names: Vec<&str> = enhanced.schemas[0].fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(enhanced_names, names);
+        assert!(enhanced.schemas[0].fields.iter().find(|f| f.name == "nickname").unwrap().is_optional);
+    }
+}
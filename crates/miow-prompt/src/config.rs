@@ -0,0 +1,194 @@
+//! Tunable knobs of context gathering in one place: how many results a search fetches, when
+//! the auditor prunes, how many items each category keeps, the token budget and the reranker
+//! weights. Read from `.miow/context_config.json` so they can be tuned without code edits;
+//! missing fields keep their defaults.
+
+use crate::{CharRatioEstimator, SmartPruner};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Config file name, stored next to the graph database in `.miow/`
+pub const CONTEXT_CONFIG_FILE: &str = "context_config.json";
+
+/// How much each signal contributes to a symbol's relevance when reranking
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RerankWeights {
+    /// Per keyword found in the symbol name
+    pub name_match: f32,
+    /// Per keyword found in the file path
+    pub path_match: f32,
+    /// Per keyword found in the symbol body
+    pub content_match: f32,
+    /// Multiplier taking a 0-1 vector similarity onto the keyword score's scale
+    pub vector_scale: f32,
+}
+
+impl Default for RerankWeights {
+    fn default() -> Self {
+        Self {
+            name_match: 3.0,
+            path_match: 2.0,
+            content_match: 1.0,
+            vector_scale: 10.0,
+        }
+    }
+}
+
+impl RerankWeights {
+    /// Keyword score of a symbol; `name`, `path` and `content` are expected lower-cased,
+    /// like `keywords`
+    pub fn keyword_score(&self, name: &str, path: &str, content: &str, keywords: &[String]) -> f32 {
+        keywords
+            .iter()
+            .filter(|keyword| !keyword.is_empty())
+            .map(|keyword| {
+                let mut score = 0.0;
+                if name.contains(keyword.as_str()) {
+                    score += self.name_match;
+                }
+                if path.contains(keyword.as_str()) {
+                    score += self.path_match;
+                }
+                if content.contains(keyword.as_str()) {
+                    score += self.content_match;
+                }
+                score
+            })
+            .sum()
+    }
+}
+
+/// Relevance and size limits applied across search, auditing and pruning
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContextConfig {
+    /// Results fetched from vector search for a prompt
+    pub search_k: usize,
+    /// Items a category may hold before the auditor cuts it down to this many
+    pub audit_threshold: usize,
+    /// Items kept per category by the pruner, and for types, constants and design tokens
+    pub max_items_per_category: usize,
+    /// Token budget of the generated prompt's context
+    pub token_budget: usize,
+    /// Characters per token assumed when estimating usage
+    pub chars_per_token: usize,
+    /// Model whose tokenizer measures usage instead of `chars_per_token`, e.g. `gpt-4o`
+    pub tokenizer: Option<String>,
+    /// Reference hops followed from a hit to pull in the helpers and types it uses
+    pub neighborhood_depth: usize,
+    /// When set, vector search picks results by Maximal Marginal Relevance with this
+    /// relevance/diversity balance (1.0 = plain top-k)
+    pub mmr_lambda: Option<f32>,
+    /// When set, a prompt whose best reranked item scores below this gets no context at all
+    /// (an "insufficient context" result) instead of weak matches; on the rerank weights'
+    /// scale, so 3.0 asks for at least one prompt keyword in a symbol name
+    pub relevance_floor: Option<f32>,
+    pub rerank: RerankWeights,
+}
+
+impl Default for ContextConfig {
+    fn default() -> Self {
+        Self {
+            search_k: 30,
+            audit_threshold: 8,
+            max_items_per_category: crate::pruner::DEFAULT_MAX_ITEMS_PER_CATEGORY,
+            token_budget: 16000,
+            chars_per_token: 4,
+            tokenizer: None,
+            neighborhood_depth: 1,
+            mmr_lambda: None,
+            relevance_floor: None,
+            rerank: RerankWeights::default(),
+        }
+    }
+}
+
+impl ContextConfig {
+    /// The config at `path`, or the defaults if there is none
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read context config {:?}", path))?;
+        serde_json::from_str(&content).with_context(|| format!("Invalid context config {:?}", path))
+    }
+
+    pub fn estimator(&self) -> CharRatioEstimator {
+        CharRatioEstimator::new(self.chars_per_token)
+    }
+
+    /// A pruner enforcing this config's token budget and per-category limit. A configured
+    /// `tokenizer` takes precedence over `chars_per_token`.
+    pub fn pruner(&self) -> SmartPruner {
+        let pruner = SmartPruner::new(self.token_budget).with_max_items_per_category(self.max_items_per_category);
+        match &self.tokenizer {
+            Some(model) => pruner.with_tokenizer(model),
+            None => pruner.with_estimator(Box::new(self.estimator())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ContextData, SymbolInfo, TypeInfo};
+
+    fn symbol(i: usize) -> SymbolInfo {
+        SymbolInfo {
+            name: format!("helper{}", i),
+            kind: "Function".to_string(),
+            content: "x".repeat(400),
+            file_path: format!("src/helper{}.ts", i),
+            start_line: 1,
+            end_line: 1,
+            props: vec![],
+            references: vec![],
+            language: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_pruner_honors_overridden_limits() {
+        let config: ContextConfig =
+            serde_json::from_str(r#"{"max_items_per_category": 3, "token_budget": 700, "chars_per_token": 2}"#).unwrap();
+        assert_eq!(config.search_k, ContextConfig::default().search_k);
+        assert_eq!(config.rerank, RerankWeights::default());
+
+        let mut context = ContextData {
+            relevant_symbols: (0..6).map(symbol).collect(),
+            similar_symbols: vec![],
+            design_tokens: vec![],
+            common_imports: vec![],
+            types: (0..6)
+                .map(|i| TypeInfo {
+                    name: format!("T{}", i),
+                    kind: "interface".to_string(),
+                    definition: String::new(),
+                })
+                .collect(),
+            constants: vec![],
+            schemas: vec![],
+        };
+        // ~2,450 chars fit 700 tokens at the default 4 chars per token, but not at 2; cutting
+        // each category to 3 items brings it down to ~615 tokens
+        let mut unpruned = context.clone();
+        SmartPruner::new(config.token_budget).prune(&mut unpruned);
+        assert_eq!(unpruned.relevant_symbols.len(), 6);
+
+        config.pruner().prune(&mut context);
+        assert_eq!(context.relevant_symbols.len(), 3);
+        assert_eq!(context.types.len(), 3);
+
+        let weights = RerankWeights {
+            name_match: 0.0,
+            path_match: 5.0,
+            ..Default::default()
+        };
+        let keywords = vec!["helper".to_string()];
+        assert_eq!(weights.keyword_score("helper1", "src/helper1.ts", "", &keywords), 5.0);
+    }
+}
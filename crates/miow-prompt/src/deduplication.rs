@@ -1,4 +1,4 @@
-use crate::ContextData;
+use crate::{CharRatioEstimator, ContextData, ContextReport, ContextStage};
 use std::collections::HashSet;
 use tracing::debug;
 
@@ -10,10 +10,11 @@ impl DeduplicationEngine {
     pub fn deduplicate(context: &mut ContextData) {
         let initial_count = context.relevant_symbols.len() + context.types.len();
         
-        // 1. Deduplicate relevant symbols (by name and path)
+        // 1. Deduplicate relevant symbols (by name and path; a usage example shares both
+        //    with the symbol it documents)
         let mut seen_symbols = HashSet::new();
         context.relevant_symbols.retain(|s| {
-            let key = format!("{}:{}", s.name, s.file_path);
+            let key = format!("{}:{}:{}", s.name, s.file_path, s.is_usage_example());
             seen_symbols.insert(key)
         });
         
@@ -22,7 +23,7 @@ impl DeduplicationEngine {
             .map(|s| s.name.clone())
             .collect();
             
-        context.similar_symbols.retain(|s| !relevant_names.contains(&s.name));
+        context.similar_symbols.retain(|s| s.is_usage_example() || !relevant_names.contains(&s.name));
         
         // 3. Deduplicate types
         let mut seen_types = HashSet::new();
@@ -50,4 +51,11 @@ impl DeduplicationEngine {
             debug!("Deduplicated {} items from context", initial_count - final_count);
         }
     }
+
+    /// Like `deduplicate`, recording the dropped repeats in `report`
+    pub fn deduplicate_traced(context: &mut ContextData, report: &mut ContextReport) {
+        let before = context.clone();
+        Self::deduplicate(context);
+        report.record_diff(ContextStage::Dedup, &before, context, &CharRatioEstimator::default());
+    }
 }
@@ -0,0 +1,145 @@
+use crate::{ContextData, SymbolInfo};
+use std::collections::HashSet;
+
+/// Kind of symbols holding a doc comment's `@example` code, as indexed by the core indexer
+pub const USAGE_EXAMPLE_KIND: &str = "usage-example";
+
+/// A shortened body's signature is looked for in at most this many leading lines
+const MAX_SIGNATURE_LINES: usize = 12;
+
+/// Replaces a body cut down to its signature
+const OMITTED_BODY_MARKER: &str = "  // ... implementation omitted, see the usage example below\n}";
+
+impl SymbolInfo {
+    pub fn is_usage_example(&self) -> bool {
+        self.kind == USAGE_EXAMPLE_KIND
+    }
+
+    fn example_key(&self) -> (&str, &str) {
+        (&self.name, &self.file_path)
+    }
+}
+
+impl ContextData {
+    /// Move each usage example right after the symbol it documents, and cut documented
+    /// symbols longer than `max_body_lines` down to their signature: the example shows how
+    /// to call the API in far less space than the implementation. Examples of symbols that
+    /// aren't in the context stay where they are. Returns how many bodies were cut.
+    pub fn prefer_usage_examples(&mut self, max_body_lines: usize) -> usize {
+        let documented: HashSet<(String, String)> = self
+            .relevant_symbols
+            .iter()
+            .chain(&self.similar_symbols)
+            .filter(|s| !s.is_usage_example())
+            .map(|s| (s.name.clone(), s.file_path.clone()))
+            .collect();
+        let is_linked = |s: &SymbolInfo| {
+            s.is_usage_example() && documented.contains(&(s.name.clone(), s.file_path.clone()))
+        };
+
+        let mut examples = Vec::new();
+        for symbols in [&mut self.relevant_symbols, &mut self.similar_symbols] {
+            let (linked, rest): (Vec<_>, Vec<_>) = std::mem::take(symbols).into_iter().partition(is_linked);
+            examples.extend(linked);
+            *symbols = rest;
+        }
+        if examples.is_empty() {
+            return 0;
+        }
+
+        let mut shortened = 0;
+        for symbols in [&mut self.relevant_symbols, &mut self.similar_symbols] {
+            let mut arranged = Vec::with_capacity(symbols.len());
+            for mut symbol in std::mem::take(symbols) {
+                if symbol.is_usage_example() {
+                    arranged.push(symbol);
+                    continue;
+                }
+                let (own, rest): (Vec<_>, Vec<_>) = std::mem::take(&mut examples)
+                    .into_iter()
+                    .partition(|e| e.example_key() == symbol.example_key());
+                examples = rest;
+                if !own.is_empty() && cut_to_signature(&mut symbol.content, max_body_lines) {
+                    shortened += 1;
+                }
+                arranged.push(symbol);
+                arranged.extend(own);
+            }
+            *symbols = arranged;
+        }
+        shortened
+    }
+}
+
+/// Replace a body longer than `max_lines` with its signature. Returns false (leaving the
+/// body alone) when it is short enough or no opening brace is found.
+fn cut_to_signature(content: &mut String, max_lines: usize) -> bool {
+    if content.lines().count() <= max_lines {
+        return false;
+    }
+    let Some(open) = content
+        .lines()
+        .take(MAX_SIGNATURE_LINES)
+        .position(|line| line.trim_end().ends_with('{'))
+    else {
+        return false;
+    };
+    let signature: Vec<&str> = content.lines().take(open + 1).collect();
+    *content = format!("{}\n{}", signature.join("\n"), OMITTED_BODY_MARKER);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(name: &str, kind: &str, content: &str) -> SymbolInfo {
+        SymbolInfo {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            content: content.to_string(),
+            file_path: "src/price.ts".to_string(),
+            start_line: 1,
+            end_line: 1,
+            props: vec![],
+            references: vec![],
+            language: "typescript".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_example_follows_its_symbol_and_replaces_a_large_body() {
+        let body: String = (0..30).map(|i| format!("  const step{} = {};\n", i, i)).collect();
+        let implementation = format!("function formatPrice(cents: number): string {{\n{}}}", body);
+        let mut context = ContextData {
+            relevant_symbols: vec![
+                symbol("formatPrice", "Function", &implementation),
+                symbol("parsePrice", "Function", "function parsePrice(s: string) {\n  return Number(s);\n}"),
+            ],
+            similar_symbols: vec![
+                symbol("formatPrice", USAGE_EXAMPLE_KIND, "formatPrice(1999); // => \"$19.99\""),
+                symbol("roundPrice", USAGE_EXAMPLE_KIND, "roundPrice(19.999);"),
+            ],
+            design_tokens: vec![],
+            common_imports: vec![],
+            types: vec![],
+            constants: vec![],
+            schemas: vec![],
+        };
+
+        assert_eq!(context.prefer_usage_examples(20), 1);
+        let order: Vec<(&str, &str)> =
+            context.relevant_symbols.iter().map(|s| (s.name.as_str(), s.kind.as_str())).collect();
+        assert_eq!(
+            order,
+            vec![("formatPrice", "Function"), ("formatPrice", USAGE_EXAMPLE_KIND), ("parsePrice", "Function")]
+        );
+        assert!(context.relevant_symbols[0].content.starts_with("function formatPrice(cents: number): string {\n"));
+        assert!(context.relevant_symbols[0].content.ends_with(OMITTED_BODY_MARKER));
+        assert!(context.relevant_symbols[2].content.contains("Number(s)"));
+
+        // No `roundPrice` in context, so its example stays put
+        assert_eq!(context.similar_symbols.len(), 1);
+        assert_eq!(context.similar_symbols[0].name, "roundPrice");
+    }
+}
@@ -0,0 +1,120 @@
+use std::path::Path;
+
+/// Languages that carry no highlighting information
+const UNTYPED_LANGUAGES: [&str; 5] = ["", "unknown", "text", "plaintext", "plain"];
+
+/// Container formats whose snippets are in an embedded block's language (a `.vue` file's
+/// `<script lang="ts">`), so a detected language beats the extension
+const CONTAINER_TAGS: [&str; 4] = ["vue", "svelte", "astro", "markdown"];
+
+/// Canonical fence tag for a file extension, if it's one we know
+fn extension_tag(extension: &str) -> Option<&'static str> {
+    let tag = match extension {
+        "tsx" => "tsx",
+        "ts" | "mts" | "cts" => "typescript",
+        "jsx" => "jsx",
+        "js" | "mjs" | "cjs" => "javascript",
+        "rs" => "rust",
+        "py" | "pyi" => "python",
+        "sql" => "sql",
+        "go" => "go",
+        "java" => "java",
+        "kt" | "kts" => "kotlin",
+        "rb" => "ruby",
+        "php" => "php",
+        "cs" => "csharp",
+        "c" | "h" => "c",
+        "cpp" | "cc" | "cxx" | "hpp" => "cpp",
+        "swift" => "swift",
+        "css" => "css",
+        "scss" => "scss",
+        "html" | "htm" => "html",
+        "vue" => "vue",
+        "astro" => "astro",
+        "svelte" => "svelte",
+        "json" => "json",
+        "yaml" | "yml" => "yaml",
+        "toml" => "toml",
+        "md" | "mdx" => "markdown",
+        "sh" | "bash" => "bash",
+        "graphql" | "gql" => "graphql",
+        "prisma" => "prisma",
+        _ => return None,
+    };
+    Some(tag)
+}
+
+/// Canonical fence tag for a detected language name
+fn language_tag(language: &str) -> String {
+    match language {
+        "ts" => "typescript".to_string(),
+        "js" => "javascript".to_string(),
+        "rs" => "rust".to_string(),
+        "py" => "python".to_string(),
+        "c#" => "csharp".to_string(),
+        "c++" => "cpp".to_string(),
+        "shell" | "sh" => "bash".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Markdown code fence tag for a chunk from `file_path` whose detected language is
+/// `language`. A known extension wins since it's more specific (`.tsx` vs "typescript");
+/// otherwise the detected language is used, falling back to the raw extension when the
+/// language is missing or "unknown"/"text". Mixed-language containers (`.vue`, `.md`, ...)
+/// prefer the detected language, which is the embedded block's.
+pub fn fence_tag(file_path: &str, language: &str) -> String {
+    let extension = Path::new(file_path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let language = language.trim().to_lowercase();
+    let typed = !UNTYPED_LANGUAGES.contains(&language.as_str());
+    match extension_tag(&extension) {
+        Some(tag) if !(typed && CONTAINER_TAGS.contains(&tag)) => tag.to_string(),
+        _ if typed => language_tag(&language),
+        Some(tag) => tag.to_string(),
+        None => extension,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ContextData, SymbolInfo};
+
+    #[test]
+    fn test_tsx_chunk_fenced_as_tsx_despite_unknown_language() {
+        assert_eq!(fence_tag("src/App.tsx", "unknown"), "tsx");
+        assert_eq!(fence_tag("src/lib.rs", "text"), "rust");
+        assert_eq!(fence_tag("db/schema", "SQL"), "sql");
+        assert_eq!(fence_tag("Makefile.custom", "unknown"), "custom");
+        assert_eq!(fence_tag("README", ""), "");
+        assert_eq!(fence_tag("src/Button.vue", "css"), "css");
+        assert_eq!(fence_tag("src/Button.vue", "unknown"), "vue");
+
+        let context = ContextData {
+            relevant_symbols: vec![SymbolInfo {
+                name: "App".to_string(),
+                kind: "Component".to_string(),
+                content: "export const App = () => <div />;".to_string(),
+                file_path: "src/App.tsx".to_string(),
+                start_line: 1,
+                end_line: 1,
+                props: vec![],
+                references: vec![],
+                language: "unknown".to_string(),
+            }],
+            similar_symbols: vec![],
+            design_tokens: vec![],
+            common_imports: vec![],
+            types: vec![],
+            constants: vec![],
+            schemas: vec![],
+        };
+
+        assert!(context
+            .to_markdown(false)
+            .contains("```tsx\nexport const App = () => <div />;\n```"));
+    }
+}
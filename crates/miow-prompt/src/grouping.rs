@@ -0,0 +1,57 @@
+use crate::{ContextData, SymbolInfo};
+use std::collections::BTreeMap;
+
+impl ContextData {
+    /// Relevant and similar symbols grouped by `file_path`, files in path order and symbols in
+    /// context order within each, for file-tree views ("3 matches in src/Button.tsx"). Types,
+    /// constants and schemas carry no path and aren't included.
+    pub fn by_file(&self) -> BTreeMap<&str, Vec<&SymbolInfo>> {
+        let mut groups: BTreeMap<&str, Vec<&SymbolInfo>> = BTreeMap::new();
+        for symbol in self.relevant_symbols.iter().chain(&self.similar_symbols) {
+            groups.entry(symbol.file_path.as_str()).or_default().push(symbol);
+        }
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(name: &str, file_path: &str) -> SymbolInfo {
+        SymbolInfo {
+            name: name.to_string(),
+            kind: "Component".to_string(),
+            content: String::new(),
+            file_path: file_path.to_string(),
+            start_line: 1,
+            end_line: 1,
+            props: vec![],
+            references: vec![],
+            language: "tsx".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_symbols_from_the_same_file_group_together() {
+        let context = ContextData {
+            relevant_symbols: vec![
+                symbol("Button", "src/components/Button.tsx"),
+                symbol("Card", "src/components/Card.tsx"),
+                symbol("ButtonProps", "src/components/Button.tsx"),
+            ],
+            similar_symbols: vec![symbol("IconButton", "src/components/Button.tsx")],
+            design_tokens: vec![],
+            common_imports: vec![],
+            types: vec![],
+            constants: vec![],
+            schemas: vec![],
+        };
+
+        let groups = context.by_file();
+        let files: Vec<&str> = groups.keys().copied().collect();
+        assert_eq!(files, vec!["src/components/Button.tsx", "src/components/Card.tsx"]);
+        let button: Vec<&str> = groups["src/components/Button.tsx"].iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(button, vec!["Button", "ButtonProps", "IconButton"]);
+    }
+}
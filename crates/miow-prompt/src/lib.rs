@@ -3,10 +3,26 @@ use serde::{Deserialize, Serialize};
 pub mod meta_prompt;
 pub mod pruner;
 pub mod deduplication;
+pub mod tokens;
+pub mod limits;
+pub mod fence;
+pub mod naming;
+pub mod grouping;
+pub mod ndjson;
+pub mod report;
+pub mod examples;
+pub mod config;
 
 pub use meta_prompt::*;
 pub use pruner::*;
 pub use deduplication::*;
+pub use tokens::*;
+pub use limits::*;
+pub use fence::*;
+pub use config::{ContextConfig, RerankWeights, CONTEXT_CONFIG_FILE};
+pub use examples::USAGE_EXAMPLE_KIND;
+pub use report::{ContextReport, ContextStage, DroppedItem, PruneStrategy, StageTrace};
+pub use naming::{KindNaming, NamingConvention, NamingOutlier, NamingReport};
 
 /// Prompt generator - creates context-aware prompts for LLMs
 pub struct PromptGenerator;
@@ -64,88 +80,7 @@ CRITICAL INSTRUCTIONS:
     }
 
     fn build_context_block(&self, context: &ContextData) -> String {
-        let mut blocks = Vec::new();
-
-        // Add relevant symbols
-        if !context.relevant_symbols.is_empty() {
-            blocks.push("## Relevant Existing Code\n".to_string());
-            for symbol in &context.relevant_symbols {
-                blocks.push(format!(
-                    "### {} ({})\n**File:** {}\n**Lines:** {}-{}\n```\n{}\n```\n",
-                    symbol.name,
-                    symbol.kind,
-                    symbol.file_path,
-                    symbol.start_line,
-                    symbol.end_line,
-                    symbol.content
-                ));
-            }
-        }
-
-        // Add similar symbols
-        if !context.similar_symbols.is_empty() {
-            blocks.push("\n## Similar Existing Patterns\n".to_string());
-            for symbol in &context.similar_symbols {
-                blocks.push(format!(
-                    "### {} ({})\n**File:** {}\n```\n{}\n```\n",
-                    symbol.name, symbol.kind, symbol.file_path, symbol.content
-                ));
-            }
-        }
-
-        // Add design tokens
-        if !context.design_tokens.is_empty() {
-            blocks.push("\n## Design Tokens & Styles\n".to_string());
-            for token in &context.design_tokens {
-                blocks.push(format!(
-                    "- **{}**: `{}` ({})\n",
-                    token.name, token.value, token.token_type
-                ));
-            }
-        }
-
-        // Add types
-        if !context.types.is_empty() {
-            blocks.push("\n## Type Definitions\n".to_string());
-            for type_info in &context.types {
-                blocks.push(format!(
-                    "### {} ({})\n```typescript\n{}\n```\n",
-                    type_info.name, type_info.kind, type_info.definition
-                ));
-            }
-        }
-
-        // Add constants
-        if !context.constants.is_empty() {
-            blocks.push("\n## Constants & Configuration\n".to_string());
-            for constant in &context.constants {
-                blocks.push(format!(
-                    "- **{}** ({}) = `{}`\n",
-                    constant.name, constant.category, constant.value
-                ));
-            }
-        }
-
-        // Add schemas
-        if !context.schemas.is_empty() {
-            blocks.push("\n## Validation Schemas\n".to_string());
-            for schema in &context.schemas {
-                blocks.push(format!(
-                    "### {} ({})\n```typescript\n{}\n```\n",
-                    schema.name, schema.schema_type, schema.definition
-                ));
-            }
-        }
-
-        // Add imports
-        if !context.common_imports.is_empty() {
-            blocks.push("\n## Common Imports\n".to_string());
-            for import in &context.common_imports {
-                blocks.push(format!("- {}\n", import));
-            }
-        }
-
-        blocks.join("\n")
+        context.to_markdown(false)
     }
 
     fn build_user_prompt(&self, original: &str, context: &ContextData) -> String {
@@ -225,7 +160,7 @@ pub struct PromptRequest {
     pub implementation_plan: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ContextData {
     pub relevant_symbols: Vec<SymbolInfo>,
     pub similar_symbols: Vec<SymbolInfo>,
@@ -236,6 +171,105 @@ pub struct ContextData {
     pub schemas: Vec<SchemaInfo>,
 }
 
+impl ContextData {
+    /// Render the context as markdown sections. In verbose mode a per-section token
+    /// estimate (e.g. "Components: 1,200 tok; Types: 800 tok") is printed first.
+    pub fn to_markdown(&self, verbose: bool) -> String {
+        let mut blocks = Vec::new();
+
+        if verbose {
+            let estimate = self.token_estimate_by_section(&CharRatioEstimator::default());
+            blocks.push(format!("_Token estimate: {}_\n", estimate));
+        }
+
+        // Add relevant symbols
+        if !self.relevant_symbols.is_empty() {
+            blocks.push("## Relevant Existing Code\n".to_string());
+            for symbol in &self.relevant_symbols {
+                blocks.push(format!(
+                    "### {} ({})\n**File:** {}\n**Lines:** {}-{}\n```{}\n{}\n```\n",
+                    symbol.name,
+                    symbol.kind,
+                    symbol.file_path,
+                    symbol.start_line,
+                    symbol.end_line,
+                    fence_tag(&symbol.file_path, &symbol.language),
+                    symbol.content
+                ));
+            }
+        }
+
+        // Add similar symbols
+        if !self.similar_symbols.is_empty() {
+            blocks.push("\n## Similar Existing Patterns\n".to_string());
+            for symbol in &self.similar_symbols {
+                blocks.push(format!(
+                    "### {} ({})\n**File:** {}\n```{}\n{}\n```\n",
+                    symbol.name,
+                    symbol.kind,
+                    symbol.file_path,
+                    fence_tag(&symbol.file_path, &symbol.language),
+                    symbol.content
+                ));
+            }
+        }
+
+        // Add design tokens
+        if !self.design_tokens.is_empty() {
+            blocks.push("\n## Design Tokens & Styles\n".to_string());
+            for token in &self.design_tokens {
+                blocks.push(format!(
+                    "- **{}**: `{}` ({})\n",
+                    token.name, token.value, token.token_type
+                ));
+            }
+        }
+
+        // Add types
+        if !self.types.is_empty() {
+            blocks.push("\n## Type Definitions\n".to_string());
+            for type_info in &self.types {
+                blocks.push(format!(
+                    "### {} ({})\n```typescript\n{}\n```\n",
+                    type_info.name, type_info.kind, type_info.definition
+                ));
+            }
+        }
+
+        // Add constants
+        if !self.constants.is_empty() {
+            blocks.push("\n## Constants & Configuration\n".to_string());
+            for constant in &self.constants {
+                blocks.push(format!(
+                    "- **{}** ({}) = `{}`\n",
+                    constant.name, constant.category, constant.value
+                ));
+            }
+        }
+
+        // Add schemas
+        if !self.schemas.is_empty() {
+            blocks.push("\n## Validation Schemas\n".to_string());
+            for schema in &self.schemas {
+                blocks.push(format!(
+                    "### {} ({})\n```typescript\n{}\n```\n",
+                    schema.name, schema.schema_type, schema.definition
+                ));
+            }
+        }
+
+        // Add imports
+        if !self.common_imports.is_empty() {
+            blocks.push("\n## Common Imports\n".to_string());
+            for import in &self.common_imports {
+                blocks.push(format!("- {}\n", import));
+            }
+        }
+
+        blocks.join("\n")
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TypeInfo {
     pub name: String,
@@ -269,6 +303,9 @@ pub struct SymbolInfo {
     pub props: Vec<String>,
     #[serde(default)]
     pub references: Vec<String>,
+    /// Detected language, often "unknown" for snippets; see `fence_tag`
+    #[serde(default)]
+    pub language: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
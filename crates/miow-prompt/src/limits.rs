@@ -0,0 +1,147 @@
+use crate::{ContextData, SymbolInfo};
+use tracing::warn;
+
+/// Default absolute ceiling on assembled context, independent of any token budget
+pub const DEFAULT_MAX_CONTEXT_BYTES: usize = 2 * 1024 * 1024;
+
+/// Appended to a body that was cut to fit the ceiling
+const TRUNCATION_MARKER: &str = "\n// ... [truncated]";
+
+/// Remaining byte allowance while walking the context in priority order
+struct ByteBudget {
+    remaining: usize,
+}
+
+impl ByteBudget {
+    /// Charge an item with fixed metadata `fixed` and a truncatable `body`.
+    /// Returns false when the item must be dropped.
+    fn fit(&mut self, fixed: usize, body: &mut String) -> bool {
+        if fixed + body.len() <= self.remaining {
+            self.remaining -= fixed + body.len();
+            return true;
+        }
+        if fixed + TRUNCATION_MARKER.len() >= self.remaining {
+            self.remaining = 0;
+            return false;
+        }
+        let mut cut = self.remaining - fixed - TRUNCATION_MARKER.len();
+        while !body.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        body.truncate(cut);
+        body.push_str(TRUNCATION_MARKER);
+        self.remaining -= fixed + body.len();
+        true
+    }
+}
+
+/// Bytes of a symbol excluding its content
+fn symbol_metadata_bytes(symbol: &SymbolInfo) -> usize {
+    symbol.name.len()
+        + symbol.kind.len()
+        + symbol.file_path.len()
+        + symbol.props.iter().map(String::len).sum::<usize>()
+        + symbol.references.iter().map(String::len).sum::<usize>()
+}
+
+impl ContextData {
+    /// Total bytes of every string the context carries
+    pub fn byte_size(&self) -> usize {
+        let symbols = self
+            .relevant_symbols
+            .iter()
+            .chain(&self.similar_symbols)
+            .map(|s| symbol_metadata_bytes(s) + s.content.len())
+            .sum::<usize>();
+
+        symbols
+            + self.types.iter().map(|t| t.name.len() + t.kind.len() + t.definition.len()).sum::<usize>()
+            + self.schemas.iter().map(|s| s.name.len() + s.schema_type.len() + s.definition.len()).sum::<usize>()
+            + self.constants.iter().map(|c| c.name.len() + c.category.len() + c.value.len()).sum::<usize>()
+            + self.design_tokens.iter().map(|d| d.name.len() + d.token_type.len() + d.value.len()).sum::<usize>()
+            + self.common_imports.iter().map(String::len).sum::<usize>()
+    }
+
+    /// Clamp the context to at most `max_bytes` (as measured by `byte_size`), whatever the
+    /// token budget. Sections are kept in priority order; the item that crosses the ceiling
+    /// has its body truncated and every item after it is dropped. Returns whether anything
+    /// was cut.
+    pub fn enforce_hard_limit(&mut self, max_bytes: usize) -> bool {
+        let before = self.byte_size();
+        if before <= max_bytes {
+            return false;
+        }
+
+        let mut budget = ByteBudget { remaining: max_bytes };
+        self.relevant_symbols
+            .retain_mut(|s| budget.fit(symbol_metadata_bytes(s), &mut s.content));
+        self.types
+            .retain_mut(|t| budget.fit(t.name.len() + t.kind.len(), &mut t.definition));
+        self.schemas
+            .retain_mut(|s| budget.fit(s.name.len() + s.schema_type.len(), &mut s.definition));
+        self.similar_symbols
+            .retain_mut(|s| budget.fit(symbol_metadata_bytes(s), &mut s.content));
+        self.constants
+            .retain_mut(|c| budget.fit(c.name.len() + c.category.len(), &mut c.value));
+        self.design_tokens
+            .retain_mut(|d| budget.fit(d.name.len() + d.token_type.len(), &mut d.value));
+        self.common_imports.retain_mut(|i| budget.fit(0, i));
+
+        warn!(
+            "Context exceeded hard limit of {} bytes ({} bytes); clamped to {} bytes",
+            max_bytes,
+            before,
+            self.byte_size()
+        );
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TypeInfo;
+
+    fn symbol(name: &str, content: String) -> SymbolInfo {
+        SymbolInfo {
+            name: name.to_string(),
+            kind: "Function".to_string(),
+            content,
+            file_path: format!("src/{}.ts", name),
+            start_line: 1,
+            end_line: 1,
+            props: vec![],
+            references: vec![],
+            language: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_huge_context_clamped_to_ceiling() {
+        let mut context = ContextData {
+            relevant_symbols: (0..50).map(|i| symbol(&format!("fn{}", i), "é".repeat(100_000))).collect(),
+            similar_symbols: vec![symbol("similar", "x".repeat(1_000_000))],
+            design_tokens: vec![],
+            common_imports: vec!["import React from 'react'".to_string(); 1_000],
+            types: vec![TypeInfo {
+                name: "Huge".to_string(),
+                kind: "Interface".to_string(),
+                definition: "y".repeat(5_000_000),
+            }],
+            constants: vec![],
+            schemas: vec![],
+        };
+        let max_bytes = 1_000_000;
+        assert!(context.byte_size() > 10 * max_bytes);
+
+        assert!(context.enforce_hard_limit(max_bytes));
+
+        assert!(context.byte_size() <= max_bytes);
+        // Highest-priority items survive; the crossing item is truncated, trailing ones dropped
+        assert_eq!(context.relevant_symbols.len(), 5);
+        assert!(context.relevant_symbols[4].content.ends_with(TRUNCATION_MARKER));
+        assert!(context.types.is_empty());
+        assert!(context.similar_symbols.is_empty());
+        assert!(!context.enforce_hard_limit(max_bytes));
+    }
+}
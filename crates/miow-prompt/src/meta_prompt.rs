@@ -1,7 +1,7 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
-use crate::{ConstantInfo, ContextData, SchemaInfo, SymbolInfo, TypeInfo};
+use crate::{ConstantInfo, ContextData, NamingReport, SchemaInfo, SymbolInfo, TypeInfo};
 
 /// Simple token counter (rough approximation: 1 token ≈ 4 characters)
 struct TokenCounter;
@@ -536,24 +536,9 @@ impl MetaPromptGenerator {
             patterns.push("- **Null Safety (Rust)**: Use `Option<T>` instead of null");
         }
         
-        // Naming convention detection
-        let camel_case = context.relevant_symbols.iter().any(|s| {
-            s.name.chars().next().map(|c| c.is_lowercase()).unwrap_or(false) && s.name.contains(char::is_uppercase)
-        });
-        let pascal_case = context.relevant_symbols.iter().any(|s| {
-            s.name.chars().next().map(|c| c.is_uppercase()).unwrap_or(false)
-        });
-        let snake_case = context.relevant_symbols.iter().any(|s| s.name.contains('_'));
-        
-        if camel_case {
-            patterns.push("- **Naming**: camelCase for variables and functions");
-        }
-        if pascal_case {
-            patterns.push("- **Naming**: PascalCase for components and types");
-        }
-        if snake_case {
-            patterns.push("- **Naming**: snake_case detected (Rust/Python style)");
-        }
+        // Dominant naming convention per kind, and the names breaking it
+        let naming = NamingReport::from_symbols(&context.relevant_symbols).guidelines();
+        patterns.extend(naming.iter().map(String::as_str));
         
         if patterns.is_empty() {
             guide.push_str("*(No specific style patterns detected)*\n\n");
@@ -680,6 +665,7 @@ mod tests {
             end_line: 1,
             props: vec!["title: string".to_string(), "isActive: boolean".to_string()],
             references: vec!["Button".to_string(), "useState".to_string()],
+            language: String::new(),
         };
 
         let formatted = format_symbol(&symbol, 1);
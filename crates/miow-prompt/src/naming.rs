@@ -0,0 +1,220 @@
+//! Naming conventions as actually practiced. For each symbol kind, the convention most names
+//! follow is taken as the team's rule, and names that break it are reported as outliers: the
+//! style guide states the rule, and the outliers show where the codebase is inconsistent.
+
+use crate::SymbolInfo;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum NamingConvention {
+    PascalCase,
+    CamelCase,
+    SnakeCase,
+    ScreamingSnakeCase,
+    KebabCase,
+}
+
+impl NamingConvention {
+    /// The convention `name` is written in, ignoring leading underscores. `None` when the name
+    /// doesn't decide: a single lowercase word (`render`) is both camelCase and snake_case.
+    pub fn classify(name: &str) -> Option<Self> {
+        let name = name.trim_start_matches('_');
+        let first = name.chars().next()?;
+        if !first.is_alphabetic() {
+            return None;
+        }
+        let has_lower = name.chars().any(char::is_lowercase);
+        let has_upper = name.chars().any(char::is_uppercase);
+        if name.contains('-') {
+            return (!has_upper).then_some(Self::KebabCase);
+        }
+        if name.contains('_') {
+            return match (has_lower, has_upper) {
+                (false, true) => Some(Self::ScreamingSnakeCase),
+                (true, false) => Some(Self::SnakeCase),
+                _ => None,
+            };
+        }
+        match (first.is_uppercase(), has_lower, has_upper) {
+            (true, true, _) => Some(Self::PascalCase),
+            // `URL`, `ID`: one all-caps word
+            (true, false, _) if name.chars().count() > 1 => Some(Self::ScreamingSnakeCase),
+            (false, _, true) => Some(Self::CamelCase),
+            _ => None,
+        }
+    }
+
+    /// Whether `name` can be read as following this convention
+    pub fn accepts(self, name: &str) -> bool {
+        match Self::classify(name) {
+            Some(convention) => convention == self,
+            // A single lowercase word fits the lowercase-first conventions
+            None => {
+                let name = name.trim_start_matches('_');
+                name.chars().next().is_some_and(char::is_lowercase)
+                    && matches!(self, Self::CamelCase | Self::SnakeCase | Self::KebabCase)
+            }
+        }
+    }
+}
+
+impl fmt::Display for NamingConvention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::PascalCase => "PascalCase",
+            Self::CamelCase => "camelCase",
+            Self::SnakeCase => "snake_case",
+            Self::ScreamingSnakeCase => "SCREAMING_SNAKE_CASE",
+            Self::KebabCase => "kebab-case",
+        })
+    }
+}
+
+/// A name that doesn't follow its kind's dominant convention
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamingOutlier {
+    pub name: String,
+    pub kind: String,
+    pub file_path: String,
+    /// What the name is written in, if it decides
+    pub found: Option<NamingConvention>,
+    pub expected: NamingConvention,
+}
+
+/// The dominant convention of one symbol kind
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KindNaming {
+    pub kind: String,
+    pub convention: NamingConvention,
+    /// Names following `convention`, out of `total`
+    pub following: usize,
+    pub total: usize,
+    pub outliers: Vec<NamingOutlier>,
+}
+
+/// Dominant naming convention per symbol kind, with the names that break it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NamingReport {
+    /// Kinds in alphabetical order
+    pub kinds: Vec<KindNaming>,
+}
+
+impl NamingReport {
+    /// Report over `(kind, name, file_path)` triples. Kinds are compared case-insensitively;
+    /// a kind whose names are all undecided (`render`, `load`) gets no entry.
+    pub fn from_names<'a>(names: impl IntoIterator<Item = (&'a str, &'a str, &'a str)>) -> Self {
+        let mut by_kind: BTreeMap<String, Vec<(&str, &str)>> = BTreeMap::new();
+        for (kind, name, file_path) in names {
+            by_kind.entry(kind.to_lowercase()).or_default().push((name, file_path));
+        }
+
+        let mut kinds = Vec::new();
+        for (kind, names) in by_kind {
+            let mut counts: BTreeMap<NamingConvention, usize> = BTreeMap::new();
+            for (name, _) in &names {
+                if let Some(convention) = NamingConvention::classify(name) {
+                    *counts.entry(convention).or_default() += 1;
+                }
+            }
+            // Most decided names wins; ties go to the earlier convention in declaration order
+            let Some(convention) = counts
+                .iter()
+                .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+                .map(|(convention, _)| *convention)
+            else {
+                continue;
+            };
+
+            let outliers: Vec<NamingOutlier> = names
+                .iter()
+                .filter(|(name, _)| !convention.accepts(name))
+                .map(|(name, file_path)| NamingOutlier {
+                    name: name.to_string(),
+                    kind: kind.clone(),
+                    file_path: file_path.to_string(),
+                    found: NamingConvention::classify(name),
+                    expected: convention,
+                })
+                .collect();
+            kinds.push(KindNaming {
+                following: names.len() - outliers.len(),
+                total: names.len(),
+                kind,
+                convention,
+                outliers,
+            });
+        }
+        Self { kinds }
+    }
+
+    pub fn from_symbols(symbols: &[SymbolInfo]) -> Self {
+        Self::from_names(symbols.iter().map(|s| (s.kind.as_str(), s.name.as_str(), s.file_path.as_str())))
+    }
+
+    /// Dominant convention for `kind`, if the report has one
+    pub fn convention_for(&self, kind: &str) -> Option<NamingConvention> {
+        let kind = kind.to_lowercase();
+        self.kinds.iter().find(|k| k.kind == kind).map(|k| k.convention)
+    }
+
+    pub fn outliers(&self) -> impl Iterator<Item = &NamingOutlier> {
+        self.kinds.iter().flat_map(|k| k.outliers.iter())
+    }
+
+    /// Style-guide lines: one rule per kind, then the names breaking them
+    pub fn guidelines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .kinds
+            .iter()
+            .map(|k| format!("- **Naming ({})**: {} ({} of {})", k.kind, k.convention, k.following, k.total))
+            .collect();
+        let outliers: Vec<String> = self
+            .outliers()
+            .map(|o| format!("`{}` ({} in {}, expected {})", o.name, o.kind, o.file_path, o.expected))
+            .collect();
+        if !outliers.is_empty() {
+            lines.push(format!("- **Inconsistent names** (don't copy): {}", outliers.join(", ")));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mixed_conventions_report_dominant_and_outlier() {
+        let names = [
+            ("Component", "UserCard", "src/UserCard.tsx"),
+            ("Component", "AvatarList", "src/AvatarList.tsx"),
+            ("Component", "profile_header", "src/profile_header.tsx"),
+            ("Function", "formatName", "src/format.ts"),
+            ("Function", "parseDate", "src/format.ts"),
+            ("Function", "render", "src/format.ts"),
+            ("Function", "Build_Url", "src/format.ts"),
+            ("Constant", "API_BASE_URL", "src/config.ts"),
+            ("Constant", "MAX_RETRIES", "src/config.ts"),
+            ("Constant", "URL", "src/config.ts"),
+        ];
+
+        let report = NamingReport::from_names(names);
+
+        assert_eq!(report.convention_for("component"), Some(NamingConvention::PascalCase));
+        assert_eq!(report.convention_for("Function"), Some(NamingConvention::CamelCase));
+        assert_eq!(report.convention_for("constant"), Some(NamingConvention::ScreamingSnakeCase));
+
+        let outliers: Vec<&str> = report.outliers().map(|o| o.name.as_str()).collect();
+        // `render` is a single lowercase word, which camelCase accepts
+        assert_eq!(outliers, vec!["profile_header", "Build_Url"]);
+        let header = report.outliers().next().unwrap();
+        assert_eq!(header.found, Some(NamingConvention::SnakeCase));
+        assert_eq!(header.expected, NamingConvention::PascalCase);
+
+        let guidelines = report.guidelines();
+        assert!(guidelines.contains(&"- **Naming (component)**: PascalCase (2 of 3)".to_string()));
+        assert!(guidelines.last().unwrap().contains("`profile_header` (component in src/profile_header.tsx, expected PascalCase)"));
+    }
+}
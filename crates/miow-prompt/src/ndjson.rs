@@ -0,0 +1,108 @@
+use crate::ContextData;
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::io::Write;
+
+impl ContextData {
+    /// Stream the context as newline-delimited JSON for pipe consumers: one
+    /// `{"section", "provenance", "item"}` object per item, in `to_markdown` section order.
+    /// `provenance` holds the file and line range for symbols and is null for items that carry
+    /// no path. Returns the number of lines written.
+    pub fn to_ndjson_writer<W: Write>(&self, mut w: W) -> Result<usize> {
+        let mut lines = 0;
+        let mut emit = |section: &str, provenance: Value, item: Value| -> Result<()> {
+            let line = json!({ "section": section, "provenance": provenance, "item": item });
+            serde_json::to_writer(&mut w, &line)?;
+            w.write_all(b"\n")?;
+            lines += 1;
+            Ok(())
+        };
+
+        for (section, symbols) in [("relevant_symbols", &self.relevant_symbols), ("similar_symbols", &self.similar_symbols)] {
+            for symbol in symbols {
+                let provenance = json!({
+                    "file_path": symbol.file_path,
+                    "start_line": symbol.start_line,
+                    "end_line": symbol.end_line,
+                });
+                emit(section, provenance, serde_json::to_value(symbol)?)?;
+            }
+        }
+        for token in &self.design_tokens {
+            emit("design_tokens", Value::Null, serde_json::to_value(token)?)?;
+        }
+        for type_info in &self.types {
+            emit("types", Value::Null, serde_json::to_value(type_info)?)?;
+        }
+        for constant in &self.constants {
+            emit("constants", Value::Null, serde_json::to_value(constant)?)?;
+        }
+        for schema in &self.schemas {
+            emit("schemas", Value::Null, serde_json::to_value(schema)?)?;
+        }
+        for import in &self.common_imports {
+            emit("common_imports", Value::Null, serde_json::to_value(import)?)?;
+        }
+
+        w.flush()?;
+        Ok(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConstantInfo, DesignTokenInfo, SymbolInfo, TypeInfo};
+
+    #[test]
+    fn test_each_item_is_one_json_line() {
+        let context = ContextData {
+            relevant_symbols: vec![SymbolInfo {
+                name: "Button".to_string(),
+                kind: "Component".to_string(),
+                content: "export function Button() {\n  return <button />;\n}".to_string(),
+                file_path: "src/components/Button.tsx".to_string(),
+                start_line: 3,
+                end_line: 5,
+                props: vec![],
+                references: vec![],
+                language: "tsx".to_string(),
+            }],
+            similar_symbols: vec![],
+            design_tokens: vec![DesignTokenInfo {
+                name: "primary".to_string(),
+                value: "#0070f3".to_string(),
+                token_type: "Color".to_string(),
+            }],
+            common_imports: vec!["import { cn } from '@/lib/utils'".to_string()],
+            types: vec![TypeInfo {
+                name: "ButtonProps".to_string(),
+                kind: "Interface".to_string(),
+                definition: "interface ButtonProps { label: string }".to_string(),
+            }],
+            constants: vec![ConstantInfo {
+                name: "API_URL".to_string(),
+                value: "\"https://api.example.com\"".to_string(),
+                category: "ApiUrl".to_string(),
+            }],
+            schemas: vec![],
+        };
+
+        let mut out = Vec::new();
+        let written = context.to_ndjson_writer(&mut out).unwrap();
+        let lines: Vec<Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(written, 5);
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0]["section"], "relevant_symbols");
+        assert_eq!(lines[0]["provenance"]["file_path"], "src/components/Button.tsx");
+        assert_eq!(lines[0]["item"]["content"], context.relevant_symbols[0].content);
+        assert_eq!(lines[1]["section"], "design_tokens");
+        assert!(lines[1]["provenance"].is_null());
+        assert_eq!(lines[4]["item"], "import { cn } from '@/lib/utils'");
+    }
+}
@@ -1,18 +1,72 @@
-use crate::ContextData;
+use crate::{CharRatioEstimator, ContextData, ContextReport, ContextStage, PruneStrategy, TokenEstimator};
 use tracing::{info, debug};
 
+/// Items kept per category by default, even under aggressive pruning.
+const DEFAULT_CATEGORY_FLOOR: usize = 1;
+
+/// Items per category kept by the limiting pass
+pub const DEFAULT_MAX_ITEMS_PER_CATEGORY: usize = 10;
+
 /// Smart context pruner to manage token budget and relevance
 pub struct SmartPruner {
     token_budget: usize,
+    category_floor: usize,
+    max_items_per_category: usize,
+    estimator: Box<dyn TokenEstimator>,
 }
 
 impl SmartPruner {
     pub fn new(token_budget: usize) -> Self {
-        Self { token_budget }
+        Self {
+            token_budget,
+            category_floor: DEFAULT_CATEGORY_FLOOR,
+            max_items_per_category: DEFAULT_MAX_ITEMS_PER_CATEGORY,
+            estimator: Box::new(CharRatioEstimator::default()),
+        }
+    }
+
+    /// Replace the default 4-chars-per-token estimator used to measure usage
+    pub fn with_estimator(mut self, estimator: Box<dyn TokenEstimator>) -> Self {
+        self.estimator = estimator;
+        self
+    }
+
+    /// Measure usage with `model`'s tokenizer instead of the 4-chars-per-token heuristic; every
+    /// budget check, including the re-checks between pruning strategies, uses it. Built without
+    /// the `tiktoken` feature, or when the tokenizer can't be loaded, usage is estimated at 4
+    /// chars per token.
+    pub fn with_tokenizer(self, model: &str) -> Self {
+        self.with_estimator(tokenizer_for(model))
+    }
+
+    /// Minimum number of items aggressive pruning leaves in each non-empty category
+    /// (the highest-priority ones, i.e. those at the front). A zero budget ignores the floor.
+    pub fn with_category_floor(mut self, floor: usize) -> Self {
+        self.category_floor = floor;
+        self
+    }
+
+    /// Items kept in each of the symbol and type categories when limiting them
+    pub fn with_max_items_per_category(mut self, max_items: usize) -> Self {
+        self.max_items_per_category = max_items;
+        self
+    }
+
+    fn floor(&self) -> usize {
+        if self.token_budget == 0 { 0 } else { self.category_floor }
     }
 
     /// Prune context to fit within token budget
     pub fn prune(&self, context: &mut ContextData) {
+        self.prune_with_report(context, None);
+    }
+
+    /// Like `prune`, recording in `report` what each strategy dropped
+    pub fn prune_traced(&self, context: &mut ContextData, report: &mut ContextReport) {
+        self.prune_with_report(context, Some(report));
+    }
+
+    fn prune_with_report(&self, context: &mut ContextData, mut report: Option<&mut ContextReport>) {
         let current_usage = self.calculate_usage(context);
         
         if current_usage <= self.token_budget {
@@ -21,16 +75,17 @@ impl SmartPruner {
         }
 
         info!("✂️ Pruning context: usage {} > budget {}", current_usage, self.token_budget);
+        debug!("Usage by section: {}", context.token_estimate_by_section(self.estimator.as_ref()));
 
         // Strategy 1: Remove test files and mocks
-        self.remove_test_files(context);
+        self.traced(PruneStrategy::TestFiles, context, report.as_deref_mut(), |context| self.remove_test_files(context));
         
         if self.calculate_usage(context) <= self.token_budget {
             return;
         }
 
         // Strategy 2: Limit number of items per category
-        self.limit_items(context);
+        self.traced(PruneStrategy::ItemLimit, context, report.as_deref_mut(), |context| self.limit_items(context));
         
         if self.calculate_usage(context) <= self.token_budget {
             return;
@@ -38,21 +93,28 @@ impl SmartPruner {
         
         // Strategy 3: Truncate large content (keep signatures if possible)
         // For now, just remove lowest priority items
-        self.aggressive_prune(context);
+        self.traced(PruneStrategy::Aggressive, context, report, |context| self.aggressive_prune(context));
+    }
+
+    /// Run `strategy`, recording what it dropped when there is a report to record it in
+    fn traced(
+        &self,
+        strategy: PruneStrategy,
+        context: &mut ContextData,
+        report: Option<&mut ContextReport>,
+        run: impl FnOnce(&mut ContextData),
+    ) {
+        let Some(report) = report else {
+            run(context);
+            return;
+        };
+        let before = context.clone();
+        run(context);
+        report.record_diff(ContextStage::Prune(strategy), &before, context, self.estimator.as_ref());
     }
     
     fn calculate_usage(&self, context: &ContextData) -> usize {
-        let mut chars = 0;
-        
-        for s in &context.relevant_symbols { chars += s.content.len() + s.name.len(); }
-        for s in &context.similar_symbols { chars += s.content.len() + s.name.len(); }
-        for t in &context.types { chars += t.definition.len() + t.name.len(); }
-        for c in &context.constants { chars += c.value.len() + c.name.len(); }
-        for d in &context.design_tokens { chars += d.value.len() + d.name.len(); }
-        for s in &context.schemas { chars += s.definition.len() + s.name.len(); }
-        
-        // Approx 4 chars per token
-        chars / 4
+        context.token_estimate(self.estimator.as_ref())
     }
     
     fn remove_test_files(&self, context: &mut ContextData) {
@@ -72,16 +134,16 @@ impl SmartPruner {
     
     fn limit_items(&self, context: &mut ContextData) {
         // Keep top N items
-        const MAX_ITEMS: usize = 10;
+        let max_items = self.max_items_per_category;
         
-        if context.relevant_symbols.len() > MAX_ITEMS {
-            context.relevant_symbols.truncate(MAX_ITEMS);
+        if context.relevant_symbols.len() > max_items {
+            context.relevant_symbols.truncate(max_items);
         }
-        if context.similar_symbols.len() > MAX_ITEMS {
-            context.similar_symbols.truncate(MAX_ITEMS);
+        if context.similar_symbols.len() > max_items {
+            context.similar_symbols.truncate(max_items);
         }
-        if context.types.len() > MAX_ITEMS {
-            context.types.truncate(MAX_ITEMS);
+        if context.types.len() > max_items {
+            context.types.truncate(max_items);
         }
         // ... others
     }
@@ -107,28 +169,54 @@ impl SmartPruner {
             if self.calculate_usage(context) <= self.token_budget { return; }
         }
 
-        // 4. Clear secondary categories if still over budget
-        context.similar_symbols.clear();
+        // 4. Cut secondary categories down to the floor if still over budget
+        let floor = self.floor();
+        context.similar_symbols.truncate(floor);
         if self.calculate_usage(context) <= self.token_budget { return; }
         
-        context.constants.clear();
+        context.constants.truncate(floor);
         if self.calculate_usage(context) <= self.token_budget { return; }
         
-        context.design_tokens.clear();
+        context.design_tokens.truncate(floor);
         if self.calculate_usage(context) <= self.token_budget { return; }
         
-        // 5. Finally, prune relevant symbols from the end (assuming least relevant are at the end)
+        // 5. Finally, prune primary categories from the end (assuming least relevant are at the end)
         // Note: In a real scenario, we should sort by relevance score first if not already sorted.
-        while self.calculate_usage(context) > self.token_budget && !context.relevant_symbols.is_empty() {
+        while self.calculate_usage(context) > self.token_budget && context.relevant_symbols.len() > floor {
             context.relevant_symbols.pop();
         }
+        while self.calculate_usage(context) > self.token_budget && context.types.len() > floor {
+            context.types.pop();
+        }
+        while self.calculate_usage(context) > self.token_budget && context.schemas.len() > floor {
+            context.schemas.pop();
+        }
     }
 }
 
+/// `model`'s BPE tokenizer, or the 4-chars-per-token estimate if it can't be loaded
+#[cfg(feature = "tiktoken")]
+fn tokenizer_for(model: &str) -> Box<dyn TokenEstimator> {
+    match crate::TiktokenEstimator::for_model(model) {
+        Ok(estimator) => Box::new(estimator),
+        Err(e) => {
+            tracing::warn!("No tokenizer for {}, estimating tokens from characters: {}", model, e);
+            Box::new(CharRatioEstimator::default())
+        }
+    }
+}
+
+/// The 4-chars-per-token estimate; real tokenizers need the `tiktoken` feature
+#[cfg(not(feature = "tiktoken"))]
+fn tokenizer_for(model: &str) -> Box<dyn TokenEstimator> {
+    debug!("Built without the `tiktoken` feature, estimating {} tokens from characters", model);
+    Box::new(CharRatioEstimator::default())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{ContextData, SymbolInfo, ConstantInfo};
+    use crate::{ContextData, SymbolInfo, ConstantInfo, TypeInfo};
 
     #[test]
     fn test_graduated_pruning() {
@@ -161,4 +249,117 @@ mod tests {
         // Should be reduced to 5, not 0
         assert_eq!(context.constants.len(), 5);
     }
+
+    #[test]
+    fn test_tokenizer_falls_back_to_char_ratio_without_tiktoken() {
+        let context = ContextData {
+            relevant_symbols: vec![SymbolInfo {
+                name: "minified".to_string(),
+                kind: "Function".to_string(),
+                content: "x=>{a[i]||(b[j]=c?.d??e);}".repeat(40),
+                file_path: "src/minified.ts".to_string(),
+                start_line: 1,
+                end_line: 1,
+                props: vec![],
+                references: vec![],
+                language: String::new(),
+            }],
+            ..Default::default()
+        };
+
+        let heuristic = SmartPruner::new(400).calculate_usage(&context);
+        let tokenized = SmartPruner::new(400).with_tokenizer("gpt-4").calculate_usage(&context);
+        if cfg!(feature = "tiktoken") {
+            assert!(tokenized > heuristic, "{} <= {}", tokenized, heuristic);
+        } else {
+            assert_eq!(tokenized, heuristic);
+        }
+    }
+
+    #[cfg(feature = "tiktoken")]
+    #[test]
+    fn test_tokenizer_budget_counts_symbol_heavy_code() {
+        let code = "x=>{a[i]||(b[j]=c?.d??e);}".repeat(40);
+        let context = ContextData {
+            relevant_symbols: vec![SymbolInfo {
+                name: "minified".to_string(),
+                kind: "Function".to_string(),
+                content: code,
+                file_path: "src/minified.ts".to_string(),
+                start_line: 1,
+                end_line: 1,
+                props: vec![],
+                references: vec![],
+                language: String::new(),
+            }],
+            ..Default::default()
+        };
+
+        let heuristic = SmartPruner::new(400);
+        let tokenized = SmartPruner::new(400).with_tokenizer("gpt-4");
+        let (estimated, counted) = (heuristic.calculate_usage(&context), tokenized.calculate_usage(&context));
+        assert!(estimated < 400, "{}", estimated);
+        assert!(counted > 400, "{}", counted);
+
+        // The char heuristic thinks it fits; the tokenizer knows it doesn't
+        let mut kept = context.clone();
+        heuristic.prune(&mut kept);
+        assert_eq!(kept.relevant_symbols.len(), 1);
+        let mut pruned = context;
+        SmartPruner::new(400).with_tokenizer("gpt-4").with_category_floor(0).prune(&mut pruned);
+        assert!(pruned.relevant_symbols.is_empty());
+    }
+
+    #[test]
+    fn test_aggressive_pruning_keeps_category_floor() {
+        let mut context = ContextData {
+            relevant_symbols: vec![],
+            similar_symbols: vec![],
+            types: vec![],
+            constants: vec![],
+            design_tokens: vec![],
+            schemas: vec![],
+            common_imports: vec![],
+        };
+
+        for i in 0..4 {
+            context.relevant_symbols.push(SymbolInfo {
+                name: format!("Symbol{}", i),
+                kind: "function".to_string(),
+                content: "x".repeat(400),
+                file_path: format!("src/symbol_{}.ts", i),
+                start_line: 1,
+                end_line: 10,
+                props: vec![],
+                references: vec![],
+                language: String::new(),
+            });
+            context.types.push(TypeInfo {
+                name: format!("Type{}", i),
+                kind: "interface".to_string(),
+                definition: "y".repeat(400),
+            });
+            context.constants.push(ConstantInfo {
+                name: format!("CONST_{}", i),
+                value: "value".to_string(),
+                category: "config".to_string(),
+            });
+        }
+
+        // Far too small for even a single item
+        let pruner = SmartPruner::new(10);
+        pruner.prune(&mut context);
+
+        assert_eq!(context.relevant_symbols.len(), 1);
+        assert_eq!(context.relevant_symbols[0].name, "Symbol0");
+        assert_eq!(context.types.len(), 1);
+        assert_eq!(context.types[0].name, "Type0");
+        assert_eq!(context.constants.len(), 1);
+
+        // Without a floor the old behaviour applies
+        let pruner = SmartPruner::new(10).with_category_floor(0);
+        pruner.prune(&mut context);
+        assert!(context.relevant_symbols.is_empty());
+        assert!(context.constants.is_empty());
+    }
 }
@@ -0,0 +1,228 @@
+//! Accounting of what the context pipeline dropped and why. Deduplication, the auditor and each
+//! pruning strategy record the items they removed and the tokens that saved, so tooling can
+//! say "dropped 6 test files, auditor removed 4 helpers, pruned 3 symbols to fit budget".
+
+use crate::{ContextData, TokenEstimator};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Pipeline stage that removed items from the context
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ContextStage {
+    /// `DeduplicationEngine` dropped a repeat
+    Dedup,
+    /// The context auditor ranked the item below its category's cut
+    Audit,
+    /// `SmartPruner` dropped the item to fit the token budget
+    Prune(PruneStrategy),
+}
+
+/// `SmartPruner` strategies, in the order they are tried
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PruneStrategy {
+    TestFiles,
+    ItemLimit,
+    Aggressive,
+}
+
+impl fmt::Display for ContextStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContextStage::Dedup => write!(f, "dedup"),
+            ContextStage::Audit => write!(f, "auditor"),
+            ContextStage::Prune(PruneStrategy::TestFiles) => write!(f, "pruning (test files)"),
+            ContextStage::Prune(PruneStrategy::ItemLimit) => write!(f, "pruning (item limit)"),
+            ContextStage::Prune(PruneStrategy::Aggressive) => write!(f, "pruning (aggressive)"),
+        }
+    }
+}
+
+/// An item a stage removed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DroppedItem {
+    /// Context section it was in, e.g. `relevant_symbols` or `helpers`
+    pub section: String,
+    pub name: String,
+    /// Source file, for items that have one
+    pub file_path: Option<String>,
+    /// Estimated tokens the item would have cost
+    pub tokens: usize,
+}
+
+/// Items one stage removed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageTrace {
+    pub stage: ContextStage,
+    pub dropped: Vec<DroppedItem>,
+}
+
+impl StageTrace {
+    pub fn tokens_saved(&self) -> usize {
+        self.dropped.iter().map(|item| item.tokens).sum()
+    }
+}
+
+/// What each pipeline stage dropped, in the order the stages ran
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContextReport {
+    pub stages: Vec<StageTrace>,
+}
+
+impl ContextReport {
+    /// Add `dropped` to `stage`'s trace. Stages that dropped nothing are not listed.
+    pub fn record(&mut self, stage: ContextStage, dropped: Vec<DroppedItem>) {
+        if dropped.is_empty() {
+            return;
+        }
+        match self.stages.iter_mut().find(|trace| trace.stage == stage) {
+            Some(trace) => trace.dropped.extend(dropped),
+            None => self.stages.push(StageTrace { stage, dropped }),
+        }
+    }
+
+    /// Record the items in `before` that `after` no longer has as dropped by `stage`
+    pub fn record_diff(&mut self, stage: ContextStage, before: &ContextData, after: &ContextData, estimator: &dyn TokenEstimator) {
+        let mut kept: HashMap<(&str, &str, Option<&str>), usize> = HashMap::new();
+        for (section, name, file_path, _) in entries(after) {
+            *kept.entry((section, name, file_path)).or_default() += 1;
+        }
+        let mut dropped = Vec::new();
+        for (section, name, file_path, text) in entries(before) {
+            match kept.get_mut(&(section, name, file_path)) {
+                Some(count) if *count > 0 => *count -= 1,
+                _ => dropped.push(DroppedItem {
+                    section: section.to_string(),
+                    name: name.to_string(),
+                    file_path: file_path.map(str::to_string),
+                    tokens: estimator.estimate(&format!("{}{}", name, text)),
+                }),
+            }
+        }
+        self.record(stage, dropped);
+    }
+
+    /// Items `stage` dropped
+    pub fn dropped_by(&self, stage: ContextStage) -> &[DroppedItem] {
+        self.stages
+            .iter()
+            .find(|trace| trace.stage == stage)
+            .map_or(&[], |trace| trace.dropped.as_slice())
+    }
+
+    /// Estimated tokens saved by every stage together
+    pub fn tokens_saved(&self) -> usize {
+        self.stages.iter().map(StageTrace::tokens_saved).sum()
+    }
+
+    /// One line per stage, e.g. "auditor dropped 4 (helpers: 4), saving ~320 tokens"
+    pub fn summary(&self) -> String {
+        if self.stages.is_empty() {
+            return "nothing dropped".to_string();
+        }
+        self.stages
+            .iter()
+            .map(|trace| {
+                let mut sections: Vec<(&str, usize)> = Vec::new();
+                for item in &trace.dropped {
+                    match sections.iter_mut().find(|(section, _)| *section == item.section) {
+                        Some((_, count)) => *count += 1,
+                        None => sections.push((&item.section, 1)),
+                    }
+                }
+                let sections: Vec<String> = sections.iter().map(|(section, count)| format!("{}: {}", section, count)).collect();
+                format!(
+                    "{} dropped {} ({}), saving ~{} tokens",
+                    trace.stage,
+                    trace.dropped.len(),
+                    sections.join(", "),
+                    trace.tokens_saved()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// `(section, name, file_path, body)` of every item, in section order
+fn entries(context: &ContextData) -> Vec<(&'static str, &str, Option<&str>, &str)> {
+    let mut entries = Vec::new();
+    for (section, symbols) in [("relevant_symbols", &context.relevant_symbols), ("similar_symbols", &context.similar_symbols)] {
+        entries.extend(symbols.iter().map(|s| (section, s.name.as_str(), Some(s.file_path.as_str()), s.content.as_str())));
+    }
+    entries.extend(context.types.iter().map(|t| ("types", t.name.as_str(), None, t.definition.as_str())));
+    entries.extend(context.schemas.iter().map(|s| ("schemas", s.name.as_str(), None, s.definition.as_str())));
+    entries.extend(context.constants.iter().map(|c| ("constants", c.name.as_str(), None, c.value.as_str())));
+    entries.extend(context.design_tokens.iter().map(|d| ("design_tokens", d.name.as_str(), None, d.value.as_str())));
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConstantInfo, DeduplicationEngine, SmartPruner, SymbolInfo, TypeInfo};
+
+    fn symbol(name: &str, file_path: &str, size: usize) -> SymbolInfo {
+        SymbolInfo {
+            name: name.to_string(),
+            kind: "Function".to_string(),
+            content: "x".repeat(size),
+            file_path: file_path.to_string(),
+            start_line: 1,
+            end_line: 10,
+            props: vec![],
+            references: vec![],
+            language: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_drops_are_attributed_to_their_stage() {
+        let user = TypeInfo {
+            name: "User".to_string(),
+            kind: "Interface".to_string(),
+            definition: "interface User { id: string }".to_string(),
+        };
+        let mut context = ContextData {
+            relevant_symbols: vec![
+                symbol("login", "src/auth.ts", 400),
+                symbol("login", "src/auth.ts", 400),
+                symbol("mockLogin", "src/auth.test.ts", 400),
+                symbol("logout", "src/auth.ts", 400),
+            ],
+            types: vec![user.clone(), user],
+            constants: vec![ConstantInfo {
+                name: "API_URL".to_string(),
+                value: "\"/api\"".to_string(),
+                category: "ApiUrl".to_string(),
+            }],
+            ..Default::default()
+        };
+
+        let mut report = ContextReport::default();
+        report.record(
+            ContextStage::Audit,
+            vec![DroppedItem {
+                section: "helpers".to_string(),
+                name: "formatDate".to_string(),
+                file_path: Some("src/dates.ts".to_string()),
+                tokens: 40,
+            }],
+        );
+        DeduplicationEngine::deduplicate_traced(&mut context, &mut report);
+        SmartPruner::new(150).prune_traced(&mut context, &mut report);
+
+        let names = |stage| report.dropped_by(stage).iter().map(|item| item.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names(ContextStage::Audit), ["formatDate"]);
+        assert_eq!(names(ContextStage::Dedup), ["login", "User"]);
+        assert_eq!(names(ContextStage::Prune(PruneStrategy::TestFiles)), ["mockLogin"]);
+        assert_eq!(names(ContextStage::Prune(PruneStrategy::Aggressive)), ["logout"]);
+        assert!(names(ContextStage::Prune(PruneStrategy::ItemLimit)).is_empty());
+        assert_eq!(report.dropped_by(ContextStage::Dedup)[0].tokens, 101);
+        assert_eq!(context.relevant_symbols.len(), 1);
+
+        let summary = report.summary();
+        assert!(summary.starts_with("auditor dropped 1 (helpers: 1), saving ~40 tokens\n"), "{}", summary);
+        assert!(summary.contains("pruning (test files) dropped 1 (relevant_symbols: 1)"));
+    }
+}
@@ -0,0 +1,185 @@
+use crate::ContextData;
+use std::fmt;
+
+/// Estimates how many LLM tokens a piece of text will cost
+pub trait TokenEstimator: Send + Sync {
+    fn estimate(&self, text: &str) -> usize;
+}
+
+/// Estimator assuming a fixed number of characters per token (4 by default)
+#[derive(Debug, Clone, Copy)]
+pub struct CharRatioEstimator {
+    chars_per_token: usize,
+}
+
+impl CharRatioEstimator {
+    pub fn new(chars_per_token: usize) -> Self {
+        Self {
+            chars_per_token: chars_per_token.max(1),
+        }
+    }
+}
+
+impl Default for CharRatioEstimator {
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+impl TokenEstimator for CharRatioEstimator {
+    fn estimate(&self, text: &str) -> usize {
+        text.len() / self.chars_per_token
+    }
+}
+
+/// Estimator counting with a real BPE tokenizer, so symbol-heavy code and plain prose are both
+/// measured as the model would see them
+#[cfg(feature = "tiktoken")]
+pub struct TiktokenEstimator {
+    bpe: tiktoken_rs::CoreBPE,
+}
+
+#[cfg(feature = "tiktoken")]
+impl TiktokenEstimator {
+    /// The encoding `model` uses. Models tiktoken doesn't know (Gemini, Claude) get
+    /// `cl100k_base`, which is far closer to their tokenizers than a character ratio.
+    pub fn for_model(model: &str) -> anyhow::Result<Self> {
+        let bpe = match tiktoken_rs::get_bpe_from_model(model) {
+            Ok(bpe) => bpe,
+            Err(_) => tiktoken_rs::cl100k_base()?,
+        };
+        Ok(Self { bpe })
+    }
+}
+
+#[cfg(feature = "tiktoken")]
+impl TokenEstimator for TiktokenEstimator {
+    fn estimate(&self, text: &str) -> usize {
+        self.bpe.encode_ordinary(text).len()
+    }
+}
+
+/// Estimated token usage of each `ContextData` section
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SectionTokenEstimate {
+    pub components: usize,
+    pub similar: usize,
+    pub types: usize,
+    pub schemas: usize,
+    pub constants: usize,
+    pub design_tokens: usize,
+}
+
+impl SectionTokenEstimate {
+    pub fn total(&self) -> usize {
+        self.components + self.similar + self.types + self.schemas + self.constants + self.design_tokens
+    }
+}
+
+impl fmt::Display for SectionTokenEstimate {
+    /// e.g. "Components: 1,200 tok; Similar: 0 tok; Types: 800 tok; ..."
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sections = [
+            ("Components", self.components),
+            ("Similar", self.similar),
+            ("Types", self.types),
+            ("Schemas", self.schemas),
+            ("Constants", self.constants),
+            ("Design tokens", self.design_tokens),
+        ];
+        let parts: Vec<String> = sections
+            .iter()
+            .map(|(label, tokens)| format!("{}: {} tok", label, group_thousands(*tokens)))
+            .collect();
+        write!(f, "{}", parts.join("; "))
+    }
+}
+
+fn group_thousands(n: usize) -> String {
+    let digits = n.to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+impl ContextData {
+    /// Estimated tokens per section; the sections sum to `token_estimate`.
+    pub fn token_estimate_by_section(&self, estimator: &dyn TokenEstimator) -> SectionTokenEstimate {
+        let section = |parts: Vec<(&str, &str)>| {
+            let text: String = parts.iter().flat_map(|(name, body)| [*name, *body]).collect();
+            estimator.estimate(&text)
+        };
+
+        SectionTokenEstimate {
+            components: section(self.relevant_symbols.iter().map(|s| (s.name.as_str(), s.content.as_str())).collect()),
+            similar: section(self.similar_symbols.iter().map(|s| (s.name.as_str(), s.content.as_str())).collect()),
+            types: section(self.types.iter().map(|t| (t.name.as_str(), t.definition.as_str())).collect()),
+            schemas: section(self.schemas.iter().map(|s| (s.name.as_str(), s.definition.as_str())).collect()),
+            constants: section(self.constants.iter().map(|c| (c.name.as_str(), c.value.as_str())).collect()),
+            design_tokens: section(self.design_tokens.iter().map(|d| (d.name.as_str(), d.value.as_str())).collect()),
+        }
+    }
+
+    /// Estimated tokens for the whole context
+    pub fn token_estimate(&self, estimator: &dyn TokenEstimator) -> usize {
+        self.token_estimate_by_section(estimator).total()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConstantInfo, DesignTokenInfo, SchemaInfo, SymbolInfo, TypeInfo};
+
+    #[test]
+    fn test_section_estimates_sum_to_total() {
+        let context = ContextData {
+            relevant_symbols: vec![SymbolInfo {
+                name: "LoginForm".to_string(),
+                kind: "Component".to_string(),
+                content: "x".repeat(4_800),
+                file_path: "src/LoginForm.tsx".to_string(),
+                start_line: 1,
+                end_line: 40,
+                props: vec![],
+                references: vec![],
+                language: String::new(),
+            }],
+            similar_symbols: vec![],
+            design_tokens: vec![DesignTokenInfo {
+                name: "primary".to_string(),
+                value: "#0055ff".to_string(),
+                token_type: "Color".to_string(),
+            }],
+            common_imports: vec![],
+            types: vec![TypeInfo {
+                name: "User".to_string(),
+                kind: "Interface".to_string(),
+                definition: "y".repeat(3_200),
+            }],
+            constants: vec![ConstantInfo {
+                name: "API_URL".to_string(),
+                value: "\"https://example.com\"".to_string(),
+                category: "Config".to_string(),
+            }],
+            schemas: vec![SchemaInfo {
+                name: "loginSchema".to_string(),
+                schema_type: "Zod".to_string(),
+                definition: "z.object({ email: z.string() })".to_string(),
+            }],
+        };
+
+        let estimator = CharRatioEstimator::default();
+        let sections = context.token_estimate_by_section(&estimator);
+
+        assert_eq!(sections.components, 1_202);
+        assert_eq!(sections.types, 801);
+        assert_eq!(sections.total(), context.token_estimate(&estimator));
+        assert!(sections.to_string().starts_with("Components: 1,202 tok; Similar: 0 tok; Types: 801 tok"));
+    }
+}
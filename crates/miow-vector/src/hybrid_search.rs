@@ -128,7 +128,13 @@ impl HybridSearch {
             .filter(|r| r.score >= config.min_score)
             .collect();
         
-        scored_results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
+        scored_results.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap()
+                .then_with(|| a.symbol.file_path.cmp(&b.symbol.file_path))
+                .then_with(|| a.symbol.name.cmp(&b.symbol.name))
+        });
         scored_results.truncate(limit);
         
         // 5. Track access for recency and popularity
@@ -207,7 +213,8 @@ impl KeywordIndex {
         }
         
         let mut results: Vec<_> = scores.into_iter().collect();
-        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        // Scores come out of a HashMap, so break ties by id for a stable order
+        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap().then_with(|| a.0.cmp(&b.0)));
         results.truncate(limit);
         
         results
@@ -1,16 +1,21 @@
 use anyhow::{bail, Result};
 use reqwest::{Client, StatusCode};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
 use serde_json::Value;
 use tracing::{debug, info, warn};
 
 pub mod file_watcher;
 pub mod hybrid_search;
+pub mod mmr;
 pub mod smart_chunking;
+pub mod vector_cache;
 
 pub use file_watcher::FileWatcher;
 pub use hybrid_search::{HybridSearch, HybridSearchConfig};
+pub use mmr::{cosine_similarity, mmr_select};
 pub use smart_chunking::{SmartChunker, ChunkingStrategy, CodeChunk};
+pub use vector_cache::{CacheStats, CachedVector, VectorCache};
 
 /// Vector store for semantic search using Qdrant
 pub struct VectorStore {
@@ -20,6 +25,8 @@ pub struct VectorStore {
     embedding_client: Client,
     embedding_url: Option<String>,
     gemini_api_key: Option<String>,
+    /// Optional bounded local copy of inserted and searched vectors
+    cache: Option<Mutex<VectorCache>>,
 }
 
 impl VectorStore {
@@ -32,12 +39,33 @@ impl VectorStore {
             embedding_client: Client::new(),
             embedding_url: std::env::var("EMBEDDING_URL").ok(),
             gemini_api_key: std::env::var("GEMINI_API_KEY").ok(),
+            cache: None,
         };
 
         store.ensure_collection().await?;
         Ok(store)
     }
 
+    /// Keep inserted and searched vectors in `cache`, bounded by its capacity
+    pub fn with_cache(mut self, cache: VectorCache) -> Self {
+        self.cache = Some(Mutex::new(cache));
+        self
+    }
+
+    /// Eviction and hit counters of the local cache, if one is configured
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache.as_ref().map(|cache| cache.lock().unwrap().stats())
+    }
+
+    /// Embedding of the symbol with id `id` from the local cache, reloading a spilled entry
+    pub fn cached_embedding(&self, id: &str) -> Result<Option<Vec<f32>>> {
+        let Some(cache) = &self.cache else {
+            return Ok(None);
+        };
+        let mut cache = cache.lock().unwrap();
+        Ok(cache.get(id)?.map(|cached| cached.embedding.clone()))
+    }
+
     /// Ensure the collection exists
     async fn ensure_collection(&self) -> Result<()> {
         let collection_url = format!("{}/collections/{}", self.qdrant_url, self.collection_name);
@@ -212,12 +240,14 @@ impl VectorStore {
 
     /// Insert a symbol with its embedding
     pub async fn insert_symbol(&self, symbol: &SymbolVector) -> Result<()> {
-        let text = format!(
-            "{} {} {}",
-            symbol.name,
-            symbol.kind,
-            symbol.content.chars().take(500).collect::<String>()
-        );
+        let text = symbol.embed_text.clone().unwrap_or_else(|| {
+            format!(
+                "{} {} {}",
+                symbol.name,
+                symbol.kind,
+                symbol.content.chars().take(500).collect::<String>()
+            )
+        });
 
         let embedding = self.generate_embedding(&text).await?;
 
@@ -227,6 +257,8 @@ impl VectorStore {
             "content": symbol.content,
             "file_path": symbol.file_path,
             "metadata": symbol.metadata,
+            // Kept beside the metadata string so Qdrant can filter on it
+            "tags": metadata_tags(&symbol.metadata),
             "original_id": symbol.id,
         });
 
@@ -252,9 +284,36 @@ impl VectorStore {
             bail!("Failed to upsert point: {}", text);
         }
 
+        if let Some(cache) = &self.cache {
+            let mut stored = symbol.clone();
+            stored.embed_text = None;
+            cache.lock().unwrap().insert(stored, embedding)?;
+        }
+
         Ok(())
     }
 
+    /// Number of points stored in the collection
+    pub async fn count(&self) -> Result<u64> {
+        let url = format!(
+            "{}/collections/{}/points/count",
+            self.qdrant_url, self.collection_name
+        );
+
+        let body = serde_json::json!({ "exact": true });
+        let resp = self.qdrant_client.post(&url).json(&body).send().await?;
+        if !resp.status().is_success() {
+            let text = resp.text().await.unwrap_or_default();
+            bail!("Failed to count points: {}", text);
+        }
+
+        let json: Value = resp.json().await?;
+        match json.pointer("/result/count").and_then(|c| c.as_u64()) {
+            Some(count) => Ok(count),
+            None => bail!("Unexpected count response: {}", json),
+        }
+    }
+
     /// Search for similar symbols
     pub async fn search_similar(
         &self,
@@ -262,7 +321,35 @@ impl VectorStore {
         limit: usize,
     ) -> Result<Vec<SymbolSearchResult>> {
         let query_embedding = self.generate_embedding(query).await?;
-        self.search_with_embedding(query_embedding, limit).await
+        self.search_with_embedding(query_embedding, limit, None).await
+    }
+
+    /// Search for similar symbols, trading relevance for diversity by Maximal Marginal
+    /// Relevance so near-duplicates don't crowd out distinct symbols. `lambda` 1.0 is plain
+    /// top-k; lower values favor results unlike those already picked.
+    pub async fn search_similar_mmr(
+        &self,
+        query: &str,
+        limit: usize,
+        lambda: f32,
+    ) -> Result<Vec<SymbolSearchResult>> {
+        let query_embedding = self.generate_embedding(query).await?;
+        let candidates = self
+            .search_points(query_embedding.clone(), limit * mmr::MMR_CANDIDATE_FACTOR, None, true)
+            .await?;
+        Ok(mmr::mmr_select(&query_embedding, candidates, limit, lambda))
+    }
+
+    /// Search for similar symbols among those tagged `tag` (e.g. `form-validation`)
+    pub async fn search_similar_with_tag(
+        &self,
+        query: &str,
+        tag: &str,
+        limit: usize,
+    ) -> Result<Vec<SymbolSearchResult>> {
+        let query_embedding = self.generate_embedding(query).await?;
+        self.search_with_embedding(query_embedding, limit, Some(tag_filter(tag)))
+            .await
     }
 
     /// Search by embedding vector
@@ -271,24 +358,41 @@ impl VectorStore {
         embedding: Vec<f32>,
         limit: usize,
     ) -> Result<Vec<SymbolSearchResult>> {
-        self.search_with_embedding(embedding, limit).await
+        self.search_with_embedding(embedding, limit, None).await
     }
 
     async fn search_with_embedding(
         &self,
         embedding: Vec<f32>,
         limit: usize,
+        filter: Option<Value>,
     ) -> Result<Vec<SymbolSearchResult>> {
+        let points = self.search_points(embedding, limit, filter, false).await?;
+        Ok(points.into_iter().map(|(result, _)| result).collect())
+    }
+
+    /// Nearest points with their stored vectors when `with_vector` (empty otherwise)
+    async fn search_points(
+        &self,
+        embedding: Vec<f32>,
+        limit: usize,
+        filter: Option<Value>,
+        with_vector: bool,
+    ) -> Result<Vec<(SymbolSearchResult, Vec<f32>)>> {
         let url = format!(
             "{}/collections/{}/points/search",
             self.qdrant_url, self.collection_name
         );
 
-        let body = serde_json::json!({
+        let mut body = serde_json::json!({
             "vector": embedding,
             "limit": limit,
-            "with_payload": true
+            "with_payload": true,
+            "with_vector": with_vector
         });
+        if let Some(filter) = filter {
+            body["filter"] = filter;
+        }
 
         let resp = self.qdrant_client.post(&url).json(&body).send().await?;
         if !resp.status().is_success() {
@@ -335,9 +439,18 @@ impl VectorStore {
                             .and_then(|v| v.as_str())
                             .unwrap_or("")
                             .to_string(),
+                        embed_text: None,
                     };
 
-                    results.push(SymbolSearchResult { symbol, score });
+                    if let Some(cache) = &self.cache {
+                        cache.lock().unwrap().touch(&symbol.id);
+                    }
+                    let vector = item
+                        .get("vector")
+                        .and_then(|v| v.as_array())
+                        .map(|v| v.iter().filter_map(|x| x.as_f64().map(|f| f as f32)).collect())
+                        .unwrap_or_default();
+                    results.push((SymbolSearchResult { symbol, score }, vector));
                 }
             }
         }
@@ -346,8 +459,30 @@ impl VectorStore {
     }
 }
 
+/// Tags in a symbol's serialized metadata. Metadata may be a JSON object or a JSON string
+/// wrapping one; anything else has no tags.
+fn metadata_tags(metadata: &str) -> Vec<String> {
+    let value = match serde_json::from_str::<Value>(metadata) {
+        Ok(Value::String(inner)) => serde_json::from_str::<Value>(&inner).unwrap_or(Value::Null),
+        Ok(value) => value,
+        Err(_) => Value::Null,
+    };
+    value
+        .get("tags")
+        .and_then(|tags| tags.as_array())
+        .map(|tags| tags.iter().filter_map(|t| t.as_str().map(str::to_string)).collect())
+        .unwrap_or_default()
+}
+
+/// Qdrant filter keeping points whose `tags` payload contains `tag`
+fn tag_filter(tag: &str) -> Value {
+    serde_json::json!({
+        "must": [{ "key": "tags", "match": { "value": tag } }]
+    })
+}
+
 /// Symbol representation for vector storage
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SymbolVector {
     pub id: String,
     pub name: String,
@@ -355,6 +490,9 @@ pub struct SymbolVector {
     pub content: String,
     pub file_path: String,
     pub metadata: String,
+    /// Text to embed instead of the name, kind and start of `content`, e.g. just the
+    /// signature and doc comment. `content` is still stored for display.
+    pub embed_text: Option<String>,
 }
 
 /// Search result with similarity score
@@ -363,3 +501,22 @@ pub struct SymbolSearchResult {
     pub symbol: SymbolVector,
     pub score: f32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tags_are_lifted_out_of_metadata_for_filtering() {
+        let metadata = r#"{"tags":["form-validation","zod-schema"],"priority":1.0}"#;
+        assert_eq!(metadata_tags(metadata), vec!["form-validation".to_string(), "zod-schema".to_string()]);
+        let wrapped = serde_json::to_string(metadata).unwrap();
+        assert_eq!(metadata_tags(&wrapped).len(), 2);
+        assert!(metadata_tags("").is_empty());
+
+        assert_eq!(
+            tag_filter("form-validation"),
+            serde_json::json!({ "must": [{ "key": "tags", "match": { "value": "form-validation" } }] })
+        );
+    }
+}
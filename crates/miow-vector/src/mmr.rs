@@ -0,0 +1,94 @@
+//! Maximal Marginal Relevance: picks results one at a time, each maximizing
+//! `lambda * relevance - (1 - lambda) * similarity to the results already picked`, so a
+//! cluster of near-duplicates (overloads, copies of one helper) doesn't fill the top-k.
+
+use crate::SymbolSearchResult;
+
+/// Candidates fetched per requested result, for MMR to choose from
+pub const MMR_CANDIDATE_FACTOR: usize = 4;
+
+/// Cosine similarity; 0 for empty or zero vectors
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// Up to `limit` of `candidates` (results with their embeddings) in MMR order. `lambda` 1.0
+/// ranks by relevance alone, 0.0 by diversity alone.
+pub fn mmr_select(
+    query: &[f32],
+    mut candidates: Vec<(SymbolSearchResult, Vec<f32>)>,
+    limit: usize,
+    lambda: f32,
+) -> Vec<SymbolSearchResult> {
+    let lambda = lambda.clamp(0.0, 1.0);
+    let relevance: Vec<f32> = candidates.iter().map(|(_, v)| cosine_similarity(query, v)).collect();
+    let mut remaining: Vec<usize> = (0..candidates.len()).collect();
+    let mut picked: Vec<usize> = Vec::new();
+
+    while picked.len() < limit && !remaining.is_empty() {
+        let (position, _) = remaining
+            .iter()
+            .enumerate()
+            .map(|(position, &i)| {
+                let redundancy = picked
+                    .iter()
+                    .map(|&j| cosine_similarity(&candidates[i].1, &candidates[j].1))
+                    .fold(0.0, f32::max);
+                (position, lambda * relevance[i] - (1.0 - lambda) * redundancy)
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap();
+        picked.push(remaining.remove(position));
+    }
+
+    let mut slots: Vec<Option<SymbolSearchResult>> = candidates.drain(..).map(|(r, _)| Some(r)).collect();
+    picked.into_iter().filter_map(|i| slots[i].take()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SymbolVector;
+
+    fn candidate(name: &str, vector: Vec<f32>) -> (SymbolSearchResult, Vec<f32>) {
+        let symbol = SymbolVector {
+            id: name.to_string(),
+            name: name.to_string(),
+            kind: "Function".to_string(),
+            content: String::new(),
+            file_path: "src/lib.ts".to_string(),
+            metadata: String::new(),
+            embed_text: None,
+        };
+        (SymbolSearchResult { symbol, score: 0.0 }, vector)
+    }
+
+    #[test]
+    fn test_mmr_includes_the_outlier_plain_top_k_misses() {
+        let query = [1.0, 0.0, 0.0];
+        let candidates = vec![
+            candidate("format", vec![1.0, 0.05, 0.0]),
+            candidate("formatOverload1", vec![1.0, 0.06, 0.0]),
+            candidate("formatOverload2", vec![1.0, 0.07, 0.0]),
+            candidate("parse", vec![0.6, 0.0, 0.8]),
+        ];
+
+        let names = |results: Vec<SymbolSearchResult>| -> Vec<String> {
+            results.into_iter().map(|r| r.symbol.name).collect()
+        };
+        // Pure relevance is plain top-k: the whole cluster, no outlier
+        let top_k = names(mmr_select(&query, candidates.clone(), 3, 1.0));
+        assert_eq!(top_k, vec!["format", "formatOverload1", "formatOverload2"]);
+
+        let diverse = names(mmr_select(&query, candidates, 3, 0.3));
+        assert_eq!(diverse[0], "format");
+        assert!(diverse.contains(&"parse".to_string()));
+    }
+}
@@ -0,0 +1,210 @@
+//! Bounded local copy of indexed vectors. Qdrant holds the full index; the store keeps the
+//! vectors it inserted or returned from a search so they can be reused without a round trip.
+//! On very large repos that copy is capped: the least recently searched entries are evicted,
+//! optionally spilled to disk and reloaded on the next miss.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::SymbolVector;
+
+/// A symbol and its embedding
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedVector {
+    pub symbol: SymbolVector,
+    pub embedding: Vec<f32>,
+}
+
+/// Counters describing how the cache has behaved since it was created
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CacheStats {
+    /// Entries currently in memory
+    pub resident: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    /// Evicted entries written to the spill directory
+    pub spilled: u64,
+    /// Misses served from the spill directory
+    pub reloaded: u64,
+}
+
+/// LRU of vectors keyed by symbol id, ordered by last search access
+pub struct VectorCache {
+    capacity: usize,
+    spill_dir: Option<PathBuf>,
+    entries: HashMap<String, (CachedVector, u64)>,
+    /// Access tick -> id, oldest first
+    order: BTreeMap<u64, String>,
+    tick: u64,
+    stats: CacheStats,
+}
+
+impl VectorCache {
+    /// Keep at most `capacity` vectors in memory (at least one)
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            spill_dir: None,
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            tick: 0,
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Write evicted vectors under `dir` and reload them on a miss instead of dropping them
+    pub fn with_spill_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.spill_dir = Some(dir.into());
+        self
+    }
+
+    /// Add or replace a vector, evicting the least recently searched ones past capacity
+    pub fn insert(&mut self, symbol: SymbolVector, embedding: Vec<f32>) -> Result<()> {
+        let id = symbol.id.clone();
+        self.store(id, CachedVector { symbol, embedding })
+    }
+
+    /// The vector for `id`, reloading it from the spill directory if it was evicted.
+    /// Counts as an access.
+    pub fn get(&mut self, id: &str) -> Result<Option<&CachedVector>> {
+        if self.entries.contains_key(id) {
+            self.stats.hits += 1;
+            self.touch(id);
+        } else {
+            self.stats.misses += 1;
+            let Some(vector) = self.reload(id)? else {
+                return Ok(None);
+            };
+            self.stats.reloaded += 1;
+            self.store(id.to_string(), vector)?;
+        }
+        Ok(self.entries.get(id).map(|(vector, _)| vector))
+    }
+
+    /// Record that `id` came back from a search, making it the most recently used
+    pub fn touch(&mut self, id: &str) {
+        let tick = self.next_tick();
+        if let Some((_, last)) = self.entries.get_mut(id) {
+            self.order.remove(last);
+            *last = tick;
+            self.order.insert(tick, id.to_string());
+        }
+    }
+
+    /// Whether `id` is resident in memory (spilled entries are not)
+    pub fn contains(&self, id: &str) -> bool {
+        self.entries.contains_key(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats { resident: self.entries.len(), ..self.stats }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn store(&mut self, id: String, vector: CachedVector) -> Result<()> {
+        let tick = self.next_tick();
+        if let Some((_, last)) = self.entries.insert(id.clone(), (vector, tick)) {
+            self.order.remove(&last);
+        }
+        self.order.insert(tick, id);
+
+        while self.entries.len() > self.capacity {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            if let Some((vector, _)) = self.entries.remove(&oldest) {
+                self.stats.evictions += 1;
+                self.spill(&oldest, &vector)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn spill_path(&self, id: &str) -> Option<PathBuf> {
+        let file = uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_URL, id.as_bytes());
+        self.spill_dir.as_ref().map(|dir| dir.join(format!("{}.json", file)))
+    }
+
+    fn spill(&mut self, id: &str, vector: &CachedVector) -> Result<()> {
+        let Some(path) = self.spill_path(id) else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("Failed to create {:?}", dir))?;
+        }
+        fs::write(&path, serde_json::to_vec(vector)?).with_context(|| format!("Failed to spill {:?}", path))?;
+        self.stats.spilled += 1;
+        Ok(())
+    }
+
+    fn reload(&self, id: &str) -> Result<Option<CachedVector>> {
+        let Some(path) = self.spill_path(id).filter(|p| p.exists()) else {
+            return Ok(None);
+        };
+        let bytes = fs::read(&path).with_context(|| format!("Failed to read {:?}", path))?;
+        let vector = serde_json::from_slice(&bytes).with_context(|| format!("Corrupt spilled vector {:?}", path))?;
+        fs::remove_file(&path).ok();
+        Ok(Some(vector))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(id: &str) -> SymbolVector {
+        SymbolVector {
+            id: id.to_string(),
+            name: id.to_string(),
+            kind: "function".to_string(),
+            content: String::new(),
+            file_path: "src/lib.rs".to_string(),
+            metadata: String::new(),
+            embed_text: None,
+        }
+    }
+
+    #[test]
+    fn test_least_recently_searched_vectors_are_evicted_and_reloaded() {
+        let spill_dir = std::env::temp_dir().join(format!("miow-vector-cache-{}", std::process::id()));
+        let mut cache = VectorCache::new(2).with_spill_dir(&spill_dir);
+        cache.insert(symbol("a"), vec![1.0]).unwrap();
+        cache.insert(symbol("b"), vec![2.0]).unwrap();
+
+        // `a` is searched after `b` was inserted, so `b` is the oldest access
+        cache.touch("a");
+        cache.insert(symbol("c"), vec![3.0]).unwrap();
+        assert!(cache.contains("a"));
+        assert!(!cache.contains("b"));
+        assert!(cache.contains("c"));
+
+        // A miss on `b` reloads it from disk, evicting `a` which is now the oldest
+        assert_eq!(cache.get("b").unwrap().unwrap().embedding, vec![2.0]);
+        assert!(!cache.contains("a"));
+        assert!(cache.get("missing").unwrap().is_none());
+
+        let stats = cache.stats();
+        assert_eq!(stats.resident, 2);
+        assert_eq!(stats.evictions, 2);
+        assert_eq!(stats.spilled, 2);
+        assert_eq!(stats.reloaded, 1);
+        assert_eq!(stats.misses, 2);
+        fs::remove_dir_all(&spill_dir).ok();
+    }
+}
@@ -1,9 +1,11 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use colored::Colorize;
-use miow_core::index_codebase;
-use miow_graph::{DesignTokenData, ImportData, KnowledgeGraph, ParsedFileData, SymbolData};
-use miow_parsers::{parse_python, parse_rust, parse_typescript};
+use miow_graph::KnowledgeGraph;
+use miow_parsers::{
+    is_css_module, is_tailwind_config, parse_css_module, parse_go, parse_json_config, parse_python, parse_rust_file, parse_tailwind_config, parse_typescript_file,
+    tag_platform,
+};
 use std::path::PathBuf;
 use std::path::Path;
 use std::collections::hash_map::DefaultHasher;
@@ -173,6 +175,17 @@ enum Commands {
         db: PathBuf,
     },
 
+    /// Report index health: files, symbols, vector store size, parse errors, import cycles
+    Status {
+        /// Path to the codebase (defaults to current directory)
+        #[arg(value_name = "PATH")]
+        path: Option<PathBuf>,
+
+        /// Database path for knowledge graph
+        #[arg(short, long, default_value = "miow.db")]
+        db: PathBuf,
+    },
+
     /// Generate context-rich prompt (ask questions about your codebase)
     Ask {
         /// User question or task
@@ -190,6 +203,10 @@ enum Commands {
         /// Output file for generated prompt
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Gather context with heuristics only (no LLM or network calls)
+        #[arg(long)]
+        no_llm: bool,
     },
 
     /// Index a codebase and store in knowledge graph (legacy command)
@@ -275,14 +292,23 @@ async fn main() -> Result<()> {
         Commands::Reindex { path, db } => {
             handle_reindex(path, db).await?;
         }
+        Commands::Status { path, db } => {
+            let codebase_path = path.unwrap_or_else(|| std::env::current_dir().unwrap());
+            handle_status(codebase_path, db).await?;
+        }
         Commands::Ask {
             question,
             path,
             db,
             output,
+            no_llm,
         } => {
             let codebase_path = path.unwrap_or_else(|| std::env::current_dir().unwrap());
-            handle_ask(question, codebase_path, db, output).await?;
+            if no_llm {
+                handle_ask_no_llm(question, codebase_path, db, output).await?;
+            } else {
+                handle_ask(question, codebase_path, db, output).await?;
+            }
         }
         Commands::Index { path, db } => {
             handle_index(path, db).await?;
@@ -351,6 +377,35 @@ async fn handle_reindex(path: PathBuf, db_path: PathBuf) -> Result<()> {
     Ok(())
 }
 
+async fn handle_status(path: PathBuf, db_path: PathBuf) -> Result<()> {
+    println!("{}", "🩺 MIOW-CONTEXT STATUS".bright_blue().bold());
+    println!("{}", "═".repeat(50).bright_black());
+    println!("📁 Codebase: {}", path.display());
+    println!("💾 Database: {}", db_path.display());
+    println!();
+
+    let mut indexer = miow_core::CodebaseIndexer::new(path.clone())?
+        .with_manifest_file(db_path.with_file_name(miow_core::INDEX_MANIFEST_FILE));
+    if db_path.exists() {
+        indexer = indexer.with_graph(std::sync::Arc::new(KnowledgeGraph::new(&db_path)?));
+    } else {
+        println!("{}", "⚠️  No database found. Run 'init' to index the codebase.".yellow());
+    }
+
+    let qdrant_url =
+        std::env::var("QDRANT_URL").unwrap_or_else(|_| "http://localhost:6333".to_string());
+    if let Ok(store) = miow_vector::VectorStore::new(&qdrant_url, &collection_name_for_path(&path)).await {
+        indexer = indexer.with_vector_store(std::sync::Arc::new(store));
+    }
+
+    let diagnostics = indexer.diagnostics().await?;
+    for line in diagnostics.summary_lines() {
+        println!("  {}", line);
+    }
+
+    Ok(())
+}
+
 async fn handle_ask(
     question: String,
     path: PathBuf,
@@ -382,6 +437,52 @@ async fn handle_ask(
     Ok(())
 }
 
+async fn handle_ask_no_llm(
+    question: String,
+    path: PathBuf,
+    db_path: PathBuf,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    println!("{}", "🧪 MIOW-CONTEXT NO-LLM QUERY".bright_blue().bold());
+    println!("{}", "═".repeat(60).bright_black());
+    println!("📝 Question: {}", question.bright_yellow());
+    println!("📁 Codebase: {}", path.display());
+    println!("💾 Database: {}", db_path.display());
+    println!();
+
+    if !db_path.exists() {
+        println!(
+            "{}",
+            "⚠️  Knowledge graph not found. Run 'miow-context init' first.".yellow()
+        );
+        return Ok(());
+    }
+
+    let orchestrator = MiowOrchestrator::new(db_path.to_str().unwrap())?;
+    let generated_prompt = orchestrator.generate_no_llm_prompt(&question, &path).await?;
+
+    println!("{}", "✅ Context-aware prompt generated (no LLM)!".green().bold());
+    println!();
+    println!("{}", generated_prompt);
+
+    if let Some(output_path) = output {
+        std::fs::write(&output_path, &generated_prompt)?;
+        println!();
+        println!("💾 Prompt saved to: {}", output_path.display());
+    }
+
+    Ok(())
+}
+
+/// `MIOW_LAZY_PACKAGES`: how many monorepo packages to keep indexed when they are indexed on
+/// demand (`0` or unset indexes everything upfront)
+fn lazy_package_capacity() -> Option<usize> {
+    std::env::var("MIOW_LAZY_PACKAGES")
+        .ok()
+        .and_then(|c| c.parse().ok())
+        .filter(|&c| c > 0)
+}
+
 async fn handle_index(path: PathBuf, db_path: PathBuf) -> Result<()> {
     println!("{}", "🔍 Indexing codebase...".cyan().bold());
     println!("Path: {}", path.display());
@@ -393,8 +494,14 @@ async fn handle_index(path: PathBuf, db_path: PathBuf) -> Result<()> {
         std::env::var("QDRANT_URL").unwrap_or_else(|_| "http://localhost:6333".to_string());
     let collection_name = collection_name_for_path(&path);
     let vector_store = match miow_vector::VectorStore::new(&qdrant_url, &collection_name).await {
-        Ok(store) => {
+        Ok(mut store) => {
             println!("{}", "✅ Vector store (Qdrant) connected!".green());
+            // Caps the local vector copy on huge repos; evicted vectors spill next to the database
+            if let Some(capacity) = std::env::var("MIOW_VECTOR_CACHE_CAPACITY").ok().and_then(|c| c.parse().ok()) {
+                store = store.with_cache(
+                    miow_vector::VectorCache::new(capacity).with_spill_dir(db_path.with_file_name("vector-spill")),
+                );
+            }
             Some(std::sync::Arc::new(store))
         }
         Err(e) => {
@@ -410,13 +517,23 @@ async fn handle_index(path: PathBuf, db_path: PathBuf) -> Result<()> {
         }
     };
 
-    let report = if let Some(vs) = vector_store {
+    // Progress lives next to the database so an interrupted run can resume
+    let mut indexer = miow_core::CodebaseIndexer::new(path.clone())?
+        .with_progress_file(db_path.with_file_name(miow_core::INDEX_PROGRESS_FILE))
+        .with_manifest_file(db_path.with_file_name(miow_core::INDEX_MANIFEST_FILE));
+    // Huge monorepos: leave workspace packages to be indexed when a search first targets them
+    if let Some(capacity) = lazy_package_capacity() {
+        println!("📦 Lazy package indexing: up to {} packages loaded at once", capacity);
+        indexer = indexer.with_config(miow_core::IndexConfig {
+            lazy_packages: Some(capacity),
+            ..Default::default()
+        });
+    }
+    if let Some(vs) = vector_store.clone() {
         // Use vector store if available
-        use miow_core::index_codebase_with_vector;
-        index_codebase_with_vector(path.clone(), vs).await?
-    } else {
-        index_codebase(path.clone()).await?
-    };
+        indexer = indexer.with_vector_store(vs);
+    }
+    let report = indexer.index().await?;
 
     println!("{}", "✅ Indexing complete!".green().bold());
     println!();
@@ -424,6 +541,12 @@ async fn handle_index(path: PathBuf, db_path: PathBuf) -> Result<()> {
     println!("  Total files: {}", report.total_files);
     println!("  Total size: {} bytes", report.total_size);
     println!("  Duration: {}ms", report.duration_ms);
+    if let Some(stats) = vector_store.as_ref().and_then(|vs| vs.cache_stats()) {
+        println!(
+            "  Vector cache: {} resident, {} evicted, {} spilled",
+            stats.resident, stats.evictions, stats.spilled
+        );
+    }
     println!();
     println!("📁 {}", "Files by language:".yellow().bold());
 
@@ -435,30 +558,65 @@ async fn handle_index(path: PathBuf, db_path: PathBuf) -> Result<()> {
     println!();
     println!("{}", "💾 Building knowledge graph...".cyan().bold());
 
-    let mut graph = KnowledgeGraph::new(&db_path)?;
+    let graph = KnowledgeGraph::new(&db_path)?;
     let mut total_symbols = 0;
+    let path_priorities = miow_core::PathPriorities::new(&miow_core::IndexConfig::default().path_priorities)?;
 
     for file in &report.files {
-        let parsed_data = match file.language {
-            miow_core::Language::TypeScript | miow_core::Language::TSX => {
-                let is_tsx = matches!(file.language, miow_core::Language::TSX);
-                match parse_typescript(&file.content, is_tsx) {
-                    Ok(parsed) => Some(convert_to_graph_data(parsed)),
+        if file.tags.iter().any(|t| t == miow_core::LOSSY_DECODE_TAG) {
+            eprintln!("  ⚠️  {} is not valid UTF-8; indexed a lossy decode", file.relative_path);
+        }
+        let parsed = match file.language {
+            _ if is_tailwind_config(&file.relative_path) => match parse_tailwind_config(&file.content) {
+                Ok(parsed) => Some(parsed),
+                Err(e) => {
+                    eprintln!("  ⚠️  Failed to parse {}: {}", file.relative_path, e);
+                    None
+                }
+            },
+            _ if is_css_module(&file.relative_path) => match parse_css_module(&file.content) {
+                Ok(parsed) => Some(parsed),
+                Err(e) => {
+                    eprintln!("  ⚠️  Failed to parse {}: {}", file.relative_path, e);
+                    None
+                }
+            },
+            miow_core::Language::TypeScript
+            | miow_core::Language::TSX
+            | miow_core::Language::JavaScript
+            | miow_core::Language::JSX => {
+                let is_tsx = matches!(file.language, miow_core::Language::TSX | miow_core::Language::JSX);
+                match parse_typescript_file(&file.content, is_tsx, &file.relative_path) {
+                    Ok(parsed) => Some(parsed),
                     Err(e) => {
                         eprintln!("  ⚠️  Failed to parse {}: {}", file.relative_path, e);
                         None
                     }
                 }
             }
-            miow_core::Language::Rust => match parse_rust(&file.content) {
-                Ok(parsed) => Some(convert_to_graph_data(parsed)),
+            miow_core::Language::Rust => match parse_rust_file(&file.content, &file.relative_path) {
+                Ok(parsed) => Some(parsed),
                 Err(e) => {
                     eprintln!("  ⚠️  Failed to parse {}: {}", file.relative_path, e);
                     None
                 }
             },
             miow_core::Language::Python => match parse_python(&file.content) {
-                Ok(parsed) => Some(convert_to_graph_data(parsed)),
+                Ok(parsed) => Some(parsed),
+                Err(e) => {
+                    eprintln!("  ⚠️  Failed to parse {}: {}", file.relative_path, e);
+                    None
+                }
+            },
+            miow_core::Language::Go => match parse_go(&file.content) {
+                Ok(parsed) => Some(parsed),
+                Err(e) => {
+                    eprintln!("  ⚠️  Failed to parse {}: {}", file.relative_path, e);
+                    None
+                }
+            },
+            miow_core::Language::JSON => match parse_json_config(&file.content, &file.relative_path) {
+                Ok(parsed) => Some(parsed),
                 Err(e) => {
                     eprintln!("  ⚠️  Failed to parse {}: {}", file.relative_path, e);
                     None
@@ -466,12 +624,23 @@ async fn handle_index(path: PathBuf, db_path: PathBuf) -> Result<()> {
             },
             _ => None,
         };
+        let parsed_data = parsed.map(|mut parsed| {
+            tag_platform(&mut parsed, &file.relative_path);
+            path_priorities.stamp(&file.relative_path, &mut parsed);
+            miow_core::to_graph_data(parsed)
+        });
 
         if let Some(data) = parsed_data {
             total_symbols += data.symbols.len();
             graph.insert_file(&file.relative_path, &data)?;
         }
     }
+    graph.compute_document_frequencies()?;
+    // Imports of sibling monorepo packages resolve into their source
+    match miow_core::ProjectSignature::detect(&path) {
+        Ok(signature) => graph.set_workspace_packages(&signature.workspace_packages)?,
+        Err(e) => eprintln!("  ⚠️  Could not detect workspace packages: {}", e),
+    }
 
     println!();
     println!("{}", "✅ Knowledge graph built!".green().bold());
@@ -491,10 +660,11 @@ async fn handle_analyze(file: PathBuf) -> Result<()> {
     let parsed = match extension {
         "tsx" | "ts" => {
             let is_tsx = extension == "tsx";
-            parse_typescript(&content, is_tsx)?
+            parse_typescript_file(&content, is_tsx, &file.to_string_lossy())?
         }
-        "rs" => parse_rust(&content)?,
+        "rs" => parse_rust_file(&content, &file.to_string_lossy())?,
         "py" => parse_python(&content)?,
+        "json" => parse_json_config(&content, &file.to_string_lossy())?,
         _ => anyhow::bail!("Unsupported file type: {}", extension),
     };
 
@@ -573,6 +743,9 @@ async fn handle_generate_autonomous(
             );
         }
     }
+    if let Some(capacity) = lazy_package_capacity() {
+        orchestrator = orchestrator.with_lazy_packages(path.clone(), capacity)?;
+    }
 
     // Try to initialize LLM if API key is available
     if let Ok(api_key) = std::env::var("GEMINI_API_KEY") {
@@ -669,69 +842,6 @@ async fn handle_generate_autonomous(
     Ok(())
 }
 
-// Helper function to convert parser output to graph data
-fn convert_to_graph_data(parsed: miow_parsers::ParsedFile) -> ParsedFileData {
-    ParsedFileData {
-        symbols: parsed.symbols.into_iter().map(convert_symbol).collect(),
-        imports: parsed
-            .imports
-            .into_iter()
-            .map(|imp| ImportData {
-                source: imp.source,
-                names: imp.names.into_iter().map(|n| n.name).collect(),
-                start_line: imp.range.start_line,
-                end_line: imp.range.end_line,
-            })
-            .collect(),
-        design_tokens: parsed
-            .design_tokens
-            .into_iter()
-            .map(|token| DesignTokenData {
-                token_type: format!("{:?}", token.token_type),
-                name: token.name,
-                value: token.value,
-                context: token.context,
-                start_line: token.range.start_line,
-                end_line: token.range.end_line,
-            })
-            .collect(),
-        type_definitions: parsed
-            .type_definitions
-            .into_iter()
-            .map(|td| miow_graph::TypeDefinitionData {
-                name: td.name,
-                kind: format!("{:?}", td.kind),
-                definition: td.definition,
-                start_line: td.range.start_line,
-                end_line: td.range.end_line,
-            })
-            .collect(),
-        constants: parsed
-            .constants
-            .into_iter()
-            .map(|c| miow_graph::ConstantData {
-                name: c.name,
-                value: c.value,
-                category: format!("{:?}", c.category),
-                start_line: c.range.start_line,
-                end_line: c.range.end_line,
-            })
-            .collect(),
-        schemas: parsed
-            .schemas
-            .into_iter()
-            .map(|s| miow_graph::SchemaData {
-                name: s.name,
-                schema_type: format!("{:?}", s.schema_type),
-                definition: s.definition,
-                start_line: s.range.start_line,
-                end_line: s.range.end_line,
-            })
-            .collect(),
-        language: parsed.language,
-    }
-}
-
 async fn test_autonomous_system(task: String, path: PathBuf) -> Result<()> {
     println!("{}", "🧠 AUTONOMOUS SYSTEM TEST".bright_blue().bold());
     println!("{}", "═══════════════════════════════════════════════════════════════".bright_black());
@@ -796,22 +906,6 @@ async fn test_autonomous_system(task: String, path: PathBuf) -> Result<()> {
     Ok(())
 }
 
-fn convert_symbol(symbol: miow_parsers::Symbol) -> SymbolData {
-    SymbolData {
-        name: symbol.name,
-        kind: format!("{:?}", symbol.kind),
-        start_line: symbol.range.start_line,
-        end_line: symbol.range.end_line,
-        start_byte: symbol.range.start_byte,
-        end_byte: symbol.range.end_byte,
-        content: symbol.content,
-        metadata: serde_json::to_string(&symbol.metadata).unwrap_or_default(),
-        style_tags: None, // Will be populated during style analysis
-        children: symbol.children.into_iter().map(convert_symbol).collect(),
-        references: symbol.references,
-    }
-}
-
 #[cfg(feature = "web")]
 async fn start_web_server(port: u16, _db_path: PathBuf) -> Result<()> {
     println!("{}", "🌐 Starting MIOW-CONTEXT Web Server".bright_blue().bold());
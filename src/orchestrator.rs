@@ -1,12 +1,12 @@
 use anyhow::Result;
 use miow_analyzer::ContextAnalyzer;
-use miow_agent::{AutonomousAgent, GeminiContextAuditor, GeminiRouterAgent, GeminiWorkerAgent, RouterAgent, SearchPlan, WorkerAgent};
-use miow_core::{IntelligentSignatureDetector, ProjectSignature};
+use miow_agent::{audit_drops, AutonomousAgent, ChunkRouting, CoLocationExpander, GeminiContextAuditor, GeminiRouterAgent, GeminiWorkerAgent, NoLlmPipeline, RouterAgent, SearchPlan, SymbolSummarizer, WorkerAgent};
+use miow_core::{ContextIgnore, IntelligentSignatureDetector, LazyPackageIndex, ProjectSignature};
 use miow_graph::{KnowledgeGraph, RelationshipInferencer};
-use miow_llm::{ContextItem, GatheredContext, LLMProvider, LLMResponse, Message, Role};
+use miow_llm::{parse_llm_json, ContextItem, GatheredContext, LLMProvider, LLMResponse, Message, Role};
 use miow_prompt::{
-    ConstantInfo, ContextData, DesignTokenInfo, PromptGenerator, PromptRequest, SchemaInfo,
-    SymbolInfo, TypeInfo,
+    ConstantInfo, ContextConfig, ContextData, ContextReport, ContextStage, DesignTokenInfo, PromptGenerator, PromptRequest, SchemaInfo,
+    SymbolInfo, TypeInfo, CONTEXT_CONFIG_FILE,
 };
 use miow_vector::VectorStore;
 use std::cmp::Ordering;
@@ -14,6 +14,9 @@ use std::collections::HashSet;
 use std::sync::Arc;
 use tracing::{info, warn};
 
+/// Documented symbols longer than this are cut to their signature next to their usage example
+const USAGE_EXAMPLE_BODY_LINES: usize = 40;
+
 /// Orchestrator that ties together all the components with LLM-powered context gathering
 #[allow(dead_code)]
 pub struct MiowOrchestrator {
@@ -22,17 +25,38 @@ pub struct MiowOrchestrator {
     prompt_generator: PromptGenerator,
     llm: Option<Arc<dyn LLMProvider>>,
     vector_store: Option<Arc<VectorStore>>,
+    signature: Option<Arc<ProjectSignature>>,
+    max_context_bytes: usize,
+    include_deprecated: bool,
+    co_located_files: bool,
+    summarize_threshold_lines: Option<usize>,
+    context_config: ContextConfig,
+    chunk_routing: ChunkRouting,
+    lazy_packages: Option<Arc<LazyPackageIndex>>,
 }
 
 #[allow(dead_code)]
 impl MiowOrchestrator {
+    /// Open the graph at `db_path`, with the `ContextConfig` stored next to it if there is one
     pub fn new(db_path: &str) -> Result<Self> {
+        let context_config = match std::path::Path::new(db_path).parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            Some(dir) => ContextConfig::load(&dir.join(CONTEXT_CONFIG_FILE))?,
+            None => ContextConfig::default(),
+        };
         Ok(Self {
             graph: Arc::new(KnowledgeGraph::new(db_path)?),
             analyzer: ContextAnalyzer::new(),
             prompt_generator: PromptGenerator::new(),
             llm: None,
             vector_store: None,
+            signature: None,
+            max_context_bytes: miow_prompt::DEFAULT_MAX_CONTEXT_BYTES,
+            include_deprecated: false,
+            co_located_files: false,
+            summarize_threshold_lines: None,
+            context_config,
+            chunk_routing: ChunkRouting::default(),
+            lazy_packages: None,
         })
     }
 
@@ -54,6 +78,61 @@ impl MiowOrchestrator {
         self
     }
 
+    /// Reuse an already-detected project signature instead of detecting it per request
+    pub fn with_signature(mut self, signature: Arc<ProjectSignature>) -> Self {
+        self.signature = Some(signature);
+        self
+    }
+
+    /// Absolute ceiling on assembled context bytes, applied after any token-budget pruning
+    pub fn with_max_context_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_context_bytes = max_bytes;
+        self
+    }
+
+    /// Suggest symbols tagged deprecated too (excluded from gathered context by default)
+    pub fn with_include_deprecated(mut self, include_deprecated: bool) -> Self {
+        self.include_deprecated = include_deprecated;
+        self
+    }
+
+    /// Pull selected components' co-located styles, tests and stories into the no-LLM context
+    pub fn with_co_located_files(mut self, co_located_files: bool) -> Self {
+        self.co_located_files = co_located_files;
+        self
+    }
+
+    /// Replace symbols longer than `threshold_lines` with LLM summaries instead of truncating
+    /// them (`None` disables; requires an LLM)
+    pub fn with_symbol_summaries(mut self, threshold_lines: Option<usize>) -> Self {
+        self.summarize_threshold_lines = threshold_lines;
+        self
+    }
+
+    /// Routes deciding which context category each worker's chunks are merged into
+    pub fn with_chunk_routing(mut self, chunk_routing: ChunkRouting) -> Self {
+        self.chunk_routing = chunk_routing;
+        self
+    }
+
+    /// Search, audit, pruning and reranking limits (token budget, items per category, weights)
+    pub fn with_context_config(mut self, context_config: ContextConfig) -> Self {
+        self.context_config = context_config;
+        self
+    }
+
+    /// Index the monorepo at `root_path`'s workspace packages on demand, when the router's
+    /// `target_paths` first point into them, keeping at most `capacity` in the graph. Call
+    /// after `with_vector_store` to embed them too.
+    pub fn with_lazy_packages(mut self, root_path: std::path::PathBuf, capacity: usize) -> Result<Self> {
+        let mut lazy = LazyPackageIndex::new(root_path, self.graph.clone(), capacity)?;
+        if let Some(store) = &self.vector_store {
+            lazy = lazy.with_vector_store(store.clone());
+        }
+        self.lazy_packages = Some(Arc::new(lazy));
+        Ok(self)
+    }
+
     /// Generate a context-aware prompt from a user request with advanced LLM-powered analysis
     pub async fn generate_context_prompt(&self, user_prompt: &str) -> Result<String> {
         info!("Generating context-aware prompt for: {}", user_prompt);
@@ -113,7 +192,7 @@ Respond with a JSON array of strings."#,
 
             match llm.generate(&system_prompt).await {
                 Ok(response) => {
-                    match serde_json::from_str::<Vec<String>>(&response.content) {
+                    match parse_llm_json::<Vec<String>>(&response.content) {
                         Ok(queries) => {
                             info!("Generated {} search queries", queries.len());
                             queries
@@ -189,9 +268,10 @@ Respond with a JSON array of strings."#,
             .await?;
 
         // Step 4: Convert gathered context to prompt context format
-        let master_context = self
+        let mut master_context = self
             .convert_to_context_data(gathered_context, &[], "Master Context")
             .await?;
+        master_context.enforce_hard_limit(self.max_context_bytes);
 
         // Step 5: Generate multi-step implementation plan using LLM
         let implementation_plan = if let Some(llm) = &self.llm {
@@ -224,6 +304,46 @@ Respond with a JSON array of strings."#,
         Ok(generated.full_prompt)
     }
 
+    /// Generate a context-aware prompt without calling any LLM (heuristic router,
+    /// keyword/graph search and pattern-only auditing).
+    pub async fn generate_no_llm_prompt(
+        &self,
+        user_prompt: &str,
+        project_root: &std::path::Path,
+    ) -> Result<String> {
+        let signature = match &self.signature {
+            Some(signature) => signature.clone(),
+            None => Arc::new(ProjectSignature::detect(project_root).unwrap_or_default()),
+        };
+        let mut pipeline = NoLlmPipeline::new(self.graph.clone())
+            .with_context_ignore(Self::load_context_ignore(project_root))
+            .with_include_deprecated(self.include_deprecated)
+            .with_context_config(&self.context_config);
+        if self.co_located_files {
+            pipeline = pipeline.with_co_location(CoLocationExpander::new(project_root));
+        }
+        let mut output = pipeline.run(user_prompt, &signature).await?;
+        if output.insufficient_context {
+            return Ok(format!(
+                "No relevant context found for: {}\n\nNothing in the index matched the request closely enough. \
+                 Ask which part of the codebase it concerns before proceeding.",
+                user_prompt
+            ));
+        }
+        output.context.enforce_hard_limit(self.max_context_bytes);
+
+        let implementation_plan =
+            self.generate_basic_implementation_plan(&output.context, &output.plan.global_intent);
+        let request = PromptRequest {
+            original_prompt: user_prompt.to_string(),
+            intent: output.plan.global_intent,
+            context: output.context,
+            implementation_plan: Some(implementation_plan),
+        };
+
+        Ok(self.prompt_generator.generate(&request).full_prompt)
+    }
+
     /// Enhanced context-aware prompt generation with Universal Knowledge Graph
     /// This method uses ALL new components: Project Signature, Question Loop, Style Analysis, Meta-Prompt
     pub async fn generate_enhanced_prompt(
@@ -243,7 +363,11 @@ Respond with a JSON array of strings."#,
             info!("🧠 Router Agent: planning search strategy with LLM...");
             let router = GeminiRouterAgent::new(llm.clone());
             match router.plan(user_prompt, &project_signature).await {
-                Ok(plan) => {
+                Ok(mut plan) => {
+                    let removed = plan.dedup_queries();
+                    if removed > 0 {
+                        info!("Router plan: dropped {} duplicate queries", removed);
+                    }
                     info!(
                         "✅ Router plan: intent='{}', {} global queries, {} worker plans, execution order: {:?}",
                         plan.global_intent,
@@ -271,7 +395,7 @@ Respond with a JSON array of strings."#,
         };
 
         // PHASE 2a: Execute Workers Sequentially (if router plan exists)
-        let worker_results: Vec<miow_agent::WorkerResult> = if let Some(ref plan) = &router_plan {
+        let mut worker_results: Vec<miow_agent::WorkerResult> = if let Some(ref plan) = &router_plan {
             if let Some(ref llm) = self.llm {
                 info!("🔄 Phase 2a: Executing workers sequentially...");
                 self.execute_workers_sequentially(llm.clone(), plan, user_prompt, &project_signature).await
@@ -284,6 +408,7 @@ Respond with a JSON array of strings."#,
 
         if !worker_results.is_empty() {
             info!("✅ Worker execution complete: {} results collected", worker_results.len());
+            miow_agent::fit_worker_results_to_budget(&mut worker_results, self.context_config.token_budget);
         }
 
         // PHASE 2: Generate Critical Questions (with detailed logging)
@@ -434,18 +559,12 @@ Respond with a JSON array of strings."#,
                     relevance_score: worker_result.confidence,
                     props: vec![],
                     references: vec![],
+                    tags: vec![],
                 };
 
-                // Categorize based on content type
-                if chunk.kind.contains("component") || chunk.kind.contains("function") {
-                    gathered_context.components.push(item);
-                } else if chunk.kind.contains("type") || chunk.kind.contains("interface") {
-                    gathered_context.types.push(item);
-                } else if chunk.kind.contains("schema") || chunk.kind.contains("model") {
-                    gathered_context.schemas.push(item);
-                } else {
-                    gathered_context.helpers.push(item);
-                }
+                self.chunk_routing
+                    .items(&mut gathered_context, &worker_result.worker_id, &chunk.kind)
+                    .push(item);
             }
         }
 
@@ -460,6 +579,7 @@ Respond with a JSON array of strings."#,
                     relevance_score: answer.confidence,
                     props: vec![],
                     references: vec![],
+                    tags: vec![],
                 };
 
                 // Add to appropriate category
@@ -475,12 +595,52 @@ Respond with a JSON array of strings."#,
             }
         }
 
+        // Drop files/symbols excluded from context by .miowignore (they stay in the graph)
+        let context_ignore = Self::load_context_ignore(project_root);
+        let ignored = context_ignore.filter_gathered(&mut gathered_context);
+        if ignored > 0 {
+            info!("🙈 Excluded {} items via .miowignore", ignored);
+        }
+        // Items the user marked irrelevant in an earlier round (`miow_agent::refine_context`)
+        if let Some(plan) = router_plan.as_ref().filter(|plan| !plan.exclude.is_empty()) {
+            let mut excluded = 0;
+            for items in [
+                &mut gathered_context.components,
+                &mut gathered_context.helpers,
+                &mut gathered_context.types,
+                &mut gathered_context.schemas,
+            ] {
+                let before = items.len();
+                items.retain(|item| !plan.excludes(&item.name, &item.file_path));
+                excluded += before - items.len();
+            }
+            info!("Excluded {} items marked irrelevant", excluded);
+        }
+
+        // What the audit, dedup and pruning drop, and why
+        let mut context_report = ContextReport::default();
+
         // Optional PHASE 4b: LLM-powered context auditing (Context Auditor Agent)
         if let Some(ref llm) = self.llm {
             info!("🧹 Context Auditor: LLM-driven pruning of gathered context...");
-            let auditor = GeminiContextAuditor::new(llm.clone());
-            if let Err(e) = auditor.audit(user_prompt, &mut gathered_context).await {
-                warn!("Context auditor failed, continuing with unfiltered context: {}", e);
+            let auditor = GeminiContextAuditor::new(llm.clone())
+                .with_audit_threshold(self.context_config.audit_threshold);
+            let before_audit = gathered_context.clone();
+            match auditor.audit(user_prompt, &mut gathered_context).await {
+                Ok(report) => {
+                    info!("✅ {} ({} removed)", report.summary(), report.total_removed());
+                    context_report.record(ContextStage::Audit, audit_drops(&before_audit, &gathered_context));
+                }
+                Err(e) => warn!("Context auditor failed, continuing with unfiltered context: {}", e),
+            }
+
+            if let Some(threshold_lines) = self.summarize_threshold_lines {
+                let summarizer =
+                    SymbolSummarizer::new(llm.clone()).with_threshold_lines(threshold_lines);
+                let summarized = summarizer.summarize(&mut gathered_context).await;
+                if summarized > 0 {
+                    info!("📝 Summarized {} oversized symbols", summarized);
+                }
             }
         }
 
@@ -513,17 +673,23 @@ Respond with a JSON array of strings."#,
             include_style_guide: true,
             include_implementation_plan: true,
             max_examples_per_type: 5,
-            token_budget: Some(16000),
+            token_budget: Some(self.context_config.token_budget),
         };
 
         // 5. Deduplicate and Prune Context
         info!("✂️ Optimizing context...");
-        miow_prompt::DeduplicationEngine::deduplicate(&mut context_data);
+        Self::apply_context_ignore(&context_ignore, &mut context_data);
+        miow_prompt::DeduplicationEngine::deduplicate_traced(&mut context_data, &mut context_report);
+        let shortened = context_data.prefer_usage_examples(USAGE_EXAMPLE_BODY_LINES);
+        if shortened > 0 {
+            info!("📘 Replaced {} long implementations with their usage examples", shortened);
+        }
 
-        if let Some(budget) = config.token_budget {
-            let pruner = miow_prompt::SmartPruner::new(budget);
-            pruner.prune(&mut context_data);
+        if config.token_budget.is_some() {
+            self.context_config.pruner().prune_traced(&mut context_data, &mut context_report);
         }
+        info!("🧾 Context budget report:\n{}", context_report.summary());
+        context_data.enforce_hard_limit(self.max_context_bytes);
 
         // 6. Generate Meta-Prompt
         info!("📝 Generating meta-prompt...");
@@ -594,6 +760,7 @@ Respond with a JSON array of strings."#,
                 end_line: 0,
                 props: Vec::new(),
                 references: Vec::new(),
+                language: String::new(),
             });
         }
 
@@ -607,8 +774,15 @@ Respond with a JSON array of strings."#,
             end_line: 0,
             props: Vec::new(),
             references: Vec::new(),
+            language: String::new(),
         });
 
+        Self::apply_context_ignore(
+            &Self::load_context_ignore(std::path::Path::new(project_root)),
+            &mut context_data,
+        );
+        context_data.enforce_hard_limit(self.max_context_bytes);
+
         let config = miow_prompt::MetaPromptConfig::default();
         let prompt = miow_prompt::MetaPromptGenerator::generate(
             user_prompt,
@@ -653,15 +827,11 @@ Respond with a JSON array of strings."#,
         let llm = self.llm.as_ref().ok_or_else(|| anyhow::anyhow!("LLM required"))?;
         let response = llm.generate(&prompt).await?;
 
-        // Clean and parse JSON
-        let clean = response.content.trim()
-            .trim_start_matches("```json")
-            .trim_start_matches("```")
-            .trim_end_matches("```")
-            .trim();
-
-        let signature: miow_core::ProjectSignature = serde_json::from_str(clean)
-            .unwrap_or_else(|_| miow_core::ProjectSignature::default());
+        let signature: miow_core::ProjectSignature = parse_llm_json(&response.content)
+            .unwrap_or_else(|e| {
+                warn!("Project signature analysis: {:#}", e);
+                miow_core::ProjectSignature::default()
+            });
 
         Ok(signature)
     }
@@ -700,6 +870,25 @@ Respond with a JSON array of strings."#,
         Ok(response.content)
     }
 
+    /// Symbols shaped like `anchor`, with embedding similarity blended in when a vector store
+    /// is configured
+    async fn similar_shapes(&self, anchor: &miow_graph::SymbolSearchResult) -> Result<Vec<miow_graph::SimilarShape>> {
+        let mut similar = self.graph.find_similar_shape(anchor)?;
+        if let Some(vs) = &self.vector_store {
+            match vs.search_similar(&anchor.content, similar.len().max(1) * 2).await {
+                Ok(results) => {
+                    let scores: std::collections::HashMap<i64, f32> = results
+                        .into_iter()
+                        .filter_map(|r| Some((r.symbol.id.parse().ok()?, r.score)))
+                        .collect();
+                    miow_graph::blend_embedding_similarity(&mut similar, &scores, miow_graph::DEFAULT_EMBEDDING_WEIGHT);
+                }
+                Err(e) => warn!("Embedding similarity for {} unavailable: {}", anchor.name, e),
+            }
+        }
+        Ok(similar)
+    }
+
     /// Gather comprehensive context from codebase
     /// If a router plan is provided, its target_paths hints are used to filter results by file path.
     async fn gather_comprehensive_context(
@@ -711,6 +900,19 @@ Respond with a JSON array of strings."#,
     ) -> Result<GatheredContext> {
         info!("Gathering comprehensive context...");
 
+        // Packages the router points at must be in the graph before it is searched
+        if let (Some(lazy), Some(plan)) = (&self.lazy_packages, router_plan) {
+            let target_paths: Vec<String> = plan
+                .search_queries
+                .iter()
+                .chain(plan.workers.iter().flat_map(|w| &w.queries))
+                .flat_map(|q| q.target_paths.iter().cloned())
+                .collect();
+            if let Err(e) = lazy.ensure_indexed(&target_paths).await {
+                warn!("Lazy package indexing failed: {}", e);
+            }
+        }
+
         // Helper: collect all target path hints for a given plain-text query.
         let get_target_paths = |query: &str| -> Vec<String> {
             let mut paths = Vec::new();
@@ -748,6 +950,9 @@ Respond with a JSON array of strings."#,
         for primitive in &ui_primitives {
             if let Ok(results) = self.graph.search_symbols(primitive) {
                 for result in results {
+                    if !self.include_deprecated && result.is_deprecated() {
+                        continue;
+                    }
                     let name_lower = result.name.to_lowercase();
                     if name_lower.contains(&primitive.to_lowercase()) {
                         let relevance = if result.name == *primitive || result.name.contains(primitive) {
@@ -781,6 +986,7 @@ Respond with a JSON array of strings."#,
                             relevance_score: relevance,
                             props,
                             references,
+                            tags: vec![],
                         };
                         gathered.components.push(item);
                     }
@@ -791,8 +997,12 @@ Respond with a JSON array of strings."#,
         // Search for components/helpers using queries, respecting router target_paths when present
         for query in search_queries {
             let target_paths = get_target_paths(query);
-            let results = self.graph.search_symbols(query)?;
+            // A barrel file's `export { Button } from './Button'` is not the component itself
+            let results = self.graph.collapse_reexports(self.graph.search_symbols(query)?)?;
             for result in results {
+                if !self.include_deprecated && result.is_deprecated() {
+                    continue;
+                }
                 if !target_paths.is_empty()
                     && !target_paths
                         .iter()
@@ -837,6 +1047,7 @@ Respond with a JSON array of strings."#,
                     relevance_score: relevance,
                     props,
                     references,
+                    tags: vec![],
                 };
 
                 if kind_lower.contains("component")
@@ -902,6 +1113,7 @@ Respond with a JSON array of strings."#,
                             relevance_score: result.score,
                             props,
                             references,
+                            tags: vec![],
                         };
 
                         let kind_lower = result.symbol.kind.to_lowercase();
@@ -934,10 +1146,42 @@ Respond with a JSON array of strings."#,
                     relevance_score: 1.0,
                     props: vec![],
                     references: vec![],
+                    tags: vec![],
                 });
             }
         }
 
+        // Refactoring toward "the others" needs the symbols shaped like the one being changed
+        let wants_refactor_advice = router_plan.is_some_and(|plan| plan.workers.iter().any(|w| w.worker_id == "refactor_advisor"));
+        if wants_refactor_advice {
+            let anchor = gathered
+                .components
+                .iter()
+                .chain(&gathered.helpers)
+                .max_by(|a, b| a.relevance_score.total_cmp(&b.relevance_score))
+                .and_then(|item| {
+                    self.graph
+                        .find_symbols_by_name(&item.name)
+                        .ok()?
+                        .into_iter()
+                        .find(|s| s.file_path == item.file_path)
+                });
+            if let Some(anchor) = anchor {
+                for similar in self.similar_shapes(&anchor).await?.into_iter().take(5) {
+                    gathered.similar_implementations.push(ContextItem {
+                        name: similar.symbol.name,
+                        kind: similar.symbol.kind,
+                        content: similar.symbol.content,
+                        file_path: similar.symbol.file_path,
+                        relevance_score: similar.score,
+                        props: vec![],
+                        references: vec![],
+                        tags: vec![format!("similar-shape:{}", anchor.name)],
+                    });
+                }
+            }
+        }
+
         // Find design tokens
         for query in search_queries {
             let target_paths = get_target_paths(query);
@@ -959,6 +1203,7 @@ Respond with a JSON array of strings."#,
                     relevance_score: 0.7,
                     props: vec![],
                     references: vec![],
+                    tags: vec![],
                 });
             }
         }
@@ -966,7 +1211,7 @@ Respond with a JSON array of strings."#,
         // Find type definitions
         for query in search_queries {
             let target_paths = get_target_paths(query);
-            match self.graph.find_type_definitions(query) {
+            match self.graph.find_merged_type_definitions(query) {
                 Ok(types) => {
                     for type_def in types {
                         if !target_paths.is_empty()
@@ -985,6 +1230,7 @@ Respond with a JSON array of strings."#,
                             relevance_score: 0.8,
                             props: vec![],
                             references: vec![],
+                            tags: vec![],
                         });
                     }
                 }
@@ -1014,6 +1260,7 @@ Respond with a JSON array of strings."#,
                             relevance_score: 0.6,
                             props: vec![],
                             references: vec![],
+                            tags: vec![],
                         });
                     }
                 }
@@ -1043,6 +1290,7 @@ Respond with a JSON array of strings."#,
                             relevance_score: 0.7,
                             props: vec![],
                             references: vec![],
+                            tags: vec![],
                         });
                     }
                 }
@@ -1055,11 +1303,15 @@ Respond with a JSON array of strings."#,
             b.relevance_score
                 .partial_cmp(&a.relevance_score)
                 .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.file_path.cmp(&b.file_path))
+                .then_with(|| a.name.cmp(&b.name))
         });
         gathered.helpers.sort_by(|a, b| {
             b.relevance_score
                 .partial_cmp(&a.relevance_score)
                 .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.file_path.cmp(&b.file_path))
+                .then_with(|| a.name.cmp(&b.name))
         });
         gathered.components.truncate(15);
         gathered.helpers.truncate(15);
@@ -1125,6 +1377,7 @@ Respond with a JSON array of strings."#,
                 end_line: 0,
                 props: item.props.clone(),
                 references: item.references.clone(),
+                language: String::new(),
             })
             .collect();
 
@@ -1140,6 +1393,7 @@ Respond with a JSON array of strings."#,
                 end_line: 0,
                 props: item.props.clone(),
                 references: item.references.clone(),
+                language: String::new(),
             })
             .collect();
 
@@ -1147,7 +1401,12 @@ Respond with a JSON array of strings."#,
         // Vector embeddings capture semantic meaning, not just keyword matches
         let mut vector_symbols_with_scores = Vec::new();
         if let Some(store) = &self.vector_store {
-            match store.search_similar(user_prompt, 30).await {
+            let search_k = self.context_config.search_k;
+            let results = match self.context_config.mmr_lambda {
+                Some(lambda) => store.search_similar_mmr(user_prompt, search_k, lambda).await,
+                None => store.search_similar(user_prompt, search_k).await,
+            };
+            match results {
                 Ok(results) => {
                     info!("🔍 Vector search found {} semantically similar symbols", results.len());
                     for res in results {
@@ -1162,6 +1421,7 @@ Respond with a JSON array of strings."#,
                                 end_line: 0,
                                 props: Vec::new(),
                                 references: Vec::new(),
+                                language: String::new(),
                             },
                         ));
                     }
@@ -1194,8 +1454,9 @@ Respond with a JSON array of strings."#,
         let similar_symbols = self.rank_symbols(similar_symbols, keywords, user_prompt, 5);
 
         let design_tokens = self.collect_design_tokens(&gathered);
-        let types = self.collect_type_info(&gathered, 10);
-        let constants = self.collect_constant_info(&gathered, 10);
+        let max_items = self.context_config.max_items_per_category;
+        let types = self.collect_type_info(&gathered, max_items);
+        let constants = self.collect_constant_info(&gathered, max_items);
         let schemas = self.collect_schema_info(&gathered, 8);
 
         Ok(ContextData {
@@ -1316,7 +1577,7 @@ Respond with a JSON array of strings."#,
                 // Boost it significantly - semantic similarity is more important than keyword matching
                 let base_score = if vector_score > 0.0 {
                     // Vector search result: use semantic score as base, then add keyword bonus
-                    vector_score * 10.0 // Scale up semantic similarity (0-1 range to 0-10)
+                    vector_score * self.context_config.rerank.vector_scale // Scale up semantic similarity (0-1 range to 0-10)
                 } else {
                     // Text search result: start from 0, rely on keyword matching
                     0.0
@@ -1328,7 +1589,12 @@ Respond with a JSON array of strings."#,
             })
             .collect();
 
-        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+        scored.sort_by(|a, b| {
+            b.0.partial_cmp(&a.0)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.1.file_path.cmp(&b.1.file_path))
+                .then_with(|| a.1.name.cmp(&b.1.name))
+        });
 
         let mut seen = HashSet::new();
         let mut ranked = Vec::new();
@@ -1365,28 +1631,14 @@ Respond with a JSON array of strings."#,
     }
 
     fn score_symbol(&self, symbol: &SymbolInfo, keywords: &[String], prompt_lower: &str) -> f32 {
-        let mut score = 0.0;
         let name_lower = symbol.name.to_lowercase();
         let file_lower = symbol.file_path.to_lowercase();
         let content_lower = symbol.content.to_lowercase();
 
-        for keyword in keywords {
-            if keyword.is_empty() {
-                continue;
-            }
-
-            if name_lower.contains(keyword) {
-                score += 3.0;
-            }
-
-            if file_lower.contains(keyword) {
-                score += 2.0;
-            }
-
-            if content_lower.contains(keyword) {
-                score += 1.0;
-            }
-        }
+        let mut score = self
+            .context_config
+            .rerank
+            .keyword_score(&name_lower, &file_lower, &content_lower, keywords);
 
         if prompt_lower.contains("login")
             && (name_lower.contains("login")
@@ -1608,6 +1860,27 @@ Format the plan as a numbered list with clear steps. Be specific about what to r
     }
 
     /// Get a reference to the knowledge graph
+    /// Load the project's `.miowignore`; a malformed file is reported and ignored.
+    fn load_context_ignore(project_root: &std::path::Path) -> ContextIgnore {
+        ContextIgnore::load(project_root).unwrap_or_else(|e| {
+            warn!("Ignoring invalid .miowignore: {}", e);
+            ContextIgnore::empty()
+        })
+    }
+
+    /// Final filter removing `.miowignore`d files and symbols from the prompt context.
+    fn apply_context_ignore(context_ignore: &ContextIgnore, context: &mut ContextData) {
+        if context_ignore.is_empty() {
+            return;
+        }
+        context.relevant_symbols.retain(|s| !context_ignore.is_ignored(&s.file_path, &s.name));
+        context.similar_symbols.retain(|s| !context_ignore.is_ignored(&s.file_path, &s.name));
+        context.types.retain(|t| !context_ignore.is_symbol_ignored(&t.name));
+        context.constants.retain(|c| !context_ignore.is_symbol_ignored(&c.name));
+        context.schemas.retain(|s| !context_ignore.is_symbol_ignored(&s.name));
+        context.design_tokens.retain(|d| !context_ignore.is_symbol_ignored(&d.name));
+    }
+
     pub fn graph(&self) -> &KnowledgeGraph {
         &self.graph
     }
@@ -1697,14 +1970,18 @@ Format the plan as a numbered list with clear steps. Be specific about what to r
     }
 
     /// Load project signature from cache or detect it
-    fn load_or_detect_signature(&self, project_root: &std::path::Path) -> Result<miow_core::ProjectSignature> {
+    fn load_or_detect_signature(&self, project_root: &std::path::Path) -> Result<Arc<ProjectSignature>> {
+        if let Some(signature) = &self.signature {
+            return Ok(signature.clone());
+        }
+
         let cache_path = project_root.join(".miow_cache.json");
 
         // Try to load from cache first
         if let Ok(cached_content) = std::fs::read_to_string(&cache_path) {
             if let Ok(signature) = serde_json::from_str(&cached_content) {
                 info!("📋 Loaded project signature from cache");
-                return Ok(signature);
+                return Ok(Arc::new(signature));
             }
         }
 
@@ -1717,7 +1994,7 @@ Format the plan as a numbered list with clear steps. Be specific about what to r
             let _ = std::fs::write(&cache_path, json); // Ignore errors, caching is optional
         }
 
-        Ok(signature)
+        Ok(Arc::new(signature))
     }
 
     /// Compile master context by intelligently merging worker results
@@ -1846,18 +2123,12 @@ Respond with JSON containing prioritized context items from all workers."#,
                         relevance_score: worker_result.confidence,
                         props: Vec::new(),
                         references: Vec::new(),
+                        tags: vec![],
                     };
 
-                    // Categorize based on content type
-                    if chunk.kind.contains("component") || chunk.kind.contains("function") {
-                        master_context.components.push(item);
-                    } else if chunk.kind.contains("type") || chunk.kind.contains("interface") {
-                        master_context.types.push(item);
-                    } else if chunk.kind.contains("schema") || chunk.kind.contains("model") {
-                        master_context.schemas.push(item);
-                    } else {
-                        master_context.helpers.push(item);
-                    }
+                    self.chunk_routing
+                        .items(&mut master_context, &worker_result.worker_id, &chunk.kind)
+                        .push(item);
                 }
             }
         }
@@ -1891,6 +2162,7 @@ Respond with JSON containing prioritized context items from all workers."#,
                 end_line: 0,
                 props: item.props.clone(),
                 references: item.references.clone(),
+                language: String::new(),
             })
             .collect(),
             similar_symbols: raw_context.helpers.iter().map(|item| SymbolInfo {
@@ -1902,6 +2174,7 @@ Respond with JSON containing prioritized context items from all workers."#,
                 end_line: 0,
                 props: item.props.clone(),
                 references: item.references.clone(),
+                language: String::new(),
             })
             .collect(),
             types: raw_context.types.iter().map(|item| TypeInfo {
@@ -1941,7 +2214,7 @@ Respond with JSON containing prioritized context items from all workers."#,
             );
 
             if let Ok(llm_response) = llm.generate(&selection_prompt).await {
-                if let Ok(selected_data) = serde_json::from_str::<serde_json::Value>(&llm_response.content) {
+                if let Ok(selected_data) = parse_llm_json::<serde_json::Value>(&llm_response.content) {
                     if let Some(indices) = selected_data.get("selected").and_then(|v| v.as_array()) {
                         let selected_indices: Vec<usize> = indices.iter()
                             .filter_map(|v| v.as_u64().map(|n| n as usize))
@@ -2062,7 +2335,7 @@ Respond with JSON containing prioritized context items from all workers."#,
         let llm = self.llm.as_ref().ok_or_else(|| anyhow::anyhow!("LLM not available"))?;
         let context_response = llm.generate(&context_prompt).await?;
         // Parse and map to symbols (simplified; in practice, integrate with vector search)
-        let json_value: serde_json::Value = serde_json::from_str(&context_response.content)?;
+        let json_value: serde_json::Value = parse_llm_json(&context_response.content)?;
         let _selected: Vec<String> = json_value["selected_symbols"]
             .as_array()
             .unwrap_or(&vec![])
@@ -2170,6 +2443,7 @@ Respond with JSON containing prioritized context items from all workers."#,
         unique_files.sort_by(|a, b| {
             b.relevance_score.partial_cmp(&a.relevance_score)
                 .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.file_path.cmp(&b.file_path))
         });
         
         // Limit to top 50
@@ -2222,6 +2496,7 @@ Respond with JSON containing prioritized context items from all workers."#,
                         end_line: symbol.end_line as i64,
                         props,
                         references,
+                        language: String::new(),
                     });
                 }
             }
@@ -2230,7 +2505,7 @@ Respond with JSON containing prioritized context items from all workers."#,
         info!("📦 Loaded {} symbols from selected files", selected_symbols.len());
         
         // Build context data with selected files
-        let context_data = ContextData {
+        let mut context_data = ContextData {
             relevant_symbols: selected_symbols,
             similar_symbols: vec![],
             design_tokens: vec![],
@@ -2239,6 +2514,8 @@ Respond with JSON containing prioritized context items from all workers."#,
             constants: vec![],
             schemas: vec![],
         };
+        Self::apply_context_ignore(&Self::load_context_ignore(project_root), &mut context_data);
+        context_data.enforce_hard_limit(self.max_context_bytes);
         
         // Generate meta-prompt
         let config = miow_prompt::MetaPromptConfig {
@@ -2246,7 +2523,7 @@ Respond with JSON containing prioritized context items from all workers."#,
             include_style_guide: true,
             include_implementation_plan: true,
             max_examples_per_type: 5,
-            token_budget: Some(16000),
+            token_budget: Some(self.context_config.token_budget),
         };
         
         let project_info = project_signature.to_description();
@@ -161,7 +161,7 @@ async fn test_full_system_integration() -> Result<()> {
         &miow_parsers::Symbol {
             name: "test".to_string(),
             kind: miow_parsers::SymbolType::Function,
-            range: miow_parsers::Range { start_line: 1, end_line: 1, start_byte: 0, end_byte: 10 },
+            range: miow_parsers::Range { start_line: 1, end_line: 1, start_byte: 0, end_byte: 10, start_col: 0, end_col: 10 },
             content: code.to_string(),
             metadata: miow_parsers::SymbolMetadata::default(),
             references: vec![],
@@ -206,3 +206,206 @@ async fn test_full_system_integration() -> Result<()> {
     
     Ok(())
 }
+
+fn index_fixture(graph: &miow_graph::KnowledgeGraph, root: &std::path::Path, files: &[(&str, &str)]) -> Result<()> {
+    for (relative, source) in files {
+        let path = root.join(relative);
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(&path, source)?;
+
+        let parsed = miow_parsers::parse_typescript(source, relative.ends_with(".tsx"))?;
+        graph.insert_file(relative, &miow_core::to_graph_data(parsed))?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_no_llm_pipeline_finds_login_symbols() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let graph = miow_graph::KnowledgeGraph::new(temp_dir.path().join("miow.db"))?;
+    index_fixture(
+        &graph,
+        temp_dir.path(),
+        &[
+            (
+                "src/components/LoginForm.tsx",
+                "import { Button } from './Button';\nexport function LoginForm() {\n  return <form><Button label=\"Sign in\" /></form>;\n}\n",
+            ),
+            (
+                "src/components/Button.tsx",
+                "export function Button({ label }: { label: string }) {\n  return <button>{label}</button>;\n}\n",
+            ),
+            (
+                "src/lib/auth.ts",
+                "export async function signIn(email: string, password: string) {\n  return fetch('/api/auth/login', { method: 'POST' });\n}\n",
+            ),
+            (
+                "src/components/Dashboard.tsx",
+                "export function Dashboard() {\n  return <main>Stats</main>;\n}\n",
+            ),
+        ],
+    )?;
+
+    let pipeline = miow_agent::NoLlmPipeline::new(Arc::new(graph));
+    let output = pipeline
+        .run("Create a login page", &miow_core::ProjectSignature::default())
+        .await?;
+
+    let names: Vec<&str> = output.context.relevant_symbols.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names.first(), Some(&"LoginForm"), "got {:?}", names);
+    assert!(names.contains(&"signIn"), "got {:?}", names);
+    assert!(!names.contains(&"Dashboard"), "got {:?}", names);
+    assert!(output.plan.global_intent.starts_with("create_login"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_deprecated_symbols_excluded_from_no_llm_context_by_default() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let graph = miow_graph::KnowledgeGraph::new(temp_dir.path().join("miow.db"))?;
+    index_fixture(
+        &graph,
+        temp_dir.path(),
+        &[
+            (
+                "src/lib/auth.ts",
+                "/**\n * @deprecated use signIn\n */\nexport async function loginUser(email: string) {\n  return fetch('/api/login');\n}\n\nexport async function signIn(email: string) {\n  return fetch('/api/auth/login');\n}\n",
+            ),
+        ],
+    )?;
+    let graph = Arc::new(graph);
+    let signature = miow_core::ProjectSignature::default();
+    let names = |output: &miow_agent::NoLlmOutput| -> Vec<String> {
+        output.context.relevant_symbols.iter().map(|s| s.name.clone()).collect()
+    };
+
+    let default = miow_agent::NoLlmPipeline::new(graph.clone())
+        .run("Add a login user flow", &signature)
+        .await?;
+    assert!(names(&default).contains(&"signIn".to_string()), "got {:?}", names(&default));
+    assert!(!names(&default).contains(&"loginUser".to_string()), "got {:?}", names(&default));
+
+    let unfiltered = miow_agent::NoLlmPipeline::new(graph)
+        .with_include_deprecated(true)
+        .run("Add a login user flow", &signature)
+        .await?;
+    assert!(names(&unfiltered).contains(&"loginUser".to_string()), "got {:?}", names(&unfiltered));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_co_located_style_pulled_in_when_enabled() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let graph = miow_graph::KnowledgeGraph::new(temp_dir.path().join("miow.db"))?;
+    index_fixture(
+        &graph,
+        temp_dir.path(),
+        &[(
+            "src/components/Button.tsx",
+            "import styles from './Button.module.css';\nexport function Button() {\n  return <button className={styles.primary} />;\n}\n",
+        )],
+    )?;
+    std::fs::write(
+        temp_dir.path().join("src/components/Button.module.css"),
+        ".primary { color: var(--brand); }\n",
+    )?;
+    std::fs::write(temp_dir.path().join("src/components/Card.module.css"), ".card {}\n")?;
+    let graph = Arc::new(graph);
+    let signature = miow_core::ProjectSignature::default();
+
+    let without = miow_agent::NoLlmPipeline::new(graph.clone())
+        .run("Add a button", &signature)
+        .await?;
+    assert!(without.context.similar_symbols.is_empty());
+
+    let with = miow_agent::NoLlmPipeline::new(graph)
+        .with_co_location(miow_agent::CoLocationExpander::new(temp_dir.path()))
+        .run("Add a button", &signature)
+        .await?;
+    let files: Vec<&str> = with.context.similar_symbols.iter().map(|s| s.file_path.as_str()).collect();
+    assert_eq!(files, vec!["src/components/Button.module.css"]);
+    assert!(with.context.similar_symbols[0].content.contains("var(--brand)"));
+    assert!(with.context.to_markdown(false).contains("```css\n.primary"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_current_file_imports_boost_no_llm_ranking() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let graph = miow_graph::KnowledgeGraph::new(temp_dir.path().join("miow.db"))?;
+    index_fixture(
+        &graph,
+        temp_dir.path(),
+        &[
+            (
+                "src/archive/Cart.tsx",
+                "export function Cart() {\n  return <div>Old cart</div>;\n}\n",
+            ),
+            (
+                "src/cart/Cart.tsx",
+                "export function Cart() {\n  return <div>Cart</div>;\n}\n",
+            ),
+            (
+                "src/checkout/Checkout.tsx",
+                "import { Cart } from '../cart/Cart';\nexport function Checkout() {\n  return <section><Cart /></section>;\n}\n",
+            ),
+        ],
+    )?;
+    let graph = Arc::new(graph);
+    let signature = miow_core::ProjectSignature::default();
+    let first_cart = |output: &miow_agent::NoLlmOutput| {
+        output
+            .context
+            .relevant_symbols
+            .iter()
+            .find(|s| s.name == "Cart")
+            .map(|s| s.file_path.clone())
+    };
+
+    let without = miow_agent::NoLlmPipeline::new(graph.clone())
+        .run("modify the checkout flow cart", &signature)
+        .await?;
+    assert_eq!(first_cart(&without).as_deref(), Some("src/archive/Cart.tsx"));
+
+    let with = miow_agent::NoLlmPipeline::new(graph)
+        .with_current_file("src/checkout/Checkout.tsx")
+        .run("modify the checkout flow cart", &signature)
+        .await?;
+    assert_eq!(first_cart(&with).as_deref(), Some("src/cart/Cart.tsx"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_miowignore_filters_context_but_not_search() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let graph = miow_graph::KnowledgeGraph::new(temp_dir.path().join("miow.db"))?;
+    index_fixture(
+        &graph,
+        temp_dir.path(),
+        &[
+            (
+                "src/components/LoginForm.tsx",
+                "export function LoginForm() {\n  return <form>Sign in</form>;\n}\n",
+            ),
+            (
+                "src/components/icons.tsx",
+                "export function LoginIcon() {\n  return <svg />;\n}\n",
+            ),
+        ],
+    )?;
+    std::fs::write(temp_dir.path().join(".miowignore"), "src/components/icons.tsx\n")?;
+    let graph = Arc::new(graph);
+
+    // Still indexed and searchable...
+    assert_eq!(graph.search_symbols("LoginIcon")?.len(), 1);
+
+    // ...but never injected into context
+    let output = miow_agent::NoLlmPipeline::new(graph.clone())
+        .with_context_ignore(miow_core::ContextIgnore::load(temp_dir.path())?)
+        .run("Create a login page", &miow_core::ProjectSignature::default())
+        .await?;
+    let names: Vec<&str> = output.context.relevant_symbols.iter().map(|s| s.name.as_str()).collect();
+    assert!(names.contains(&"LoginForm"), "got {:?}", names);
+    assert!(!names.contains(&"LoginIcon"), "got {:?}", names);
+    Ok(())
+}